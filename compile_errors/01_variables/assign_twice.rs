@@ -0,0 +1,6 @@
+fn main() {
+    let x = 5;
+    println!("x = {}", x);
+    x = 10;
+    println!("x = {}", x);
+}
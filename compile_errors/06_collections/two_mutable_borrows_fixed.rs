@@ -0,0 +1,7 @@
+fn main() {
+    let mut v = vec![1, 2, 3];
+    let a = &mut v;
+    a.push(4);
+    let b = &mut v;
+    b.push(5);
+}
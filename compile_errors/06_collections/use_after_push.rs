@@ -0,0 +1,6 @@
+fn main() {
+    let s1 = String::from("hello");
+    let mut v = Vec::new();
+    v.push(s1);
+    println!("s1: {}", s1);
+}
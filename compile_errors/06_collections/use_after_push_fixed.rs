@@ -0,0 +1,7 @@
+fn main() {
+    let s1 = String::from("hello");
+    let mut v = Vec::new();
+    v.push(s1.clone());
+    println!("s1: {}", s1);
+    println!("v: {:?}", v);
+}
@@ -0,0 +1,10 @@
+mod math {
+    pub fn add(a: i32, b: i32) -> i32 {
+        a + b
+    }
+}
+
+fn main() {
+    let sum = math::add(5, 3);
+    println!("sum = {}", sum);
+}
@@ -0,0 +1,7 @@
+use std::num::ParseIntError;
+
+fn main() -> Result<(), ParseIntError> {
+    let number: i32 = "42".parse()?;
+    println!("number = {}", number);
+    Ok(())
+}
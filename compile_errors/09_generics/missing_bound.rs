@@ -0,0 +1,11 @@
+use std::fmt::Display;
+use std::ops::Add;
+
+fn display_and_add<T: Display + Add<Output = T> + Copy>(a: T, b: T) {
+    println!("{} + {} = {}", a, b, a + b);
+}
+
+fn main() {
+    display_and_add(10, 20);
+    display_and_add("hello", "world");
+}
@@ -0,0 +1,8 @@
+fn main() {
+    let x = 5;
+    let r;
+    {
+        r = &x;
+    }
+    println!("r = {}", r);
+}
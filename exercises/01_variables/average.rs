@@ -0,0 +1,7 @@
+// 练习：计算平均值
+// 实现 average 函数，返回切片中所有整数的平均值，切片为空时返回 0.0
+// 提示：整数和浮点数之间不会自动转换，需要使用 as 进行类型转换
+
+fn average(values: &[i32]) -> f64 {
+    todo!()
+}
@@ -0,0 +1,14 @@
+// 练习：计算平均值（参考答案）
+
+fn average(values: &[i32]) -> f64 {
+    if values.is_empty() {
+        return 0.0;
+    }
+
+    let mut sum: i64 = 0;
+    for value in values {
+        sum += *value as i64;
+    }
+
+    sum as f64 / values.len() as f64
+}
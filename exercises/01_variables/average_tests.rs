@@ -0,0 +1,19 @@
+#[test]
+fn average_of_integers() {
+    assert_eq!(average(&[1, 2, 3, 4]), 2.5);
+}
+
+#[test]
+fn average_of_single_value() {
+    assert_eq!(average(&[-7]), -7.0);
+}
+
+#[test]
+fn average_of_empty_slice() {
+    assert_eq!(average(&[]), 0.0);
+}
+
+#[test]
+fn average_does_not_overflow() {
+    assert_eq!(average(&[i32::MAX, i32::MAX]), i32::MAX as f64);
+}
@@ -0,0 +1,11 @@
+// 练习：FizzBuzz
+// 实现 fizzbuzz 函数：
+// - n 能被 15 整除时返回 "FizzBuzz"
+// - n 能被 3 整除时返回 "Fizz"
+// - n 能被 5 整除时返回 "Buzz"
+// - 其他情况返回 n 本身，例如 "7"
+// 提示：if 是表达式，可以直接作为函数的返回值
+
+fn fizzbuzz(n: u32) -> String {
+    todo!()
+}
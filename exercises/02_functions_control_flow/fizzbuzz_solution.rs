@@ -0,0 +1,13 @@
+// 练习：FizzBuzz（参考答案）
+
+fn fizzbuzz(n: u32) -> String {
+    if n % 15 == 0 {
+        String::from("FizzBuzz")
+    } else if n % 3 == 0 {
+        String::from("Fizz")
+    } else if n % 5 == 0 {
+        String::from("Buzz")
+    } else {
+        n.to_string()
+    }
+}
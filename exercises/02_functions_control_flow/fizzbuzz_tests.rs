@@ -0,0 +1,23 @@
+#[test]
+fn plain_numbers() {
+    assert_eq!(fizzbuzz(1), "1");
+    assert_eq!(fizzbuzz(7), "7");
+}
+
+#[test]
+fn multiples_of_three() {
+    assert_eq!(fizzbuzz(3), "Fizz");
+    assert_eq!(fizzbuzz(9), "Fizz");
+}
+
+#[test]
+fn multiples_of_five() {
+    assert_eq!(fizzbuzz(5), "Buzz");
+    assert_eq!(fizzbuzz(20), "Buzz");
+}
+
+#[test]
+fn multiples_of_fifteen() {
+    assert_eq!(fizzbuzz(15), "FizzBuzz");
+    assert_eq!(fizzbuzz(45), "FizzBuzz");
+}
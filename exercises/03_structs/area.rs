@@ -0,0 +1,20 @@
+// 练习：矩形的面积
+// 为 Rectangle 实现两个函数：
+// - 方法 area：返回矩形的面积
+// - 关联函数 square：创建一个宽和高都等于 size 的正方形
+// 提示：方法的第一个参数是 &self，关联函数没有 self 参数，通过 Rectangle::square(10) 调用
+
+struct Rectangle {
+    width: u32,
+    height: u32,
+}
+
+impl Rectangle {
+    fn area(&self) -> u32 {
+        todo!()
+    }
+
+    fn square(size: u32) -> Rectangle {
+        todo!()
+    }
+}
@@ -0,0 +1,19 @@
+#[test]
+fn area_of_rectangle() {
+    let rect = Rectangle { width: 30, height: 50 };
+    assert_eq!(rect.area(), 1500);
+}
+
+#[test]
+fn area_of_empty_rectangle() {
+    let rect = Rectangle { width: 0, height: 50 };
+    assert_eq!(rect.area(), 0);
+}
+
+#[test]
+fn square_has_equal_sides() {
+    let square = Rectangle::square(12);
+    assert_eq!(square.width, 12);
+    assert_eq!(square.height, 12);
+    assert_eq!(square.area(), 144);
+}
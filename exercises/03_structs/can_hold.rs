@@ -0,0 +1,14 @@
+// 练习：判断矩形能否容纳另一个矩形
+// 实现 Rectangle::can_hold：当 self 的宽和高都不小于 other 的宽和高时返回 true
+// 提示：other 是 &Rectangle，可以直接通过 other.width 读取它的字段
+
+struct Rectangle {
+    width: u32,
+    height: u32,
+}
+
+impl Rectangle {
+    fn can_hold(&self, other: &Rectangle) -> bool {
+        todo!()
+    }
+}
@@ -0,0 +1,12 @@
+// 练习：判断矩形能否容纳另一个矩形（参考答案）
+
+struct Rectangle {
+    width: u32,
+    height: u32,
+}
+
+impl Rectangle {
+    fn can_hold(&self, other: &Rectangle) -> bool {
+        self.width >= other.width && self.height >= other.height
+    }
+}
@@ -0,0 +1,28 @@
+#[test]
+fn larger_can_hold_smaller() {
+    let larger = Rectangle { width: 30, height: 50 };
+    let smaller = Rectangle { width: 20, height: 40 };
+    assert!(larger.can_hold(&smaller));
+}
+
+#[test]
+fn smaller_cannot_hold_larger() {
+    let larger = Rectangle { width: 30, height: 50 };
+    let smaller = Rectangle { width: 20, height: 40 };
+    assert!(!smaller.can_hold(&larger));
+}
+
+#[test]
+fn both_sides_must_fit() {
+    let wide = Rectangle { width: 60, height: 10 };
+    let tall = Rectangle { width: 10, height: 60 };
+    assert!(!wide.can_hold(&tall));
+    assert!(!tall.can_hold(&wide));
+}
+
+#[test]
+fn rectangle_can_hold_itself() {
+    let rect = Rectangle { width: 15, height: 15 };
+    let same = Rectangle { width: 15, height: 15 };
+    assert!(rect.can_hold(&same));
+}
@@ -0,0 +1,20 @@
+// 练习：硬币的面值
+// 实现 value_in_cents，返回每种硬币对应的美分数：
+// Penny 是 1，Nickel 是 5，Dime 是 10，Quarter 是 25
+// 再实现 total_value，计算一组硬币的总面值，没有硬币时返回 0
+// 提示：match 必须处理枚举的所有变体
+
+enum Coin {
+    Penny,
+    Nickel,
+    Dime,
+    Quarter,
+}
+
+fn value_in_cents(coin: &Coin) -> u32 {
+    todo!()
+}
+
+fn total_value(coins: &[Coin]) -> u32 {
+    todo!()
+}
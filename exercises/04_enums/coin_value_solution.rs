@@ -0,0 +1,21 @@
+// 练习：硬币的面值（参考答案）
+
+enum Coin {
+    Penny,
+    Nickel,
+    Dime,
+    Quarter,
+}
+
+fn value_in_cents(coin: &Coin) -> u32 {
+    match coin {
+        Coin::Penny => 1,
+        Coin::Nickel => 5,
+        Coin::Dime => 10,
+        Coin::Quarter => 25,
+    }
+}
+
+fn total_value(coins: &[Coin]) -> u32 {
+    coins.iter().map(value_in_cents).sum()
+}
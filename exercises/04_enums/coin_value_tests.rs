@@ -0,0 +1,18 @@
+#[test]
+fn value_of_each_coin() {
+    assert_eq!(value_in_cents(&Coin::Penny), 1);
+    assert_eq!(value_in_cents(&Coin::Nickel), 5);
+    assert_eq!(value_in_cents(&Coin::Dime), 10);
+    assert_eq!(value_in_cents(&Coin::Quarter), 25);
+}
+
+#[test]
+fn total_of_several_coins() {
+    let coins = [Coin::Quarter, Coin::Dime, Coin::Penny, Coin::Penny];
+    assert_eq!(total_value(&coins), 37);
+}
+
+#[test]
+fn total_of_no_coins() {
+    assert_eq!(total_value(&[]), 0);
+}
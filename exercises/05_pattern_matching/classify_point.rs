@@ -0,0 +1,10 @@
+// 练习：判断点的位置
+// 实现 classify，根据坐标返回点所在的位置：
+// - (0, 0) 返回 "原点"
+// - 在 x 轴上（y 为 0）返回 "x 轴"，在 y 轴上（x 为 0）返回 "y 轴"
+// - 其他情况返回所在的象限："第一象限"、"第二象限"、"第三象限" 或 "第四象限"
+// 提示：可以对元组进行解构，并使用匹配守卫（if 条件）区分正负
+
+fn classify(point: (i32, i32)) -> &'static str {
+    todo!()
+}
@@ -0,0 +1,13 @@
+// 练习：判断点的位置（参考答案）
+
+fn classify(point: (i32, i32)) -> &'static str {
+    match point {
+        (0, 0) => "原点",
+        (_, 0) => "x 轴",
+        (0, _) => "y 轴",
+        (x, y) if x > 0 && y > 0 => "第一象限",
+        (x, y) if x < 0 && y > 0 => "第二象限",
+        (x, y) if x < 0 && y < 0 => "第三象限",
+        _ => "第四象限",
+    }
+}
@@ -0,0 +1,20 @@
+#[test]
+fn origin() {
+    assert_eq!(classify((0, 0)), "原点");
+}
+
+#[test]
+fn points_on_axes() {
+    assert_eq!(classify((5, 0)), "x 轴");
+    assert_eq!(classify((-3, 0)), "x 轴");
+    assert_eq!(classify((0, 7)), "y 轴");
+    assert_eq!(classify((0, -2)), "y 轴");
+}
+
+#[test]
+fn quadrants() {
+    assert_eq!(classify((1, 1)), "第一象限");
+    assert_eq!(classify((-1, 1)), "第二象限");
+    assert_eq!(classify((-1, -1)), "第三象限");
+    assert_eq!(classify((1, -1)), "第四象限");
+}
@@ -0,0 +1,10 @@
+// 练习：统计单词出现的次数
+// 实现 word_count，统计文本中每个单词出现的次数
+// 单词之间用空白字符分隔，统计时不区分大小写（"Rust" 和 "rust" 是同一个单词）
+// 提示：split_whitespace 可以按空白拆分字符串，HashMap 的 entry API 可以方便地累加计数
+
+use std::collections::HashMap;
+
+fn word_count(text: &str) -> HashMap<String, usize> {
+    todo!()
+}
@@ -0,0 +1,13 @@
+// 练习：统计单词出现的次数（参考答案）
+
+use std::collections::HashMap;
+
+fn word_count(text: &str) -> HashMap<String, usize> {
+    let mut counts = HashMap::new();
+
+    for word in text.split_whitespace() {
+        *counts.entry(word.to_lowercase()).or_insert(0) += 1;
+    }
+
+    counts
+}
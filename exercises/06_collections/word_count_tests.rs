@@ -0,0 +1,27 @@
+#[test]
+fn counts_repeated_words() {
+    let counts = word_count("one fish two fish");
+    assert_eq!(counts.get("fish"), Some(&2));
+    assert_eq!(counts.get("one"), Some(&1));
+    assert_eq!(counts.get("two"), Some(&1));
+    assert_eq!(counts.len(), 3);
+}
+
+#[test]
+fn ignores_case() {
+    let counts = word_count("Rust rust RUST");
+    assert_eq!(counts.get("rust"), Some(&3));
+    assert_eq!(counts.len(), 1);
+}
+
+#[test]
+fn handles_extra_whitespace() {
+    let counts = word_count("  hello\tworld\n\nhello  ");
+    assert_eq!(counts.get("hello"), Some(&2));
+    assert_eq!(counts.get("world"), Some(&1));
+}
+
+#[test]
+fn empty_text_has_no_words() {
+    assert!(word_count("").is_empty());
+}
@@ -0,0 +1,17 @@
+// 练习：模块的可见性
+// geometry 模块中的 circle_area 和 rectangle_area 需要在模块外部调用：
+// - 实现这两个函数，圆周率使用 std::f64::consts::PI
+// - 修改可见性，让 geometry::shapes::circle_area 和 geometry::shapes::rectangle_area 可以从模块外部访问
+// 提示：模块中的项默认是私有的，外部访问时路径上的每一层都需要是公共的
+
+mod geometry {
+    mod shapes {
+        fn circle_area(radius: f64) -> f64 {
+            todo!()
+        }
+
+        fn rectangle_area(width: f64, height: f64) -> f64 {
+            todo!()
+        }
+    }
+}
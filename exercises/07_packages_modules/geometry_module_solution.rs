@@ -0,0 +1,15 @@
+// 练习：模块的可见性（参考答案）
+
+mod geometry {
+    pub mod shapes {
+        use std::f64::consts::PI;
+
+        pub fn circle_area(radius: f64) -> f64 {
+            PI * radius * radius
+        }
+
+        pub fn rectangle_area(width: f64, height: f64) -> f64 {
+            width * height
+        }
+    }
+}
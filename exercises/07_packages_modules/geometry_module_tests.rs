@@ -0,0 +1,10 @@
+#[test]
+fn circle_area_is_public() {
+    let area = geometry::shapes::circle_area(2.0);
+    assert!((area - 12.566370614359172).abs() < 1e-9);
+}
+
+#[test]
+fn rectangle_area_is_public() {
+    assert_eq!(geometry::shapes::rectangle_area(3.0, 4.5), 13.5);
+}
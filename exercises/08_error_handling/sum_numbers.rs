@@ -0,0 +1,12 @@
+// 练习：解析并求和
+// 实现 sum_numbers，把用逗号分隔的整数列表（例如 "1, 2, 3"）解析出来并求和
+// - 每个数字两边可能有空格
+// - 只要有一个数字无法解析，就返回对应的 ParseIntError
+// - 空字符串的和是 0
+// 提示：使用 ? 运算符可以在解析失败时提前返回错误
+
+use std::num::ParseIntError;
+
+fn sum_numbers(input: &str) -> Result<i32, ParseIntError> {
+    todo!()
+}
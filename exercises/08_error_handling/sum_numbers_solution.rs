@@ -0,0 +1,16 @@
+// 练习：解析并求和（参考答案）
+
+use std::num::ParseIntError;
+
+fn sum_numbers(input: &str) -> Result<i32, ParseIntError> {
+    if input.trim().is_empty() {
+        return Ok(0);
+    }
+
+    let mut sum = 0;
+    for part in input.split(',') {
+        sum += part.trim().parse::<i32>()?;
+    }
+
+    Ok(sum)
+}
@@ -0,0 +1,20 @@
+#[test]
+fn sums_numbers() {
+    assert_eq!(sum_numbers("1,2,3"), Ok(6));
+}
+
+#[test]
+fn trims_spaces() {
+    assert_eq!(sum_numbers(" 10 , -4 ,  5 "), Ok(11));
+}
+
+#[test]
+fn empty_input_is_zero() {
+    assert_eq!(sum_numbers(""), Ok(0));
+}
+
+#[test]
+fn reports_invalid_numbers() {
+    let error = sum_numbers("1, two, 3").unwrap_err();
+    assert_eq!(error, "two".parse::<i32>().unwrap_err());
+}
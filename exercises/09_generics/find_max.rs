@@ -0,0 +1,8 @@
+// 练习：泛型函数 find_max
+// 实现 find_max，返回切片中最大元素的引用，切片为空时返回 None
+// 这个函数需要同时适用于整数、浮点数和字符串等可以比较大小的类型
+// 提示：T: PartialOrd 约束让我们可以使用 > 比较两个 T 类型的值
+
+fn find_max<T: PartialOrd>(list: &[T]) -> Option<&T> {
+    todo!()
+}
@@ -0,0 +1,13 @@
+// 练习：泛型函数 find_max（参考答案）
+
+fn find_max<T: PartialOrd>(list: &[T]) -> Option<&T> {
+    let mut max = list.first()?;
+
+    for item in list {
+        if item > max {
+            max = item;
+        }
+    }
+
+    Some(max)
+}
@@ -0,0 +1,20 @@
+#[test]
+fn max_of_integers() {
+    assert_eq!(find_max(&[1, 5, 3, 9, 2]), Some(&9));
+}
+
+#[test]
+fn max_of_floats() {
+    assert_eq!(find_max(&[1.5, -5.2, 3.7]), Some(&3.7));
+}
+
+#[test]
+fn max_of_strings() {
+    assert_eq!(find_max(&["apple", "banana", "orange", "pear"]), Some(&"pear"));
+}
+
+#[test]
+fn max_of_empty_slice() {
+    let empty: [i32; 0] = [];
+    assert_eq!(find_max(&empty), None);
+}
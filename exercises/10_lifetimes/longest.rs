@@ -0,0 +1,10 @@
+// 练习：为 longest 添加生命周期注解
+// 下面的 longest 返回两个字符串切片中较长的那一个，长度相同时返回第一个
+// 这段代码现在无法通过编译：返回值是一个引用，编译器不知道它借用自 x 还是 y
+// - 为函数签名添加生命周期注解，让代码通过编译
+// - 实现函数体
+// 提示：运行 `rustlearn exercise 10.1` 先看看编译器给出的错误信息和建议
+
+fn longest(x: &str, y: &str) -> &str {
+    todo!()
+}
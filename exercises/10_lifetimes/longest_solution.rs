@@ -0,0 +1,5 @@
+// 练习：为 longest 添加生命周期注解（参考答案）
+
+fn longest<'a>(x: &'a str, y: &'a str) -> &'a str {
+    if y.len() > x.len() { y } else { x }
+}
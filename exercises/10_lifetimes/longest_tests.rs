@@ -0,0 +1,23 @@
+#[test]
+fn returns_longer_string() {
+    assert_eq!(longest("hello", "hi"), "hello");
+    assert_eq!(longest("hi", "hello"), "hello");
+}
+
+#[test]
+fn returns_first_when_equal() {
+    let first = String::from("abc");
+    let second = String::from("xyz");
+    assert!(std::ptr::eq(longest(&first, &second), first.as_str()));
+}
+
+#[test]
+fn works_with_different_scopes() {
+    let outer = String::from("long string is long");
+    let result;
+    {
+        let inner = String::from("xyz");
+        result = longest(outer.as_str(), inner.as_str()).to_string();
+    }
+    assert_eq!(result, "long string is long");
+}
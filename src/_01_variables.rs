@@ -10,45 +10,180 @@
 // 6. 常量和静态变量
 
 // 导入标准输出模块
+use crate::compile_error::CompileError;
+use crate::context::LessonContext;
+use crate::exercise::Exercise;
+use crate::graph::Prerequisite;
+use crate::lesson::{Lesson, Section};
+use crate::quiz::{Question, QuestionKind};
+
+// 本课在课程注册表中的登记信息
+// 主程序的菜单等前端都通过 Lesson trait 读取这些信息
+pub struct VariablesLesson;
+
+impl Lesson for VariablesLesson {
+    fn id(&self) -> &'static str {
+        "1"
+    }
+
+    fn title(&self) -> &'static str {
+        "变量和数据类型 (Variables and Data Types)"
+    }
+
+    fn summary(&self) -> &'static str {
+        "本示例将介绍 Rust 中的变量声明、可变性、基本数据类型和类型转换。"
+    }
+
+    fn topics(&self) -> &'static [&'static str] {
+        &[
+            "变量的可变性与不可变性",
+            "变量声明和初始化",
+            "基本数据类型：整数、浮点数、布尔值、字符",
+            "类型标注",
+            "类型转换",
+            "常量和静态变量",
+        ]
+    }
+
+    fn sections(&self) -> &'static [Section] {
+        &[
+            Section { id: "variable_mutability", title: "变量的可变性与不可变性", run: variable_mutability },
+            Section { id: "basic_data_types", title: "基本数据类型", run: basic_data_types },
+            Section { id: "type_annotations", title: "类型标注", run: type_annotations },
+            Section { id: "type_conversions", title: "类型转换", run: type_conversions },
+            Section { id: "constants_and_statics", title: "常量和静态变量", run: constants_and_statics },
+        ]
+    }
+
+    fn source(&self) -> &'static str {
+        include_str!("_01_variables.rs")
+    }
+
+    fn source_path(&self) -> &'static str {
+        file!()
+    }
+
+    fn quiz(&self) -> &'static [Question] {
+        QUIZ
+    }
+
+    fn exercises(&self) -> &'static [Exercise] {
+        EXERCISES
+    }
+
+    fn compile_errors(&self) -> &'static [CompileError] {
+        COMPILE_ERRORS
+    }
+
+    fn prerequisites(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    fn section_prerequisites(&self) -> &'static [Prerequisite] {
+        &[]
+    }
+
+    fn run(&self, ctx: &mut LessonContext) {
+        run(ctx);
+    }
+}
+
+// 本课的测验题目
+const QUIZ: &[Question] = &[
+    Question {
+        prompt: "下面的代码会输出什么？",
+        kind: QuestionKind::PredictOutput {
+            section: "variable_mutability",
+            code: r#"let mut y = 5;
+y = 10;
+let y = y + 5;
+println!("变量遮蔽后，y = {}", y);"#,
+            options: &["变量遮蔽后，y = 5", "变量遮蔽后，y = 10", "变量遮蔽后，y = 15"],
+            answer: 2,
+        },
+        explanation: "y 先被修改为 10，随后 let y = y + 5 声明了一个新的 y 遮蔽原来的变量，新值是 10 + 5 = 15",
+    },
+    Question {
+        prompt: "声明一个之后还可以修改的变量，应该使用哪种写法？",
+        kind: QuestionKind::Choice {
+            options: &["let x = 5;", "let mut x = 5;", "const X: i32 = 5;"],
+            answer: 1,
+        },
+        explanation: "Rust 中的变量默认是不可变的，需要使用 mut 关键字才能修改；常量永远不能修改",
+    },
+    Question {
+        prompt: "常量的值必须在编译时就能确定。",
+        kind: QuestionKind::TrueFalse { answer: true },
+        explanation: "常量使用 const 声明，必须标注类型，并且只能用编译时可以计算出的表达式赋值",
+    },
+];
+
+// 本课的动手练习
+const EXERCISES: &[Exercise] = &[
+    Exercise {
+        name: "average",
+        title: "计算平均值",
+        description: "实现 average 函数，返回切片中所有整数的平均值，切片为空时返回 0.0",
+        starter: include_str!("../exercises/01_variables/average.rs"),
+        tests: include_str!("../exercises/01_variables/average_tests.rs"),
+        solution: include_str!("../exercises/01_variables/average_solution.rs"),
+        hints: &[
+            "整数不能直接除以 usize，先用 as 把总和和长度都转换成 f64",
+            "两个很大的 i32 相加可能溢出，可以先把每个值转换成 i64 或 f64 再求和",
+            "别忘了处理空切片，否则会得到 NaN",
+        ],
+    },
+];
+
+// 本课的编译错误示例
+const COMPILE_ERRORS: &[CompileError] = &[
+    CompileError {
+        name: "assign_twice",
+        title: "给不可变变量第二次赋值",
+        section: "variable_mutability",
+        code: "E0384",
+        broken: include_str!("../compile_errors/01_variables/assign_twice.rs"),
+        fixed: include_str!("../compile_errors/01_variables/assign_twice_fixed.rs"),
+        explanation: "用 let 声明的变量默认是不可变的，绑定了值之后就不能再赋值。编译器指出了第一次赋值的位置，并建议把它改成 let mut x。如果只是想用新的值，也可以用 let x = 10; 遮蔽原来的变量",
+    },
+];
 
 // 定义一个公共函数 run()，作为本模块的入口点
 // 当用户在主程序中选择 1 时，将调用此函数
-pub fn run() {
-    println!("=== 第1课：变量和数据类型 ===");
-    println!("本示例将介绍 Rust 中的变量声明、可变性、基本数据类型和类型转换。\n");
-    
-    // 调用各个示例函数
-    variable_mutability();
-    basic_data_types();
-    type_annotations();
-    type_conversions();
-    constants_and_statics();
+pub fn run(ctx: &mut LessonContext) {
+    ctx.lesson_heading("第1课：变量和数据类型");
+    writeln!(ctx, "本示例将介绍 Rust 中的变量声明、可变性、基本数据类型和类型转换。\n");
+    
+    // 按顺序调用各个示例函数，小节列表统一登记在 sections() 中
+    for section in VariablesLesson.sections() {
+        (section.run)(ctx);
+    }
 }
 
 // 演示变量的可变性与不可变性
 // 在 Rust 中，默认情况下变量是不可变的（immutable）
 // 需要使用 mut 关键字来声明可变变量
-fn variable_mutability() {
-    println!("\n--- 变量的可变性与不可变性 ---");
+fn variable_mutability(ctx: &mut LessonContext) {
+    ctx.section_heading("变量的可变性与不可变性");
     
     // 声明一个不可变的变量
     let x = 5;
-    println!("不可变变量 x = {}", x);
+    writeln!(ctx, "不可变变量 x = {}", x);
     
     // 以下代码会导致编译错误，因为尝试修改不可变变量
     // x = 10; // 错误：不能对不可变变量 x 进行二次赋值
     
     // 声明一个可变的变量，使用 mut 关键字
     let mut y = 5;
-    println!("可变变量 y = {}", y);
+    writeln!(ctx, "可变变量 y = {}", y);
     
     // 可以修改可变变量的值
     y = 10;
-    println!("修改后，可变变量 y = {}", y);
+    writeln!(ctx, "修改后，可变变量 y = {}", y);
     
     // 变量遮蔽（Variable Shadowing）：使用相同的名称声明新变量
     let y = y + 5; // 这里的 y 是一个新的不可变变量，遮蔽了原来的可变变量 y
-    println!("变量遮蔽后，y = {}", y);
+    writeln!(ctx, "变量遮蔽后，y = {}", y);
     
     // 运行结果：
     // 不可变变量 x = 5
@@ -59,8 +194,8 @@ fn variable_mutability() {
 
 // 演示 Rust 的基本数据类型
 // Rust 提供了多种基本数据类型，包括整数、浮点数、布尔值和字符
-fn basic_data_types() {
-    println!("\n--- 基本数据类型 ---");
+fn basic_data_types(ctx: &mut LessonContext) {
+    ctx.section_heading("基本数据类型");
     
     // 整数类型
     // i 表示有符号整数，u 表示无符号整数，后面的数字表示位数
@@ -71,30 +206,30 @@ fn basic_data_types() {
     let integer_isize: isize = -100; // 指针大小的有符号整数
     let integer_usize: usize = 100; // 指针大小的无符号整数，常用于索引
     
-    println!("整数类型：");
-    println!("i32: {}", integer_i32);
-    println!("u32: {}", integer_u32);
-    println!("i64: {}", integer_i64);
-    println!("u64: {}", integer_u64);
-    println!("isize: {}", integer_isize);
-    println!("usize: {}", integer_usize);
+    writeln!(ctx, "整数类型：");
+    writeln!(ctx, "i32: {}", integer_i32);
+    writeln!(ctx, "u32: {}", integer_u32);
+    writeln!(ctx, "i64: {}", integer_i64);
+    writeln!(ctx, "u64: {}", integer_u64);
+    writeln!(ctx, "isize: {}", integer_isize);
+    writeln!(ctx, "usize: {}", integer_usize);
     
     // 浮点数类型
     // f32 是单精度浮点数，f64 是双精度浮点数
     let float_f32: f32 = 3.14; // 单精度浮点数
     let float_f64: f64 = 3.14159265359; // 双精度浮点数（默认浮点类型）
     
-    println!("\n浮点数类型：");
-    println!("f32: {}", float_f32);
-    println!("f64: {}", float_f64);
+    writeln!(ctx, "\n浮点数类型：");
+    writeln!(ctx, "f32: {}", float_f32);
+    writeln!(ctx, "f64: {}", float_f64);
     
     // 布尔值类型
     let boolean_true: bool = true;
     let boolean_false: bool = false;
     
-    println!("\n布尔值类型：");
-    println!("true: {}", boolean_true);
-    println!("false: {}", boolean_false);
+    writeln!(ctx, "\n布尔值类型：");
+    writeln!(ctx, "true: {}", boolean_true);
+    writeln!(ctx, "false: {}", boolean_false);
     
     // 字符类型
     // Rust 的 char 类型表示 Unicode 标量值
@@ -102,10 +237,10 @@ fn basic_data_types() {
     let char_zh: char = '中';
     let char_emoji: char = '😊';
     
-    println!("\n字符类型：");
-    println!("'a': {}", char_a);
-    println!("'中': {}", char_zh);
-    println!("'😊': {}", char_emoji);
+    writeln!(ctx, "\n字符类型：");
+    writeln!(ctx, "'a': {}", char_a);
+    writeln!(ctx, "'中': {}", char_zh);
+    writeln!(ctx, "'😊': {}", char_emoji);
     
     // 运行结果：
     // 整数类型：
@@ -132,31 +267,31 @@ fn basic_data_types() {
 
 // 演示类型标注
 // 在 Rust 中，编译器通常可以推断变量的类型，但有时需要显式标注
-fn type_annotations() {
-    println!("\n--- 类型标注 ---");
+fn type_annotations(ctx: &mut LessonContext) {
+    ctx.section_heading("类型标注");
     
     // 编译器可以推断类型
     let inferred_integer = 42; // 编译器推断为 i32
     let inferred_float = 3.14; // 编译器推断为 f64
     let inferred_boolean = true; // 编译器推断为 bool
     
-    println!("编译器推断的类型：");
-    println!("inferred_integer = {}, 类型: i32", inferred_integer);
-    println!("inferred_float = {}, 类型: f64", inferred_float);
-    println!("inferred_boolean = {}, 类型: bool", inferred_boolean);
+    writeln!(ctx, "编译器推断的类型：");
+    writeln!(ctx, "inferred_integer = {}, 类型: i32", inferred_integer);
+    writeln!(ctx, "inferred_float = {}, 类型: f64", inferred_float);
+    writeln!(ctx, "inferred_boolean = {}, 类型: bool", inferred_boolean);
     
     // 显式类型标注
     let explicit_integer: i64 = 42; // 显式指定为 i64
     let explicit_float: f32 = 3.14; // 显式指定为 f32
     
-    println!("\n显式标注的类型：");
-    println!("explicit_integer = {}, 类型: i64", explicit_integer);
-    println!("explicit_float = {}, 类型: f32", explicit_float);
+    writeln!(ctx, "\n显式标注的类型：");
+    writeln!(ctx, "explicit_integer = {}, 类型: i64", explicit_integer);
+    writeln!(ctx, "explicit_float = {}, 类型: f32", explicit_float);
     
     // 类型标注在函数参数和返回值中的应用
-    println!("\n调用 add 函数：");
+    writeln!(ctx, "\n调用 add 函数：");
     let result = add(10, 20);
-    println!("10 + 20 = {}", result);
+    writeln!(ctx, "10 + 20 = {}", result);
     
     // 运行结果：
     // 编译器推断的类型：
@@ -174,36 +309,36 @@ fn type_annotations() {
 
 // 演示类型转换
 // Rust 不允许隐式类型转换，必须使用 as 关键字进行显式转换
-fn type_conversions() {
-    println!("\n--- 类型转换 ---");
+fn type_conversions(ctx: &mut LessonContext) {
+    ctx.section_heading("类型转换");
     
     // 整数之间的转换
     let a: i32 = 100;
     let b: u32 = a as u32; // 将 i32 转换为 u32
-    println!("i32 {} 转换为 u32: {}", a, b);
+    writeln!(ctx, "i32 {} 转换为 u32: {}", a, b);
     
     // 整数转换为浮点数
     let c: i32 = 42;
     let d: f64 = c as f64; // 将 i32 转换为 f64
-    println!("i32 {} 转换为 f64: {}", c, d);
+    writeln!(ctx, "i32 {} 转换为 f64: {}", c, d);
     
     // 浮点数转换为整数（会截断小数部分）
     let e: f64 = 3.99;
     let f: i32 = e as i32; // 将 f64 转换为 i32
-    println!("f64 {} 转换为 i32: {}", e, f);
+    writeln!(ctx, "f64 {} 转换为 i32: {}", e, f);
     
     // 布尔值转换为整数
     let g: bool = true;
     let h: i32 = g as i32; // true 转换为 1
     let i: bool = false;
     let j: i32 = i as i32; // false 转换为 0
-    println!("bool true 转换为 i32: {}", h);
-    println!("bool false 转换为 i32: {}", j);
+    writeln!(ctx, "bool true 转换为 i32: {}", h);
+    writeln!(ctx, "bool false 转换为 i32: {}", j);
     
     // 字符转换为整数（获取 Unicode 码点）
     let k: char = 'A';
     let l: u32 = k as u32; // 获取 'A' 的 Unicode 码点
-    println!("char 'A' 转换为 u32 (Unicode 码点): {}", l);
+    writeln!(ctx, "char 'A' 转换为 u32 (Unicode 码点): {}", l);
     
     // 运行结果：
     // i32 100 转换为 u32: 100
@@ -216,17 +351,17 @@ fn type_conversions() {
 
 // 演示常量和静态变量
 // 常量（const）和静态变量（static）都是在编译时已知的值，但有一些重要区别
-fn constants_and_statics() {
-    println!("\n--- 常量和静态变量 ---");
+fn constants_and_statics(ctx: &mut LessonContext) {
+    ctx.section_heading("常量和静态变量");
     
     // 常量声明，使用 const 关键字
     // 常量的值必须在编译时确定
     const MAX_SCORE: u32 = 100;
-    println!("常量 MAX_SCORE = {}", MAX_SCORE);
+    writeln!(ctx, "常量 MAX_SCORE = {}", MAX_SCORE);
     
     // 常量可以在任何作用域中声明
     const PI: f64 = 3.14159;
-    println!("常量 PI = {}", PI);
+    writeln!(ctx, "常量 PI = {}", PI);
     
     // 静态变量声明，使用 static 关键字
     // 静态变量在程序的整个生命周期内都存在
@@ -236,11 +371,11 @@ fn constants_and_statics() {
     unsafe {
         COUNTER += 1;
         let counter_value1 = COUNTER;
-        println!("静态变量 COUNTER = {}", counter_value1);
+        writeln!(ctx, "静态变量 COUNTER = {}", counter_value1);
         
         COUNTER += 1;
         let counter_value2 = COUNTER;
-        println!("更新后，静态变量 COUNTER = {}", counter_value2);
+        writeln!(ctx, "更新后，静态变量 COUNTER = {}", counter_value2);
     }
     
     // 常量和静态变量的区别：
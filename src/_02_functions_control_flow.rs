@@ -13,39 +13,167 @@
 // 9. match 表达式
 
 // 导入标准输出模块
+use crate::compile_error::CompileError;
+use crate::context::LessonContext;
+use crate::exercise::Exercise;
+use crate::graph::Prerequisite;
+use crate::lesson::{Lesson, Section};
+use crate::quiz::{Question, QuestionKind};
+
+// 本课在课程注册表中的登记信息
+// 主程序的菜单等前端都通过 Lesson trait 读取这些信息
+pub struct FunctionsControlFlowLesson;
+
+impl Lesson for FunctionsControlFlowLesson {
+    fn id(&self) -> &'static str {
+        "2"
+    }
+
+    fn title(&self) -> &'static str {
+        "函数和流程控制 (Functions and Control Flow)"
+    }
+
+    fn summary(&self) -> &'static str {
+        "本示例将介绍 Rust 中的函数定义、参数传递、返回值以及各种流程控制语句。"
+    }
+
+    fn topics(&self) -> &'static [&'static str] {
+        &[
+            "函数定义与调用",
+            "函数参数",
+            "函数返回值",
+            "if/else 条件语句",
+            "loop 循环语句",
+            "while 循环语句",
+            "for 循环语句",
+            "break 和 continue 关键字",
+            "match 表达式",
+        ]
+    }
+
+    fn sections(&self) -> &'static [Section] {
+        &[
+            Section { id: "function_definition", title: "函数的定义与调用", run: function_definition },
+            Section { id: "function_parameters", title: "函数参数", run: function_parameters },
+            Section { id: "function_return_values", title: "函数返回值", run: function_return_values },
+            Section { id: "if_else_statements", title: "if/else 条件语句", run: if_else_statements },
+            Section { id: "loop_statements", title: "loop 循环语句", run: loop_statements },
+            Section { id: "while_statements", title: "while 循环语句", run: while_statements },
+            Section { id: "for_statements", title: "for 循环语句", run: for_statements },
+            Section { id: "break_continue_keywords", title: "break 和 continue 关键字", run: break_continue_keywords },
+            Section { id: "match_expressions", title: "match 表达式", run: match_expressions },
+        ]
+    }
+
+    fn source(&self) -> &'static str {
+        include_str!("_02_functions_control_flow.rs")
+    }
+
+    fn source_path(&self) -> &'static str {
+        file!()
+    }
+
+    fn quiz(&self) -> &'static [Question] {
+        QUIZ
+    }
+
+    fn exercises(&self) -> &'static [Exercise] {
+        EXERCISES
+    }
+
+    fn compile_errors(&self) -> &'static [CompileError] {
+        &[]
+    }
+
+    fn prerequisites(&self) -> &'static [&'static str] {
+        &["1"]
+    }
+
+    fn section_prerequisites(&self) -> &'static [Prerequisite] {
+        &[]
+    }
+
+    fn run(&self, ctx: &mut LessonContext) {
+        run(ctx);
+    }
+}
+
+// 本课的测验题目
+const QUIZ: &[Question] = &[
+    Question {
+        prompt: "下面的代码最后一行会输出什么？",
+        kind: QuestionKind::PredictOutput {
+            section: "loop_statements",
+            code: r#"let mut attempts = 0;
+let result = loop {
+    attempts += 1;
+    if attempts == 5 {
+        break attempts * 10;
+    }
+};
+println!("loop 表达式返回值: {}", result);"#,
+            options: &["loop 表达式返回值: 5", "loop 表达式返回值: 50", "loop 表达式返回值: 60"],
+            answer: 1,
+        },
+        explanation: "attempts 等于 5 时执行 break attempts * 10，break 后面的值就是整个 loop 表达式的值，也就是 50",
+    },
+    Question {
+        prompt: "函数体中最后一个表达式不加分号时，会发生什么？",
+        kind: QuestionKind::Choice {
+            options: &["编译错误", "它的值成为函数的返回值", "函数返回单元类型 ()"],
+            answer: 1,
+        },
+        explanation: "不加分号的最后一个表达式就是函数的返回值；加上分号后它变成语句，函数返回 ()",
+    },
+    Question {
+        prompt: "在 Rust 中，if 是一个表达式，可以写在 let 语句的右边。",
+        kind: QuestionKind::TrueFalse { answer: true },
+        explanation: "if 是表达式，例如 let number = if condition { 5 } else { 6 };，但各个分支的类型必须相同",
+    },
+];
+
+// 本课的动手练习
+const EXERCISES: &[Exercise] = &[
+    Exercise {
+        name: "fizzbuzz",
+        title: "FizzBuzz",
+        description: "实现 fizzbuzz 函数：能被 15 整除时返回 \"FizzBuzz\"，能被 3 整除时返回 \"Fizz\"，能被 5 整除时返回 \"Buzz\"，其他情况返回数字本身",
+        starter: include_str!("../exercises/02_functions_control_flow/fizzbuzz.rs"),
+        tests: include_str!("../exercises/02_functions_control_flow/fizzbuzz_tests.rs"),
+        solution: include_str!("../exercises/02_functions_control_flow/fizzbuzz_solution.rs"),
+        hints: &[
+            "先判断能否被 15 整除，否则 15 会在判断 3 的分支中返回 \"Fizz\"",
+            "数字可以通过 n.to_string() 转换成 String",
+        ],
+    },
+];
 
 // 定义一个公共函数 run()，作为本模块的入口点
 // 当用户在主程序中选择 2 时，将调用此函数
-pub fn run() {
-    println!("=== 第2课：函数和流程控制 ===");
-    println!("本示例将介绍 Rust 中的函数定义、参数传递、返回值以及各种流程控制语句。\n");
-    
-    // 调用各个示例函数
-    function_definition();
-    function_parameters();
-    function_return_values();
-    if_else_statements();
-    loop_statements();
-    while_statements();
-    for_statements();
-    break_continue_keywords();
-    match_expressions();
+pub fn run(ctx: &mut LessonContext) {
+    ctx.lesson_heading("第2课：函数和流程控制");
+    writeln!(ctx, "本示例将介绍 Rust 中的函数定义、参数传递、返回值以及各种流程控制语句。\n");
+    
+    // 按顺序调用各个示例函数，小节列表统一登记在 sections() 中
+    for section in FunctionsControlFlowLesson.sections() {
+        (section.run)(ctx);
+    }
 }
 
 // 演示函数的定义与调用
 // 在 Rust 中，函数使用 fn 关键字定义，函数名使用 snake_case 命名规范
-fn function_definition() {
-    println!("\n--- 函数的定义与调用 ---");
+fn function_definition(ctx: &mut LessonContext) {
+    ctx.section_heading("函数的定义与调用");
     
     // 调用一个简单的函数
-    say_hello();
+    say_hello(ctx);
     
     // 调用带参数的函数
-    greet_person("Alice");
+    greet_person(ctx, "Alice");
     
     // 调用带返回值的函数
     let sum = calculate_sum(5, 10);
-    println!("5 + 10 = {}", sum);
+    writeln!(ctx, "5 + 10 = {}", sum);
     
     // 运行结果：
     // Hello, Rust!
@@ -55,21 +183,21 @@ fn function_definition() {
 
 // 演示函数参数
 // Rust 中的函数参数需要显式指定类型
-fn function_parameters() {
-    println!("\n--- 函数参数 ---");
+fn function_parameters(ctx: &mut LessonContext) {
+    ctx.section_heading("函数参数");
     
     // 调用带多个参数的函数
     let result = multiply(3, 4);
-    println!("3 * 4 = {}", result);
+    writeln!(ctx, "3 * 4 = {}", result);
     
     // 调用带不同类型参数的函数
     let description = describe_number(42, true);
-    println!("描述: {}", description);
+    writeln!(ctx, "描述: {}", description);
     
     // 调用带可变引用参数的函数
     let mut value = 10;
     increment(&mut value, 5);
-    println!("递增后的值: {}", value);
+    writeln!(ctx, "递增后的值: {}", value);
     
     // 运行结果：
     // 3 * 4 = 12
@@ -79,20 +207,20 @@ fn function_parameters() {
 
 // 演示函数返回值
 // Rust 中的函数可以返回单个值，使用 -> 指定返回类型
-fn function_return_values() {
-    println!("\n--- 函数返回值 ---");
+fn function_return_values(ctx: &mut LessonContext) {
+    ctx.section_heading("函数返回值");
     
     // 调用返回单个值的函数
     let square_result = square(5);
-    println!("5 的平方 = {}", square_result);
+    writeln!(ctx, "5 的平方 = {}", square_result);
     
     // 调用使用 return 关键字的函数
     let max_value = find_max(10, 20);
-    println!("10 和 20 中的最大值 = {}", max_value);
+    writeln!(ctx, "10 和 20 中的最大值 = {}", max_value);
     
     // 调用返回元组的函数（多返回值）
     let (sum_result, product_result) = calculate_sum_and_product(3, 7);
-    println!("3 + 7 = {}, 3 * 7 = {}", sum_result, product_result);
+    writeln!(ctx, "3 + 7 = {}, 3 * 7 = {}", sum_result, product_result);
     
     // 运行结果：
     // 5 的平方 = 25
@@ -102,15 +230,15 @@ fn function_return_values() {
 
 // 演示 if/else 条件语句
 // Rust 的 if 语句是表达式，而不是语句，这意味着它可以返回一个值
-fn if_else_statements() {
-    println!("\n--- if/else 条件语句 ---");
+fn if_else_statements(ctx: &mut LessonContext) {
+    ctx.section_heading("if/else 条件语句");
     
     // 基本的 if/else 结构
     let number = 7;
     if number > 5 {
-        println!("{} 大于 5", number);
+        writeln!(ctx, "{} 大于 5", number);
     } else {
-        println!("{} 小于或等于 5", number);
+        writeln!(ctx, "{} 小于或等于 5", number);
     }
     
     // if 作为表达式返回值
@@ -119,18 +247,18 @@ fn if_else_statements() {
     } else {
         "奇数"
     };
-    println!("{} 是一个 {}", number, result);
+    writeln!(ctx, "{} 是一个 {}", number, result);
     
     // 多个 else if 分支
     let score = 85;
     if score >= 90 {
-        println!("优秀");
+        writeln!(ctx, "优秀");
     } else if score >= 80 {
-        println!("良好");
+        writeln!(ctx, "良好");
     } else if score >= 60 {
-        println!("及格");
+        writeln!(ctx, "及格");
     } else {
-        println!("不及格");
+        writeln!(ctx, "不及格");
     }
     
     // 运行结果：
@@ -141,14 +269,14 @@ fn if_else_statements() {
 
 // 演示 loop 循环语句
 // loop 语句创建一个无限循环，可以使用 break 语句退出
-fn loop_statements() {
-    println!("\n--- loop 循环语句 ---");
+fn loop_statements(ctx: &mut LessonContext) {
+    ctx.section_heading("loop 循环语句");
     
     // 基本的 loop 循环
     let mut count = 0;
     loop {
         count += 1;
-        println!("循环计数: {}", count);
+        writeln!(ctx, "循环计数: {}", count);
         
         if count >= 3 {
             break; // 当计数达到 3 时退出循环
@@ -159,13 +287,13 @@ fn loop_statements() {
     let mut attempts = 0;
     let result = loop {
         attempts += 1;
-        println!("尝试次数: {}", attempts);
+        writeln!(ctx, "尝试次数: {}", attempts);
         
         if attempts == 5 {
             break attempts * 10; // 返回一个值
         }
     };
-    println!("loop 表达式返回值: {}", result);
+    writeln!(ctx, "loop 表达式返回值: {}", result);
     
     // 运行结果：
     // 循环计数: 1
@@ -181,22 +309,22 @@ fn loop_statements() {
 
 // 演示 while 循环语句
 // while 循环在条件为真时执行
-fn while_statements() {
-    println!("\n--- while 循环语句 ---");
+fn while_statements(ctx: &mut LessonContext) {
+    ctx.section_heading("while 循环语句");
     
     // 基本的 while 循环
     let mut countdown = 5;
     while countdown > 0 {
-        println!("倒计时: {}", countdown);
+        writeln!(ctx, "倒计时: {}", countdown);
         countdown -= 1;
     }
-    println!("倒计时结束！");
+    writeln!(ctx, "倒计时结束！");
     
     // 使用 while 循环遍历数组
     let numbers = [10, 20, 30, 40, 50];
     let mut index = 0;
     while index < numbers.len() {
-        println!("数组元素[{}]: {}", index, numbers[index]);
+        writeln!(ctx, "数组元素[{}]: {}", index, numbers[index]);
         index += 1;
     }
     
@@ -216,32 +344,32 @@ fn while_statements() {
 
 // 演示 for 循环语句
 // for 循环在迭代器上执行，是 Rust 中最常用的循环形式
-fn for_statements() {
-    println!("\n--- for 循环语句 ---");
+fn for_statements(ctx: &mut LessonContext) {
+    ctx.section_heading("for 循环语句");
     
     // 遍历范围
-    println!("遍历范围 1 到 5:");
+    writeln!(ctx, "遍历范围 1 到 5:");
     for number in 1..6 {
-        println!("数字: {}", number);
+        writeln!(ctx, "数字: {}", number);
     }
     
     // 遍历数组
     let fruits = ["苹果", "香蕉", "橙子", "葡萄"];
-    println!("\n遍历水果数组:");
+    writeln!(ctx, "\n遍历水果数组:");
     for fruit in fruits.iter() {
-        println!("水果: {}", fruit);
+        writeln!(ctx, "水果: {}", fruit);
     }
     
     // 遍历带索引的数组
-    println!("\n遍历带索引的水果数组:");
+    writeln!(ctx, "\n遍历带索引的水果数组:");
     for (index, fruit) in fruits.iter().enumerate() {
-        println!("水果[{}]: {}", index, fruit);
+        writeln!(ctx, "水果[{}]: {}", index, fruit);
     }
     
     // 遍历字符串
-    println!("\n遍历字符串中的字符:");
+    writeln!(ctx, "\n遍历字符串中的字符:");
     for c in "Hello".chars() {
-        println!("字符: {}", c);
+        writeln!(ctx, "字符: {}", c);
     }
     
     // 运行结果：
@@ -274,26 +402,26 @@ fn for_statements() {
 
 // 演示 break 和 continue 关键字
 // break 用于退出循环，continue 用于跳过当前迭代，进入下一次迭代
-fn break_continue_keywords() {
-    println!("\n--- break 和 continue 关键字 ---");
+fn break_continue_keywords(ctx: &mut LessonContext) {
+    ctx.section_heading("break 和 continue 关键字");
     
     // 使用 break 退出循环
-    println!("寻找第一个大于 10 的数字:");
+    writeln!(ctx, "寻找第一个大于 10 的数字:");
     for number in 1..20 {
-        println!("检查: {}", number);
+        writeln!(ctx, "检查: {}", number);
         if number > 10 {
-            println!("找到大于 10 的数字: {}", number);
+            writeln!(ctx, "找到大于 10 的数字: {}", number);
             break;
         }
     }
     
     // 使用 continue 跳过某些迭代
-    println!("\n打印 1 到 10 之间的偶数:");
+    writeln!(ctx, "\n打印 1 到 10 之间的偶数:");
     for number in 1..11 {
         if number % 2 != 0 {
             continue; // 跳过奇数
         }
-        println!("偶数: {}", number);
+        writeln!(ctx, "偶数: {}", number);
     }
     
     // 运行结果：
@@ -321,18 +449,18 @@ fn break_continue_keywords() {
 
 // 演示 match 表达式
 // match 表达式类似于 switch 语句，但更加强大
-fn match_expressions() {
-    println!("\n--- match 表达式 ---");
+fn match_expressions(ctx: &mut LessonContext) {
+    ctx.section_heading("match 表达式");
     
     // 基本的 match 表达式
     let number = 3;
     match number {
-        1 => println!("一"),
-        2 => println!("二"),
-        3 => println!("三"),
-        4 => println!("四"),
-        5 => println!("五"),
-        _ => println!("其他数字"), // _ 是通配符，匹配任何值
+        1 => writeln!(ctx, "一"),
+        2 => writeln!(ctx, "二"),
+        3 => writeln!(ctx, "三"),
+        4 => writeln!(ctx, "四"),
+        5 => writeln!(ctx, "五"),
+        _ => writeln!(ctx, "其他数字"), // _ 是通配符，匹配任何值
     }
     
     // match 作为表达式返回值
@@ -343,16 +471,16 @@ fn match_expressions() {
         "yellow" => "注意",
         _ => "未知颜色",
     };
-    println!("颜色 {} 表示: {}", color, status);
+    writeln!(ctx, "颜色 {} 表示: {}", color, status);
     
     // 匹配范围
     let score = 85;
     match score {
-        0..=59 => println!("不及格"),
-        60..=79 => println!("及格"),
-        80..=89 => println!("良好"),
-        90..=100 => println!("优秀"),
-        _ => println!("分数无效"),
+        0..=59 => writeln!(ctx, "不及格"),
+        60..=79 => writeln!(ctx, "及格"),
+        80..=89 => writeln!(ctx, "良好"),
+        90..=100 => writeln!(ctx, "优秀"),
+        _ => writeln!(ctx, "分数无效"),
     }
     
     // 运行结果：
@@ -364,14 +492,15 @@ fn match_expressions() {
 // 以下是示例中使用的辅助函数
 
 // 一个简单的无参数、无返回值的函数
-fn say_hello() {
-    println!("Hello, Rust!");
+// ctx 是课程的输出上下文，只用来代替 println! 打印文字，不算作示例的参数
+fn say_hello(ctx: &mut LessonContext) {
+    writeln!(ctx, "Hello, Rust!");
 }
 
 // 带一个参数的函数
 // 参数 name: &str - 要问候的人的名字
-fn greet_person(name: &str) {
-    println!("Hello, {}!", name);
+fn greet_person(ctx: &mut LessonContext, name: &str) {
+    writeln!(ctx, "Hello, {}!", name);
 }
 
 // 带两个参数并返回结果的函数
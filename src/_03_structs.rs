@@ -12,28 +12,166 @@
 // 8. 解构结构体
 
 // 导入标准输出模块
+use crate::compile_error::CompileError;
+use crate::context::LessonContext;
+use crate::exercise::Exercise;
+use crate::graph::Prerequisite;
+use crate::lesson::{Lesson, Section};
+use crate::quiz::{Question, QuestionKind};
+
+// 本课在课程注册表中的登记信息
+// 主程序的菜单等前端都通过 Lesson trait 读取这些信息
+pub struct StructsLesson;
+
+impl Lesson for StructsLesson {
+    fn id(&self) -> &'static str {
+        "3"
+    }
+
+    fn title(&self) -> &'static str {
+        "结构体 (Structs)"
+    }
+
+    fn summary(&self) -> &'static str {
+        "本示例将介绍 Rust 中的结构体定义、实例化、方法和关联函数等知识。"
+    }
+
+    fn topics(&self) -> &'static [&'static str] {
+        &[
+            "结构体定义与实例化",
+            "元组结构体",
+            "单元结构体",
+            "结构体方法",
+            "关联函数",
+            "结构体字段可见性",
+            "结构体更新语法",
+            "解构结构体",
+        ]
+    }
+
+    fn sections(&self) -> &'static [Section] {
+        &[
+            Section { id: "struct_definition", title: "结构体定义与实例化", run: struct_definition },
+            Section { id: "tuple_structs", title: "元组结构体", run: tuple_structs },
+            Section { id: "unit_structs", title: "单元结构体", run: unit_structs },
+            Section { id: "struct_methods", title: "结构体方法", run: struct_methods },
+            Section { id: "associated_functions", title: "关联函数", run: associated_functions },
+            Section { id: "struct_field_visibility", title: "结构体字段可见性", run: struct_field_visibility },
+            Section { id: "struct_update_syntax", title: "结构体更新语法", run: struct_update_syntax },
+            Section { id: "destructuring_structs", title: "解构结构体", run: destructuring_structs },
+        ]
+    }
+
+    fn source(&self) -> &'static str {
+        include_str!("_03_structs.rs")
+    }
+
+    fn source_path(&self) -> &'static str {
+        file!()
+    }
+
+    fn quiz(&self) -> &'static [Question] {
+        QUIZ
+    }
+
+    fn exercises(&self) -> &'static [Exercise] {
+        EXERCISES
+    }
+
+    fn compile_errors(&self) -> &'static [CompileError] {
+        &[]
+    }
+
+    fn prerequisites(&self) -> &'static [&'static str] {
+        &["2"]
+    }
+
+    fn section_prerequisites(&self) -> &'static [Prerequisite] {
+        &[]
+    }
+
+    fn run(&self, ctx: &mut LessonContext) {
+        run(ctx);
+    }
+}
+
+// 本课的测验题目
+const QUIZ: &[Question] = &[
+    Question {
+        prompt: "下面的代码会输出什么？",
+        kind: QuestionKind::PredictOutput {
+            section: "struct_methods",
+            code: r#"fn can_hold(&self, other: &Rectangle) -> bool {
+    self.width >= other.width && self.height >= other.height
+}
+
+let rect1 = Rectangle { width: 30, height: 50 };
+let rect2 = Rectangle { width: 20, height: 40 };
+println!("rect1 可以容纳 rect2: {}", rect1.can_hold(&rect2));"#,
+            options: &["rect1 可以容纳 rect2: true", "rect1 可以容纳 rect2: false"],
+            answer: 0,
+        },
+        explanation: "rect1 的宽 30 不小于 20，高 50 也不小于 40，所以 can_hold 返回 true",
+    },
+    Question {
+        prompt: "方法的第一个参数写成 &self 表示什么？",
+        kind: QuestionKind::Choice {
+            options: &["获取实例的所有权", "不可变地借用实例", "可变地借用实例"],
+            answer: 1,
+        },
+        explanation: "&self 是 self: &Self 的简写，方法只读取实例；需要修改实例时使用 &mut self，需要获取所有权时使用 self",
+    },
+    Question {
+        prompt: "关联函数（没有 self 参数的函数）使用 :: 语法调用，例如 Rectangle::square(10)。",
+        kind: QuestionKind::TrueFalse { answer: true },
+        explanation: "关联函数不作用于某个实例，所以通过类型名加 :: 调用，常用来编写构造函数",
+    },
+];
+
+// 本课的动手练习
+const EXERCISES: &[Exercise] = &[
+    Exercise {
+        name: "area",
+        title: "矩形的面积",
+        description: "为 Rectangle 实现 area 方法和 square 关联函数",
+        starter: include_str!("../exercises/03_structs/area.rs"),
+        tests: include_str!("../exercises/03_structs/area_tests.rs"),
+        solution: include_str!("../exercises/03_structs/area_solution.rs"),
+        hints: &[
+            "area 通过 self.width 和 self.height 读取字段",
+            "square 没有 self 参数，返回一个新的 Rectangle { width: size, height: size }",
+        ],
+    },
+    Exercise {
+        name: "can_hold",
+        title: "判断矩形能否容纳另一个矩形",
+        description: "实现 Rectangle::can_hold：当 self 的宽和高都不小于 other 的宽和高时返回 true",
+        starter: include_str!("../exercises/03_structs/can_hold.rs"),
+        tests: include_str!("../exercises/03_structs/can_hold_tests.rs"),
+        solution: include_str!("../exercises/03_structs/can_hold_solution.rs"),
+        hints: &[
+            "宽和高需要同时满足条件，可以用 && 连接两个比较",
+            "大小相同的矩形也可以容纳对方，比较时使用 >= 而不是 >",
+        ],
+    },
+];
 
 // 定义一个公共函数 run()，作为本模块的入口点
 // 当用户在主程序中选择 3 时，将调用此函数
-pub fn run() {
-    println!("=== 第3课：结构体 ===");
-    println!("本示例将介绍 Rust 中的结构体定义、实例化、方法和关联函数等知识。\n");
-    
-    // 调用各个示例函数
-    struct_definition();
-    tuple_structs();
-    unit_structs();
-    struct_methods();
-    associated_functions();
-    struct_field_visibility();
-    struct_update_syntax();
-    destructuring_structs();
+pub fn run(ctx: &mut LessonContext) {
+    ctx.lesson_heading("第3课：结构体");
+    writeln!(ctx, "本示例将介绍 Rust 中的结构体定义、实例化、方法和关联函数等知识。\n");
+    
+    // 按顺序调用各个示例函数，小节列表统一登记在 sections() 中
+    for section in StructsLesson.sections() {
+        (section.run)(ctx);
+    }
 }
 
 // 演示结构体的定义与实例化
 // 结构体是一种自定义数据类型，允许我们组合多个相关的值
-fn struct_definition() {
-    println!("\n--- 结构体定义与实例化 ---");
+fn struct_definition(ctx: &mut LessonContext) {
+    ctx.section_heading("结构体定义与实例化");
     
     // 定义一个结构体（在函数内部定义结构体是允许的，但通常在模块级别定义）
     struct Person {
@@ -50,9 +188,9 @@ fn struct_definition() {
     };
     
     // 访问结构体字段
-    println!("姓名: {}", person1.name);
-    println!("年龄: {}", person1.age);
-    println!("是否是学生: {}", person1.is_student);
+    writeln!(ctx, "姓名: {}", person1.name);
+    writeln!(ctx, "年龄: {}", person1.age);
+    writeln!(ctx, "是否是学生: {}", person1.is_student);
     
     // 实例化可变结构体
     let mut person2 = Person {
@@ -65,10 +203,10 @@ fn struct_definition() {
     person2.age = 31;
     person2.is_student = true;
     
-    println!("\n修改后的信息:");
-    println!("姓名: {}", person2.name);
-    println!("年龄: {}", person2.age);
-    println!("是否是学生: {}", person2.is_student);
+    writeln!(ctx, "\n修改后的信息:");
+    writeln!(ctx, "姓名: {}", person2.name);
+    writeln!(ctx, "年龄: {}", person2.age);
+    writeln!(ctx, "是否是学生: {}", person2.is_student);
     
     // 运行结果：
     // 姓名: 张三
@@ -83,8 +221,8 @@ fn struct_definition() {
 
 // 演示元组结构体
 // 元组结构体是结构体的一种特殊形式，看起来像元组，但有名称
-fn tuple_structs() {
-    println!("\n--- 元组结构体 ---");
+fn tuple_structs(ctx: &mut LessonContext) {
+    ctx.section_heading("元组结构体");
     
     // 定义元组结构体
     struct Point(i32, i32);
@@ -96,15 +234,15 @@ fn tuple_structs() {
     let blue = Color(0, 0, 255);
     
     // 访问元组结构体的字段
-    println!("原点坐标: ({}, {})", origin.0, origin.1);
-    println!("红色 RGB 值: ({}, {}, {})", red.0, red.1, red.2);
-    println!("蓝色 RGB 值: ({}, {}, {})", blue.0, blue.1, blue.2);
+    writeln!(ctx, "原点坐标: ({}, {})", origin.0, origin.1);
+    writeln!(ctx, "红色 RGB 值: ({}, {}, {})", red.0, red.1, red.2);
+    writeln!(ctx, "蓝色 RGB 值: ({}, {}, {})", blue.0, blue.1, blue.2);
     
     // 元组结构体的可变性
     let mut point = Point(10, 20);
     point.0 = 15; // 修改第一个字段
     point.1 = 25; // 修改第二个字段
-    println!("修改后的坐标: ({}, {})", point.0, point.1);
+    writeln!(ctx, "修改后的坐标: ({}, {})", point.0, point.1);
     
     // 运行结果：
     // 原点坐标: (0, 0)
@@ -115,8 +253,8 @@ fn tuple_structs() {
 
 // 演示单元结构体
 // 单元结构体没有任何字段，类似于单元类型 ()
-fn unit_structs() {
-    println!("\n--- 单元结构体 ---");
+fn unit_structs(ctx: &mut LessonContext) {
+    ctx.section_heading("单元结构体");
     
     // 定义单元结构体
     struct Unit;
@@ -126,7 +264,7 @@ fn unit_structs() {
     
     // 单元结构体通常用于实现 traits 或作为标记
     // 这里我们简单地确认它被创建了
-    println!("单元结构体已创建: Unit");
+    writeln!(ctx, "单元结构体已创建: Unit");
     
     // 运行结果：
     // 单元结构体已创建: Unit
@@ -161,8 +299,8 @@ impl Rectangle {
 
 // 演示结构体方法
 // 方法是与结构体关联的函数，使用 impl 块定义
-fn struct_methods() {
-    println!("\n--- 结构体方法 ---");
+fn struct_methods(ctx: &mut LessonContext) {
+    ctx.section_heading("结构体方法");
     
     // 创建 Rectangle 实例
     let rect1 = Rectangle {
@@ -171,7 +309,7 @@ fn struct_methods() {
     };
     
     // 调用方法
-    println!("矩形面积: {}", rect1.area());
+    writeln!(ctx, "矩形面积: {}", rect1.area());
     
     // 创建另一个 Rectangle 实例
     let rect2 = Rectangle {
@@ -180,7 +318,7 @@ fn struct_methods() {
     };
     
     // 调用方法检查是否可以容纳另一个矩形
-    println!("rect1 可以容纳 rect2: {}", rect1.can_hold(&rect2));
+    writeln!(ctx, "rect1 可以容纳 rect2: {}", rect1.can_hold(&rect2));
     
     // 创建可变的 Rectangle 实例
     let mut rect3 = Rectangle {
@@ -188,12 +326,12 @@ fn struct_methods() {
         height: 20,
     };
     
-    println!("修改前的面积: {}", rect3.area());
+    writeln!(ctx, "修改前的面积: {}", rect3.area());
     
     // 调用修改方法
     rect3.resize(15, 25);
     
-    println!("修改后的面积: {}", rect3.area());
+    writeln!(ctx, "修改后的面积: {}", rect3.area());
     
     // 运行结果：
     // 矩形面积: 1500
@@ -224,17 +362,17 @@ impl Rectangle {
 
 // 演示关联函数
 // 关联函数是与结构体关联但不作用于特定实例的函数
-fn associated_functions() {
-    println!("\n--- 关联函数 ---");
+fn associated_functions(ctx: &mut LessonContext) {
+    ctx.section_heading("关联函数");
     
     // 使用关联函数创建正方形
     let square = Rectangle::square(20);
-    println!("正方形 - 宽: {}, 高: {}, 面积: {}", 
+    writeln!(ctx, "正方形 - 宽: {}, 高: {}, 面积: {}", 
              square.width, square.height, square.area());
     
     // 使用关联函数创建默认矩形
     let default_rect = Rectangle::default();
-    println!("默认矩形 - 宽: {}, 高: {}, 面积: {}", 
+    writeln!(ctx, "默认矩形 - 宽: {}, 高: {}, 面积: {}", 
              default_rect.width, default_rect.height, default_rect.area());
     
     // 运行结果：
@@ -245,8 +383,8 @@ fn associated_functions() {
 // 演示结构体字段可见性
 // 默认情况下，结构体字段是私有的，使用 pub 关键字可以使其变为公有的
 // 注意：这个示例在模块级别才会有明显效果
-fn struct_field_visibility() {
-    println!("\n--- 结构体字段可见性 ---");
+fn struct_field_visibility(ctx: &mut LessonContext) {
+    ctx.section_heading("结构体字段可见性");
     
     // 在当前模块中，我们可以访问结构体的所有字段
     let rect = Rectangle {
@@ -254,7 +392,7 @@ fn struct_field_visibility() {
         height: 30,
     };
     
-    println!("访问私有字段: 宽 = {}, 高 = {}", rect.width, rect.height);
+    writeln!(ctx, "访问私有字段: 宽 = {}, 高 = {}", rect.width, rect.height);
     
     // 注意：在实际项目中，如果结构体定义在另一个模块中，
     // 默认情况下我们无法直接访问其私有字段
@@ -274,8 +412,8 @@ struct User {
 
 // 演示结构体更新语法
 // 结构体更新语法允许我们从另一个实例复制部分值
-fn struct_update_syntax() {
-    println!("\n--- 结构体更新语法 ---");
+fn struct_update_syntax(ctx: &mut LessonContext) {
+    ctx.section_heading("结构体更新语法");
     
     // 创建一个 User 实例
     let user1 = User {
@@ -294,10 +432,10 @@ fn struct_update_syntax() {
     };
     
     // 打印 user2 的信息
-    println!("user2 用户名: {}", user2.username);
-    println!("user2 邮箱: {}", user2.email);
-    println!("user2 登录次数: {}", user2.sign_in_count);
-    println!("user2 是否活跃: {}", user2.active);
+    writeln!(ctx, "user2 用户名: {}", user2.username);
+    writeln!(ctx, "user2 邮箱: {}", user2.email);
+    writeln!(ctx, "user2 登录次数: {}", user2.sign_in_count);
+    writeln!(ctx, "user2 是否活跃: {}", user2.active);
     
     // 注意：当使用 .. 语法时，Rust 会尝试移动所有权
     // 所以在上面的例子中，user1 在创建 user2 后不能再被使用
@@ -312,8 +450,8 @@ fn struct_update_syntax() {
 
 // 演示解构结构体
 // 解构允许我们将结构体的字段值绑定到变量
-fn destructuring_structs() {
-    println!("\n--- 解构结构体 ---");
+fn destructuring_structs(ctx: &mut LessonContext) {
+    ctx.section_heading("解构结构体");
     
     // 创建一个 Rectangle 实例
     let rect = Rectangle {
@@ -323,20 +461,20 @@ fn destructuring_structs() {
     
     // 解构结构体
     let Rectangle { width, height } = rect;
-    println!("解构后的宽: {}, 解构后的高: {}", width, height);
+    writeln!(ctx, "解构后的宽: {}, 解构后的高: {}", width, height);
     
     // 在模式匹配中解构
     match rect {
-        Rectangle { width: 0, .. } => println!("宽度为 0"),
-        Rectangle { height: 0, .. } => println!("高度为 0"),
+        Rectangle { width: 0, .. } => writeln!(ctx, "宽度为 0"),
+        Rectangle { height: 0, .. } => writeln!(ctx, "高度为 0"),
         Rectangle { width, height } => {
-            println!("在 match 中解构: 宽 = {}, 高 = {}", width, height);
+            writeln!(ctx, "在 match 中解构: 宽 = {}, 高 = {}", width, height);
         },
     }
     
     // 部分解构
     let Rectangle { width, .. } = rect;
-    println!("只解构宽度: {}", width);
+    writeln!(ctx, "只解构宽度: {}", width);
     
     // 运行结果：
     // 解构后的宽: 40, 解构后的高: 60
@@ -11,6 +11,12 @@
 // 7. Result 枚举
 
 // 导入标准输出模块
+use crate::compile_error::CompileError;
+use crate::context::LessonContext;
+use crate::exercise::Exercise;
+use crate::graph::Prerequisite;
+use crate::lesson::{Lesson, Section};
+use crate::quiz::{Question, QuestionKind};
 
 // 定义全局可见的枚举
 // 方向枚举
@@ -60,26 +66,161 @@ enum Coin {
     Quarter,
 }
 
+// 本课在课程注册表中的登记信息
+// 主程序的菜单等前端都通过 Lesson trait 读取这些信息
+pub struct EnumsLesson;
+
+impl Lesson for EnumsLesson {
+    fn id(&self) -> &'static str {
+        "4"
+    }
+
+    fn title(&self) -> &'static str {
+        "枚举 (Enums)"
+    }
+
+    fn summary(&self) -> &'static str {
+        "本示例将介绍 Rust 中的枚举定义、模式匹配、关联数据和方法等知识。"
+    }
+
+    fn topics(&self) -> &'static [&'static str] {
+        &[
+            "枚举定义与实例化",
+            "枚举的变体",
+            "枚举的模式匹配",
+            "带关联数据的枚举",
+            "为枚举实现方法",
+            "Option 枚举",
+            "Result 枚举",
+        ]
+    }
+
+    fn sections(&self) -> &'static [Section] {
+        &[
+            Section { id: "enum_definition", title: "枚举定义与实例化", run: enum_definition },
+            Section { id: "enum_variants", title: "枚举的变体", run: enum_variants },
+            Section { id: "enum_pattern_matching", title: "枚举的模式匹配", run: enum_pattern_matching },
+            Section { id: "enum_with_data", title: "带关联数据的枚举", run: enum_with_data },
+            Section { id: "enum_methods", title: "为枚举实现方法", run: enum_methods },
+            Section { id: "option_enum", title: "Option 枚举", run: option_enum },
+            Section { id: "result_enum", title: "Result 枚举", run: result_enum },
+        ]
+    }
+
+    fn source(&self) -> &'static str {
+        include_str!("_04_enums.rs")
+    }
+
+    fn source_path(&self) -> &'static str {
+        file!()
+    }
+
+    fn quiz(&self) -> &'static [Question] {
+        QUIZ
+    }
+
+    fn exercises(&self) -> &'static [Exercise] {
+        EXERCISES
+    }
+
+    fn compile_errors(&self) -> &'static [CompileError] {
+        COMPILE_ERRORS
+    }
+
+    fn prerequisites(&self) -> &'static [&'static str] {
+        &["3"]
+    }
+
+    fn section_prerequisites(&self) -> &'static [Prerequisite] {
+        &[
+            Prerequisite {
+                section: "enum_methods",
+                lesson: "3",
+                requires: "struct_methods",
+                reason: "为枚举实现方法和为结构体实现方法一样，都写在 impl 块中",
+            },
+        ]
+    }
+
+    fn run(&self, ctx: &mut LessonContext) {
+        run(ctx);
+    }
+}
+
+// 本课的测验题目
+const QUIZ: &[Question] = &[
+    Question {
+        prompt: "下面的代码会输出什么？",
+        kind: QuestionKind::PredictOutput {
+            section: "option_enum",
+            code: r#"let absent_number: Option<i32> = None;
+let default_value = absent_number.unwrap_or(0);
+println!("使用 unwrap_or 获取的值: {}", default_value);"#,
+            options: &["使用 unwrap_or 获取的值: 0", "使用 unwrap_or 获取的值: None", "程序 panic"],
+            answer: 0,
+        },
+        explanation: "unwrap_or 在值为 None 时返回提供的默认值 0，不会像 unwrap 那样 panic",
+    },
+    Question {
+        prompt: "Rust 使用哪个类型表示 \"可能有值，也可能没有值\"？",
+        kind: QuestionKind::Choice {
+            options: &["null", "Option<T>", "Result<T, E>"],
+            answer: 1,
+        },
+        explanation: "Rust 没有 null，可能缺失的值用 Option<T> 表示，它有 Some(T) 和 None 两个变体；Result<T, E> 用来表示可能失败的操作",
+    },
+    Question {
+        prompt: "对枚举使用 match 时，必须处理所有可能的变体。",
+        kind: QuestionKind::TrueFalse { answer: true },
+        explanation: "match 必须是穷尽的，遗漏变体时编译器会报错，可以用 _ 通配符处理其余的情况",
+    },
+];
+
+// 本课的动手练习
+const EXERCISES: &[Exercise] = &[
+    Exercise {
+        name: "coin_value",
+        title: "硬币的面值",
+        description: "实现 value_in_cents 返回每种硬币的美分数，并实现 total_value 计算一组硬币的总面值",
+        starter: include_str!("../exercises/04_enums/coin_value.rs"),
+        tests: include_str!("../exercises/04_enums/coin_value_tests.rs"),
+        solution: include_str!("../exercises/04_enums/coin_value_solution.rs"),
+        hints: &[
+            "用 match 为 Coin 的每个变体返回对应的数字",
+            "total_value 可以遍历切片并累加 value_in_cents 的结果，也可以使用 iter().map(...).sum()",
+        ],
+    },
+];
+
+// 本课的编译错误示例
+const COMPILE_ERRORS: &[CompileError] = &[
+    CompileError {
+        name: "missing_variant",
+        title: "match 没有覆盖所有变体",
+        section: "enum_pattern_matching",
+        code: "E0004",
+        broken: include_str!("../compile_errors/04_enums/missing_variant.rs"),
+        fixed: include_str!("../compile_errors/04_enums/missing_variant_fixed.rs"),
+        explanation: "match 必须穷尽所有可能的值。Coin 有四个变体，但只处理了三个，编译器会列出没有覆盖的 Coin::Quarter。补上缺少的分支，或者在确实不关心其余变体时使用 _ 通配分支",
+    },
+];
+
 // 定义一个公共函数 run()，作为本模块的入口点
 // 当用户在主程序中选择 4 时，将调用此函数
-pub fn run() {
-    println!("=== 第4课：枚举 ===");
-    println!("本示例将介绍 Rust 中的枚举定义、模式匹配、关联数据和方法等知识。\n");
-    
-    // 调用各个示例函数
-    enum_definition();
-    enum_variants();
-    enum_pattern_matching();
-    enum_with_data();
-    enum_methods();
-    option_enum();
-    result_enum();
+pub fn run(ctx: &mut LessonContext) {
+    ctx.lesson_heading("第4课：枚举");
+    writeln!(ctx, "本示例将介绍 Rust 中的枚举定义、模式匹配、关联数据和方法等知识。\n");
+    
+    // 按顺序调用各个示例函数，小节列表统一登记在 sections() 中
+    for section in EnumsLesson.sections() {
+        (section.run)(ctx);
+    }
 }
 
 // 演示枚举的定义与实例化
 // 枚举是一种自定义数据类型，它允许我们定义一组命名的值
-fn enum_definition() {
-    println!("\n--- 枚举定义与实例化 ---");
+fn enum_definition(ctx: &mut LessonContext) {
+    ctx.section_heading("枚举定义与实例化");
     
     // 实例化枚举
     let north = Direction::North;
@@ -88,11 +229,11 @@ fn enum_definition() {
     let west = Direction::West;
     
     // 使用 match 表达式处理枚举
-    println!("枚举实例化与匹配:");
-    print_direction(north);
-    print_direction(east);
-    print_direction(south);
-    print_direction(west);
+    writeln!(ctx, "枚举实例化与匹配:");
+    print_direction(ctx, north);
+    print_direction(ctx, east);
+    print_direction(ctx, south);
+    print_direction(ctx, west);
     
     // 运行结果：
     // 枚举实例化与匹配:
@@ -104,16 +245,16 @@ fn enum_definition() {
 
 // 演示枚举的变体
 // 枚举的每个可能值称为变体（variant）
-fn enum_variants() {
-    println!("\n--- 枚举的变体 ---");
+fn enum_variants(ctx: &mut LessonContext) {
+    ctx.section_heading("枚举的变体");
     
     // 枚举变体可以有整数常量值
     let status_ok = HttpStatusCode::Ok;
     let status_not_found = HttpStatusCode::NotFound;
     
     // 获取枚举变体的整数值
-    println!("HTTP OK 状态码: {}", status_ok as i32);
-    println!("HTTP Not Found 状态码: {}", status_not_found as i32);
+    writeln!(ctx, "HTTP OK 状态码: {}", status_ok as i32);
+    writeln!(ctx, "HTTP Not Found 状态码: {}", status_not_found as i32);
     
     // 实例化复杂枚举
     let msg1 = Message::Quit;
@@ -122,11 +263,11 @@ fn enum_variants() {
     let msg4 = Message::ChangeColor(255, 0, 0);
     
     // 打印消息类型
-    println!("\n复杂枚举类型:");
-    print_message_type(msg1);
-    print_message_type(msg2);
-    print_message_type(msg3);
-    print_message_type(msg4);
+    writeln!(ctx, "\n复杂枚举类型:");
+    print_message_type(ctx, msg1);
+    print_message_type(ctx, msg2);
+    print_message_type(ctx, msg3);
+    print_message_type(ctx, msg4);
     
     // 运行结果：
     // HTTP OK 状态码: 200
@@ -141,14 +282,14 @@ fn enum_variants() {
 
 // 演示枚举的模式匹配
 // 模式匹配是处理枚举的强大工具
-fn enum_pattern_matching() {
-    println!("\n--- 枚举的模式匹配 ---");
+fn enum_pattern_matching(ctx: &mut LessonContext) {
+    ctx.section_heading("枚举的模式匹配");
     
     // 定义一个函数，使用 match 表达式处理枚举
-    fn value_in_cents(coin: Coin) -> u8 {
+    fn value_in_cents(ctx: &mut LessonContext, coin: Coin) -> u8 {
         match coin {
             Coin::Penny => {
-                println!("幸运便士！");
+                writeln!(ctx, "幸运便士！");
                 1
             },
             Coin::Nickel => 5,
@@ -161,8 +302,10 @@ fn enum_pattern_matching() {
     let penny = Coin::Penny;
     let nickel = Coin::Nickel;
     
-    println!("便士的价值: {}", value_in_cents(penny));
-    println!("镍币的价值: {}", value_in_cents(nickel));
+    let penny_value = value_in_cents(ctx, penny);
+    writeln!(ctx, "便士的价值: {}", penny_value);
+    let nickel_value = value_in_cents(ctx, nickel);
+    writeln!(ctx, "镍币的价值: {}", nickel_value);
     
     // 运行结果：
     // 幸运便士！
@@ -172,26 +315,26 @@ fn enum_pattern_matching() {
 
 // 演示带关联数据的枚举
 // 枚举的变体可以携带不同类型和数量的数据
-fn enum_with_data() {
-    println!("\n--- 带关联数据的枚举 ---");
+fn enum_with_data(ctx: &mut LessonContext) {
+    ctx.section_heading("带关联数据的枚举");
     
     // 实例化带关联数据的枚举
     let home = IpAddr::V4(127, 0, 0, 1);
     let loopback = IpAddr::V6(String::from("::1"));
     
     // 处理带关联数据的枚举
-    print_ip_address(home);
-    print_ip_address(loopback);
+    print_ip_address(ctx, home);
+    print_ip_address(ctx, loopback);
     
     // 实例化并处理用户输入枚举
     let input1 = Input::Number(42);
     let input2 = Input::Text(String::from("Hello"));
     let input3 = Input::Boolean(true);
     
-    println!("\n用户输入处理:");
-    process_input(input1);
-    process_input(input2);
-    process_input(input3);
+    writeln!(ctx, "\n用户输入处理:");
+    process_input(ctx, input1);
+    process_input(ctx, input2);
+    process_input(ctx, input3);
     
     // 运行结果：
     // IPv4 地址: 127.0.0.1
@@ -218,8 +361,8 @@ impl Message {
 }
 
 // 演示为枚举实现方法
-fn enum_methods() {
-    println!("\n--- 为枚举实现方法 ---");
+fn enum_methods(ctx: &mut LessonContext) {
+    ctx.section_heading("为枚举实现方法");
     
     // 实例化 Message 枚举
     let msg1 = Message::Quit;
@@ -228,10 +371,10 @@ fn enum_methods() {
     let msg4 = Message::ChangeColor(255, 0, 0);
     
     // 调用枚举的方法
-    println!("消息 1 描述: {}", msg1.description());
-    println!("消息 2 描述: {}", msg2.description());
-    println!("消息 3 描述: {}", msg3.description());
-    println!("消息 4 描述: {}", msg4.description());
+    writeln!(ctx, "消息 1 描述: {}", msg1.description());
+    writeln!(ctx, "消息 2 描述: {}", msg2.description());
+    writeln!(ctx, "消息 3 描述: {}", msg3.description());
+    writeln!(ctx, "消息 4 描述: {}", msg4.description());
     
     // 运行结果：
     // 消息 1 描述: 退出消息
@@ -242,8 +385,8 @@ fn enum_methods() {
 
 // 演示 Option 枚举
 // Option 是 Rust 标准库中的枚举，用于表示可能存在或不存在的值
-fn option_enum() {
-    println!("\n--- Option 枚举 ---");
+fn option_enum(ctx: &mut LessonContext) {
+    ctx.section_heading("Option 枚举");
     
     // Option<T> 枚举有两个变体:
     // - Some(T): 包含一个值
@@ -255,19 +398,19 @@ fn option_enum() {
     let absent_number: Option<i32> = None;
     
     // 访问 Option 中的值
-    println!("Option 值处理:");
-    print_option(some_number);
-    print_option_string(some_string);
-    print_option(absent_number);
+    writeln!(ctx, "Option 值处理:");
+    print_option(ctx, some_number);
+    print_option_string(ctx, some_string);
+    print_option(ctx, absent_number);
     
     // 使用 unwrap 方法访问 Option 中的值（如果是 None 会导致程序崩溃）
     // 注意：在实际代码中应谨慎使用 unwrap
     let value = some_number.unwrap();
-    println!("使用 unwrap 获取的值: {}", value);
+    writeln!(ctx, "使用 unwrap 获取的值: {}", value);
     
     // 使用 unwrap_or 提供默认值
     let default_value = absent_number.unwrap_or(0);
-    println!("使用 unwrap_or 获取的值: {}", default_value);
+    writeln!(ctx, "使用 unwrap_or 获取的值: {}", default_value);
     
     // 运行结果：
     // Option 值处理:
@@ -280,8 +423,8 @@ fn option_enum() {
 
 // 演示 Result 枚举
 // Result 是 Rust 标准库中的枚举，用于处理可能成功或失败的操作
-fn result_enum() {
-    println!("\n--- Result 枚举 ---");
+fn result_enum(ctx: &mut LessonContext) {
+    ctx.section_heading("Result 枚举");
     
     // Result<T, E> 枚举有两个变体:
     // - Ok(T): 表示操作成功，包含成功的值
@@ -298,14 +441,14 @@ fn result_enum() {
     
     // 测试成功的情况
     let result1 = divide(10, 2);
-    println!("10 / 2 = {}", match result1 {
+    writeln!(ctx, "10 / 2 = {}", match result1 {
         Ok(value) => value.to_string(),
         Err(err) => err,
     });
     
     // 测试失败的情况
     let result2 = divide(10, 0);
-    println!("10 / 0 = {}", match result2 {
+    writeln!(ctx, "10 / 0 = {}", match result2 {
         Ok(value) => value.to_string(),
         Err(err) => err,
     });
@@ -320,12 +463,12 @@ fn result_enum() {
     let result3 = calculate(10, 2);
     let result4 = calculate(10, 0);
     
-    println!("calculate(10, 2) = {}", match result3 {
+    writeln!(ctx, "calculate(10, 2) = {}", match result3 {
         Ok(value) => value.to_string(),
         Err(err) => err,
     });
     
-    println!("calculate(10, 0) = {}", match result4 {
+    writeln!(ctx, "calculate(10, 0) = {}", match result4 {
         Ok(value) => value.to_string(),
         Err(err) => err,
     });
@@ -341,60 +484,60 @@ fn result_enum() {
 
 // 打印方向
 // 参数 dir: Direction - 要打印的方向
-fn print_direction(dir: Direction) {
+fn print_direction(ctx: &mut LessonContext, dir: Direction) {
     match dir {
-        Direction::North => println!("向北"),
-        Direction::East => println!("向东"),
-        Direction::South => println!("向南"),
-        Direction::West => println!("向西"),
+        Direction::North => writeln!(ctx, "向北"),
+        Direction::East => writeln!(ctx, "向东"),
+        Direction::South => writeln!(ctx, "向南"),
+        Direction::West => writeln!(ctx, "向西"),
     }
 }
 
 // 打印消息类型
 // 参数 msg: Message - 要打印类型的消息
-fn print_message_type(msg: Message) {
+fn print_message_type(ctx: &mut LessonContext, msg: Message) {
     match msg {
-        Message::Quit => println!("消息类型: Quit"),
-        Message::Move { .. } => println!("消息类型: Move"),
-        Message::Write(_) => println!("消息类型: Write"),
-        Message::ChangeColor(_, _, _) => println!("消息类型: ChangeColor"),
+        Message::Quit => writeln!(ctx, "消息类型: Quit"),
+        Message::Move { .. } => writeln!(ctx, "消息类型: Move"),
+        Message::Write(_) => writeln!(ctx, "消息类型: Write"),
+        Message::ChangeColor(_, _, _) => writeln!(ctx, "消息类型: ChangeColor"),
     }
 }
 
 // 打印 IP 地址
 // 参数 ip: IpAddr - 要打印的 IP 地址
-fn print_ip_address(ip: IpAddr) {
+fn print_ip_address(ctx: &mut LessonContext, ip: IpAddr) {
     match ip {
-        IpAddr::V4(a, b, c, d) => println!("IPv4 地址: {}.{}.{}.{}", a, b, c, d),
-        IpAddr::V6(s) => println!("IPv6 地址: {}", s),
+        IpAddr::V4(a, b, c, d) => writeln!(ctx, "IPv4 地址: {}.{}.{}.{}", a, b, c, d),
+        IpAddr::V6(s) => writeln!(ctx, "IPv6 地址: {}", s),
     }
 }
 
 // 处理用户输入
 // 参数 input: Input - 要处理的用户输入
-fn process_input(input: Input) {
+fn process_input(ctx: &mut LessonContext, input: Input) {
     match input {
-        Input::Number(n) => println!("数字输入: {}", n),
-        Input::Text(s) => println!("文本输入: {}", s),
-        Input::Boolean(b) => println!("布尔输入: {}", b),
+        Input::Number(n) => writeln!(ctx, "数字输入: {}", n),
+        Input::Text(s) => writeln!(ctx, "文本输入: {}", s),
+        Input::Boolean(b) => writeln!(ctx, "布尔输入: {}", b),
     }
 }
 
 // 打印 Option<i32> 值
 // 参数 option: Option<i32> - 要打印的 Option 值
-fn print_option(option: Option<i32>) {
+fn print_option(ctx: &mut LessonContext, option: Option<i32>) {
     match option {
-        Some(value) => println!("有值: {}", value),
-        None => println!("无值"),
+        Some(value) => writeln!(ctx, "有值: {}", value),
+        None => writeln!(ctx, "无值"),
     }
 }
 
 // 打印 Option<String> 值
 // 参数 option: Option<String> - 要打印的 Option 值
-fn print_option_string(option: Option<String>) {
+fn print_option_string(ctx: &mut LessonContext, option: Option<String>) {
     match option {
-        Some(value) => println!("有字符串值: {}", value),
-        None => println!("无值"),
+        Some(value) => writeln!(ctx, "有字符串值: {}", value),
+        None => writeln!(ctx, "无值"),
     }
 }
 
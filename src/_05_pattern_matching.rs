@@ -14,30 +14,181 @@
 // 10. 高级模式匹配技巧
 
 // 导入标准输出模块
+use crate::compile_error::CompileError;
+use crate::context::LessonContext;
+use crate::exercise::Exercise;
+use crate::graph::Prerequisite;
+use crate::lesson::{Lesson, Section};
+use crate::quiz::{Question, QuestionKind};
+
+// 本课在课程注册表中的登记信息
+// 主程序的菜单等前端都通过 Lesson trait 读取这些信息
+pub struct PatternMatchingLesson;
+
+impl Lesson for PatternMatchingLesson {
+    fn id(&self) -> &'static str {
+        "5"
+    }
+
+    fn title(&self) -> &'static str {
+        "模式匹配 (Pattern Matching)"
+    }
+
+    fn summary(&self) -> &'static str {
+        "本示例将介绍 Rust 中的模式匹配语法和应用场景。"
+    }
+
+    fn topics(&self) -> &'static [&'static str] {
+        &[
+            "match 表达式基础",
+            "模式匹配中的解构",
+            "模式匹配中的范围匹配",
+            "模式匹配中的通配符",
+            "if let 表达式",
+            "while let 表达式",
+            "for 循环中的模式",
+            "let 语句中的模式",
+            "函数参数中的模式",
+            "高级模式匹配技巧",
+        ]
+    }
+
+    fn sections(&self) -> &'static [Section] {
+        &[
+            Section { id: "match_basics", title: "match 表达式基础", run: match_basics },
+            Section { id: "pattern_destructuring", title: "模式匹配中的解构", run: pattern_destructuring },
+            Section { id: "range_matching", title: "模式匹配中的范围匹配", run: range_matching },
+            Section { id: "wildcards", title: "模式匹配中的通配符", run: wildcards },
+            Section { id: "if_let_expressions", title: "if let 表达式", run: if_let_expressions },
+            Section { id: "while_let_expressions", title: "while let 表达式", run: while_let_expressions },
+            Section { id: "for_loops_with_patterns", title: "for 循环中的模式", run: for_loops_with_patterns },
+            Section { id: "let_statements_with_patterns", title: "let 语句中的模式", run: let_statements_with_patterns },
+            Section { id: "function_parameters_with_patterns", title: "函数参数中的模式", run: function_parameters_with_patterns },
+            Section { id: "advanced_pattern_matching", title: "高级模式匹配技巧", run: advanced_pattern_matching },
+        ]
+    }
+
+    fn source(&self) -> &'static str {
+        include_str!("_05_pattern_matching.rs")
+    }
+
+    fn source_path(&self) -> &'static str {
+        file!()
+    }
+
+    fn quiz(&self) -> &'static [Question] {
+        QUIZ
+    }
+
+    fn exercises(&self) -> &'static [Exercise] {
+        EXERCISES
+    }
+
+    fn compile_errors(&self) -> &'static [CompileError] {
+        &[]
+    }
+
+    fn prerequisites(&self) -> &'static [&'static str] {
+        &["4"]
+    }
+
+    fn section_prerequisites(&self) -> &'static [Prerequisite] {
+        &[
+            Prerequisite {
+                section: "match_basics",
+                lesson: "4",
+                requires: "enum_pattern_matching",
+                reason: "沿用第4课的 Coin 枚举和 value_in_cents 函数，把 match 讲得更完整",
+            },
+            Prerequisite {
+                section: "pattern_destructuring",
+                lesson: "3",
+                requires: "destructuring_structs",
+                reason: "先在 let 语句中见过结构体的解构，再把它用到 match 的分支中",
+            },
+            Prerequisite {
+                section: "if_let_expressions",
+                lesson: "4",
+                requires: "option_enum",
+                reason: "if let 最常见的用法是处理 Option",
+            },
+        ]
+    }
+
+    fn run(&self, ctx: &mut LessonContext) {
+        run(ctx);
+    }
+}
+
+// 本课的测验题目
+const QUIZ: &[Question] = &[
+    Question {
+        prompt: "下面的代码会输出什么？",
+        kind: QuestionKind::PredictOutput {
+            section: "range_matching",
+            code: r#"fn grade(score: u32) -> &'static str {
+    match score {
+        0..=59 => "不及格",
+        60..=79 => "及格",
+        80..=89 => "良好",
+        90..=100 => "优秀",
+        _ => "无效成绩",
+    }
+}
+
+println!("成绩 85: {}", grade(85));"#,
+            options: &["成绩 85: 及格", "成绩 85: 良好", "成绩 85: 优秀"],
+            answer: 1,
+        },
+        explanation: "85 落在 80..=89 这个闭区间内，所以匹配到 \"良好\" 分支",
+    },
+    Question {
+        prompt: "模式中的 _ 表示什么？",
+        kind: QuestionKind::Choice {
+            options: &["匹配任意值，但不绑定到变量", "只匹配 0", "匹配任意值并绑定到名为 _ 的变量"],
+            answer: 0,
+        },
+        explanation: "_ 是通配符，可以匹配任何值但不会绑定，常用作 match 的最后一个分支来处理其余的情况",
+    },
+    Question {
+        prompt: "if let 可以看作只关心一种模式的 match 的简写。",
+        kind: QuestionKind::TrueFalse { answer: true },
+        explanation: "if let Some(x) = value { ... } 只处理匹配的那一种情况，其余情况可以放在 else 分支中",
+    },
+];
+
+// 本课的动手练习
+const EXERCISES: &[Exercise] = &[
+    Exercise {
+        name: "classify_point",
+        title: "判断点的位置",
+        description: "实现 classify，根据坐标返回 \"原点\"、\"x 轴\"、\"y 轴\" 或者所在的象限",
+        starter: include_str!("../exercises/05_pattern_matching/classify_point.rs"),
+        tests: include_str!("../exercises/05_pattern_matching/classify_point_tests.rs"),
+        solution: include_str!("../exercises/05_pattern_matching/classify_point_solution.rs"),
+        hints: &[
+            "match 的分支按顺序匹配，先写 (0, 0)，再写坐标轴，最后写象限",
+            "(x, y) if x > 0 && y > 0 这样的匹配守卫可以区分不同的象限",
+        ],
+    },
+];
 
 // 定义一个公共函数 run()，作为本模块的入口点
 // 当用户在主程序中选择 5 时，将调用此函数
-pub fn run() {
-    println!("=== 第5课：模式匹配 ===");
-    println!("本示例将介绍 Rust 中的模式匹配语法和应用场景。\n");
-    
-    // 调用各个示例函数
-    match_basics();
-    pattern_destructuring();
-    range_matching();
-    wildcards();
-    if_let_expressions();
-    while_let_expressions();
-    for_loops_with_patterns();
-    let_statements_with_patterns();
-    function_parameters_with_patterns();
-    advanced_pattern_matching();
+pub fn run(ctx: &mut LessonContext) {
+    ctx.lesson_heading("第5课：模式匹配");
+    writeln!(ctx, "本示例将介绍 Rust 中的模式匹配语法和应用场景。\n");
+    
+    // 按顺序调用各个示例函数，小节列表统一登记在 sections() 中
+    for section in PatternMatchingLesson.sections() {
+        (section.run)(ctx);
+    }
 }
 
 // 演示 match 表达式基础
 // match 表达式是 Rust 中最强大的模式匹配工具
-fn match_basics() {
-    println!("\n--- match 表达式基础 ---");
+fn match_basics(ctx: &mut LessonContext) {
+    ctx.section_heading("match 表达式基础");
     
     // 定义一个简单的枚举
     enum Coin {
@@ -63,10 +214,10 @@ fn match_basics() {
     let dime = Coin::Dime;
     let quarter = Coin::Quarter;
     
-    println!("便士的价值: {}", value_in_cents(penny));
-    println!("镍币的价值: {}", value_in_cents(nickel));
-    println!("一角硬币的价值: {}", value_in_cents(dime));
-    println!("二角五分硬币的价值: {}", value_in_cents(quarter));
+    writeln!(ctx, "便士的价值: {}", value_in_cents(penny));
+    writeln!(ctx, "镍币的价值: {}", value_in_cents(nickel));
+    writeln!(ctx, "一角硬币的价值: {}", value_in_cents(dime));
+    writeln!(ctx, "二角五分硬币的价值: {}", value_in_cents(quarter));
     
     // match 表达式必须是穷尽的，必须覆盖所有可能的情况
     
@@ -79,8 +230,8 @@ fn match_basics() {
 
 // 演示模式匹配中的解构
 // 模式匹配可以解构复杂的数据结构
-fn pattern_destructuring() {
-    println!("\n--- 模式匹配中的解构 ---");
+fn pattern_destructuring(ctx: &mut LessonContext) {
+    ctx.section_heading("模式匹配中的解构");
     
     // 定义一个结构体
     struct Point {
@@ -99,22 +250,22 @@ fn pattern_destructuring() {
     // 解构结构体
     let p = Point { x: 10, y: 20 };
     let Point { x, y } = p;
-    println!("解构结构体: x = {}, y = {}", x, y);
+    writeln!(ctx, "解构结构体: x = {}, y = {}", x, y);
     
     // 在 match 表达式中解构结构体
     match p {
-        Point { x: 0, y } => println!("x 坐标为 0, y = {}", y),
-        Point { x, y: 0 } => println!("y 坐标为 0, x = {}", x),
-        Point { x, y } => println!("普通点: ({}, {})", x, y),
+        Point { x: 0, y } => writeln!(ctx, "x 坐标为 0, y = {}", y),
+        Point { x, y: 0 } => writeln!(ctx, "y 坐标为 0, x = {}", x),
+        Point { x, y } => writeln!(ctx, "普通点: ({}, {})", x, y),
     }
     
     // 在 match 表达式中解构枚举
     let msg = Message::Move { x: 30, y: 40 };
     match msg {
-        Message::Quit => println!("退出消息"),
-        Message::Move { x, y } => println!("移动到: ({}, {})", x, y),
-        Message::Write(text) => println!("写入文本: {}", text),
-        Message::ChangeColor(r, g, b) => println!("更改为颜色: RGB({}, {}, {})", r, g, b),
+        Message::Quit => writeln!(ctx, "退出消息"),
+        Message::Move { x, y } => writeln!(ctx, "移动到: ({}, {})", x, y),
+        Message::Write(text) => writeln!(ctx, "写入文本: {}", text),
+        Message::ChangeColor(r, g, b) => writeln!(ctx, "更改为颜色: RGB({}, {}, {})", r, g, b),
     }
     
     // 运行结果：
@@ -125,8 +276,8 @@ fn pattern_destructuring() {
 
 // 演示模式匹配中的范围匹配
 // 可以使用范围运算符 ..= 来匹配一系列值
-fn range_matching() {
-    println!("\n--- 模式匹配中的范围匹配 ---");
+fn range_matching(ctx: &mut LessonContext) {
+    ctx.section_heading("模式匹配中的范围匹配");
     
     // 定义一个函数，使用范围匹配来判断成绩等级
     fn grade(score: u32) -> &'static str {
@@ -140,19 +291,19 @@ fn range_matching() {
     }
     
     // 测试范围匹配
-    println!("成绩 50: {}", grade(50));
-    println!("成绩 75: {}", grade(75));
-    println!("成绩 85: {}", grade(85));
-    println!("成绩 95: {}", grade(95));
-    println!("成绩 101: {}", grade(101));
+    writeln!(ctx, "成绩 50: {}", grade(50));
+    writeln!(ctx, "成绩 75: {}", grade(75));
+    writeln!(ctx, "成绩 85: {}", grade(85));
+    writeln!(ctx, "成绩 95: {}", grade(95));
+    writeln!(ctx, "成绩 101: {}", grade(101));
     
     // 字符也可以进行范围匹配
     let c = 'R';
     match c {
-        'A'..='Z' => println!("{} 是大写字母", c),
-        'a'..='z' => println!("{} 是小写字母", c),
-        '0'..='9' => println!("{} 是数字", c),
-        _ => println!("{} 是其他字符", c),
+        'A'..='Z' => writeln!(ctx, "{} 是大写字母", c),
+        'a'..='z' => writeln!(ctx, "{} 是小写字母", c),
+        '0'..='9' => writeln!(ctx, "{} 是数字", c),
+        _ => writeln!(ctx, "{} 是其他字符", c),
     }
     
     // 运行结果：
@@ -166,8 +317,8 @@ fn range_matching() {
 
 // 演示模式匹配中的通配符
 // 通配符 _ 用于匹配任何值，但不会绑定到变量
-fn wildcards() {
-    println!("\n--- 模式匹配中的通配符 ---");
+fn wildcards(ctx: &mut LessonContext) {
+    ctx.section_heading("模式匹配中的通配符");
     
     // 定义一个结构体
     struct Point {
@@ -181,14 +332,14 @@ fn wildcards() {
     
     // 使用通配符忽略某些字段
     let Point { x, .. } = p;
-    println!("只关心 x 坐标: x = {}", x);
+    writeln!(ctx, "只关心 x 坐标: x = {}", x);
     
     // 在 match 表达式中使用通配符
     match p {
-        Point { x: 0, y, z } => println!("x 为 0: y = {}, z = {}", y, z),
-        Point { x, y: 0, z } => println!("y 为 0: x = {}, z = {}", x, z),
-        Point { x, y, z: 0 } => println!("z 为 0: x = {}, y = {}", x, y),
-        _ => println!("所有坐标都不为 0"),
+        Point { x: 0, y, z } => writeln!(ctx, "x 为 0: y = {}, z = {}", y, z),
+        Point { x, y: 0, z } => writeln!(ctx, "y 为 0: x = {}, z = {}", x, z),
+        Point { x, y, z: 0 } => writeln!(ctx, "z 为 0: x = {}, y = {}", x, y),
+        _ => writeln!(ctx, "所有坐标都不为 0"),
     }
     
     // 定义一个枚举
@@ -202,11 +353,11 @@ fn wildcards() {
     // 在 match 表达式中使用通配符
     let color = Color::Custom(255, 0, 0);
     match color {
-        Color::Red => println!("红色"),
-        Color::Green => println!("绿色"),
-        Color::Blue => println!("蓝色"),
-        Color::Custom(r, g, b) => println!("自定义颜色: RGB({}, {}, {})", r, g, b),
-        _ => println!("其他颜色"), // 这个分支永远不会执行，因为我们已经覆盖了所有变体
+        Color::Red => writeln!(ctx, "红色"),
+        Color::Green => writeln!(ctx, "绿色"),
+        Color::Blue => writeln!(ctx, "蓝色"),
+        Color::Custom(r, g, b) => writeln!(ctx, "自定义颜色: RGB({}, {}, {})", r, g, b),
+        _ => writeln!(ctx, "其他颜色"), // 这个分支永远不会执行，因为我们已经覆盖了所有变体
     }
     
     // 运行结果：
@@ -217,8 +368,8 @@ fn wildcards() {
 
 // 演示 if let 表达式
 // if let 表达式是 match 表达式的简化形式，用于处理只有一个模式需要匹配的情况
-fn if_let_expressions() {
-    println!("\n--- if let 表达式 ---");
+fn if_let_expressions(ctx: &mut LessonContext) {
+    ctx.section_heading("if let 表达式");
     
     // 定义一个 Option 类型
     let some_number: Option<i32> = Some(42);
@@ -226,20 +377,20 @@ fn if_let_expressions() {
     
     // 使用 match 表达式处理 Option
     match some_number {
-        Some(n) => println!("有值: {}", n),
+        Some(n) => writeln!(ctx, "有值: {}", n),
         _ => (),
     }
     
     // 使用 if let 表达式简化上面的代码
     if let Some(n) = some_number {
-        println!("使用 if let 有值: {}", n);
+        writeln!(ctx, "使用 if let 有值: {}", n);
     }
     
     // if let 也可以有 else 部分
     if let Some(n) = absent_number {
-        println!("这个不会执行，因为 absent_number 是 None");
+        writeln!(ctx, "这个不会执行，因为 absent_number 是 None");
     } else {
-        println!("absent_number 是 None");
+        writeln!(ctx, "absent_number 是 None");
     }
     
     // 定义一个枚举
@@ -253,7 +404,7 @@ fn if_let_expressions() {
     // 使用 if let 处理枚举
     let coin = Coin::Penny;
     if let Coin::Penny = coin {
-        println!("找到一个便士！");
+        writeln!(ctx, "找到一个便士！");
     }
     
     // 运行结果：
@@ -265,28 +416,28 @@ fn if_let_expressions() {
 
 // 演示 while let 表达式
 // while let 表达式结合了 while 循环和 if let 表达式的功能
-fn while_let_expressions() {
-    println!("\n--- while let 表达式 ---");
+fn while_let_expressions(ctx: &mut LessonContext) {
+    ctx.section_heading("while let 表达式");
     
     // 创建一个可变的 Vec
     let mut stack = vec![1, 2, 3, 4, 5];
     
     // 使用 while let 表达式弹出并处理栈中的元素
-    println!("弹出栈中的元素:");
+    writeln!(ctx, "弹出栈中的元素:");
     while let Some(top) = stack.pop() {
-        println!("弹出: {}", top);
+        writeln!(ctx, "弹出: {}", top);
     }
     
     // 定义一个包含 Option 的迭代器
     let mut optional_numbers = vec![Some(1), Some(2), None, Some(4), None, Some(6)];
     
     // 使用 while let 处理包含 Option 的迭代器
-    println!("\n处理包含 None 的迭代器:");
+    writeln!(ctx, "\n处理包含 None 的迭代器:");
     while let Some(optional) = optional_numbers.pop() {
         if let Some(number) = optional {
-            println!("处理数字: {}", number);
+            writeln!(ctx, "处理数字: {}", number);
         } else {
-            println!("遇到 None");
+            writeln!(ctx, "遇到 None");
         }
     }
     
@@ -309,25 +460,25 @@ fn while_let_expressions() {
 
 // 演示 for 循环中的模式
 // for 循环中也可以使用模式匹配
-fn for_loops_with_patterns() {
-    println!("\n--- for 循环中的模式 ---");
+fn for_loops_with_patterns(ctx: &mut LessonContext) {
+    ctx.section_heading("for 循环中的模式");
     
     // 定义一个数组
     let v = vec![10, 20, 30, 40, 50];
     
     // 使用 for 循环和 enumerate 方法获取索引和值
-    println!("遍历数组并获取索引:");
+    writeln!(ctx, "遍历数组并获取索引:");
     for (index, value) in v.iter().enumerate() {
-        println!("索引 {}: 值 {}", index, value);
+        writeln!(ctx, "索引 {}: 值 {}", index, value);
     }
     
     // 定义一个元组数组
     let positions = [(1, 2), (3, 4), (5, 6)];
     
     // 使用 for 循环解构元组
-    println!("\n遍历元组数组:");
+    writeln!(ctx, "\n遍历元组数组:");
     for (x, y) in positions.iter() {
-        println!("位置: ({}, {})", x, y);
+        writeln!(ctx, "位置: ({}, {})", x, y);
     }
     
     // 运行结果：
@@ -346,16 +497,16 @@ fn for_loops_with_patterns() {
 
 // 演示 let 语句中的模式
 // let 语句本身也是一种模式匹配
-fn let_statements_with_patterns() {
-    println!("\n--- let 语句中的模式 ---");
+fn let_statements_with_patterns(ctx: &mut LessonContext) {
+    ctx.section_heading("let 语句中的模式");
     
     // 基本的 let 语句
     let x = 5;
-    println!("x = {}", x);
+    writeln!(ctx, "x = {}", x);
     
     // 使用模式匹配解构元组
     let (a, b) = (10, 20);
-    println!("解构元组: a = {}, b = {}", a, b);
+    writeln!(ctx, "解构元组: a = {}, b = {}", a, b);
     
     // 定义一个结构体
     struct Point {
@@ -366,15 +517,15 @@ fn let_statements_with_patterns() {
     // 使用模式匹配解构结构体
     let p = Point { x: 30, y: 40 };
     let Point { x: px, y: py } = p;
-    println!("解构结构体: px = {}, py = {}", px, py);
+    writeln!(ctx, "解构结构体: px = {}, py = {}", px, py);
     
     // 简化的结构体解构语法
     let Point { x, y } = p;
-    println!("简化解构: x = {}, y = {}", x, y);
+    writeln!(ctx, "简化解构: x = {}, y = {}", x, y);
     
     // 使用模式匹配忽略某些值
     let (_, c) = (50, 60);
-    println!("忽略第一个值: c = {}", c);
+    writeln!(ctx, "忽略第一个值: c = {}", c);
     
     // 运行结果：
     // x = 5
@@ -386,17 +537,17 @@ fn let_statements_with_patterns() {
 
 // 演示函数参数中的模式
 // 函数参数也可以使用模式匹配
-fn function_parameters_with_patterns() {
-    println!("\n--- 函数参数中的模式 ---");
+fn function_parameters_with_patterns(ctx: &mut LessonContext) {
+    ctx.section_heading("函数参数中的模式");
     
     // 定义一个接受元组参数的函数
-    fn print_coordinates((x, y): (i32, i32)) {
-        println!("坐标: ({}, {})", x, y);
+    fn print_coordinates(ctx: &mut LessonContext, (x, y): (i32, i32)) {
+        writeln!(ctx, "坐标: ({}, {})", x, y);
     }
     
     // 调用函数
     let point = (100, 200);
-    print_coordinates(point);
+    print_coordinates(ctx, point);
     
     // 定义一个结构体
     struct Rectangle {
@@ -411,19 +562,19 @@ fn function_parameters_with_patterns() {
     
     // 创建一个 Rectangle 实例并调用函数
     let rect = Rectangle { width: 10, height: 20 };
-    println!("矩形面积: {}", area(&rect));
+    writeln!(ctx, "矩形面积: {}", area(&rect));
     
     // 定义一个接受 Option 参数的函数
-    fn process_option(option: Option<i32>) {
+    fn process_option(ctx: &mut LessonContext, option: Option<i32>) {
         match option {
-            Some(value) => println!("处理值: {}", value),
-            None => println!("没有值"),
+            Some(value) => writeln!(ctx, "处理值: {}", value),
+            None => writeln!(ctx, "没有值"),
         }
     }
     
     // 调用函数
-    process_option(Some(42));
-    process_option(None);
+    process_option(ctx, Some(42));
+    process_option(ctx, None);
     
     // 运行结果：
     // 坐标: (100, 200)
@@ -434,16 +585,16 @@ fn function_parameters_with_patterns() {
 
 // 演示高级模式匹配技巧
 // Rust 的模式匹配非常强大，支持许多高级特性
-fn advanced_pattern_matching() {
-    println!("\n--- 高级模式匹配技巧 ---");
+fn advanced_pattern_matching(ctx: &mut LessonContext) {
+    ctx.section_heading("高级模式匹配技巧");
     
     // 1. 匹配守卫（Match Guards）
     // 匹配守卫是附加在 match 分支后的条件
     let num = Some(4);
     match num {
-        Some(x) if x < 5 => println!("小于 5 的数字: {}", x),
-        Some(x) => println!("大于或等于 5 的数字: {}", x),
-        None => println!("没有数字"),
+        Some(x) if x < 5 => writeln!(ctx, "小于 5 的数字: {}", x),
+        Some(x) => writeln!(ctx, "大于或等于 5 的数字: {}", x),
+        None => writeln!(ctx, "没有数字"),
     }
     
     // 2. @ 绑定
@@ -455,13 +606,13 @@ fn advanced_pattern_matching() {
     let msg = Message::Hello { id: 5 };
     match msg {
         Message::Hello { id: id_variable @ 3..=7 } => {
-            println!("找到 ID 在范围内: {}", id_variable);
+            writeln!(ctx, "找到 ID 在范围内: {}", id_variable);
         },
         Message::Hello { id: 10..=12 } => {
-            println!("找到 ID 在 10-12 范围内");
+            writeln!(ctx, "找到 ID 在 10-12 范围内");
         },
         Message::Hello { id } => {
-            println!("找到其他 ID: {}", id);
+            writeln!(ctx, "找到其他 ID: {}", id);
         },
     }
     
@@ -486,7 +637,7 @@ fn advanced_pattern_matching() {
             top_left: Point { x: left, y: top },
             bottom_right: Point { x: right, y: bottom },
         } => {
-            println!("矩形: 左上角({}, {}), 右下角({}, {})", left, top, right, bottom);
+            writeln!(ctx, "矩形: 左上角({}, {}), 右下角({}, {})", left, top, right, bottom);
         },
     }
     
@@ -15,75 +15,243 @@
 
 // 导入标准输出模块和需要的集合类型
 use std::collections::{HashMap, HashSet, BTreeMap, BTreeSet};
+use crate::compile_error::CompileError;
+use crate::context::LessonContext;
+use crate::exercise::Exercise;
+use crate::graph::Prerequisite;
+use crate::lesson::{Lesson, Section};
+use crate::quiz::{Question, QuestionKind};
+
+// 本课在课程注册表中的登记信息
+// 主程序的菜单等前端都通过 Lesson trait 读取这些信息
+pub struct CollectionsLesson;
+
+impl Lesson for CollectionsLesson {
+    fn id(&self) -> &'static str {
+        "6"
+    }
+
+    fn title(&self) -> &'static str {
+        "常见集合及其操作 (Collections)"
+    }
+
+    fn summary(&self) -> &'static str {
+        "本示例将介绍 Rust 中的常见集合类型和操作方法。"
+    }
+
+    fn topics(&self) -> &'static [&'static str] {
+        &[
+            "Vector (动态数组)",
+            "String (字符串)",
+            "HashMap (哈希映射)",
+            "HashSet (哈希集合)",
+            "BTreeMap (有序映射)",
+            "BTreeSet (有序集合)",
+            "集合的遍历和迭代",
+            "集合的常见操作",
+            "集合的性能特点",
+            "集合的所有权问题",
+        ]
+    }
+
+    fn sections(&self) -> &'static [Section] {
+        &[
+            Section { id: "vector_collection", title: "Vector (动态数组)", run: vector_collection },
+            Section { id: "string_collection", title: "String (字符串)", run: string_collection },
+            Section { id: "hashmap_collection", title: "HashMap (哈希映射)", run: hashmap_collection },
+            Section { id: "hashset_collection", title: "HashSet (哈希集合)", run: hashset_collection },
+            Section { id: "btreemap_collection", title: "BTreeMap (有序映射)", run: btreemap_collection },
+            Section { id: "btreeset_collection", title: "BTreeSet (有序集合)", run: btreeset_collection },
+            Section { id: "collection_iteration", title: "集合的遍历和迭代", run: collection_iteration },
+            Section { id: "common_operations", title: "集合的常见操作", run: common_operations },
+            Section { id: "performance_considerations", title: "集合的性能特点", run: performance_considerations },
+            Section { id: "ownership_issues", title: "集合的所有权问题", run: ownership_issues },
+        ]
+    }
+
+    fn source(&self) -> &'static str {
+        include_str!("_06_collections.rs")
+    }
+
+    fn source_path(&self) -> &'static str {
+        file!()
+    }
+
+    fn quiz(&self) -> &'static [Question] {
+        QUIZ
+    }
+
+    fn exercises(&self) -> &'static [Exercise] {
+        EXERCISES
+    }
+
+    fn compile_errors(&self) -> &'static [CompileError] {
+        COMPILE_ERRORS
+    }
+
+    fn prerequisites(&self) -> &'static [&'static str] {
+        &["4"]
+    }
+
+    fn section_prerequisites(&self) -> &'static [Prerequisite] {
+        &[
+            Prerequisite {
+                section: "hashmap_collection",
+                lesson: "4",
+                requires: "option_enum",
+                reason: "HashMap 的 get 返回 Option，需要先知道怎样处理 Some 和 None",
+            },
+        ]
+    }
+
+    fn run(&self, ctx: &mut LessonContext) {
+        run(ctx);
+    }
+}
+
+// 本课的测验题目
+const QUIZ: &[Question] = &[
+    Question {
+        prompt: "下面的代码最后一行会输出什么？",
+        kind: QuestionKind::PredictOutput {
+            section: "ownership_issues",
+            code: r#"let s3 = String::from("rust");
+let s4 = String::from("programming");
+
+let mut v_refs = Vec::new();
+v_refs.push(&s3);
+v_refs.push(&s4);
+
+println!("s3: {}, s4: {}", s3, s4);"#,
+            options: &["s3: rust, s4: programming", "编译错误：s3 和 s4 已经被移动", "s3: , s4: "],
+            answer: 0,
+        },
+        explanation: "Vector 中保存的是 &s3 和 &s4 这两个引用，s3 和 s4 只是被借用，所有权没有转移，之后仍然可以使用",
+    },
+    Question {
+        prompt: "需要按照键的顺序遍历键值对时，应该使用哪种集合？",
+        kind: QuestionKind::Choice {
+            options: &["HashMap", "BTreeMap", "HashSet"],
+            answer: 1,
+        },
+        explanation: "BTreeMap 按照键的顺序保存元素；HashMap 的遍历顺序是不确定的，HashSet 只保存值",
+    },
+    Question {
+        prompt: "把一个 String push 进 Vec 之后，原来的变量仍然可以继续使用。",
+        kind: QuestionKind::TrueFalse { answer: false },
+        explanation: "push 会把 String 的所有权转移给 Vec，之后再使用原来的变量会导致编译错误；如果还需要使用，可以 push 它的克隆或者引用",
+    },
+];
+
+// 本课的动手练习
+const EXERCISES: &[Exercise] = &[
+    Exercise {
+        name: "word_count",
+        title: "统计单词出现的次数",
+        description: "实现 word_count，统计文本中每个单词出现的次数，不区分大小写",
+        starter: include_str!("../exercises/06_collections/word_count.rs"),
+        tests: include_str!("../exercises/06_collections/word_count_tests.rs"),
+        solution: include_str!("../exercises/06_collections/word_count_solution.rs"),
+        hints: &[
+            "split_whitespace 会跳过连续的空白字符",
+            "to_lowercase 把单词转换成小写后再作为键",
+            "*map.entry(key).or_insert(0) += 1 可以一步完成查找和计数",
+        ],
+    },
+];
+
+// 本课的编译错误示例
+const COMPILE_ERRORS: &[CompileError] = &[
+    CompileError {
+        name: "use_after_push",
+        title: "把 String push 进 Vec 之后继续使用",
+        section: "ownership_issues",
+        code: "E0382",
+        broken: include_str!("../compile_errors/06_collections/use_after_push.rs"),
+        fixed: include_str!("../compile_errors/06_collections/use_after_push_fixed.rs"),
+        explanation: "push 会把 s1 的所有权转移给 Vector，之后 s1 就不能再使用了，编译器会指出值在哪里被移动。还需要使用原来的字符串时，可以 push 它的克隆，或者让 Vector 保存引用",
+    },
+    CompileError {
+        name: "push_while_borrowed",
+        title: "持有元素的引用时修改 Vector",
+        section: "vector_collection",
+        code: "E0502",
+        broken: include_str!("../compile_errors/06_collections/push_while_borrowed.rs"),
+        fixed: include_str!("../compile_errors/06_collections/push_while_borrowed_fixed.rs"),
+        explanation: "first 是对 v 中元素的不可变借用，而 push 需要可变借用。push 可能重新分配内存，让 first 指向已经释放的内存，所以在 first 最后一次使用之前不能修改 v。把元素复制出来，或者在使用完 first 之后再 push",
+    },
+    CompileError {
+        name: "two_mutable_borrows",
+        title: "同时存在两个可变借用",
+        section: "ownership_issues",
+        code: "E0499",
+        broken: include_str!("../compile_errors/06_collections/two_mutable_borrows.rs"),
+        fixed: include_str!("../compile_errors/06_collections/two_mutable_borrows_fixed.rs"),
+        explanation: "同一时间只能有一个可变借用。a 在 b 创建之后还会被使用，两个可变借用的作用域发生了重叠。调整代码顺序，让 a 最后一次使用之后再创建 b",
+    },
+];
 
 // 定义一个公共函数 run()，作为本模块的入口点
 // 当用户在主程序中选择 6 时，将调用此函数
-pub fn run() {
-    println!("=== 第6课：常见集合及其操作 ===");
-    println!("本示例将介绍 Rust 中的常见集合类型和操作方法。\n");
-    
-    // 调用各个示例函数
-    vector_collection();
-    string_collection();
-    hashmap_collection();
-    hashset_collection();
-    btreemap_collection();
-    btreeset_collection();
-    collection_iteration();
-    common_operations();
-    performance_considerations();
-    ownership_issues();
+pub fn run(ctx: &mut LessonContext) {
+    ctx.lesson_heading("第6课：常见集合及其操作");
+    writeln!(ctx, "本示例将介绍 Rust 中的常见集合类型和操作方法。\n");
+    
+    // 按顺序调用各个示例函数，小节列表统一登记在 sections() 中
+    for section in CollectionsLesson.sections() {
+        (section.run)(ctx);
+    }
 }
 
 // 演示 Vector 集合
 // Vector 是一个动态数组，可以存储多个相同类型的元素
-fn vector_collection() {
-    println!("\n--- Vector (动态数组) ---");
+fn vector_collection(ctx: &mut LessonContext) {
+    ctx.section_heading("Vector (动态数组)");
     
     // 创建一个新的空 Vector
     let mut v1: Vec<i32> = Vec::new();
-    println!("创建空 Vector: v1 = {:?}", v1);
+    writeln!(ctx, "创建空 Vector: v1 = {:?}", v1);
     
     // 使用 vec! 宏创建包含初始值的 Vector
     let v2 = vec![1, 2, 3, 4, 5];
-    println!("使用 vec! 宏创建 Vector: v2 = {:?}", v2);
+    writeln!(ctx, "使用 vec! 宏创建 Vector: v2 = {:?}", v2);
     
     // 向 Vector 中添加元素
     v1.push(10);
     v1.push(20);
     v1.push(30);
-    println!("添加元素后: v1 = {:?}", v1);
+    writeln!(ctx, "添加元素后: v1 = {:?}", v1);
     
     // 访问 Vector 中的元素
     // 方法 1: 使用索引访问（如果索引越界会导致程序崩溃）
     let first = v2[0];
-    println!("v2 的第一个元素: {}", first);
+    writeln!(ctx, "v2 的第一个元素: {}", first);
     
     // 方法 2: 使用 get 方法访问（安全，返回 Option）
     let second = v2.get(1);
     match second {
-        Some(value) => println!("v2 的第二个元素: {}", value),
-        None => println!("索引超出范围"),
+        Some(value) => writeln!(ctx, "v2 的第二个元素: {}", value),
+        None => writeln!(ctx, "索引超出范围"),
     }
     
     // 遍历 Vector
-    println!("遍历 v2 中的元素:");
+    writeln!(ctx, "遍历 v2 中的元素:");
     for element in &v2 {
-        println!("{} ", element);
+        writeln!(ctx, "{} ", element);
     }
     
     // 修改 Vector 中的元素
-    println!("修改前: v1 = {:?}", v1);
+    writeln!(ctx, "修改前: v1 = {:?}", v1);
     if let Some(element) = v1.get_mut(0) {
         *element = 100;
     }
-    println!("修改后: v1 = {:?}", v1);
+    writeln!(ctx, "修改后: v1 = {:?}", v1);
     
     // 获取 Vector 的长度
-    println!("v1 的长度: {}", v1.len());
+    writeln!(ctx, "v1 的长度: {}", v1.len());
     
     // 检查 Vector 是否为空
-    println!("v1 是否为空: {}", v1.is_empty());
+    writeln!(ctx, "v1 是否为空: {}", v1.is_empty());
     
     // 运行结果：
     // 创建空 Vector: v1 = []
@@ -105,46 +273,46 @@ fn vector_collection() {
 
 // 演示 String 集合
 // String 是 Rust 中的可变字符串类型
-fn string_collection() {
-    println!("\n--- String (字符串) ---");
+fn string_collection(ctx: &mut LessonContext) {
+    ctx.section_heading("String (字符串)");
     
     // 创建一个空字符串
     let mut s1 = String::new();
-    println!("创建空字符串: s1 = '{}'", s1);
+    writeln!(ctx, "创建空字符串: s1 = '{}'", s1);
     
     // 使用字符串字面量创建 String
     let s2 = String::from("Hello");
-    println!("使用 from 方法创建字符串: s2 = '{}'", s2);
+    writeln!(ctx, "使用 from 方法创建字符串: s2 = '{}'", s2);
     
     // 拼接字符串
     s1.push_str("Rust");
-    println!("使用 push_str 添加字符串: s1 = '{}'", s1);
+    writeln!(ctx, "使用 push_str 添加字符串: s1 = '{}'", s1);
     
     // 添加单个字符
     s1.push('!');
-    println!("使用 push 添加字符: s1 = '{}'", s1);
+    writeln!(ctx, "使用 push 添加字符: s1 = '{}'", s1);
     
     // 使用 + 运算符拼接字符串
     let s3 = s2.clone() + " " + &s1;
-    println!("使用 + 运算符拼接字符串: s3 = '{}'", s3);
+    writeln!(ctx, "使用 + 运算符拼接字符串: s3 = '{}'", s3);
     
     // 使用 format! 宏拼接字符串
     let s4 = format!("{} {} {}", s2, s1, "World");
-    println!("使用 format! 宏拼接字符串: s4 = '{}'", s4);
+    writeln!(ctx, "使用 format! 宏拼接字符串: s4 = '{}'", s4);
     
     // 访问字符串长度
-    println!("s4 的长度: {}", s4.len());
+    writeln!(ctx, "s4 的长度: {}", s4.len());
     
     // 遍历字符串中的字符
-    println!("遍历 s4 中的字符:");
+    writeln!(ctx, "遍历 s4 中的字符:");
     for c in s4.chars() {
-        println!("{}", c);
+        writeln!(ctx, "{}", c);
     }
     
     // 遍历字符串中的字节
-    println!("遍历 s4 中的前 10 个字节:");
+    writeln!(ctx, "遍历 s4 中的前 10 个字节:");
     for (i, byte) in s4.bytes().take(10).enumerate() {
-        println!("字节 {}: {}", i, byte);
+        writeln!(ctx, "字节 {}: {}", i, byte);
     }
     
     // 注意：Rust 的 String 不支持直接通过索引访问字符
@@ -191,8 +359,8 @@ fn string_collection() {
 
 // 演示 HashMap 集合
 // HashMap 是一个键值对集合，基于哈希表实现
-fn hashmap_collection() {
-    println!("\n--- HashMap (哈希映射) ---");
+fn hashmap_collection(ctx: &mut LessonContext) {
+    ctx.section_heading("HashMap (哈希映射)");
     
     // 创建一个新的空 HashMap
     let mut scores = HashMap::new();
@@ -202,44 +370,44 @@ fn hashmap_collection() {
     scores.insert(String::from("Bob"), 85);
     scores.insert(String::from("Charlie"), 90);
     
-    println!("HashMap: {:?}", scores);
+    writeln!(ctx, "HashMap: {:?}", scores);
     
     // 访问 HashMap 中的值
     let name = String::from("Alice");
     if let Some(score) = scores.get(&name) {
-        println!("{} 的分数: {}", name, score);
+        writeln!(ctx, "{} 的分数: {}", name, score);
     } else {
-        println!("未找到 {} 的分数", name);
+        writeln!(ctx, "未找到 {} 的分数", name);
     }
     
     // 遍历 HashMap
-    println!("遍历 HashMap:");
+    writeln!(ctx, "遍历 HashMap:");
     for (key, value) in &scores {
-        println!("{}: {}", key, value);
+        writeln!(ctx, "{}: {}", key, value);
     }
     
     // 检查键是否存在
     let name2 = String::from("David");
-    println!("HashMap 中是否包含 {}: {}", name2, scores.contains_key(&name2));
+    writeln!(ctx, "HashMap 中是否包含 {}: {}", name2, scores.contains_key(&name2));
     
     // 插入新值并获取旧值
     let old_score = scores.insert(String::from("Alice"), 105);
-    println!("Alice 的旧分数: {:?}", old_score);
-    println!("更新后的 HashMap: {:?}", scores);
+    writeln!(ctx, "Alice 的旧分数: {:?}", old_score);
+    writeln!(ctx, "更新后的 HashMap: {:?}", scores);
     
     // 只在键不存在时插入
     scores.entry(String::from("David")).or_insert(75);
     scores.entry(String::from("Alice")).or_insert(0); // 不会覆盖现有值
-    println!("使用 entry 方法后的 HashMap: {:?}", scores);
+    writeln!(ctx, "使用 entry 方法后的 HashMap: {:?}", scores);
     
     // 获取 HashMap 的长度
-    println!("HashMap 的长度: {}", scores.len());
+    writeln!(ctx, "HashMap 的长度: {}", scores.len());
     
     // 删除键值对
     scores.remove(&name);
-    println!("删除 Alice 后的 HashMap: {:?}", scores);
+    writeln!(ctx, "删除 Alice 后的 HashMap: {:?}", scores);
     
-    // 运行结果：
+    // 运行结果（顺序不固定）：
     // HashMap: {"Bob": 85, "Alice": 100, "Charlie": 90}
     // Alice 的分数: 100
     // 遍历 HashMap:
@@ -256,8 +424,8 @@ fn hashmap_collection() {
 
 // 演示 HashSet 集合
 // HashSet 是一个存储唯一值的集合，基于哈希表实现
-fn hashset_collection() {
-    println!("\n--- HashSet (哈希集合) ---");
+fn hashset_collection(ctx: &mut LessonContext) {
+    ctx.section_heading("HashSet (哈希集合)");
     
     // 创建一个新的空 HashSet
     let mut numbers = HashSet::new();
@@ -271,26 +439,26 @@ fn hashset_collection() {
     
     // 尝试插入重复的元素（将被忽略）
     let result = numbers.insert(3);
-    println!("插入重复元素 3 的结果: {}", result);
+    writeln!(ctx, "插入重复元素 3 的结果: {}", result);
     
-    println!("HashSet: {:?}", numbers);
+    writeln!(ctx, "HashSet: {:?}", numbers);
     
     // 检查元素是否存在
-    println!("HashSet 中是否包含 3: {}", numbers.contains(&3));
-    println!("HashSet 中是否包含 10: {}", numbers.contains(&10));
+    writeln!(ctx, "HashSet 中是否包含 3: {}", numbers.contains(&3));
+    writeln!(ctx, "HashSet 中是否包含 10: {}", numbers.contains(&10));
     
     // 遍历 HashSet
-    println!("遍历 HashSet:");
+    writeln!(ctx, "遍历 HashSet:");
     for number in &numbers {
-        println!("{}", number);
+        writeln!(ctx, "{}", number);
     }
     
     // 获取 HashSet 的长度
-    println!("HashSet 的长度: {}", numbers.len());
+    writeln!(ctx, "HashSet 的长度: {}", numbers.len());
     
     // 删除元素
     numbers.remove(&3);
-    println!("删除 3 后的 HashSet: {:?}", numbers);
+    writeln!(ctx, "删除 3 后的 HashSet: {:?}", numbers);
     
     // 集合操作
     let mut set1: HashSet<i32> = vec![1, 2, 3, 4, 5].into_iter().collect();
@@ -298,13 +466,13 @@ fn hashset_collection() {
     
     // 交集
     let intersection: HashSet<_> = set1.intersection(&set2).collect();
-    println!("set1 和 set2 的交集: {:?}", intersection);
+    writeln!(ctx, "set1 和 set2 的交集: {:?}", intersection);
     
     // 并集
     set1.extend(&set2);
-    println!("set1 和 set2 的并集: {:?}", set1);
+    writeln!(ctx, "set1 和 set2 的并集: {:?}", set1);
     
-    // 运行结果：
+    // 运行结果（顺序不固定）：
     // 插入重复元素 3 的结果: false
     // HashSet: {3, 1, 2, 5, 4}
     // HashSet 中是否包含 3: true
@@ -323,8 +491,8 @@ fn hashset_collection() {
 
 // 演示 BTreeMap 集合
 // BTreeMap 是一个按键排序的键值对集合，基于 B 树实现
-fn btreemap_collection() {
-    println!("\n--- BTreeMap (有序映射) ---");
+fn btreemap_collection(ctx: &mut LessonContext) {
+    ctx.section_heading("BTreeMap (有序映射)");
     
     // 创建一个新的空 BTreeMap
     let mut scores = BTreeMap::new();
@@ -334,12 +502,12 @@ fn btreemap_collection() {
     scores.insert(String::from("Bob"), 85);
     scores.insert(String::from("Charlie"), 90);
     
-    println!("BTreeMap: {:?}", scores);
+    writeln!(ctx, "BTreeMap: {:?}", scores);
     
     // 遍历 BTreeMap（按键排序）
-    println!("遍历 BTreeMap (按键排序):");
+    writeln!(ctx, "遍历 BTreeMap (按键排序):");
     for (key, value) in &scores {
-        println!("{}: {}", key, value);
+        writeln!(ctx, "{}: {}", key, value);
     }
     
     // BTreeMap 支持的其他操作与 HashMap 类似
@@ -355,8 +523,8 @@ fn btreemap_collection() {
 
 // 演示 BTreeSet 集合
 // BTreeSet 是一个存储唯一值并自动排序的集合，基于 B 树实现
-fn btreeset_collection() {
-    println!("\n--- BTreeSet (有序集合) ---");
+fn btreeset_collection(ctx: &mut LessonContext) {
+    ctx.section_heading("BTreeSet (有序集合)");
     
     // 创建一个新的空 BTreeSet
     let mut numbers = BTreeSet::new();
@@ -368,12 +536,12 @@ fn btreeset_collection() {
     numbers.insert(1);
     numbers.insert(9);
     
-    println!("BTreeSet: {:?}", numbers);
+    writeln!(ctx, "BTreeSet: {:?}", numbers);
     
     // 遍历 BTreeSet（自动排序）
-    println!("遍历 BTreeSet (自动排序):");
+    writeln!(ctx, "遍历 BTreeSet (自动排序):");
     for number in &numbers {
-        println!("{}", number);
+        writeln!(ctx, "{}", number);
     }
     
     // BTreeSet 支持的其他操作与 HashSet 类似
@@ -391,28 +559,28 @@ fn btreeset_collection() {
 
 // 演示集合的遍历和迭代
 // 所有集合类型都支持迭代操作
-fn collection_iteration() {
-    println!("\n--- 集合的遍历和迭代 ---");
+fn collection_iteration(ctx: &mut LessonContext) {
+    ctx.section_heading("集合的遍历和迭代");
     
     // 遍历 Vector
     let v = vec![10, 20, 30, 40, 50];
-    println!("遍历 Vector (不可变引用):");
+    writeln!(ctx, "遍历 Vector (不可变引用):");
     for element in &v {
-        println!("{}", element);
+        writeln!(ctx, "{}", element);
     }
     
     // 可变遍历 Vector
     let mut mv = vec![10, 20, 30, 40, 50];
-    println!("\n遍历 Vector (可变引用，增加值):");
+    writeln!(ctx, "\n遍历 Vector (可变引用，增加值):");
     for element in &mut mv {
         *element += 5;
-        println!("{}", element);
+        writeln!(ctx, "{}", element);
     }
     
     // 使用 into_iter 消耗集合
-    println!("\n使用 into_iter 消耗 Vector:");
+    writeln!(ctx, "\n使用 into_iter 消耗 Vector:");
     let sum: i32 = v.into_iter().sum();
-    println!("元素总和: {}", sum);
+    writeln!(ctx, "元素总和: {}", sum);
     
     // 遍历 HashMap
     let mut map = HashMap::new();
@@ -420,22 +588,22 @@ fn collection_iteration() {
     map.insert("two", 2);
     map.insert("three", 3);
     
-    println!("\n遍历 HashMap 的键:");
+    writeln!(ctx, "\n遍历 HashMap 的键:");
     for key in map.keys() {
-        println!("{}", key);
+        writeln!(ctx, "{}", key);
     }
     
-    println!("\n遍历 HashMap 的值:");
+    writeln!(ctx, "\n遍历 HashMap 的值:");
     for value in map.values() {
-        println!("{}", value);
+        writeln!(ctx, "{}", value);
     }
     
-    println!("\n遍历 HashMap 的键值对:");
+    writeln!(ctx, "\n遍历 HashMap 的键值对:");
     for (key, value) in &map {
-        println!("{}: {}", key, value);
+        writeln!(ctx, "{}: {}", key, value);
     }
     
-    // 运行结果：
+    // 运行结果（顺序不固定）：
     // 遍历 Vector (不可变引用):
     // 10
     // 20
@@ -471,46 +639,46 @@ fn collection_iteration() {
 
 // 演示集合的常见操作
 // 各种集合都支持一些常见的操作
-fn common_operations() {
-    println!("\n--- 集合的常见操作 ---");
+fn common_operations(ctx: &mut LessonContext) {
+    ctx.section_heading("集合的常见操作");
     
     // Vector 的常见操作
     let mut v = vec![1, 2, 3, 4, 5];
-    println!("原始 Vector: {:?}", v);
+    writeln!(ctx, "原始 Vector: {:?}", v);
     
     // 添加元素
     v.push(6);
-    println!("添加元素后: {:?}", v);
+    writeln!(ctx, "添加元素后: {:?}", v);
     
     // 删除最后一个元素
     let last = v.pop();
-    println!("删除的最后一个元素: {:?}", last);
-    println!("删除后: {:?}", v);
+    writeln!(ctx, "删除的最后一个元素: {:?}", last);
+    writeln!(ctx, "删除后: {:?}", v);
     
     // 插入元素
     v.insert(2, 100);
-    println!("在索引 2 插入 100 后: {:?}", v);
+    writeln!(ctx, "在索引 2 插入 100 后: {:?}", v);
     
     // 删除指定索引的元素
     let removed = v.remove(2);
-    println!("删除索引 2 的元素: {}", removed);
-    println!("删除后: {:?}", v);
+    writeln!(ctx, "删除索引 2 的元素: {}", removed);
+    writeln!(ctx, "删除后: {:?}", v);
     
     // 字符串的常见操作
     let mut s = String::from("Hello");
-    println!("\n原始字符串: '{}'", s);
+    writeln!(ctx, "\n原始字符串: '{}'", s);
     
     // 追加字符串
     s.push_str(" Rust");
-    println!("追加字符串后: '{}'", s);
+    writeln!(ctx, "追加字符串后: '{}'", s);
     
     // 检查前缀和后缀
-    println!("以 'Hello' 开头: {}", s.starts_with("Hello"));
-    println!("以 'Rust' 结尾: {}", s.ends_with("Rust"));
+    writeln!(ctx, "以 'Hello' 开头: {}", s.starts_with("Hello"));
+    writeln!(ctx, "以 'Rust' 结尾: {}", s.ends_with("Rust"));
     
     // 替换子字符串
     let new_s = s.replace("Rust", "World");
-    println!("替换后: '{}'", new_s);
+    writeln!(ctx, "替换后: '{}'", new_s);
     
     // 运行结果：
     // 原始 Vector: [1, 2, 3, 4, 5]
@@ -530,41 +698,41 @@ fn common_operations() {
 
 // 演示集合的性能特点
 // 不同的集合类型有不同的性能特点
-fn performance_considerations() {
-    println!("\n--- 集合的性能特点 ---");
+fn performance_considerations(ctx: &mut LessonContext) {
+    ctx.section_heading("集合的性能特点");
     
     // Vector 性能特点
-    println!("Vector 性能特点:");
-    println!("- 随机访问: O(1)");
-    println!("- 在末尾添加/删除元素: 平均 O(1)");
-    println!("- 在中间插入/删除元素: O(n)");
+    writeln!(ctx, "Vector 性能特点:");
+    writeln!(ctx, "- 随机访问: O(1)");
+    writeln!(ctx, "- 在末尾添加/删除元素: 平均 O(1)");
+    writeln!(ctx, "- 在中间插入/删除元素: O(n)");
     
     // String 性能特点
-    println!("\nString 性能特点:");
-    println!("- 追加字符串到末尾: 平均 O(1)");
-    println!("- 随机访问字符: O(n) (因为 UTF-8 编码)");
+    writeln!(ctx, "\nString 性能特点:");
+    writeln!(ctx, "- 追加字符串到末尾: 平均 O(1)");
+    writeln!(ctx, "- 随机访问字符: O(n) (因为 UTF-8 编码)");
     
     // HashMap 性能特点
-    println!("\nHashMap 性能特点:");
-    println!("- 插入键值对: 平均 O(1)");
-    println!("- 查找键值对: 平均 O(1)");
-    println!("- 删除键值对: 平均 O(1)");
-    println!("- 遍历: O(n)");
+    writeln!(ctx, "\nHashMap 性能特点:");
+    writeln!(ctx, "- 插入键值对: 平均 O(1)");
+    writeln!(ctx, "- 查找键值对: 平均 O(1)");
+    writeln!(ctx, "- 删除键值对: 平均 O(1)");
+    writeln!(ctx, "- 遍历: O(n)");
     
     // BTreeMap 性能特点
-    println!("\nBTreeMap 性能特点:");
-    println!("- 插入键值对: O(log n)");
-    println!("- 查找键值对: O(log n)");
-    println!("- 删除键值对: O(log n)");
-    println!("- 有序遍历: O(n)");
+    writeln!(ctx, "\nBTreeMap 性能特点:");
+    writeln!(ctx, "- 插入键值对: O(log n)");
+    writeln!(ctx, "- 查找键值对: O(log n)");
+    writeln!(ctx, "- 删除键值对: O(log n)");
+    writeln!(ctx, "- 有序遍历: O(n)");
     
     // 选择集合的建议
-    println!("\n选择集合的建议:");
-    println!("- 需要动态数组: 使用 Vector");
-    println!("- 需要键值对映射且不需要排序: 使用 HashMap");
-    println!("- 需要键值对映射且需要排序: 使用 BTreeMap");
-    println!("- 需要存储唯一值且不需要排序: 使用 HashSet");
-    println!("- 需要存储唯一值且需要排序: 使用 BTreeSet");
+    writeln!(ctx, "\n选择集合的建议:");
+    writeln!(ctx, "- 需要动态数组: 使用 Vector");
+    writeln!(ctx, "- 需要键值对映射且不需要排序: 使用 HashMap");
+    writeln!(ctx, "- 需要键值对映射且需要排序: 使用 BTreeMap");
+    writeln!(ctx, "- 需要存储唯一值且不需要排序: 使用 HashSet");
+    writeln!(ctx, "- 需要存储唯一值且需要排序: 使用 BTreeSet");
     
     // 运行结果：
     // Vector 性能特点:
@@ -598,8 +766,8 @@ fn performance_considerations() {
 
 // 演示集合的所有权问题
 // 在 Rust 中使用集合时需要注意所有权问题
-fn ownership_issues() {
-    println!("\n--- 集合的所有权问题 ---");
+fn ownership_issues(ctx: &mut LessonContext) {
+    ctx.section_heading("集合的所有权问题");
     
     // Vector 的所有权
     let s1 = String::from("hello");
@@ -609,7 +777,7 @@ fn ownership_issues() {
     v.push(s1); // s1 的所有权转移给了 Vector
     v.push(s2); // s2 的所有权转移给了 Vector
     
-    println!("Vector 中的字符串: {:?}", v);
+    writeln!(ctx, "Vector 中的字符串: {:?}", v);
     
     // 以下代码会导致编译错误，因为 s1 和 s2 的所有权已经转移
     // println!("s1: {}", s1); // 错误
@@ -623,8 +791,8 @@ fn ownership_issues() {
     v_refs.push(&s3); // 借用 s3
     v_refs.push(&s4); // 借用 s4
     
-    println!("Vector 中的字符串引用: {:?}", v_refs);
-    println!("s3: {}, s4: {}", s3, s4); // 仍然可以使用 s3 和 s4
+    writeln!(ctx, "Vector 中的字符串引用: {:?}", v_refs);
+    writeln!(ctx, "s3: {}, s4: {}", s3, s4); // 仍然可以使用 s3 和 s4
     
     // HashMap 的所有权
     let mut map = HashMap::new();
@@ -633,7 +801,7 @@ fn ownership_issues() {
     
     map.insert(key1, value1); // key1 和 value1 的所有权转移给了 HashMap
     
-    println!("HashMap: {:?}", map);
+    writeln!(ctx, "HashMap: {:?}", map);
     
     // 以下代码会导致编译错误
     // println!("key1: {}", key1); // 错误
@@ -646,8 +814,8 @@ fn ownership_issues() {
     let mut map_refs = HashMap::new();
     map_refs.insert(&key2, &value2); // 借用 key2 和 value2
     
-    println!("HashMap with references: {:?}", map_refs);
-    println!("key2: {}, value2: {}", key2, value2); // 仍然可以使用 key2 和 value2
+    writeln!(ctx, "HashMap with references: {:?}", map_refs);
+    writeln!(ctx, "key2: {}, value2: {}", key2, value2); // 仍然可以使用 key2 和 value2
     
     // 运行结果：
     // Vector 中的字符串: ["hello", "world"]
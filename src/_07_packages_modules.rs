@@ -14,47 +14,196 @@
 // 10. 工作空间（Workspace）
 
 // 导入必要的模块
+use crate::compile_error::CompileError;
+use crate::context::LessonContext;
+use crate::exercise::Exercise;
+use crate::graph::Prerequisite;
+use crate::lesson::{Lesson, Section};
+use crate::quiz::{Question, QuestionKind};
+
+// 本课在课程注册表中的登记信息
+// 主程序的菜单等前端都通过 Lesson trait 读取这些信息
+pub struct PackagesModulesLesson;
+
+impl Lesson for PackagesModulesLesson {
+    fn id(&self) -> &'static str {
+        "7"
+    }
+
+    fn title(&self) -> &'static str {
+        "包和模块 (Packages and Modules)"
+    }
+
+    fn summary(&self) -> &'static str {
+        "本示例将介绍 Rust 中的包和模块系统。"
+    }
+
+    fn topics(&self) -> &'static [&'static str] {
+        &[
+            "包（Package）的概念",
+            "Crate 的概念和类型",
+            "模块（Module）的定义和使用",
+            "可见性控制（public/private）",
+            "使用 use 关键字导入模块",
+            "嵌套模块",
+            "模块文件结构",
+            "绝对路径和相对路径",
+            "外部包的使用",
+            "工作空间（Workspace）",
+        ]
+    }
+
+    fn sections(&self) -> &'static [Section] {
+        &[
+            Section { id: "package_and_crate_concepts", title: "包和 Crate 的概念", run: package_and_crate_concepts },
+            Section { id: "module_definition", title: "模块的定义", run: module_definition },
+            Section { id: "visibility_control", title: "可见性控制", run: visibility_control },
+            Section { id: "using_use_keyword", title: "使用 use 关键字导入模块", run: using_use_keyword },
+            Section { id: "nested_modules", title: "嵌套模块", run: nested_modules },
+            Section { id: "module_file_structure", title: "模块文件结构", run: module_file_structure },
+            Section { id: "paths_in_rust", title: "Rust 中的路径", run: paths_in_rust },
+            Section { id: "external_crates", title: "外部包的使用", run: external_crates },
+            Section { id: "workspaces", title: "工作空间", run: workspaces },
+            Section { id: "practical_example", title: "实用的模块组织示例", run: practical_example },
+        ]
+    }
+
+    fn source(&self) -> &'static str {
+        include_str!("_07_packages_modules.rs")
+    }
+
+    fn source_path(&self) -> &'static str {
+        file!()
+    }
+
+    fn quiz(&self) -> &'static [Question] {
+        QUIZ
+    }
+
+    fn exercises(&self) -> &'static [Exercise] {
+        EXERCISES
+    }
+
+    fn compile_errors(&self) -> &'static [CompileError] {
+        COMPILE_ERRORS
+    }
+
+    fn prerequisites(&self) -> &'static [&'static str] {
+        &["3"]
+    }
+
+    fn section_prerequisites(&self) -> &'static [Prerequisite] {
+        &[
+            Prerequisite {
+                section: "visibility_control",
+                lesson: "3",
+                requires: "struct_field_visibility",
+                reason: "模块中的 pub 和结构体字段的可见性遵循同一套规则",
+            },
+        ]
+    }
+
+    fn run(&self, ctx: &mut LessonContext) {
+        run(ctx);
+    }
+}
+
+// 本课的测验题目
+const QUIZ: &[Question] = &[
+    Question {
+        prompt: "下面的代码会输出什么？",
+        kind: QuestionKind::PredictOutput {
+            section: "module_definition",
+            code: r#"mod math {
+    pub fn subtract(a: i32, b: i32) -> i32 {
+        a - b
+    }
+}
+
+let difference = math::subtract(10, 4);
+println!("调用公共模块函数 math::subtract(10, 4) = {}", difference);"#,
+            options: &["调用公共模块函数 math::subtract(10, 4) = 6", "调用公共模块函数 math::subtract(10, 4) = 14", "编译错误：subtract 是私有的"],
+            answer: 0,
+        },
+        explanation: "subtract 使用 pub 声明为公共函数，可以在模块外部通过 math::subtract 调用，10 - 4 = 6",
+    },
+    Question {
+        prompt: "模块中的函数如果没有加 pub，它的可见性是什么？",
+        kind: QuestionKind::Choice {
+            options: &["在整个 crate 中可见", "只在当前模块及其子模块中可见", "在任何地方都可见"],
+            answer: 1,
+        },
+        explanation: "Rust 中的项默认是私有的，只能在定义它的模块及其子模块中使用，需要 pub 才能从外部访问",
+    },
+    Question {
+        prompt: "以 crate:: 开头的路径是相对路径。",
+        kind: QuestionKind::TrueFalse { answer: false },
+        explanation: "crate:: 从 crate 根开始，是绝对路径；以 self::、super:: 或者当前模块中的名字开头的才是相对路径",
+    },
+];
+
+// 本课的动手练习
+const EXERCISES: &[Exercise] = &[
+    Exercise {
+        name: "geometry_module",
+        title: "模块的可见性",
+        description: "实现 geometry::shapes 中的两个函数，并修改可见性让它们可以从模块外部调用",
+        starter: include_str!("../exercises/07_packages_modules/geometry_module.rs"),
+        tests: include_str!("../exercises/07_packages_modules/geometry_module_tests.rs"),
+        solution: include_str!("../exercises/07_packages_modules/geometry_module_solution.rs"),
+        hints: &[
+            "从外部访问 geometry::shapes::circle_area 时，shapes 模块和 circle_area 函数都需要加上 pub",
+            "圆的面积是 PI * radius * radius，PI 定义在 std::f64::consts 中",
+        ],
+    },
+];
+
+// 本课的编译错误示例
+const COMPILE_ERRORS: &[CompileError] = &[
+    CompileError {
+        name: "private_function",
+        title: "调用模块中的私有函数",
+        section: "visibility_control",
+        code: "E0603",
+        broken: include_str!("../compile_errors/07_packages_modules/private_function.rs"),
+        fixed: include_str!("../compile_errors/07_packages_modules/private_function_fixed.rs"),
+        explanation: "模块中的项默认是私有的，只有模块内部和它的子模块可以使用。在模块外调用 math::add 需要先用 pub 把它公开",
+    },
+];
 
 // 定义一个公共函数 run()，作为本模块的入口点
 // 当用户在主程序中选择 7 时，将调用此函数
-pub fn run() {
-    println!("=== 第7课：包和模块 ===");
-    println!("本示例将介绍 Rust 中的包和模块系统。\n");
-    
-    // 调用各个示例函数
-    package_and_crate_concepts();
-    module_definition();
-    visibility_control();
-    using_use_keyword();
-    nested_modules();
-    module_file_structure();
-    paths_in_rust();
-    external_crates();
-    workspaces();
-    practical_example();
+pub fn run(ctx: &mut LessonContext) {
+    ctx.lesson_heading("第7课：包和模块");
+    writeln!(ctx, "本示例将介绍 Rust 中的包和模块系统。\n");
+    
+    // 按顺序调用各个示例函数，小节列表统一登记在 sections() 中
+    for section in PackagesModulesLesson.sections() {
+        (section.run)(ctx);
+    }
 }
 
 // 演示包和 Crate 的概念
-fn package_and_crate_concepts() {
-    println!("\n--- 包和 Crate 的概念 ---");
+fn package_and_crate_concepts(ctx: &mut LessonContext) {
+    ctx.section_heading("包和 Crate 的概念");
     
-    println!("Rust 的代码组织层次：");
-    println!("1. 包（Package）：是一个项目的基本单位，包含一个 Cargo.toml 文件");
-    println!("   - 可以包含多个 Crate");
-    println!("   - 至少包含一个 Crate");
+    writeln!(ctx, "Rust 的代码组织层次：");
+    writeln!(ctx, "1. 包（Package）：是一个项目的基本单位，包含一个 Cargo.toml 文件");
+    writeln!(ctx, "   - 可以包含多个 Crate");
+    writeln!(ctx, "   - 至少包含一个 Crate");
     
-    println!("2. Crate：是一个编译单元，可以生成可执行文件或库");
-    println!("   - 二进制 Crate（Binary Crate）：生成可执行文件，有 main 函数");
-    println!("   - 库 Crate（Library Crate）：生成库文件，没有 main 函数");
+    writeln!(ctx, "2. Crate：是一个编译单元，可以生成可执行文件或库");
+    writeln!(ctx, "   - 二进制 Crate（Binary Crate）：生成可执行文件，有 main 函数");
+    writeln!(ctx, "   - 库 Crate（Library Crate）：生成库文件，没有 main 函数");
     
-    println!("3. 模块（Module）：用于组织 Crate 中的代码，可以嵌套");
+    writeln!(ctx, "3. 模块（Module）：用于组织 Crate 中的代码，可以嵌套");
     
-    println!("\n当前项目结构：");
-    println!("- rustlearn/ (包)");
-    println!("  - Cargo.toml (包配置文件)");
-    println!("  - src/ (源代码目录)");
-    println!("    - main.rs (二进制 Crate 的入口文件)");
-    println!("    - lib.rs (库 Crate 的入口文件，如果存在)");
+    writeln!(ctx, "\n当前项目结构：");
+    writeln!(ctx, "- rustlearn/ (包)");
+    writeln!(ctx, "  - Cargo.toml (包配置文件)");
+    writeln!(ctx, "  - src/ (源代码目录)");
+    writeln!(ctx, "    - main.rs (二进制 Crate 的入口文件)");
+    writeln!(ctx, "    - lib.rs (库 Crate 的入口文件，如果存在)");
     
     // 运行结果：
     // 包和 Crate 的概念
@@ -77,17 +226,17 @@ fn package_and_crate_concepts() {
 
 // 演示模块的定义
 // 在这个函数中，我们将定义一些简单的模块
-fn module_definition() {
-    println!("\n--- 模块的定义 ---");
+fn module_definition(ctx: &mut LessonContext) {
+    ctx.section_heading("模块的定义");
     
     // 定义一个简单的模块
     // 注意：在 Rust 中，模块定义通常放在文件的顶部或单独的文件中
     // 这里为了演示，我们在函数内部定义模块
     
-    println!("模块的定义使用 'mod' 关键字：");
-    println!("mod module_name {{");
-    println!("    // 模块内容");
-    println!("}}");
+    writeln!(ctx, "模块的定义使用 'mod' 关键字：");
+    writeln!(ctx, "mod module_name {{");
+    writeln!(ctx, "    // 模块内容");
+    writeln!(ctx, "}}");
     
     // 定义一个数学模块
     mod math {
@@ -107,7 +256,7 @@ fn module_definition() {
     
     // 但我们可以调用公共的 subtract 函数
     let difference = math::subtract(10, 4);
-    println!("调用公共模块函数 math::subtract(10, 4) = {}", difference);
+    writeln!(ctx, "调用公共模块函数 math::subtract(10, 4) = {}", difference);
     
     // 运行结果：
     // 模块的定义
@@ -119,13 +268,13 @@ fn module_definition() {
 }
 
 // 演示可见性控制
-fn visibility_control() {
-    println!("\n--- 可见性控制 ---");
+fn visibility_control(ctx: &mut LessonContext) {
+    ctx.section_heading("可见性控制");
     
-    println!("Rust 使用 'pub' 关键字控制可见性：");
-    println!("- 默认情况下，所有内容都是私有的");
-    println!("- 使用 'pub' 使其变为公共的");
-    println!("- 私有项只能在定义它的模块及其子模块中访问");
+    writeln!(ctx, "Rust 使用 'pub' 关键字控制可见性：");
+    writeln!(ctx, "- 默认情况下，所有内容都是私有的");
+    writeln!(ctx, "- 使用 'pub' 使其变为公共的");
+    writeln!(ctx, "- 私有项只能在定义它的模块及其子模块中访问");
     
     // 定义一个包含私有和公共项的模块
     mod company {
@@ -173,11 +322,11 @@ fn visibility_control() {
     
     // 创建一个部门
     let engineering = company::Department::new(String::from("Engineering"));
-    println!("部门名称: {}", engineering.get_name());
+    writeln!(ctx, "部门名称: {}", engineering.get_name());
     
     // 创建一个项目
     let website = company::create_project(String::from("Website Redesign"), 50000);
-    println!("项目名称: {}, 预算: {}", website.name, website.budget);
+    writeln!(ctx, "项目名称: {}, 预算: {}", website.name, website.budget);
     
     // 以下代码会导致编译错误：
     // let employee = company::Employee { ... }; // Employee 是私有的
@@ -194,14 +343,16 @@ fn visibility_control() {
 }
 
 // 演示使用 use 关键字导入模块
-fn using_use_keyword() {
-    println!("\n--- 使用 use 关键字导入模块 ---");
+fn using_use_keyword(ctx: &mut LessonContext) {
+    ctx.section_heading("使用 use 关键字导入模块");
     
-    println!("'use' 关键字用于导入模块，避免每次都写完整路径：");
+    writeln!(ctx, "'use' 关键字用于导入模块，避免每次都写完整路径：");
     
     // 定义一个模块层次结构
     mod instruments {
         pub mod strings {
+            use crate::context::LessonContext;
+            
             pub struct Guitar {
                 pub brand: String,
                 pub model: String,
@@ -215,8 +366,8 @@ fn using_use_keyword() {
                     }
                 }
                 
-                pub fn play(&self) {
-                    println!("Playing {} {}", self.brand, self.model);
+                pub fn play(&self, ctx: &mut LessonContext) {
+                    writeln!(ctx, "Playing {} {}", self.brand, self.model);
                 }
             }
             
@@ -254,29 +405,29 @@ fn using_use_keyword() {
     
     // 不使用 use 关键字的情况
     let guitar1 = instruments::strings::Guitar::new(String::from("Fender"), String::from("Stratocaster"));
-    println!("不使用 use 关键字: 创建了 {} {}", guitar1.brand, guitar1.model);
+    writeln!(ctx, "不使用 use 关键字: 创建了 {} {}", guitar1.brand, guitar1.model);
     
     // 使用 use 关键字导入整个模块
     use instruments::strings;
     let piano = strings::Piano::new(String::from("Yamaha"), String::from("U3"));
-    println!("使用 use 导入模块: 创建了 {} {}", piano.brand, piano.model);
+    writeln!(ctx, "使用 use 导入模块: 创建了 {} {}", piano.brand, piano.model);
     
     // 使用 use 关键字导入特定类型
     use instruments::strings::Guitar;
     let guitar2 = Guitar::new(String::from("Gibson"), String::from("Les Paul"));
-    println!("使用 use 导入特定类型: 创建了 {} {}", guitar2.brand, guitar2.model);
-    guitar2.play();
+    writeln!(ctx, "使用 use 导入特定类型: 创建了 {} {}", guitar2.brand, guitar2.model);
+    guitar2.play(ctx);
     
     // 使用 as 关键字重命名导入
     use instruments::percussion::Drums as DrumKit;
     let drums = DrumKit::new(String::from("Pearl"), 5);
-    println!("使用 use as 重命名导入: 创建了 {} 鼓组，共 {} 件", drums.brand, drums.pieces);
+    writeln!(ctx, "使用 use as 重命名导入: 创建了 {} 鼓组，共 {} 件", drums.brand, drums.pieces);
     
     // 使用星号导入模块中的所有公共项
     use instruments::strings::*;
     let guitar3 = Guitar::new(String::from("Ibanez"), String::from("RG"));
     let piano2 = Piano::new(String::from("Steinway"), String::from("Model D"));
-    println!("使用 * 导入所有公共项: 创建了 {} 和 {} {}", guitar3.brand, piano2.brand, piano2.model);
+    writeln!(ctx, "使用 * 导入所有公共项: 创建了 {} 和 {} {}", guitar3.brand, piano2.brand, piano2.model);
     
     // 运行结果：
     // 使用 use 关键字导入模块
@@ -290,15 +441,17 @@ fn using_use_keyword() {
 }
 
 // 演示嵌套模块
-fn nested_modules() {
-    println!("\n--- 嵌套模块 ---");
+fn nested_modules(ctx: &mut LessonContext) {
+    ctx.section_heading("嵌套模块");
     
-    println!("Rust 允许模块嵌套，形成层次结构：");
+    writeln!(ctx, "Rust 允许模块嵌套，形成层次结构：");
     
     // 定义一个嵌套模块结构
     mod university {
         pub mod faculty {
             pub mod department {
+                use crate::context::LessonContext;
+                
                 pub struct Professor {
                     pub name: String,
                     pub subject: String,
@@ -312,8 +465,8 @@ fn nested_modules() {
                         }
                     }
                     
-                    pub fn teach(&self) {
-                        println!("Professor {} is teaching {}", self.name, self.subject);
+                    pub fn teach(&self, ctx: &mut LessonContext) {
+                        writeln!(ctx, "Professor {} is teaching {}", self.name, self.subject);
                     }
                 }
                 
@@ -341,7 +494,7 @@ fn nested_modules() {
         String::from("Dr. Smith"),
         String::from("Computer Science")
     );
-    prof.teach();
+    prof.teach(ctx);
     
     // 使用 use 简化嵌套模块的访问
     use university::faculty::department::research::Project;
@@ -349,7 +502,7 @@ fn nested_modules() {
         String::from("AI Research"),
         100000
     );
-    println!("研究项目: {},  funding: ${}", project.title, project.funding);
+    writeln!(ctx, "研究项目: {},  funding: ${}", project.title, project.funding);
     
     // 运行结果：
     // 嵌套模块
@@ -359,33 +512,33 @@ fn nested_modules() {
 }
 
 // 演示模块文件结构
-fn module_file_structure() {
-    println!("\n--- 模块文件结构 ---");
-    
-    println!("Rust 中模块与文件系统的关系：");
-    println!("1. 每个文件都是一个模块");
-    println!("2. 模块可以通过两种方式定义：");
-    println!("   a. 在文件中使用 'mod' 关键字定义内联模块");
-    println!("   b. 使用单独的文件或目录来定义模块");
-    println!("3. 对于同名的目录和文件，目录会被优先使用");
-    
-    println!("\n模块文件结构示例：");
-    println!("src/");
-    println!("  main.rs (或 lib.rs)");
-    println!("  module1.rs (模块文件)");
-    println!("  module2.rs (模块文件)");
-    println!("  module3/ (模块目录)");
-    println!("    mod.rs (模块目录的入口文件)");
-    println!("    submodule1.rs");
-    println!("    submodule2.rs");
-    
-    println!("\n在当前项目中，我们的模块结构：");
-    println!("src/");
-    println!("  main.rs (主入口文件)");
-    println!("  lesson_01.rs (第1课模块)");
-    println!("  lesson_02.rs (第2课模块)");
-    println!("  ...");
-    println!("  lesson_07.rs (当前模块)");
+fn module_file_structure(ctx: &mut LessonContext) {
+    ctx.section_heading("模块文件结构");
+    
+    writeln!(ctx, "Rust 中模块与文件系统的关系：");
+    writeln!(ctx, "1. 每个文件都是一个模块");
+    writeln!(ctx, "2. 模块可以通过两种方式定义：");
+    writeln!(ctx, "   a. 在文件中使用 'mod' 关键字定义内联模块");
+    writeln!(ctx, "   b. 使用单独的文件或目录来定义模块");
+    writeln!(ctx, "3. 对于同名的目录和文件，目录会被优先使用");
+    
+    writeln!(ctx, "\n模块文件结构示例：");
+    writeln!(ctx, "src/");
+    writeln!(ctx, "  main.rs (或 lib.rs)");
+    writeln!(ctx, "  module1.rs (模块文件)");
+    writeln!(ctx, "  module2.rs (模块文件)");
+    writeln!(ctx, "  module3/ (模块目录)");
+    writeln!(ctx, "    mod.rs (模块目录的入口文件)");
+    writeln!(ctx, "    submodule1.rs");
+    writeln!(ctx, "    submodule2.rs");
+    
+    writeln!(ctx, "\n在当前项目中，我们的模块结构：");
+    writeln!(ctx, "src/");
+    writeln!(ctx, "  main.rs (主入口文件)");
+    writeln!(ctx, "  lesson_01.rs (第1课模块)");
+    writeln!(ctx, "  lesson_02.rs (第2课模块)");
+    writeln!(ctx, "  ...");
+    writeln!(ctx, "  lesson_07.rs (当前模块)");
     
     // 运行结果：
     // 模块文件结构
@@ -416,52 +569,56 @@ fn module_file_structure() {
 }
 
 // 演示 Rust 中的路径
-fn paths_in_rust() {
-    println!("\n--- Rust 中的路径 ---");
+fn paths_in_rust(ctx: &mut LessonContext) {
+    ctx.section_heading("Rust 中的路径");
     
-    println!("Rust 中有两种路径表示方式：");
-    println!("1. 绝对路径：从 crate 根开始，使用 crate:: 前缀");
-    println!("2. 相对路径：从当前模块开始，使用 self::、super:: 或模块名称");
+    writeln!(ctx, "Rust 中有两种路径表示方式：");
+    writeln!(ctx, "1. 绝对路径：从 crate 根开始，使用 crate:: 前缀");
+    writeln!(ctx, "2. 相对路径：从当前模块开始，使用 self::、super:: 或模块名称");
     
     // 定义模块结构来演示路径
     mod animals {
         pub mod mammals {
+            use crate::context::LessonContext;
+            
             pub struct Dog {
                 pub name: String,
             }
             
             impl Dog {
-                pub fn bark(&self) {
-                    println!("{} is barking!", self.name);
+                pub fn bark(&self, ctx: &mut LessonContext) {
+                    writeln!(ctx, "{} is barking!", self.name);
                 }
             }
         }
         
         pub mod birds {
+            use crate::context::LessonContext;
+            
             pub struct Sparrow {
                 pub name: String,
             }
             
             impl Sparrow {
-                pub fn chirp(&self) {
-                    println!("{} is chirping!", self.name);
+                pub fn chirp(&self, ctx: &mut LessonContext) {
+                    writeln!(ctx, "{} is chirping!", self.name);
                 }
                 
                 // 演示相对路径和绝对路径
-                pub fn interact_with_mammal(&self) {
+                pub fn interact_with_mammal(&self, ctx: &mut LessonContext) {
                     // 绝对路径 - 修改为相对路径，因为animals模块在函数内部
                     let dog = super::mammals::Dog {
                         name: String::from("Rex"),
                     };
-                    dog.bark();
+                    dog.bark(ctx);
                     
                     // 相对路径（使用 super）
                     let dog2 = super::mammals::Dog {
                         name: String::from("Fido"),
                     };
-                    dog2.bark();
+                    dog2.bark(ctx);
                     
-                    println!("{} is watching the dogs", self.name);
+                    writeln!(ctx, "{} is watching the dogs", self.name);
                 }
             }
         }
@@ -471,14 +628,14 @@ fn paths_in_rust() {
     let dog = animals::mammals::Dog {
         name: String::from("Buddy"),
     };
-    dog.bark();
+    dog.bark(ctx);
     
     // 使用相对路径
     let sparrow = animals::birds::Sparrow {
         name: String::from("Tweety"),
     };
-    sparrow.chirp();
-    sparrow.interact_with_mammal();
+    sparrow.chirp(ctx);
+    sparrow.interact_with_mammal(ctx);
     
     // 运行结果：
     // Rust 中的路径
@@ -493,27 +650,27 @@ fn paths_in_rust() {
 }
 
 // 演示外部包的使用
-fn external_crates() {
-    println!("\n--- 外部包的使用 ---");
+fn external_crates(ctx: &mut LessonContext) {
+    ctx.section_heading("外部包的使用");
     
-    println!("在 Rust 中使用外部包的步骤：");
-    println!("1. 在 Cargo.toml 文件中添加依赖");
-    println!("2. 使用 'use' 关键字导入外部包中的项");
+    writeln!(ctx, "在 Rust 中使用外部包的步骤：");
+    writeln!(ctx, "1. 在 Cargo.toml 文件中添加依赖");
+    writeln!(ctx, "2. 使用 'use' 关键字导入外部包中的项");
     
-    println!("\n例如，要使用 rand 包生成随机数：");
-    println!("// 在 Cargo.toml 中添加：");
-    println!("[dependencies]");
-    println!("rand = \"0.8.5\"\n");
+    writeln!(ctx, "\n例如，要使用 rand 包生成随机数：");
+    writeln!(ctx, "// 在 Cargo.toml 中添加：");
+    writeln!(ctx, "[dependencies]");
+    writeln!(ctx, "rand = \"0.8.5\"\n");
     
-    println!("// 在代码中使用：");
-    println!("use rand::Rng;");
-    println!("fn main() {{");
-    println!("    let random_number = rand::thread_rng().gen_range(1..=100);");
-    println!("    println!(\"随机数: {{}}\" , random_number);");
-    println!("}}\n");
+    writeln!(ctx, "// 在代码中使用：");
+    writeln!(ctx, "use rand::Rng;");
+    writeln!(ctx, "fn main() {{");
+    writeln!(ctx, "    let random_number = rand::thread_rng().gen_range(1..=100);");
+    writeln!(ctx, "    println!(\"随机数: {{}}\" , random_number);");
+    writeln!(ctx, "}}\n");
     
-    println!("当前项目没有添加额外的外部依赖，所以我们不能实际演示外部包的使用。");
-    println!("如果需要使用外部包，请在 Cargo.toml 文件中添加依赖。");
+    writeln!(ctx, "当前项目没有添加额外的外部依赖，所以我们不能实际演示外部包的使用。");
+    writeln!(ctx, "如果需要使用外部包，请在 Cargo.toml 文件中添加依赖。");
     
     // 运行结果：
     // 外部包的使用
@@ -530,7 +687,7 @@ fn external_crates() {
     // use rand::Rng;
     // fn main() {
     //     let random_number = rand::thread_rng().gen_range(1..=100);
-    //     println!("随机数: {{}}" , random_number);
+    //     println!("随机数: {}" , random_number);
     // }
     // 
     // 当前项目没有添加额外的外部依赖，所以我们不能实际演示外部包的使用。
@@ -538,25 +695,25 @@ fn external_crates() {
 }
 
 // 演示工作空间
-fn workspaces() {
-    println!("\n--- 工作空间 ---");
-    
-    println!("工作空间（Workspace）是一组共享相同 Cargo.lock 和输出目录的包：");
-    println!("1. 用于管理多个相互依赖的包");
-    println!("2. 创建一个根目录，包含 Cargo.toml 文件定义工作空间");
-    
-    println!("\n工作空间的 Cargo.toml 示例：");
-    println!("[workspace]");
-    println!("members = [");
-    println!("    \"package1\",");
-    println!("    \"package2\",");
-    println!("    \"path/to/package3\",");
-    println!("]");
-    
-    println!("\n工作空间的优势：");
-    println!("- 共享依赖，避免重复下载");
-    println!("- 统一构建和测试");
-    println!("- 方便管理多包项目");
+fn workspaces(ctx: &mut LessonContext) {
+    ctx.section_heading("工作空间");
+    
+    writeln!(ctx, "工作空间（Workspace）是一组共享相同 Cargo.lock 和输出目录的包：");
+    writeln!(ctx, "1. 用于管理多个相互依赖的包");
+    writeln!(ctx, "2. 创建一个根目录，包含 Cargo.toml 文件定义工作空间");
+    
+    writeln!(ctx, "\n工作空间的 Cargo.toml 示例：");
+    writeln!(ctx, "[workspace]");
+    writeln!(ctx, "members = [");
+    writeln!(ctx, "    \"package1\",");
+    writeln!(ctx, "    \"package2\",");
+    writeln!(ctx, "    \"path/to/package3\",");
+    writeln!(ctx, "]");
+    
+    writeln!(ctx, "\n工作空间的优势：");
+    writeln!(ctx, "- 共享依赖，避免重复下载");
+    writeln!(ctx, "- 统一构建和测试");
+    writeln!(ctx, "- 方便管理多包项目");
     
     // 运行结果：
     // 工作空间
@@ -579,12 +736,14 @@ fn workspaces() {
 }
 
 // 实用的模块组织示例
-fn practical_example() {
-    println!("\n--- 实用的模块组织示例 ---");
+fn practical_example(ctx: &mut LessonContext) {
+    ctx.section_heading("实用的模块组织示例");
     
     // 定义一个更复杂的模块结构，模拟一个简单的电子商务系统
     mod ecommerce {
         pub mod products {
+            use crate::context::LessonContext;
+            
             pub struct Product {
                 pub id: u32,
                 pub name: String,
@@ -602,8 +761,8 @@ fn practical_example() {
                     }
                 }
                 
-                pub fn display(&self) {
-                    println!("Product #{}: {}, ${}, Category: {}", 
+                pub fn display(&self, ctx: &mut LessonContext) {
+                    writeln!(ctx, "Product #{}: {}, ${}, Category: {}", 
                              self.id, self.name, self.price, self.category);
                 }
             }
@@ -611,6 +770,7 @@ fn practical_example() {
         
         pub mod cart {
             use super::products::Product;
+            use crate::context::LessonContext;
             
             pub struct ShoppingCart {
                 items: Vec<(Product, u32)>, // (产品, 数量)
@@ -637,15 +797,15 @@ fn practical_example() {
                         .sum()
                 }
                 
-                pub fn display(&self) {
-                    println!("购物车内容：");
+                pub fn display(&self, ctx: &mut LessonContext) {
+                    writeln!(ctx, "购物车内容：");
                     for (product, quantity) in &self.items {
-                        product.display();
-                        println!("数量: {}", quantity);
-                        println!("小计: ${}", product.price * *quantity as f64);
-                        println!("---");
+                        product.display(ctx);
+                        writeln!(ctx, "数量: {}", quantity);
+                        writeln!(ctx, "小计: ${}", product.price * *quantity as f64);
+                        writeln!(ctx, "---");
                     }
-                    println!("总计: ${}", self.calculate_total());
+                    writeln!(ctx, "总计: ${}", self.calculate_total());
                 }
             }
         }
@@ -689,13 +849,13 @@ fn practical_example() {
     cart.add_item(book, 3);
     
     // 显示客户信息和购物车
-    println!("客户: {}, Email: {}", customer.name, customer.email);
-    cart.display();
+    writeln!(ctx, "客户: {}, Email: {}", customer.name, customer.email);
+    cart.display(ctx);
     
     // 删除一个产品并重新计算总价
-    println!("\n删除产品 2 后的购物车：");
+    writeln!(ctx, "\n删除产品 2 后的购物车：");
     cart.remove_item(2);
-    cart.display();
+    cart.display(ctx);
     
     // 运行结果：
     // 实用的模块组织示例
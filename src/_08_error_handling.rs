@@ -5,107 +5,270 @@
 use std::fs::File;
 use std::io::{self, Read, Write};
 use std::num::ParseIntError;
+use crate::compile_error::CompileError;
+use crate::context::LessonContext;
+use crate::exercise::Exercise;
+use crate::graph::Prerequisite;
+use crate::lesson::{Lesson, Section};
+use crate::quiz::{Question, QuestionKind};
+
+// 本课在课程注册表中的登记信息
+// 主程序的菜单等前端都通过 Lesson trait 读取这些信息
+pub struct ErrorHandlingLesson;
+
+impl Lesson for ErrorHandlingLesson {
+    fn id(&self) -> &'static str {
+        "8"
+    }
+
+    fn title(&self) -> &'static str {
+        "错误处理 (Error Handling)"
+    }
+
+    fn summary(&self) -> &'static str {
+        "本示例将介绍 Rust 中的错误处理机制。"
+    }
+
+    fn topics(&self) -> &'static [&'static str] {
+        &[
+            "错误的类型",
+            "panic! 宏的使用",
+            "Result 枚举的使用",
+            "错误传播",
+            "自定义错误类型",
+            "错误转换",
+            "错误链",
+            "unwrap 和 expect 方法",
+            "错误处理的最佳实践",
+            "错误处理库的使用",
+        ]
+    }
+
+    fn sections(&self) -> &'static [Section] {
+        &[
+            Section { id: "error_types", title: "错误的类型", run: error_types },
+            Section { id: "panic_example", title: "panic! 宏的使用", run: panic_example },
+            Section { id: "result_enum", title: "Result 枚举的使用", run: result_enum },
+            Section { id: "error_propagation", title: "错误传播", run: error_propagation },
+            Section { id: "custom_error_types", title: "自定义错误类型", run: custom_error_types },
+            Section { id: "error_conversion", title: "错误转换", run: error_conversion },
+            Section { id: "error_chaining", title: "错误链", run: error_chaining },
+            Section { id: "unwrap_and_expect", title: "unwrap 和 expect 方法", run: unwrap_and_expect },
+            Section { id: "best_practices", title: "错误处理的最佳实践", run: best_practices },
+            Section { id: "error_libraries", title: "错误处理库的使用", run: error_libraries },
+        ]
+    }
+
+    fn source(&self) -> &'static str {
+        include_str!("_08_error_handling.rs")
+    }
+
+    fn source_path(&self) -> &'static str {
+        file!()
+    }
+
+    fn quiz(&self) -> &'static [Question] {
+        QUIZ
+    }
+
+    fn exercises(&self) -> &'static [Exercise] {
+        EXERCISES
+    }
+
+    fn compile_errors(&self) -> &'static [CompileError] {
+        COMPILE_ERRORS
+    }
+
+    fn prerequisites(&self) -> &'static [&'static str] {
+        &["5"]
+    }
+
+    fn section_prerequisites(&self) -> &'static [Prerequisite] {
+        &[
+            Prerequisite {
+                section: "result_enum",
+                lesson: "4",
+                requires: "result_enum",
+                reason: "第4课介绍了 Result 的定义，本节讲怎样使用它",
+            },
+            Prerequisite {
+                section: "custom_error_types",
+                lesson: "4",
+                requires: "enum_with_data",
+                reason: "自定义错误类型通常是一个每个变体都带有数据的枚举",
+            },
+        ]
+    }
+
+    fn run(&self, ctx: &mut LessonContext) {
+        run(ctx);
+    }
+}
+
+// 本课的测验题目
+const QUIZ: &[Question] = &[
+    Question {
+        prompt: "调用 process_input(\"-1\") 时，下面的代码会输出什么？",
+        kind: QuestionKind::PredictOutput {
+            section: "custom_error_types",
+            code: r#"fn process_input(input: &str) -> Result<i32, CustomError> {
+    if input.is_empty() {
+        return Err(CustomError::InvalidInput("输入不能为空".to_string()));
+    }
+    let number = input.parse::<i32>()?;
+    if number < 0 {
+        return Err(CustomError::InvalidInput("输入必须为正数".to_string()));
+    }
+    Ok(number)
+}
+
+match process_input("-1") {
+    Ok(value) => println!("处理成功: {}", value),
+    Err(error) => println!("处理失败: {:?}", error),
+}"#,
+            options: &["处理成功: -1", "处理失败: ParseError(ParseIntError { kind: InvalidDigit })", "处理失败: InvalidInput(\"输入必须为正数\")"],
+            answer: 2,
+        },
+        explanation: "\"-1\" 可以成功解析为 i32，所以 ? 不会提前返回；随后 number < 0 成立，返回 InvalidInput 错误",
+    },
+    Question {
+        prompt: "在返回 Result 的函数中，对一个 Err 值使用 ? 运算符会发生什么？",
+        kind: QuestionKind::Choice {
+            options: &["程序 panic", "通过 From 转换错误类型后，从当前函数提前返回这个错误", "忽略错误继续执行"],
+            answer: 1,
+        },
+        explanation: "? 遇到 Err 时会调用 From::from 把错误转换成函数的错误类型并立即返回；遇到 Ok 时取出其中的值",
+    },
+    Question {
+        prompt: "对于可以恢复的错误，例如用户输入的格式不对，应该优先使用 panic!。",
+        kind: QuestionKind::TrueFalse { answer: false },
+        explanation: "可以恢复的错误应该返回 Result，让调用者决定如何处理；panic! 用于程序无法继续运行的情况",
+    },
+];
+
+// 本课的动手练习
+const EXERCISES: &[Exercise] = &[
+    Exercise {
+        name: "sum_numbers",
+        title: "解析并求和",
+        description: "实现 sum_numbers，把用逗号分隔的整数列表解析出来并求和，解析失败时返回 ParseIntError",
+        starter: include_str!("../exercises/08_error_handling/sum_numbers.rs"),
+        tests: include_str!("../exercises/08_error_handling/sum_numbers_tests.rs"),
+        solution: include_str!("../exercises/08_error_handling/sum_numbers_solution.rs"),
+        hints: &[
+            "split(',') 按逗号拆分，trim() 去掉数字两边的空格",
+            "part.trim().parse::<i32>()? 会在解析失败时直接返回错误",
+            "空字符串需要单独处理，因为 \"\".parse::<i32>() 会失败",
+        ],
+    },
+];
+
+// 本课的编译错误示例
+const COMPILE_ERRORS: &[CompileError] = &[
+    CompileError {
+        name: "question_mark_in_main",
+        title: "在返回 () 的函数中使用 ? 运算符",
+        section: "error_propagation",
+        code: "E0277",
+        broken: include_str!("../compile_errors/08_error_handling/question_mark_in_main.rs"),
+        fixed: include_str!("../compile_errors/08_error_handling/question_mark_in_main_fixed.rs"),
+        explanation: "? 运算符在出错时会提前返回错误，所以只能用在返回 Result 或 Option 的函数中。把 main 的返回类型改成 Result<(), ParseIntError>，并在最后返回 Ok(())",
+    },
+];
 
 // 定义一个公共函数 run()，作为本模块的入口点
-pub fn run() {
-    println!("=== 第8课：错误处理 ===");
-    println!("本示例将介绍 Rust 中的错误处理机制。\n");
-    
-    // 调用各个示例函数
-    error_types();
-    panic_example();
-    result_enum();
-    error_propagation();
-    custom_error_types();
-    error_conversion();
-    error_chaining();
-    unwrap_and_expect();
-    best_practices();
-    error_libraries();
+pub fn run(ctx: &mut LessonContext) {
+    ctx.lesson_heading("第8课：错误处理");
+    writeln!(ctx, "本示例将介绍 Rust 中的错误处理机制。\n");
+    
+    // 按顺序调用各个示例函数，小节列表统一登记在 sections() 中
+    for section in ErrorHandlingLesson.sections() {
+        (section.run)(ctx);
+    }
 }
 
 // 演示错误的类型
-fn error_types() {
-    println!("\n--- 错误的类型 ---");
-    
-    println!("Rust 中有两种主要的错误类型：");
-    println!("1. 可恢复错误（Recoverable Errors）");
-    println!("   - 表示可能会失败但程序可以继续执行的情况");
-    println!("   - 使用 Result<T, E> 枚举来处理");
-    println!("   - 例如：文件未找到、网络连接失败等");
-    
-    println!("2. 不可恢复错误（Unrecoverable Errors）");
-    println!("   - 表示程序无法继续执行的严重错误");
-    println!("   - 使用 panic! 宏来处理");
-    println!("   - 例如：索引越界、断言失败等");
-    
-    println!("\nRust 的错误处理理念：");
-    println!("- 显式处理错误而不是忽略它们");
-    println!("- 错误也是值，可以像其他值一样处理");
-    println!("- 区分可恢复和不可恢复错误，采取不同的处理策略");
+fn error_types(ctx: &mut LessonContext) {
+    ctx.section_heading("错误的类型");
+    
+    writeln!(ctx, "Rust 中有两种主要的错误类型：");
+    writeln!(ctx, "1. 可恢复错误（Recoverable Errors）");
+    writeln!(ctx, "   - 表示可能会失败但程序可以继续执行的情况");
+    writeln!(ctx, "   - 使用 Result<T, E> 枚举来处理");
+    writeln!(ctx, "   - 例如：文件未找到、网络连接失败等");
+    
+    writeln!(ctx, "2. 不可恢复错误（Unrecoverable Errors）");
+    writeln!(ctx, "   - 表示程序无法继续执行的严重错误");
+    writeln!(ctx, "   - 使用 panic! 宏来处理");
+    writeln!(ctx, "   - 例如：索引越界、断言失败等");
+    
+    writeln!(ctx, "\nRust 的错误处理理念：");
+    writeln!(ctx, "- 显式处理错误而不是忽略它们");
+    writeln!(ctx, "- 错误也是值，可以像其他值一样处理");
+    writeln!(ctx, "- 区分可恢复和不可恢复错误，采取不同的处理策略");
 }
 
 // 演示 panic! 宏的使用
-fn panic_example() {
-    println!("\n--- panic! 宏的使用 ---");
+fn panic_example(ctx: &mut LessonContext) {
+    ctx.section_heading("panic! 宏的使用");
     
-    println!("panic! 宏用于处理不可恢复的错误，它会：");
-    println!("1. 打印错误信息");
-    println!("2. 展开调用栈（backtrace）");
-    println!("3. 终止程序");
+    writeln!(ctx, "panic! 宏用于处理不可恢复的错误，它会：");
+    writeln!(ctx, "1. 打印错误信息");
+    writeln!(ctx, "2. 展开调用栈（backtrace）");
+    writeln!(ctx, "3. 终止程序");
     
-    println!("\n以下是 panic! 的示例，但我们不会实际触发它，因为它会终止程序：");
-    println!("// panic!()");
+    writeln!(ctx, "\n以下是 panic! 的示例，但我们不会实际触发它，因为它会终止程序：");
+    writeln!(ctx, "// panic!()");
     
-    println!("\npanic! 的常见使用场景：");
-    println!("1. 开发和调试阶段，用于快速发现和处理错误");
-    println!("2. 发生了不可能恢复的严重错误");
-    println!("3. 断言失败，验证条件不满足");
+    writeln!(ctx, "\npanic! 的常见使用场景：");
+    writeln!(ctx, "1. 开发和调试阶段，用于快速发现和处理错误");
+    writeln!(ctx, "2. 发生了不可能恢复的严重错误");
+    writeln!(ctx, "3. 断言失败，验证条件不满足");
 }
 
 // 演示 Result 枚举的使用
-fn result_enum() {
-    println!("\n--- Result 枚举的使用 ---");
+fn result_enum(ctx: &mut LessonContext) {
+    ctx.section_heading("Result 枚举的使用");
     
-    println!("Result<T, E> 是一个枚举，用于处理可恢复的错误：");
-    println!("- Ok(T)：表示操作成功，包含成功值");
-    println!("- Err(E)：表示操作失败，包含错误值");
+    writeln!(ctx, "Result<T, E> 是一个枚举，用于处理可恢复的错误：");
+    writeln!(ctx, "- Ok(T)：表示操作成功，包含成功值");
+    writeln!(ctx, "- Err(E)：表示操作失败，包含错误值");
     
     // 示例：文件操作
-    println!("\n文件操作示例：");
+    writeln!(ctx, "\n文件操作示例：");
     let file_result = File::open("nonexistent_file.txt");
     
     match file_result {
-        Ok(file) => println!("成功打开文件"),
-        Err(error) => println!("无法打开文件: {:?}", error),
+        Ok(file) => writeln!(ctx, "成功打开文件"),
+        Err(error) => writeln!(ctx, "无法打开文件: {:?}", error),
     }
     
     // 示例：字符串解析
-    println!("\n字符串解析示例：");
+    writeln!(ctx, "\n字符串解析示例：");
     let number_str = "42";
     let parse_result = number_str.parse::<i32>();
     
     match parse_result {
-        Ok(number) => println!("解析成功: {}", number),
-        Err(error) => println!("解析失败: {:?}", error),
+        Ok(number) => writeln!(ctx, "解析成功: {}", number),
+        Err(error) => writeln!(ctx, "解析失败: {:?}", error),
     }
     
     let invalid_str = "not a number";
     let invalid_result = invalid_str.parse::<i32>();
     
     match invalid_result {
-        Ok(number) => println!("解析成功: {}", number),
-        Err(error) => println!("解析失败: {:?}", error),
+        Ok(number) => writeln!(ctx, "解析成功: {}", number),
+        Err(error) => writeln!(ctx, "解析失败: {:?}", error),
     }
 }
 
 // 演示错误传播
-fn error_propagation() {
-    println!("\n--- 错误传播 ---");
+fn error_propagation(ctx: &mut LessonContext) {
+    ctx.section_heading("错误传播");
     
-    println!("错误传播是指将函数中的错误传递给调用者处理：");
-    println!("- 使用 ? 操作符可以简化错误传播代码");
-    println!("- ? 操作符只能用于返回 Result<T, E> 或 Option<T> 的函数");
+    writeln!(ctx, "错误传播是指将函数中的错误传递给调用者处理：");
+    writeln!(ctx, "- 使用 ? 操作符可以简化错误传播代码");
+    writeln!(ctx, "- ? 操作符只能用于返回 Result<T, E> 或 Option<T> 的函数");
     
     // 定义一个读取文件内容的函数，不使用 ? 操作符
     fn read_file_verbose() -> Result<String, io::Error> {
@@ -133,26 +296,26 @@ fn error_propagation() {
     }
     
     // 测试错误传播
-    println!("\n测试 verbose 版本:");
+    writeln!(ctx, "\n测试 verbose 版本:");
     let result1 = read_file_verbose();
     match result1 {
-        Ok(contents) => println!("文件内容: {}", contents),
-        Err(error) => println!("错误: {:?}", error),
+        Ok(contents) => writeln!(ctx, "文件内容: {}", contents),
+        Err(error) => writeln!(ctx, "错误: {:?}", error),
     }
     
-    println!("\n测试 simple 版本 (使用 ? 操作符):");
+    writeln!(ctx, "\n测试 simple 版本 (使用 ? 操作符):");
     let result2 = read_file_simple();
     match result2 {
-        Ok(contents) => println!("文件内容: {}", contents),
-        Err(error) => println!("错误: {:?}", error),
+        Ok(contents) => writeln!(ctx, "文件内容: {}", contents),
+        Err(error) => writeln!(ctx, "错误: {:?}", error),
     }
 }
 
 // 演示自定义错误类型
-fn custom_error_types() {
-    println!("\n--- 自定义错误类型 ---");
+fn custom_error_types(ctx: &mut LessonContext) {
+    ctx.section_heading("自定义错误类型");
     
-    println!("在实际项目中，我们经常需要定义自己的错误类型：");
+    writeln!(ctx, "在实际项目中，我们经常需要定义自己的错误类型：");
     
     // 定义一个简单的自定义错误类型
     #[derive(Debug)]
@@ -195,22 +358,22 @@ fn custom_error_types() {
     let test_cases = ["42", "-1", "not a number", ""];
     
     for test in &test_cases {
-        println!("\n测试输入: '{}'", test);
+        writeln!(ctx, "\n测试输入: '{}'", test);
         let result = process_input(test);
         match result {
-            Ok(value) => println!("处理成功: {}", value),
-            Err(error) => println!("处理失败: {:?}", error),
+            Ok(value) => writeln!(ctx, "处理成功: {}", value),
+            Err(error) => writeln!(ctx, "处理失败: {:?}", error),
         }
     }
 }
 
 // 演示错误转换
-fn error_conversion() {
-    println!("\n--- 错误转换 ---");
+fn error_conversion(ctx: &mut LessonContext) {
+    ctx.section_heading("错误转换");
     
-    println!("错误转换允许我们在不同的错误类型之间进行转换：");
-    println!("- 使用 From trait 和 Into trait");
-    println!("- 使用 map_err 方法转换错误类型");
+    writeln!(ctx, "错误转换允许我们在不同的错误类型之间进行转换：");
+    writeln!(ctx, "- 使用 From trait 和 Into trait");
+    writeln!(ctx, "- 使用 map_err 方法转换错误类型");
     
     // 定义两种不同的错误类型
     #[derive(Debug)]
@@ -251,19 +414,19 @@ fn error_conversion() {
     }
     
     // 测试错误转换
-    println!("\n使用 map_err 转换错误:");
+    writeln!(ctx, "\n使用 map_err 转换错误:");
     let result1 = process_data_map_err();
     match result1 {
-        Ok(data) => println!("成功获取数据: {}", data),
-        Err(error) => println!("API 错误: {:?}", error),
+        Ok(data) => writeln!(ctx, "成功获取数据: {}", data),
+        Err(error) => writeln!(ctx, "API 错误: {:?}", error),
     }
 }
 
 // 演示错误链
-fn error_chaining() {
-    println!("\n--- 错误链 ---");
+fn error_chaining(ctx: &mut LessonContext) {
+    ctx.section_heading("错误链");
     
-    println!("错误链是指在处理错误时保留原始错误的上下文：");
+    writeln!(ctx, "错误链是指在处理错误时保留原始错误的上下文：");
     
     // 定义一些错误类型
     #[derive(Debug)]
@@ -304,53 +467,53 @@ fn error_chaining() {
     }
     
     // 测试错误链
-    println!("\n测试无效数字输入:");
+    writeln!(ctx, "\n测试无效数字输入:");
     let result1 = parse_data("not a number");
     match result1 {
-        Ok(msg) => println!("成功: {}", msg),
-        Err(err) => println!("错误链: {:?}", err),
+        Ok(msg) => writeln!(ctx, "成功: {}", msg),
+        Err(err) => writeln!(ctx, "错误链: {:?}", err),
     }
 }
 
 // 演示 unwrap 和 expect 方法
-fn unwrap_and_expect() {
-    println!("\n--- unwrap 和 expect 方法 ---");
+fn unwrap_and_expect(ctx: &mut LessonContext) {
+    ctx.section_heading("unwrap 和 expect 方法");
     
-    println!("unwrap 和 expect 方法是处理 Result 和 Option 的便捷方法：");
-    println!("- unwrap(): 如果是 Ok/Some 则返回值，否则 panic!");
-    println!("- expect(msg): 类似于 unwrap，但提供自定义 panic 消息");
+    writeln!(ctx, "unwrap 和 expect 方法是处理 Result 和 Option 的便捷方法：");
+    writeln!(ctx, "- unwrap(): 如果是 Ok/Some 则返回值，否则 panic!");
+    writeln!(ctx, "- expect(msg): 类似于 unwrap，但提供自定义 panic 消息");
     
     // Ok 结果的 unwrap
     let ok_result: Result<i32, &str> = Ok(42);
     let value1 = ok_result.unwrap();
-    println!("Ok.unwrap() = {}", value1);
+    writeln!(ctx, "Ok.unwrap() = {}", value1);
     
     // Some 值的 unwrap
     let some_value: Option<i32> = Some(100);
     let value2 = some_value.unwrap();
-    println!("Some.unwrap() = {}", value2);
+    writeln!(ctx, "Some.unwrap() = {}", value2);
     
     // 使用 expect 提供自定义错误消息
     let ok_result2: Result<i32, &str> = Ok(99);
     let value3 = ok_result2.expect("这不会发生");
-    println!("Ok.expect() = {}", value3);
+    writeln!(ctx, "Ok.expect() = {}", value3);
     
-    println!("\nunwrap 和 expect 的适用场景：");
-    println!("1. 原型开发和快速测试");
-    println!("2. 确定不会失败的操作");
-    println!("3. 开发和调试阶段");
+    writeln!(ctx, "\nunwrap 和 expect 的适用场景：");
+    writeln!(ctx, "1. 原型开发和快速测试");
+    writeln!(ctx, "2. 确定不会失败的操作");
+    writeln!(ctx, "3. 开发和调试阶段");
 }
 
 // 演示错误处理的最佳实践
-fn best_practices() {
-    println!("\n--- 错误处理的最佳实践 ---");
+fn best_practices(ctx: &mut LessonContext) {
+    ctx.section_heading("错误处理的最佳实践");
     
-    println!("Rust 错误处理的一些最佳实践：");
-    println!("1. 优先使用 Result 处理可恢复错误，而不是 panic!");
-    println!("2. 为公共 API 定义明确的错误类型");
-    println!("3. 实现 From trait 以支持错误转换");
-    println!("4. 使用 ? 操作符简化错误传播");
-    println!("5. 提供有意义的错误信息");
+    writeln!(ctx, "Rust 错误处理的一些最佳实践：");
+    writeln!(ctx, "1. 优先使用 Result 处理可恢复错误，而不是 panic!");
+    writeln!(ctx, "2. 为公共 API 定义明确的错误类型");
+    writeln!(ctx, "3. 实现 From trait 以支持错误转换");
+    writeln!(ctx, "4. 使用 ? 操作符简化错误传播");
+    writeln!(ctx, "5. 提供有意义的错误信息");
     
     // 示例：实现良好的错误处理
     #[derive(Debug)]
@@ -371,7 +534,7 @@ fn best_practices() {
     }
     
     // 定义一个符合最佳实践的函数
-    fn create_user(username: &str, password: &str) -> Result<(), UserError> {
+    fn create_user(ctx: &mut LessonContext, username: &str, password: &str) -> Result<(), UserError> {
         // 验证输入
         if username.len() < 3 {
             return Err(UserError::InvalidUsername(username.to_string()));
@@ -382,7 +545,7 @@ fn best_practices() {
         }
         
         // 这里应该是实际的用户创建逻辑
-        println!("用户创建逻辑将在这里执行...");
+        writeln!(ctx, "用户创建逻辑将在这里执行...");
         
         // 模拟成功
         Ok(())
@@ -392,38 +555,277 @@ fn best_practices() {
     let test_cases = [("alice", "password123"), ("bo", "password123"), ("charlie", "pass")];
     
     for (username, password) in &test_cases {
-        println!("\n测试创建用户: username='{}', password='{}'", username, password);
-        let result = create_user(username, password);
+        writeln!(ctx, "\n测试创建用户: username='{}', password='{}'", username, password);
+        let result = create_user(ctx, username, password);
         match result {
-            Ok(_) => println!("用户创建成功！"),
-            Err(error) => println!("用户创建失败: {}", error),
+            Ok(_) => writeln!(ctx, "用户创建成功！"),
+            Err(error) => writeln!(ctx, "用户创建失败: {}", error),
         }
     }
 }
 
 // 演示错误处理库的使用
     // 演示错误处理库的使用
-    fn error_libraries() {
-        println!("\n--- 错误处理库的使用 ---");
+    fn error_libraries(ctx: &mut LessonContext) {
+        ctx.section_heading("错误处理库的使用");
         
-        println!("Rust 社区提供了一些优秀的错误处理库，可以简化错误处理代码：");
-        println!("1. thiserror: 主要用于定义库的错误类型");
-        println!("2. anyhow: 主要用于应用程序中的错误处理");
+        writeln!(ctx, "Rust 社区提供了一些优秀的错误处理库，可以简化错误处理代码：");
+        writeln!(ctx, "1. thiserror: 主要用于定义库的错误类型");
+        writeln!(ctx, "2. anyhow: 主要用于应用程序中的错误处理");
         
-        println!("\n使用 thiserror 的优势：");
-        println!("- 自动派生常见的 trait（如 Debug、Display）");
-        println!("- 简化 From trait 的实现");
-        println!("- 支持错误原因链接");
+        writeln!(ctx, "\n使用 thiserror 的优势：");
+        writeln!(ctx, "- 自动派生常见的 trait（如 Debug、Display）");
+        writeln!(ctx, "- 简化 From trait 的实现");
+        writeln!(ctx, "- 支持错误原因链接");
         
-        println!("\n使用 anyhow 的优势：");
-        println!("- 可以处理任何实现了 Error trait 的错误类型");
-        println!("- 提供了方便的上下文添加方法");
-        println!("- 简化错误处理代码");
+        writeln!(ctx, "\n使用 anyhow 的优势：");
+        writeln!(ctx, "- 可以处理任何实现了 Error trait 的错误类型");
+        writeln!(ctx, "- 提供了方便的上下文添加方法");
+        writeln!(ctx, "- 简化错误处理代码");
         
-        println!("\n要使用这些库，需要在 Cargo.toml 中添加依赖:");
-        println!("[dependencies]");
-        println!("thiserror = \"1.0\"");
-        println!("anyhow = \"1.0\"");
+        writeln!(ctx, "\n要使用这些库，需要在 Cargo.toml 中添加依赖:");
+        writeln!(ctx, "[dependencies]");
+        writeln!(ctx, "thiserror = \"2\"");
+        writeln!(ctx, "anyhow = \"1\"");
         
-        println!("\n注意: 由于我们没有在当前项目中添加这些依赖,所以这里不提供具体的代码示例.");
+        // 本项目把这两个库作为可选依赖，只有打开 error-libraries 特性时才编译下面的示例
+        #[cfg(not(feature = "error-libraries"))]
+        {
+            writeln!(ctx, "\n本项目把它们作为可选依赖，默认构建不会下载和编译。打开 error-libraries 特性就能运行用它们改写的示例：");
+            writeln!(ctx, "cargo run --features error-libraries -- run 8 --section error_libraries");
+        }
+        
+        #[cfg(feature = "error-libraries")]
+        {
+            use std::backtrace::{Backtrace, BacktraceStatus};
+            use std::error::Error;
+            
+            use anyhow::Context;
+            
+            // 沿着 source() 逐层打印错误的原因，手写的错误类型和 thiserror 生成的类型都可以这样遍历
+            fn print_sources(ctx: &mut LessonContext, error: &dyn Error) {
+                writeln!(ctx, "  错误: {}", error);
+                let mut source = error.source();
+                while let Some(cause) = source {
+                    writeln!(ctx, "  原因: {}", cause);
+                    source = cause.source();
+                }
+            }
+            
+            // 1. 用 thiserror 改写 CustomError
+            // #[error] 生成 Display，#[from] 生成和手写版本一样的 From 实现，同时让 source() 返回原来的错误
+            #[derive(Debug, thiserror::Error)]
+            enum CustomError {
+                #[error("读取输入失败")]
+                IoError(#[from] io::Error),
+                #[error("输入不是有效的数字")]
+                ParseError(#[from] ParseIntError),
+                #[error("无效的输入: {0}")]
+                InvalidInput(String),
+            }
+            
+            fn process_input(input: &str) -> Result<i32, CustomError> {
+                if input.is_empty() {
+                    return Err(CustomError::InvalidInput("输入不能为空".to_string()));
+                }
+                
+                // ? 调用的是 #[from] 生成的 From 实现
+                let number = input.parse::<i32>()?;
+                
+                if number < 0 {
+                    return Err(CustomError::InvalidInput("输入必须为正数".to_string()));
+                }
+                
+                Ok(number)
+            }
+            
+            writeln!(ctx, "\n1. 用 thiserror 改写 CustomError");
+            writeln!(ctx, "手写版本: 为 io::Error 和 ParseIntError 各写一个 impl From，只派生了 Debug");
+            writeln!(ctx, "thiserror: #[from] 生成同样的 From 实现，#[error] 生成 Display，source() 返回原来的错误");
+            for test in ["42", "not a number", ""] {
+                writeln!(ctx, "\n测试输入: '{}'", test);
+                match process_input(test) {
+                    Ok(value) => writeln!(ctx, "处理成功: {}", value),
+                    Err(error) => {
+                        // 派生的 Debug 输出和手写版本完全一样
+                        writeln!(ctx, "  Debug: {:?}", error);
+                        print_sources(ctx, &error);
+                    }
+                }
+            }
+            
+            // 2. 用 thiserror 改写 ApiError
+            // 需要额外设置 error_code，所以 From 仍然手写；和手写版本不同的是，#[source] 字段保留了原来的 NetworkError
+            #[derive(Debug, thiserror::Error)]
+            #[error("{message}")]
+            struct NetworkError {
+                message: String,
+            }
+            
+            #[derive(Debug, thiserror::Error)]
+            #[error("API 错误 {error_code}: {details}")]
+            struct ApiError {
+                error_code: u32,
+                details: String,
+                #[source]
+                source: NetworkError,
+            }
+            
+            impl From<NetworkError> for ApiError {
+                fn from(error: NetworkError) -> Self {
+                    ApiError {
+                        error_code: 503,
+                        details: "网络不可用".to_string(),
+                        source: error,
+                    }
+                }
+            }
+            
+            fn fetch_data() -> Result<String, NetworkError> {
+                Err(NetworkError {
+                    message: "连接超时".to_string(),
+                })
+            }
+            
+            fn process_data() -> Result<String, ApiError> {
+                let data = fetch_data()?;
+                Ok(data)
+            }
+            
+            writeln!(ctx, "\n2. 用 thiserror 改写 ApiError");
+            writeln!(ctx, "手写版本: From 实现把网络错误格式化进 details，原来的 NetworkError 就丢失了");
+            writeln!(ctx, "thiserror: From 仍然手写以便设置 error_code，#[source] 字段保留了原来的错误");
+            if let Err(error) = process_data() {
+                print_sources(ctx, &error);
+            }
+            
+            // 3. 用 thiserror 改写 DataError 和 ServiceError
+            // 每一层的 #[from] 都把下一层的错误作为 source()，错误链不再只能从 Debug 输出中看出来
+            #[derive(Debug, thiserror::Error)]
+            enum DataError {
+                #[error("无法解析数据")]
+                Parse(#[from] ParseIntError),
+                #[error("数据验证失败: {0}")]
+                Validation(String),
+            }
+            
+            #[derive(Debug, thiserror::Error)]
+            enum ServiceError {
+                #[error("数据处理失败")]
+                Data(#[from] DataError),
+                #[error("读写失败")]
+                Io(#[from] io::Error),
+                #[error("意外错误: {0}")]
+                Unexpected(String),
+            }
+            
+            fn parse_data(data: &str) -> Result<i32, DataError> {
+                let num = data.parse::<i32>()?;
+                
+                if num < 0 {
+                    return Err(DataError::Validation("数值必须为正数".to_string()));
+                }
+                
+                Ok(num)
+            }
+            
+            fn load_service(data: &str) -> Result<i32, ServiceError> {
+                let value = parse_data(data)?; // DataError 自动转换为 ServiceError::Data
+                
+                if value > 1000 {
+                    return Err(ServiceError::Unexpected(format!("数值 {} 超出范围", value)));
+                }
+                
+                Ok(value)
+            }
+            
+            writeln!(ctx, "\n3. 用 thiserror 改写 DataError 和 ServiceError");
+            writeln!(ctx, "手写版本: 每一层都要写 From 实现，source() 返回 None，错误链只能从 {{:?}} 中看出来");
+            writeln!(ctx, "thiserror: 每一层的 #[from] 都连接到下一层，可以沿着 source() 一直找到最初的错误");
+            for data in ["not a number", "-5", "5000"] {
+                writeln!(ctx, "\n测试输入: '{}'", data);
+                if let Err(error) = load_service(data) {
+                    writeln!(ctx, "  Debug: {:?}", error);
+                    print_sources(ctx, &error);
+                }
+            }
+            
+            // 4. 用 anyhow 添加上下文
+            // anyhow::Error 可以保存任何实现了 Error 的错误，context() 在外面再包一层说明，形成上下文链
+            fn start_service(data: &str) -> anyhow::Result<i32> {
+                let value = load_service(data).with_context(|| format!("无法用配置 '{}' 启动服务", data))?;
+                Ok(value)
+            }
+            
+            fn run_app() -> anyhow::Result<()> {
+                start_service("not a number").context("应用程序启动失败")?;
+                Ok(())
+            }
+            
+            writeln!(ctx, "\n4. 用 anyhow 添加上下文");
+            if let Err(error) = run_app() {
+                writeln!(ctx, "Display 只显示最外层: {}", error);
+                writeln!(ctx, "{{:#}} 显示整条链: {:#}", error);
+                // chain() 就是从最外层开始沿着 source() 遍历
+                writeln!(ctx, "chain() 逐层遍历:");
+                for (index, cause) in error.chain().enumerate() {
+                    writeln!(ctx, "  第 {} 层: {}", index, cause);
+                }
+                // 原来的错误类型仍然保存在链中，可以用 downcast_ref 取回后再用 match 处理
+                if let Some(service_error) = error.chain().find_map(|cause| cause.downcast_ref::<ServiceError>()) {
+                    writeln!(ctx, "用 downcast_ref 取回 ServiceError: {:?}", service_error);
+                }
+            }
+            
+            // 5. 错误发生时的调用栈
+            // 手写的错误类型要自己添加 Backtrace 字段，并在 From 实现中调用 Backtrace::capture()
+            #[derive(Debug)]
+            struct ManualConfigError {
+                source: ParseIntError,
+                backtrace: Backtrace,
+            }
+            
+            impl From<ParseIntError> for ManualConfigError {
+                fn from(source: ParseIntError) -> Self {
+                    ManualConfigError {
+                        source,
+                        backtrace: Backtrace::capture(),
+                    }
+                }
+            }
+            
+            // thiserror 也支持 Backtrace 字段，但是需要 nightly 版本的 error_generic_member_access 特性，
+            // 在稳定版上派生带有 Backtrace 字段的类型会编译失败，所以这里只对比手写版本和 anyhow
+            
+            fn describe(status: BacktraceStatus) -> &'static str {
+                match status {
+                    BacktraceStatus::Captured => "已捕获",
+                    BacktraceStatus::Disabled => "未启用",
+                    _ => "当前平台不支持",
+                }
+            }
+            
+            fn read_config_manual(text: &str) -> Result<i32, ManualConfigError> {
+                Ok(text.parse::<i32>()?)
+            }
+            
+            // anyhow::Error 在创建时捕获调用栈
+            fn read_config_anyhow(text: &str) -> anyhow::Result<i32> {
+                Ok(text.parse::<i32>()?)
+            }
+            
+            writeln!(ctx, "\n5. 错误发生时的调用栈");
+            if let Err(error) = read_config_manual("x") {
+                writeln!(ctx, "手写 From + Backtrace::capture(): {}（原因: {}）", describe(error.backtrace.status()), error.source);
+            }
+            writeln!(ctx, "thiserror: Backtrace 字段需要 nightly 版本，稳定版上无法使用");
+            if let Err(error) = read_config_anyhow("x") {
+                writeln!(ctx, "anyhow::Error: {}", describe(error.backtrace().status()));
+            }
+            writeln!(ctx, "默认不捕获调用栈，设置环境变量 RUST_BACKTRACE=1 或 RUST_LIB_BACKTRACE=1 后再运行就会捕获");
+            writeln!(ctx, "捕获的调用栈可以用 {{}} 打印，anyhow::Error 用 {{:?}} 打印时会把它放在原因链的后面");
+            
+            writeln!(ctx, "\n总结: thiserror 适合库，定义具体的错误类型，调用者可以用 match 处理每一种错误；");
+            writeln!(ctx, "anyhow 适合应用程序，用 ? 和 context() 汇总各种错误，同时保留完整的原因链");
+        }
     }
\ No newline at end of file
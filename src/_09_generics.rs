@@ -16,6 +16,12 @@
 // 导入必要的模块
 use std::fmt::Display;
 use std::ops::{Add, Sub};
+use crate::compile_error::CompileError;
+use crate::context::LessonContext;
+use crate::exercise::Exercise;
+use crate::graph::Prerequisite;
+use crate::lesson::{Lesson, Section};
+use crate::quiz::{Question, QuestionKind};
 
 // 定义泛型版本的Container trait，避免编译错误
 pub trait Container<T> {
@@ -23,48 +29,201 @@ pub trait Container<T> {
     fn get(&self, index: usize) -> Option<&T>;
 }
 
+// 本课在课程注册表中的登记信息
+// 主程序的菜单等前端都通过 Lesson trait 读取这些信息
+pub struct GenericsLesson;
+
+impl Lesson for GenericsLesson {
+    fn id(&self) -> &'static str {
+        "9"
+    }
+
+    fn title(&self) -> &'static str {
+        "泛型 (Generics)"
+    }
+
+    fn summary(&self) -> &'static str {
+        "本示例将介绍 Rust 中的泛型机制。"
+    }
+
+    fn topics(&self) -> &'static [&'static str] {
+        &[
+            "泛型的基本概念",
+            "泛型函数",
+            "泛型结构体",
+            "泛型枚举",
+            "泛型方法",
+            "泛型约束",
+            "多态性和泛型",
+            "泛型的性能考量",
+            "泛型与特征（Trait）",
+            "泛型的高级用法",
+        ]
+    }
+
+    fn sections(&self) -> &'static [Section] {
+        &[
+            Section { id: "generics_basics", title: "泛型的基本概念", run: generics_basics },
+            Section { id: "generic_functions", title: "泛型函数", run: generic_functions },
+            Section { id: "generic_structs", title: "泛型结构体", run: generic_structs },
+            Section { id: "generic_enums", title: "泛型枚举", run: generic_enums },
+            Section { id: "generic_methods", title: "泛型方法", run: generic_methods },
+            Section { id: "generic_constraints", title: "泛型约束", run: generic_constraints },
+            Section { id: "polymorphism_with_generics", title: "多态性和泛型", run: polymorphism_with_generics },
+            Section { id: "performance_considerations", title: "泛型的性能考量", run: performance_considerations },
+            Section { id: "generics_with_traits", title: "泛型与特征（Trait）的结合使用", run: generics_with_traits },
+            Section { id: "advanced_generics", title: "泛型的高级用法", run: advanced_generics },
+        ]
+    }
+
+    fn source(&self) -> &'static str {
+        include_str!("_09_generics.rs")
+    }
+
+    fn source_path(&self) -> &'static str {
+        file!()
+    }
+
+    fn quiz(&self) -> &'static [Question] {
+        QUIZ
+    }
+
+    fn exercises(&self) -> &'static [Exercise] {
+        EXERCISES
+    }
+
+    fn compile_errors(&self) -> &'static [CompileError] {
+        COMPILE_ERRORS
+    }
+
+    fn prerequisites(&self) -> &'static [&'static str] {
+        &["3", "4"]
+    }
+
+    fn section_prerequisites(&self) -> &'static [Prerequisite] {
+        &[
+            Prerequisite {
+                section: "generic_structs",
+                lesson: "3",
+                requires: "struct_definition",
+                reason: "泛型结构体只是在结构体定义上加了类型参数",
+            },
+            Prerequisite {
+                section: "generic_enums",
+                lesson: "4",
+                requires: "option_enum",
+                reason: "标准库中的 Option<T> 就是最常见的泛型枚举",
+            },
+            Prerequisite {
+                section: "generic_methods",
+                lesson: "3",
+                requires: "struct_methods",
+                reason: "泛型方法同样写在 impl 块中，只是 impl 后面多了类型参数",
+            },
+        ]
+    }
+
+    fn run(&self, ctx: &mut LessonContext) {
+        run(ctx);
+    }
+}
+
+// 本课的测验题目
+const QUIZ: &[Question] = &[
+    Question {
+        prompt: "下面的代码会输出什么？",
+        kind: QuestionKind::PredictOutput {
+            section: "generic_functions",
+            code: r#"fn find_max<T: PartialOrd>(list: &[T]) -> Option<&T> { ... }
+
+let strings = ["apple", "banana", "orange", "pear"];
+if let Some(max) = find_max(&strings) {
+    println!("字符串数组中的最大值: {}", max);
+}"#,
+            options: &["字符串数组中的最大值: banana", "字符串数组中的最大值: orange", "字符串数组中的最大值: pear"],
+            answer: 2,
+        },
+        explanation: "字符串按字典序比较，首字母 p 排在 a、b、o 之后，所以 \"pear\" 最大，与字符串的长度无关",
+    },
+    Question {
+        prompt: "fn find_max<T: PartialOrd>(list: &[T]) 中的 T: PartialOrd 表示什么？",
+        kind: QuestionKind::Choice {
+            options: &["T 必须是数字类型", "T 必须支持比较大小", "T 必须可以打印"],
+            answer: 1,
+        },
+        explanation: "T: PartialOrd 是 trait 约束，要求 T 实现 PartialOrd，这样函数中才能使用 > 比较两个值",
+    },
+    Question {
+        prompt: "使用泛型的代码在运行时比使用具体类型的代码更慢。",
+        kind: QuestionKind::TrueFalse { answer: false },
+        explanation: "Rust 在编译时对泛型进行单态化，为每个用到的具体类型生成一份代码，运行时没有额外开销",
+    },
+];
+
+// 本课的动手练习
+const EXERCISES: &[Exercise] = &[
+    Exercise {
+        name: "find_max",
+        title: "泛型函数 find_max",
+        description: "实现 find_max，返回切片中最大元素的引用，切片为空时返回 None",
+        starter: include_str!("../exercises/09_generics/find_max.rs"),
+        tests: include_str!("../exercises/09_generics/find_max_tests.rs"),
+        solution: include_str!("../exercises/09_generics/find_max_solution.rs"),
+        hints: &[
+            "list.first() 返回 Option<&T>，可以用 ? 在切片为空时直接返回 None",
+            "遍历切片时得到的是 &T，比较两个引用时会比较它们指向的值",
+        ],
+    },
+];
+
+// 本课的编译错误示例
+const COMPILE_ERRORS: &[CompileError] = &[
+    CompileError {
+        name: "missing_bound",
+        title: "用不满足约束的类型调用泛型函数",
+        section: "generic_constraints",
+        code: "E0277",
+        broken: include_str!("../compile_errors/09_generics/missing_bound.rs"),
+        fixed: include_str!("../compile_errors/09_generics/missing_bound_fixed.rs"),
+        explanation: "display_and_add 要求 T 实现 Add。&str 没有实现 Add，所以 display_and_add(\"hello\", \"world\") 不满足泛型约束。拼接字符串需要一个拥有所有权的 String，例如 String::from(\"hello\") + \"world\"",
+    },
+];
+
 // 定义一个公共函数 run()，作为本模块的入口点
 // 当用户在主程序中选择 9 时，将调用此函数
-pub fn run() {
-    println!("=== 第9课：泛型 ===");
-    println!("本示例将介绍 Rust 中的泛型机制。\n");
-    
-    // 调用各个示例函数
-    generics_basics();
-    generic_functions();
-    generic_structs();
-    generic_enums();
-    generic_methods();
-    generic_constraints();
-    polymorphism_with_generics();
-    performance_considerations();
-    generics_with_traits();
-    advanced_generics();
+pub fn run(ctx: &mut LessonContext) {
+    ctx.lesson_heading("第9课：泛型");
+    writeln!(ctx, "本示例将介绍 Rust 中的泛型机制。\n");
+    
+    // 按顺序调用各个示例函数，小节列表统一登记在 sections() 中
+    for section in GenericsLesson.sections() {
+        (section.run)(ctx);
+    }
 }
 
 // 演示泛型的基本概念
-fn generics_basics() {
-    println!("\n--- 泛型的基本概念 ---");
+fn generics_basics(ctx: &mut LessonContext) {
+    ctx.section_heading("泛型的基本概念");
     
-    println!("泛型是一种编程概念，允许我们编写可以处理不同类型数据的代码：");
-    println!("- 在 Rust 中，泛型使用尖括号 <T> 表示");
-    println!("- T 是一个类型参数，可以是任何类型");
-    println!("- 泛型让我们可以编写更通用、可重用的代码");
-    println!("- 泛型在编译时会被单态化（monomorphization），不会有运行时开销");
+    writeln!(ctx, "泛型是一种编程概念，允许我们编写可以处理不同类型数据的代码：");
+    writeln!(ctx, "- 在 Rust 中，泛型使用尖括号 <T> 表示");
+    writeln!(ctx, "- T 是一个类型参数，可以是任何类型");
+    writeln!(ctx, "- 泛型让我们可以编写更通用、可重用的代码");
+    writeln!(ctx, "- 泛型在编译时会被单态化（monomorphization），不会有运行时开销");
     
-    println!("\n泛型的使用场景：");
-    println!("1. 集合类型（如 Vec<T>, HashMap<K, V>）");
-    println!("2. 函数和方法需要处理多种类型的数据");
-    println!("3. 结构体和枚举需要存储不同类型的数据");
-    println!("4. 实现多态性行为");
+    writeln!(ctx, "\n泛型的使用场景：");
+    writeln!(ctx, "1. 集合类型（如 Vec<T>, HashMap<K, V>）");
+    writeln!(ctx, "2. 函数和方法需要处理多种类型的数据");
+    writeln!(ctx, "3. 结构体和枚举需要存储不同类型的数据");
+    writeln!(ctx, "4. 实现多态性行为");
     
     // 一个简单的泛型示例
     let numbers = vec![1, 2, 3, 4, 5]; // Vec<i32>
     let words = vec!["hello", "world"]; // Vec<&str>
     // let mixed = vec![1, "two", 3.0]; // 这在 Rust 中是不可能的，因为 Vec 只能存储同一种类型
     
-    println!("numbers 是一个 Vec<i32> 类型: {:?}", numbers);
-    println!("words 是一个 Vec<&str> 类型: {:?}", words);
+    writeln!(ctx, "numbers 是一个 Vec<i32> 类型: {:?}", numbers);
+    writeln!(ctx, "words 是一个 Vec<&str> 类型: {:?}", words);
     
     // 运行结果：
     // 泛型的基本概念
@@ -84,10 +243,10 @@ fn generics_basics() {
 }
 
 // 演示泛型函数
-fn generic_functions() {
-    println!("\n--- 泛型函数 ---");
+fn generic_functions(ctx: &mut LessonContext) {
+    ctx.section_heading("泛型函数");
     
-    println!("泛型函数是可以接受不同类型参数的函数：");
+    writeln!(ctx, "泛型函数是可以接受不同类型参数的函数：");
     
     // 定义一个泛型函数，用于查找数组中的最大值
     fn find_max<T: PartialOrd>(list: &[T]) -> Option<&T> {
@@ -107,30 +266,30 @@ fn generic_functions() {
     }
     
     // 定义一个简单的泛型函数，用于打印任何实现了 Display trait 的值
-    fn print_value<T: Display>(value: T) {
-        println!("Value: {}", value);
+    fn print_value<T: Display>(ctx: &mut LessonContext, value: T) {
+        writeln!(ctx, "Value: {}", value);
     }
     
     // 测试泛型函数
     let numbers = [1, 5, 3, 9, 2];
     if let Some(max) = find_max(&numbers) {
-        println!("数组中的最大值: {}", max);
+        writeln!(ctx, "数组中的最大值: {}", max);
     }
     
     let floats = [1.5, 5.2, 3.7, 9.1, 2.8];
     if let Some(max) = find_max(&floats) {
-        println!("浮点数数组中的最大值: {}", max);
+        writeln!(ctx, "浮点数数组中的最大值: {}", max);
     }
     
     let strings = ["apple", "banana", "orange", "pear"];
     if let Some(max) = find_max(&strings) {
-        println!("字符串数组中的最大值: {}", max);
+        writeln!(ctx, "字符串数组中的最大值: {}", max);
     }
     
     // 测试 print_value 函数
-    print_value(42);
-    print_value(3.14);
-    print_value("Hello, Rust!");
+    print_value(ctx, 42);
+    print_value(ctx, 3.14);
+    print_value(ctx, "Hello, Rust!");
     
     // 运行结果：
     // 泛型函数
@@ -144,10 +303,10 @@ fn generic_functions() {
 }
 
 // 演示泛型结构体
-fn generic_structs() {
-    println!("\n--- 泛型结构体 ---");
+fn generic_structs(ctx: &mut LessonContext) {
+    ctx.section_heading("泛型结构体");
     
-    println!("泛型结构体是可以包含不同类型字段的结构体：");
+    writeln!(ctx, "泛型结构体是可以包含不同类型字段的结构体：");
     
     // 定义一个泛型结构体 Point，表示二维坐标点
     struct Point<T> {
@@ -202,21 +361,21 @@ fn generic_structs() {
     
     // 测试泛型结构体
     let integer_point = Point::new(10, 20);
-    println!("整数坐标点: ({}, {})", integer_point.get_x(), integer_point.get_y());
-    println!("到原点的距离: {}", integer_point.distance_from_origin());
+    writeln!(ctx, "整数坐标点: ({}, {})", integer_point.get_x(), integer_point.get_y());
+    writeln!(ctx, "到原点的距离: {}", integer_point.distance_from_origin());
     
     let float_point = Point::new(1.5, 2.5);
-    println!("浮点坐标点: ({}, {})", float_point.get_x(), float_point.get_y());
+    writeln!(ctx, "浮点坐标点: ({}, {})", float_point.get_x(), float_point.get_y());
     // 以下代码会导致编译错误，因为 Point<f64> 没有实现 distance_from_origin 方法
     // println!("到原点的距离: {}", float_point.distance_from_origin());
     
     let pair1 = Pair::new("name", "Alice");
     let (key1, value1) = pair1.get();
-    println!("键值对1: {} = {}", key1, value1);
+    writeln!(ctx, "键值对1: {} = {}", key1, value1);
     
     let pair2 = Pair::new(1, 100);
     let (key2, value2) = pair2.get();
-    println!("键值对2: {} = {}", key2, value2);
+    writeln!(ctx, "键值对2: {} = {}", key2, value2);
     
     // 运行结果：
     // 泛型结构体
@@ -229,10 +388,10 @@ fn generic_structs() {
 }
 
 // 演示泛型枚举
-fn generic_enums() {
-    println!("\n--- 泛型枚举 ---");
+fn generic_enums(ctx: &mut LessonContext) {
+    ctx.section_heading("泛型枚举");
     
-    println!("泛型枚举是可以包含不同类型关联数据的枚举：");
+    writeln!(ctx, "泛型枚举是可以包含不同类型关联数据的枚举：");
     
     // 定义一个简单的泛型枚举
     enum MyOption<T> {
@@ -260,34 +419,34 @@ fn generic_enums() {
     
     // 为 Message 实现方法
     impl<T: std::fmt::Debug, U: std::fmt::Debug> Message<T, U> {
-        fn print(&self) {
+        fn print(&self, ctx: &mut LessonContext) {
             match self {
-                Message::Text(s) => println!("Text: {}", s),
-                Message::Number(n) => println!("Number: {:?}", n),
-                Message::Pair(a, b) => println!("Pair: {:?}, {:?}", a, b),
-                Message::Empty => println!("Empty message"),
+                Message::Text(s) => writeln!(ctx, "Text: {}", s),
+                Message::Number(n) => writeln!(ctx, "Number: {:?}", n),
+                Message::Pair(a, b) => writeln!(ctx, "Pair: {:?}, {:?}", a, b),
+                Message::Empty => writeln!(ctx, "Empty message"),
             }
         }
     }
     
     // 测试泛型枚举
     let msg1: Message<i32, i32> = Message::Text(String::from("Hello"));
-    msg1.print();
+    msg1.print(ctx);
     
     let msg2: Message<i32, i32> = Message::Number(42);
-    msg2.print();
+    msg2.print(ctx);
     
     let msg3: Message<&str, i32> = Message::Pair("x", 100);
-    msg3.print();
+    msg3.print(ctx);
     
     let msg4: Message<i32, i32> = Message::Empty;
-    msg4.print();
+    msg4.print(ctx);
     
     // 使用标准库中的 Option 枚举
     let some_value = Some(5);
     let none_value: Option<i32> = None;
     
-    println!("Option 值: {:?}, {:?}", some_value, none_value);
+    writeln!(ctx, "Option 值: {:?}, {:?}", some_value, none_value);
     
     // 运行结果：
     // 泛型枚举
@@ -300,10 +459,10 @@ fn generic_enums() {
 }
 
 // 演示泛型方法
-fn generic_methods() {
-    println!("\n--- 泛型方法 ---");
+fn generic_methods(ctx: &mut LessonContext) {
+    ctx.section_heading("泛型方法");
     
-    println!("泛型方法是在结构体或枚举上定义的可以处理不同类型数据的方法：");
+    writeln!(ctx, "泛型方法是在结构体或枚举上定义的可以处理不同类型数据的方法：");
     
     // 定义一个泛型结构体
     struct Container<T> {
@@ -348,23 +507,23 @@ fn generic_methods() {
     
     // 测试泛型方法
     let container1 = Container::new(42);
-    println!("Container 1 的值: {}", container1.get());
+    writeln!(ctx, "Container 1 的值: {}", container1.get());
     
     let mut container2 = Container::new(10);
     container2.increment();
-    println!("Container 2 递增后的值: {}", container2.get());
+    writeln!(ctx, "Container 2 递增后的值: {}", container2.get());
     
     let squared = container2.square();
-    println!("Container 2 的平方: {}", squared.get());
+    writeln!(ctx, "Container 2 的平方: {}", squared.get());
     
     // 使用 map 方法转换类型
     let container3 = Container::new(5);
     let mapped = container3.map(|x| x.to_string());
-    println!("转换为字符串后的值: {}", mapped.get());
+    writeln!(ctx, "转换为字符串后的值: {}", mapped.get());
     
     let container4 = Container::new("hello");
     let mapped2 = container4.map(|s| s.len());
-    println!("字符串长度: {}", mapped2.get());
+    writeln!(ctx, "字符串长度: {}", mapped2.get());
     
     // 运行结果：
     // 泛型方法
@@ -377,12 +536,12 @@ fn generic_methods() {
 }
 
 // 演示泛型约束
-fn generic_constraints() {
-    println!("\n--- 泛型约束 ---");
+fn generic_constraints(ctx: &mut LessonContext) {
+    ctx.section_heading("泛型约束");
     
-    println!("泛型约束用于限制泛型参数可以接受的类型：");
-    println!("- 使用 where 子句或直接在尖括号中指定约束");
-    println!("- 常见的约束包括：Trait 约束、生命周期约束等");
+    writeln!(ctx, "泛型约束用于限制泛型参数可以接受的类型：");
+    writeln!(ctx, "- 使用 where 子句或直接在尖括号中指定约束");
+    writeln!(ctx, "- 常见的约束包括：Trait 约束、生命周期约束等");
     
     // 定义一个带有 Trait 约束的泛型函数
     fn add<T: Add<Output = T>>(a: T, b: T) -> T {
@@ -398,26 +557,26 @@ fn generic_constraints() {
     }
     
     // 多个约束
-    fn display_and_add<T>(a: T, b: T)
+    fn display_and_add<T>(ctx: &mut LessonContext, a: T, b: T)
     where
         T: Add<Output = T> + Display + Clone,
     {
         let result = a.clone() + b.clone();
-        println!("{} + {} = {}", a, b, result);
+        writeln!(ctx, "{} + {} = {}", a, b, result);
     }
     
     // 测试带有约束的泛型函数
-    println!("1 + 2 = {}", add(1, 2));
-    println!("3.5 + 2.5 = {}", add(3.5, 2.5));
+    writeln!(ctx, "1 + 2 = {}", add(1, 2));
+    writeln!(ctx, "3.5 + 2.5 = {}", add(3.5, 2.5));
     
-    println!("5 - 3 = {}", subtract(5, 3));
-    println!("10.5 - 4.2 = {}", subtract(10.5, 4.2));
+    writeln!(ctx, "5 - 3 = {}", subtract(5, 3));
+    writeln!(ctx, "10.5 - 4.2 = {}", subtract(10.5, 4.2));
     
-    display_and_add(10, 20);
-    display_and_add(5.5, 4.5);
+    display_and_add(ctx, 10, 20);
+    display_and_add(ctx, 5.5, 4.5);
     
     // 以下代码会导致编译错误，因为字符串切片没有实现 Add 操作符
-    // display_and_add("hello", "world");
+    // display_and_add(ctx, "hello", "world");
     
     // 修改Calculator结构体，添加Copy trait约束
     struct Calculator<T: Add<Output = T> + Sub<Output = T> + Copy> {
@@ -454,16 +613,16 @@ fn generic_constraints() {
     }
     
     let mut calc = Calculator::new(100);
-    println!("初始值: {}", calc.get());
+    writeln!(ctx, "初始值: {}", calc.get());
     calc.add(50);
-    println!("加 50 后: {}", calc.get());
+    writeln!(ctx, "加 50 后: {}", calc.get());
     calc.subtract(25);
-    println!("减 25 后: {}", calc.get());
+    writeln!(ctx, "减 25 后: {}", calc.get());
     
     let mut float_calc = Calculator::new(10.5);
-    println!("初始浮点值: {}", float_calc.get());
+    writeln!(ctx, "初始浮点值: {}", float_calc.get());
     float_calc.add(5.5);
-    println!("加 5.5 后: {}", float_calc.get());
+    writeln!(ctx, "加 5.5 后: {}", float_calc.get());
     
     // 运行结果：
     // 泛型约束
@@ -484,16 +643,16 @@ fn generic_constraints() {
 }
 
 // 演示多态性和泛型
-fn polymorphism_with_generics() {
-    println!("\n--- 多态性和泛型 ---");
+fn polymorphism_with_generics(ctx: &mut LessonContext) {
+    ctx.section_heading("多态性和泛型");
     
-    println!("泛型允许我们实现编译时多态性：");
-    println!("- 相同的代码可以处理不同类型的数据");
-    println!("- 编译器会为每种具体类型生成专门的代码");
+    writeln!(ctx, "泛型允许我们实现编译时多态性：");
+    writeln!(ctx, "- 相同的代码可以处理不同类型的数据");
+    writeln!(ctx, "- 编译器会为每种具体类型生成专门的代码");
     
     // 定义一个特征（Trait）
     trait Draw {
-        fn draw(&self);
+        fn draw(&self, ctx: &mut LessonContext);
     }
     
     // 实现该特征的具体类型
@@ -502,8 +661,8 @@ fn polymorphism_with_generics() {
     }
     
     impl Draw for Circle {
-        fn draw(&self) {
-            println!("绘制一个半径为 {} 的圆形", self.radius);
+        fn draw(&self, ctx: &mut LessonContext) {
+            writeln!(ctx, "绘制一个半径为 {} 的圆形", self.radius);
         }
     }
     
@@ -513,8 +672,8 @@ fn polymorphism_with_generics() {
     }
     
     impl Draw for Rectangle {
-        fn draw(&self) {
-            println!("绘制一个 {}x{} 的矩形", self.width, self.height);
+        fn draw(&self, ctx: &mut LessonContext) {
+            writeln!(ctx, "绘制一个 {}x{} 的矩形", self.width, self.height);
         }
     }
     
@@ -524,19 +683,19 @@ fn polymorphism_with_generics() {
     }
     
     impl Draw for Triangle {
-        fn draw(&self) {
-            println!("绘制一个底为 {}，高为 {} 的三角形", self.base, self.height);
+        fn draw(&self, ctx: &mut LessonContext) {
+            writeln!(ctx, "绘制一个底为 {}，高为 {} 的三角形", self.base, self.height);
         }
     }
     
     // 使用泛型实现多态
-    fn draw_shape<T: Draw>(shape: T) {
-        shape.draw();
+    fn draw_shape<T: Draw>(ctx: &mut LessonContext, shape: T) {
+        shape.draw(ctx);
     }
     
     // 使用特征对象实现运行时多态
-    fn draw_shape_dyn(shape: &dyn Draw) {
-        shape.draw();
+    fn draw_shape_dyn(ctx: &mut LessonContext, shape: &dyn Draw) {
+        shape.draw(ctx);
     }
     
     // 测试多态性
@@ -544,20 +703,20 @@ fn polymorphism_with_generics() {
     let rectangle = Rectangle { width: 10.0, height: 5.0 };
     let triangle = Triangle { base: 6.0, height: 8.0 };
     
-    println!("使用泛型函数：");
-    draw_shape(circle);
-    draw_shape(rectangle);
-    draw_shape(triangle);
+    writeln!(ctx, "使用泛型函数：");
+    draw_shape(ctx, circle);
+    draw_shape(ctx, rectangle);
+    draw_shape(ctx, triangle);
     
     // 由于上面的调用已经消耗了对象，我们需要重新创建
     let circle = Circle { radius: 5.0 };
     let rectangle = Rectangle { width: 10.0, height: 5.0 };
     let triangle = Triangle { base: 6.0, height: 8.0 };
     
-    println!("\n使用特征对象：");
-    draw_shape_dyn(&circle);
-    draw_shape_dyn(&rectangle);
-    draw_shape_dyn(&triangle);
+    writeln!(ctx, "\n使用特征对象：");
+    draw_shape_dyn(ctx, &circle);
+    draw_shape_dyn(ctx, &rectangle);
+    draw_shape_dyn(ctx, &triangle);
     
     // 创建一个特征对象的集合
     let shapes: Vec<Box<dyn Draw>> = vec![
@@ -566,9 +725,9 @@ fn polymorphism_with_generics() {
         Box::new(Triangle { base: 5.0, height: 7.0 }),
     ];
     
-    println!("\n遍历特征对象集合：");
+    writeln!(ctx, "\n遍历特征对象集合：");
     for shape in shapes {
-        shape.draw();
+        shape.draw(ctx);
     }
     
     // 运行结果：
@@ -594,10 +753,10 @@ fn polymorphism_with_generics() {
 
 // 定义Container trait，避免Vec<T>实现时找不到trait
 // 演示泛型的高级用法
-fn advanced_generics() {
-    println!("\n--- 泛型的高级用法 ---");
+fn advanced_generics(ctx: &mut LessonContext) {
+    ctx.section_heading("泛型的高级用法");
     
-    println!("Rust 中的泛型还有一些高级用法：");
+    writeln!(ctx, "Rust 中的泛型还有一些高级用法：");
     
     // 1. 关联类型（Associated Types）
     trait Container {
@@ -621,12 +780,12 @@ fn advanced_generics() {
     
     // 2. 默认泛型参数
     trait PrintWithPrefix {
-        fn print_with_prefix(&self, prefix: &str);
+        fn print_with_prefix(&self, ctx: &mut LessonContext, prefix: &str);
     }
     
     impl<T: Display> PrintWithPrefix for T {
-        fn print_with_prefix(&self, prefix: &str) {
-            println!("{}, value: {}", prefix, self);
+        fn print_with_prefix(&self, ctx: &mut LessonContext, prefix: &str) {
+            writeln!(ctx, "{}, value: {}", prefix, self);
         }
     }
     
@@ -665,28 +824,28 @@ fn advanced_generics() {
     vec.push(30);
     
     if let Some(value) = <[i32]>::get(vec.as_slice(), 1) {
-        println!("Vec 中的第二个元素: {}", value);
+        writeln!(ctx, "Vec 中的第二个元素: {}", value);
     }
     
     let number = 42;
-    number.print_with_prefix("数字");
+    number.print_with_prefix(ctx, "数字");
     
     let text = "Hello, Rust!";
-    text.print_with_prefix("文本");
+    text.print_with_prefix(ctx, "文本");
     
     // 使用完全限定语法调用关联函数
-    println!("Dog 的名称: {}", <Dog as Animal>::name());
-    println!("Cat 的名称: {}", <Cat as Animal>::name());
+    writeln!(ctx, "Dog 的名称: {}", <Dog as Animal>::name());
+    writeln!(ctx, "Cat 的名称: {}", <Cat as Animal>::name());
     
     // 测试闭包的泛型使用
     let doubled = apply_function(5, |x| x * 2);
-    println!("5 的两倍: {}", doubled);
+    writeln!(ctx, "5 的两倍: {}", doubled);
     
     let squared = apply_function(5, |x| x * x);
-    println!("5 的平方: {}", squared);
+    writeln!(ctx, "5 的平方: {}", squared);
     
     let result = apply_function("hello", |s| s.len());
-    println!("字符串 'hello' 的长度: {}", result);
+    writeln!(ctx, "字符串 'hello' 的长度: {}", result);
     
     // 运行结果：
     // 泛型的高级用法
@@ -702,10 +861,10 @@ fn advanced_generics() {
 }
 
 // 演示泛型与特征（Trait）的结合使用
-fn generics_with_traits() {
-    println!("\n--- 泛型与特征（Trait）的结合使用 ---");
+fn generics_with_traits(ctx: &mut LessonContext) {
+    ctx.section_heading("泛型与特征（Trait）的结合使用");
     
-    println!("泛型与特征（Trait）的结合使用是 Rust 类型系统的重要特性：");
+    writeln!(ctx, "泛型与特征（Trait）的结合使用是 Rust 类型系统的重要特性：");
     
     // 定义一个特征
     trait Summary {
@@ -747,25 +906,25 @@ fn generics_with_traits() {
     }
     
     // 定义一个泛型函数，接受任何实现了 Summary trait 的类型
-    fn notify<T: Summary>(item: T) {
-        println!("Breaking news! {}", item.summarize());
+    fn notify<T: Summary>(ctx: &mut LessonContext, item: T) {
+        writeln!(ctx, "Breaking news! {}", item.summarize());
     }
     
     // 使用 where 子句的等价写法
-    fn notify_where<T>(item: T)
+    fn notify_where<T>(ctx: &mut LessonContext, item: T)
     where
         T: Summary,
     {
-        println!("Breaking news! {}", item.summarize());
+        writeln!(ctx, "Breaking news! {}", item.summarize());
     }
     
     // 多个 trait 约束
-    fn display_and_summarize<T>(item: T)
+    fn display_and_summarize<T>(ctx: &mut LessonContext, item: T)
     where
         T: Summary + Display,
     {
-        println!("Display: {}", item);
-        println!("Summary: {}", item.summarize());
+        writeln!(ctx, "Display: {}", item);
+        writeln!(ctx, "Summary: {}", item.summarize());
     }
     
     // 返回实现了特定 trait 的类型
@@ -793,11 +952,11 @@ fn generics_with_traits() {
         retweet: false,
     };
     
-    println!("Article summary: {}", article.summarize());
-    println!("Tweet summary: {}", tweet.summarize());
+    writeln!(ctx, "Article summary: {}", article.summarize());
+    writeln!(ctx, "Tweet summary: {}", tweet.summarize());
     
-    notify(article);
-    notify_where(tweet);
+    notify(ctx, article);
+    notify_where(ctx, tweet);
     
     // 重新创建实例，因为上面的调用已经消耗了对象
     let article = NewsArticle {
@@ -807,10 +966,10 @@ fn generics_with_traits() {
         content: String::from("Another championship for Pittsburgh!"),
     };
     
-    display_and_summarize(article);
+    display_and_summarize(ctx, article);
     
     let summarizable = returns_summarizable();
-    println!("Returned summary: {}", summarizable.summarize());
+    writeln!(ctx, "Returned summary: {}", summarizable.summarize());
     
     // 运行结果：
     // 泛型与特征（Trait）的结合使用
@@ -819,20 +978,20 @@ fn generics_with_traits() {
     // Tweet summary: horse_ebooks: of course, as you probably already know, people
     // Breaking news! Penguins win the Stanley Cup Championship, by Iceburgh (Pittsburgh, PA, USA)
     // Breaking news! horse_ebooks: of course, as you probably already know, people
-    // Display: NewsArticle { headline: "Penguins win again", location: "Pittsburgh", author: "Iceburgh", content: "Another championship for Pittsburgh!" }
+    // Display: Penguins win again, by Iceburgh (Pittsburgh)
     // Summary: Penguins win again, by Iceburgh (Pittsburgh)
     // Returned summary: horse_ebooks: of course, as you probably already know, people
 }
 
 // 演示泛型的性能考量
-fn performance_considerations() {
-    println!("\n--- 泛型的性能考量 ---");
+fn performance_considerations(ctx: &mut LessonContext) {
+    ctx.section_heading("泛型的性能考量");
     
-    println!("Rust 中的泛型在性能方面有几个重要特点：");
-    println!("1. 单态化（monomorphization）：编译器为每种使用的具体类型生成专用的代码");
-    println!("2. 零运行时开销：泛型不会引入额外的运行时开销");
-    println!("3. 静态分发：使用泛型的函数调用在编译时确定，与具体类型直接调用一样高效");
-    println!("4. 类型擦除 vs 单态化：与某些语言的类型擦除不同，Rust 的单态化确保了最佳性能");
+    writeln!(ctx, "Rust 中的泛型在性能方面有几个重要特点：");
+    writeln!(ctx, "1. 单态化（monomorphization）：编译器为每种使用的具体类型生成专用的代码");
+    writeln!(ctx, "2. 零运行时开销：泛型不会引入额外的运行时开销");
+    writeln!(ctx, "3. 静态分发：使用泛型的函数调用在编译时确定，与具体类型直接调用一样高效");
+    writeln!(ctx, "4. 类型擦除 vs 单态化：与某些语言的类型擦除不同，Rust 的单态化确保了最佳性能");
     
     // 定义一个简单的泛型函数
     fn multiply<T: std::ops::Mul<Output = T>>(a: T, b: T) -> T {
@@ -844,16 +1003,16 @@ fn performance_considerations() {
     
     // 执行一些计算
     let result = multiply(10, 20);
-    println!("整数乘法结果: {}", result);
+    writeln!(ctx, "整数乘法结果: {}", result);
     
     let result = multiply(10.5, 20.5);
-    println!("浮点数乘法结果: {}", result);
+    writeln!(ctx, "浮点数乘法结果: {}", result);
     
     let duration = start.elapsed();
-    println!("计算耗时: {:?}", duration);
+    writeln!(ctx, "计算耗时: {:?}", duration);
     
     // 运行结果：
-    // 泛型的性能考核
+    // 泛型的性能考量
     // Rust 中的泛型在性能方面有几个重要特点：
     // 1. 单态化（monomorphization）：编译器为每种使用的具体类型生成专用的代码
     // 2. 零运行时开销：泛型不会引入额外的运行时开销
@@ -15,41 +15,215 @@
 
 // 导入必要的模块
 use std::fmt::Display;
+use crate::compile_error::CompileError;
+use crate::context::LessonContext;
+use crate::exercise::Exercise;
+use crate::graph::Prerequisite;
+use crate::lesson::{Lesson, Section};
+use crate::quiz::{Question, QuestionKind};
+
+// 本课在课程注册表中的登记信息
+// 主程序的菜单等前端都通过 Lesson trait 读取这些信息
+pub struct LifetimesLesson;
+
+impl Lesson for LifetimesLesson {
+    fn id(&self) -> &'static str {
+        "10"
+    }
+
+    fn title(&self) -> &'static str {
+        "生命周期 (Lifetimes)"
+    }
+
+    fn summary(&self) -> &'static str {
+        "本示例将介绍 Rust 中的生命周期机制。"
+    }
+
+    fn topics(&self) -> &'static [&'static str] {
+        &[
+            "生命周期的基本概念",
+            "生命周期注解语法",
+            "函数签名中的生命周期",
+            "结构体中的生命周期",
+            "方法定义中的生命周期",
+            "生命周期省略规则",
+            "静态生命周期",
+            "生命周期约束",
+            "生命周期子类型化",
+            "高级生命周期用法",
+        ]
+    }
+
+    fn sections(&self) -> &'static [Section] {
+        &[
+            Section { id: "lifetimes_basics", title: "生命周期的基本概念", run: lifetimes_basics },
+            Section { id: "lifetime_annotations", title: "生命周期注解语法", run: lifetime_annotations },
+            Section { id: "function_signatures", title: "函数签名中的生命周期", run: function_signatures },
+            Section { id: "struct_lifetimes", title: "结构体中的生命周期", run: struct_lifetimes },
+            Section { id: "method_lifetimes", title: "方法定义中的生命周期", run: method_lifetimes },
+            Section { id: "lifetime_elision", title: "生命周期省略规则", run: lifetime_elision },
+            Section { id: "static_lifetimes", title: "静态生命周期", run: static_lifetimes },
+            Section { id: "lifetime_bounds", title: "生命周期约束", run: lifetime_bounds },
+            Section { id: "lifetime_subtyping", title: "生命周期子类型化", run: lifetime_subtyping },
+            Section { id: "advanced_lifetimes", title: "高级生命周期用法", run: advanced_lifetimes },
+        ]
+    }
+
+    fn source(&self) -> &'static str {
+        include_str!("_10_lifetimes.rs")
+    }
+
+    fn source_path(&self) -> &'static str {
+        file!()
+    }
+
+    fn quiz(&self) -> &'static [Question] {
+        QUIZ
+    }
+
+    fn exercises(&self) -> &'static [Exercise] {
+        EXERCISES
+    }
+
+    fn compile_errors(&self) -> &'static [CompileError] {
+        COMPILE_ERRORS
+    }
+
+    fn prerequisites(&self) -> &'static [&'static str] {
+        &["3", "9"]
+    }
+
+    fn section_prerequisites(&self) -> &'static [Prerequisite] {
+        &[
+            Prerequisite {
+                section: "struct_lifetimes",
+                lesson: "3",
+                requires: "struct_definition",
+                reason: "结构体保存引用时，需要为引用字段加上生命周期注解",
+            },
+            Prerequisite {
+                section: "method_lifetimes",
+                lesson: "3",
+                requires: "struct_methods",
+                reason: "为带生命周期参数的结构体实现方法",
+            },
+            Prerequisite {
+                section: "lifetime_bounds",
+                lesson: "9",
+                requires: "generic_constraints",
+                reason: "T: 'a 和 T: Display 一样，是写在泛型参数上的约束",
+            },
+        ]
+    }
+
+    fn run(&self, ctx: &mut LessonContext) {
+        run(ctx);
+    }
+}
+
+// 本课的测验题目
+const QUIZ: &[Question] = &[
+    Question {
+        prompt: "下面的代码会输出什么？",
+        kind: QuestionKind::PredictOutput {
+            section: "function_signatures",
+            code: r#"fn longest<'a>(x: &'a str, y: &'a str) -> &'a str {
+    if x.len() > y.len() {
+        x
+    } else {
+        y
+    }
+}
+
+let string1 = String::from("hello");
+let string2 = String::from("world");
+let result = longest(string1.as_str(), string2.as_str());
+println!("最长的字符串: {}", result);"#,
+            options: &["最长的字符串: hello", "最长的字符串: world", "最长的字符串: helloworld"],
+            answer: 1,
+        },
+        explanation: "两个字符串的长度都是 5，x.len() > y.len() 不成立，所以返回 else 分支中的 y，也就是 \"world\"",
+    },
+    Question {
+        prompt: "fn longest<'a>(x: &'a str, y: &'a str) -> &'a str 中的 'a 表示什么？",
+        kind: QuestionKind::Choice {
+            options: &["返回的引用至少和 x、y 中存活时间较短的那个一样长", "返回的引用永远有效", "x 和 y 必须指向同一个字符串"],
+            answer: 0,
+        },
+        explanation: "生命周期注解描述了参数和返回值之间的关系：返回值只在 x 和 y 都有效的范围内有效",
+    },
+    Question {
+        prompt: "生命周期注解可以延长引用的存活时间。",
+        kind: QuestionKind::TrueFalse { answer: false },
+        explanation: "生命周期注解只是告诉编译器各个引用之间的关系，不会改变任何值的存活时间",
+    },
+];
+
+// 本课的动手练习
+const EXERCISES: &[Exercise] = &[
+    Exercise {
+        name: "longest",
+        title: "为 longest 添加生命周期注解",
+        description: "为 longest 的函数签名添加生命周期注解让代码通过编译，并返回两个字符串切片中较长的那一个，长度相同时返回第一个",
+        starter: include_str!("../exercises/10_lifetimes/longest.rs"),
+        tests: include_str!("../exercises/10_lifetimes/longest_tests.rs"),
+        solution: include_str!("../exercises/10_lifetimes/longest_solution.rs"),
+        hints: &[
+            "返回值可能来自 x，也可能来自 y，所以两个参数和返回值需要使用同一个生命周期，例如 fn longest<'a>(x: &'a str, y: &'a str) -> &'a str",
+            "长度相同时要返回 x，比较时注意使用 > 还是 >=",
+        ],
+    },
+];
+
+// 本课的编译错误示例
+const COMPILE_ERRORS: &[CompileError] = &[
+    CompileError {
+        name: "longest",
+        title: "函数返回的引用缺少生命周期",
+        section: "lifetime_elision",
+        code: "E0106",
+        broken: include_str!("../compile_errors/10_lifetimes/longest.rs"),
+        fixed: include_str!("../compile_errors/10_lifetimes/longest_fixed.rs"),
+        explanation: "longest 有两个引用参数，返回的引用可能来自其中任何一个，省略规则无法推断返回值的生命周期。用 <'a> 声明生命周期参数，表示返回的引用至少和两个参数中较短的那个活得一样久",
+    },
+    CompileError {
+        name: "dangling_reference",
+        title: "引用比被引用的值活得更久",
+        section: "lifetimes_basics",
+        code: "E0597",
+        broken: include_str!("../compile_errors/10_lifetimes/dangling_reference.rs"),
+        fixed: include_str!("../compile_errors/10_lifetimes/dangling_reference_fixed.rs"),
+        explanation: "x 在内部作用域结束时就被释放了，而 r 在作用域之外还要使用，r 会成为悬垂引用。让 x 的作用域覆盖 r 的所有使用",
+    },
+];
 
 // 定义一个公共函数 run()，作为本模块的入口点
 // 当用户在主程序中选择 10 时，将调用此函数
-pub fn run() {
-    println!("=== 第10课：生命周期 ===");
-    println!("本示例将介绍 Rust 中的生命周期机制。\n");
-    
-    // 调用各个示例函数
-    lifetimes_basics();
-    lifetime_annotations();
-    function_signatures();
-    struct_lifetimes();
-    method_lifetimes();
-    lifetime_elision();
-    static_lifetimes();
-    lifetime_bounds();
-    lifetime_subtyping();
-    advanced_lifetimes();
+pub fn run(ctx: &mut LessonContext) {
+    ctx.lesson_heading("第10课：生命周期");
+    writeln!(ctx, "本示例将介绍 Rust 中的生命周期机制。\n");
+    
+    // 按顺序调用各个示例函数，小节列表统一登记在 sections() 中
+    for section in LifetimesLesson.sections() {
+        (section.run)(ctx);
+    }
 }
 
 // 演示生命周期的基本概念
-fn lifetimes_basics() {
-    println!("\n--- 生命周期的基本概念 ---");
+fn lifetimes_basics(ctx: &mut LessonContext) {
+    ctx.section_heading("生命周期的基本概念");
     
-    println!("生命周期是 Rust 中的一个关键概念，用于确保引用的有效性：");
-    println!("- 生命周期是对引用有效的时间段的抽象");
-    println!("- 它帮助编译器在编译时确保所有引用都是有效的");
-    println!("- 生命周期解决了悬垂引用（dangling references）的问题");
-    println!("- 生命周期不改变任何引用或变量的存活时间");
-    println!("- 它们只是被编译器用来验证引用的有效性");
+    writeln!(ctx, "生命周期是 Rust 中的一个关键概念，用于确保引用的有效性：");
+    writeln!(ctx, "- 生命周期是对引用有效的时间段的抽象");
+    writeln!(ctx, "- 它帮助编译器在编译时确保所有引用都是有效的");
+    writeln!(ctx, "- 生命周期解决了悬垂引用（dangling references）的问题");
+    writeln!(ctx, "- 生命周期不改变任何引用或变量的存活时间");
+    writeln!(ctx, "- 它们只是被编译器用来验证引用的有效性");
     
     // 一个简单的生命周期示例
     let x = 5; // x 的生命周期开始
     let y = &x; // y 引用 x，y 的生命周期不能超过 x
-    println!("x = {}, y = {}", x, y);
+    writeln!(ctx, "x = {}, y = {}", x, y);
     // x 的生命周期结束，y 也不再有效
     
     // 错误示例（注释掉以避免编译错误）：
@@ -60,11 +234,11 @@ fn lifetimes_basics() {
     // }
     // println!("r = {}", r);
     
-    println!("\n生命周期的主要用途：");
-    println!("1. 确保引用在使用时不会指向已释放的内存");
-    println!("2. 防止悬垂引用");
-    println!("3. 帮助编译器进行借用检查");
-    println!("4. 支持复杂的引用关系");
+    writeln!(ctx, "\n生命周期的主要用途：");
+    writeln!(ctx, "1. 确保引用在使用时不会指向已释放的内存");
+    writeln!(ctx, "2. 防止悬垂引用");
+    writeln!(ctx, "3. 帮助编译器进行借用检查");
+    writeln!(ctx, "4. 支持复杂的引用关系");
     
     // 运行结果：
     // 生命周期的基本概念
@@ -84,21 +258,21 @@ fn lifetimes_basics() {
 }
 
 // 演示生命周期注解语法
-fn lifetime_annotations() {
-    println!("\n--- 生命周期注解语法 ---");
+fn lifetime_annotations(ctx: &mut LessonContext) {
+    ctx.section_heading("生命周期注解语法");
     
-    println!("生命周期注解是描述引用生命周期关系的语法：");
-    println!("- 使用撇号（'）后跟名称来表示生命周期参数，如 'a、'b、'c");
-    println!("- 生命周期参数放在尖括号中，如 <'a>");
-    println!("- 生命周期注解不会改变引用的实际生命周期");
-    println!("- 它们只是告诉编译器多个引用之间的生命周期关系");
+    writeln!(ctx, "生命周期注解是描述引用生命周期关系的语法：");
+    writeln!(ctx, "- 使用撇号（'）后跟名称来表示生命周期参数，如 'a、'b、'c");
+    writeln!(ctx, "- 生命周期参数放在尖括号中，如 <'a>");
+    writeln!(ctx, "- 生命周期注解不会改变引用的实际生命周期");
+    writeln!(ctx, "- 它们只是告诉编译器多个引用之间的生命周期关系");
     
     // 生命周期注解示例
     let string1 = String::from("abcd");
     let string2 = "xyz";
     
     let result = longest(string1.as_str(), string2);
-    println!("较长的字符串是: {}", result);
+    writeln!(ctx, "较长的字符串是: {}", result);
     
     // 定义一个带有生命周期注解的函数
     fn longest<'a>(x: &'a str, y: &'a str) -> &'a str {
@@ -114,11 +288,11 @@ fn lifetime_annotations() {
     // - 'static 表示整个程序执行期间的生命周期
     // - 更具描述性的名称（如 'context, 'iter）可以在复杂情况下使用
     
-    println!("\n生命周期注解的位置：");
-    println!("1. 函数参数：fn function<'a>(x: &'a Type)");
-    println!("2. 函数返回值：fn function<'a>(x: &'a Type) -> &'a Type");
-    println!("3. 结构体字段：struct Struct<'a> {{ field: &'a Type }}");
-    println!("4. 泛型参数一起使用：fn function<'a, T>(x: &'a T)");
+    writeln!(ctx, "\n生命周期注解的位置：");
+    writeln!(ctx, "1. 函数参数：fn function<'a>(x: &'a Type)");
+    writeln!(ctx, "2. 函数返回值：fn function<'a>(x: &'a Type) -> &'a Type");
+    writeln!(ctx, "3. 结构体字段：struct Struct<'a> {{ field: &'a Type }}");
+    writeln!(ctx, "4. 泛型参数一起使用：fn function<'a, T>(x: &'a T)");
     
     // 运行结果：
     // 生命周期注解语法
@@ -137,10 +311,10 @@ fn lifetime_annotations() {
 }
 
 // 演示函数签名中的生命周期
-fn function_signatures() {
-    println!("\n--- 函数签名中的生命周期 ---");
+fn function_signatures(ctx: &mut LessonContext) {
+    ctx.section_heading("函数签名中的生命周期");
     
-    println!("在函数签名中使用生命周期注解来表示参数和返回值之间的生命周期关系：");
+    writeln!(ctx, "在函数签名中使用生命周期注解来表示参数和返回值之间的生命周期关系：");
     
     // 示例1：返回两个引用中存活时间较短的那个
     fn longest<'a>(x: &'a str, y: &'a str) -> &'a str {
@@ -170,8 +344,8 @@ fn function_signatures() {
     }
     
     // 示例4：生命周期与泛型结合
-    fn print_ref<'a, T: Display>(x: &'a T) {
-        println!("{}", x);
+    fn print_ref<'a, T: Display>(ctx: &mut LessonContext, x: &'a T) {
+        writeln!(ctx, "{}", x);
     }
     
     // 测试函数
@@ -179,17 +353,17 @@ fn function_signatures() {
     let string2 = String::from("world");
     
     let result = longest(string1.as_str(), string2.as_str());
-    println!("最长的字符串: {}", result);
+    writeln!(ctx, "最长的字符串: {}", result);
     
     let sentence = String::from("hello world");
     let word = first_word(&sentence);
-    println!("第一个单词: {}", word);
+    writeln!(ctx, "第一个单词: {}", word);
     
     let (ref1, ref2) = mix_lifetimes(&string1, &string2);
-    println!("混合引用: {}, {}", ref1, ref2);
+    writeln!(ctx, "混合引用: {}, {}", ref1, ref2);
     
-    print_ref(&42);
-    print_ref(&3.14);
+    print_ref(ctx, &42);
+    print_ref(ctx, &3.14);
     
     // 运行结果：
     // 函数签名中的生命周期
@@ -202,10 +376,10 @@ fn function_signatures() {
 }
 
 // 演示结构体中的生命周期
-fn struct_lifetimes() {
-    println!("\n--- 结构体中的生命周期 ---");
+fn struct_lifetimes(ctx: &mut LessonContext) {
+    ctx.section_heading("结构体中的生命周期");
     
-    println!("当结构体包含引用时，必须为这些引用添加生命周期注解：");
+    writeln!(ctx, "当结构体包含引用时，必须为这些引用添加生命周期注解：");
     
     // 定义一个包含引用的结构体
     struct ImportantExcerpt<'a> {
@@ -218,8 +392,8 @@ fn struct_lifetimes() {
             3
         }
         
-        fn announce_and_return_part(&self, announcement: &str) -> &str {
-            println!("Attention please: {}", announcement);
+        fn announce_and_return_part(&self, ctx: &mut LessonContext, announcement: &str) -> &str {
+            writeln!(ctx, "Attention please: {}", announcement);
             self.part
         }
     }
@@ -238,12 +412,12 @@ fn struct_lifetimes() {
         part: first_sentence,
     };
     
-    println!("重要摘录: {}", i.part);
-    println!("摘录级别: {}", i.level());
+    writeln!(ctx, "重要摘录: {}", i.part);
+    writeln!(ctx, "摘录级别: {}", i.level());
     
     let announcement = "New chapter released!";
-    let part = i.announce_and_return_part(announcement);
-    println!("返回的部分: {}", part);
+    let part = i.announce_and_return_part(ctx, announcement);
+    writeln!(ctx, "返回的部分: {}", part);
     
     // 测试多个生命周期参数的结构体
     let string1 = "hello";
@@ -254,7 +428,7 @@ fn struct_lifetimes() {
         second: &string2,
     };
     
-    println!("多引用结构体: {}, {}", multi.first, multi.second);
+    writeln!(ctx, "多引用结构体: {}, {}", multi.first, multi.second);
     
     // 运行结果：
     // 结构体中的生命周期
@@ -267,10 +441,10 @@ fn struct_lifetimes() {
 }
 
 // 演示方法定义中的生命周期
-fn method_lifetimes() {
-    println!("\n--- 方法定义中的生命周期 ---");
+fn method_lifetimes(ctx: &mut LessonContext) {
+    ctx.section_heading("方法定义中的生命周期");
     
-    println!("在结构体或枚举的方法中使用生命周期注解：");
+    writeln!(ctx, "在结构体或枚举的方法中使用生命周期注解：");
     
     // 定义Descriptor trait（提前定义，以便在Book中使用）
     trait Descriptor {
@@ -332,14 +506,14 @@ fn method_lifetimes() {
         author: author1,
     };
     
-    println!("书籍描述: {}", book1.describe());
+    writeln!(ctx, "书籍描述: {}", book1.describe());
     
     let other_title = "Programming Rust";
     let is_same = book1.compare_title(other_title);
-    println!("标题相同? {}", is_same);
+    writeln!(ctx, "标题相同? {}", is_same);
     
     let longer_title = book1.get_longer_title(other_title);
-    println!("较长的标题: {}", longer_title);
+    writeln!(ctx, "较长的标题: {}", longer_title);
     
     let title2 = "Effective Rust";
     let author2 = "Various Authors";
@@ -350,7 +524,7 @@ fn method_lifetimes() {
     };
     
     let combined = book1.combine_titles(&book2);
-    println!("组合标题: {}", combined);
+    writeln!(ctx, "组合标题: {}", combined);
     
     // 运行结果：
     // 方法定义中的生命周期
@@ -362,14 +536,14 @@ fn method_lifetimes() {
 }
 
 // 演示生命周期省略规则
-fn lifetime_elision() {
-    println!("\n--- 生命周期省略规则 ---");
+fn lifetime_elision(ctx: &mut LessonContext) {
+    ctx.section_heading("生命周期省略规则");
     
-    println!("Rust 有一套生命周期省略规则，可以在某些情况下省略显式的生命周期注解：");
-    println!("1. 每个引用参数获得自己的生命周期参数");
-    println!("2. 如果只有一个输入生命周期参数，它被赋予所有输出生命周期参数");
-    println!("3. 如果有多个输入生命周期参数，但其中一个是 &self 或 &mut self，");
-    println!("   那么 self 的生命周期被赋予所有输出生命周期参数");
+    writeln!(ctx, "Rust 有一套生命周期省略规则，可以在某些情况下省略显式的生命周期注解：");
+    writeln!(ctx, "1. 每个引用参数获得自己的生命周期参数");
+    writeln!(ctx, "2. 如果只有一个输入生命周期参数，它被赋予所有输出生命周期参数");
+    writeln!(ctx, "3. 如果有多个输入生命周期参数，但其中一个是 &self 或 &mut self，");
+    writeln!(ctx, "   那么 self 的生命周期被赋予所有输出生命周期参数");
     
     // 示例：应用省略规则的函数
     
@@ -409,11 +583,11 @@ fn lifetime_elision() {
     // 测试省略规则
     let sentence = String::from("hello world");
     let word = first_word(&sentence);
-    println!("第一个单词: {}", word);
+    writeln!(ctx, "第一个单词: {}", word);
     
     let person = Person { name: String::from("Alice") };
     let name = person.get_name();
-    println!("人名: {}", name);
+    writeln!(ctx, "人名: {}", name);
     
     // 运行结果：
     // 生命周期省略规则
@@ -427,17 +601,17 @@ fn lifetime_elision() {
 }
 
 // 演示静态生命周期
-fn static_lifetimes() {
-    println!("\n--- 静态生命周期 ---");
+fn static_lifetimes(ctx: &mut LessonContext) {
+    ctx.section_heading("静态生命周期");
     
-    println!("'static 是一个特殊的生命周期，表示整个程序的执行期间：");
-    println!("- 字符串字面量默认具有 'static 生命周期");
-    println!("- 可以显式地将变量标记为 'static");
-    println!("- 'static 生命周期的引用必须指向在程序整个生命周期内都有效的数据");
+    writeln!(ctx, "'static 是一个特殊的生命周期，表示整个程序的执行期间：");
+    writeln!(ctx, "- 字符串字面量默认具有 'static 生命周期");
+    writeln!(ctx, "- 可以显式地将变量标记为 'static");
+    writeln!(ctx, "- 'static 生命周期的引用必须指向在程序整个生命周期内都有效的数据");
     
     // 字符串字面量默认具有 'static 生命周期
     let s: &'static str = "I have a static lifetime";
-    println!("静态字符串: {}", s);
+    writeln!(ctx, "静态字符串: {}", s);
     
     // 显式声明静态变量
     static mut COUNTER: i32 = 0;
@@ -446,7 +620,7 @@ fn static_lifetimes() {
     unsafe {
         COUNTER += 1;
         let counter_value = COUNTER;
-        println!("计数器值: {}", counter_value);
+        writeln!(ctx, "计数器值: {}", counter_value);
     }
     
     // 函数返回 'static 生命周期的引用
@@ -466,10 +640,10 @@ fn static_lifetimes() {
     }
     
     let static_str = get_static_string();
-    println!("从函数获取的静态字符串: {}", static_str);
+    writeln!(ctx, "从函数获取的静态字符串: {}", static_str);
     
     let created_static = create_static_string();
-    println!("创建的静态字符串: {}", created_static);
+    writeln!(ctx, "创建的静态字符串: {}", created_static);
     
     // 运行结果：
     // 静态生命周期
@@ -484,20 +658,20 @@ fn static_lifetimes() {
 }
 
 // 演示生命周期约束
-fn lifetime_bounds() {
-    println!("\n--- 生命周期约束 ---");
+fn lifetime_bounds(ctx: &mut LessonContext) {
+    ctx.section_heading("生命周期约束");
     
-    println!("生命周期约束用于指定泛型类型参数与生命周期之间的关系：");
+    writeln!(ctx, "生命周期约束用于指定泛型类型参数与生命周期之间的关系：");
     
     // 定义一个带有生命周期约束的泛型函数
-    fn print_longest<'a, T>(x: &'a T, y: &'a T)
+    fn print_longest<'a, T>(ctx: &mut LessonContext, x: &'a T, y: &'a T)
     where
         T: Display + PartialOrd,
     {
         if x > y {
-            println!("较长的值: {}", x);
+            writeln!(ctx, "较长的值: {}", x);
         } else {
-            println!("较长的值: {}", y);
+            writeln!(ctx, "较长的值: {}", y);
         }
     }
     
@@ -507,34 +681,34 @@ fn lifetime_bounds() {
     }
     
     // 定义一个带有生命周期和特征约束的函数
-    fn process_and_print<'a, T>(item: &'a T)
+    fn process_and_print<'a, T>(ctx: &mut LessonContext, item: &'a T)
     where
         T: Display + 'a,
     {
-        println!("处理并打印: {}", item);
+        writeln!(ctx, "处理并打印: {}", item);
     }
     
     // 测试生命周期约束
     let num1 = 42;
     let num2 = 100;
-    print_longest(&num1, &num2);
+    print_longest(ctx, &num1, &num2);
     
     let float1 = 3.14;
     let float2 = 2.71;
-    print_longest(&float1, &float2);
+    print_longest(ctx, &float1, &float2);
     
     let string1 = String::from("hello");
     let string2 = String::from("world");
-    print_longest(&string1, &string2);
+    print_longest(ctx, &string1, &string2);
     
     // 测试带有生命周期约束的结构体
     let value = 42;
     let container = Container { item: &value };
-    println!("容器中的项目: {}", container.item);
+    writeln!(ctx, "容器中的项目: {}", container.item);
     
     // 测试带有生命周期和特征约束的函数
-    process_and_print(&num1);
-    process_and_print(&string1);
+    process_and_print(ctx, &num1);
+    process_and_print(ctx, &string1);
     
     // 运行结果：
     // 生命周期约束
@@ -544,16 +718,16 @@ fn lifetime_bounds() {
     // 较长的值: world
     // 容器中的项目: 42
     // 处理并打印: 42
-    // 处理并打印: world
+    // 处理并打印: hello
 }
 
 // 演示生命周期子类型化
-fn lifetime_subtyping() {
-    println!("\n--- 生命周期子类型化 ---");
+fn lifetime_subtyping(ctx: &mut LessonContext) {
+    ctx.section_heading("生命周期子类型化");
     
-    println!("生命周期子类型化允许我们表达一个生命周期比另一个生命周期长的关系：");
-    println!("- 如果 'a 是 'b 的子类型，表示 'a 的生命周期至少与 'b 一样长");
-    println!("- 记作 'a: 'b");
+    writeln!(ctx, "生命周期子类型化允许我们表达一个生命周期比另一个生命周期长的关系：");
+    writeln!(ctx, "- 如果 'a 是 'b 的子类型，表示 'a 的生命周期至少与 'b 一样长");
+    writeln!(ctx, "- 记作 'a: 'b");
     
     // 定义一个使用生命周期子类型化的函数
     fn longer_lived<'a, 'b>(x: &'a i32, _: &'b i32) -> &'a i32
@@ -574,14 +748,14 @@ fn lifetime_subtyping() {
     {
         let inner = 200;
         let result = longer_lived(&outer, &inner);
-        println!("结果: {}", result);
+        writeln!(ctx, "结果: {}", result);
         
         // 这里 outer 的生命周期比 inner 长，所以是有效的
         let pair = RefPair {
             first: &outer,
             second: &inner,
         };
-        println!("RefPair: {}, {}", pair.first, pair.second);
+        writeln!(ctx, "RefPair: {}, {}", pair.first, pair.second);
     }
     
     // 生命周期子类型化在 trait 对象中的应用
@@ -600,12 +774,12 @@ fn lifetime_subtyping() {
     }
     
     // 接受任何生命周期的 trait 对象
-    fn print_description<'a>(desc: &'a dyn Descriptor) {
-        println!("描述: {}", desc.describe());
+    fn print_description<'a>(ctx: &mut LessonContext, desc: &'a dyn Descriptor) {
+        writeln!(ctx, "描述: {}", desc.describe());
     }
     
     let description = Description { text: "示例描述" };
-    print_description(&description);
+    print_description(ctx, &description);
     
     // 运行结果：
     // 生命周期子类型化
@@ -618,10 +792,10 @@ fn lifetime_subtyping() {
 }
 
 // 演示高级生命周期用法
-fn advanced_lifetimes() {
-    println!("\n--- 高级生命周期用法 ---");
+fn advanced_lifetimes(ctx: &mut LessonContext) {
+    ctx.section_heading("高级生命周期用法");
     
-    println!("Rust 中的一些高级生命周期用法：");
+    writeln!(ctx, "Rust 中的一些高级生命周期用法：");
     
     // 1. 高阶函数中的生命周期
     fn apply_function<'a, F, R>(f: F) -> R
@@ -647,34 +821,34 @@ fn advanced_lifetimes() {
     // 4. 协变和逆变
     // 注意：这是一个高级概念，这里只做简单演示
     trait Handler<'a> {
-        fn handle(&self, data: &'a str);
+        fn handle(&self, ctx: &mut LessonContext, data: &'a str);
     }
     
     struct SimpleHandler;
     
     impl<'a> Handler<'a> for SimpleHandler {
-        fn handle(&self, data: &'a str) {
-            println!("处理数据: {}", data);
+        fn handle(&self, ctx: &mut LessonContext, data: &'a str) {
+            writeln!(ctx, "处理数据: {}", data);
         }
     }
     
     // 测试高级生命周期用法
     let string = String::from("hello");
     let result = apply_function(|| string.len());
-    println!("字符串长度: {}", result);
+    writeln!(ctx, "字符串长度: {}", result);
     
     let outer = "outer";
     let inner = &outer;
     let nested = nested_references(&inner);
-    println!("嵌套引用: {}", nested);
+    writeln!(ctx, "嵌套引用: {}", nested);
     
     let number = 42;
     let processed = with_lifetime(&number, |n| n * 2);
-    println!("处理后的数字: {}", processed);
+    writeln!(ctx, "处理后的数字: {}", processed);
     
     let handler = SimpleHandler;
     let data = "test data";
-    handler.handle(data);
+    handler.handle(ctx, data);
     
     // 运行结果：
     // 高级生命周期用法
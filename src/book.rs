@@ -0,0 +1,360 @@
+// 导出静态书籍
+// 不想运行程序的读者也可以把课程当作一本书来阅读：
+// `rustlearn book` 把十课内容导出成 mdBook 格式的 Markdown 目录，可以直接用 mdBook 等工具生成网站
+//
+//   book/
+//   ├── book.toml
+//   └── src/
+//       ├── SUMMARY.md            目录，根据课程注册表生成
+//       ├── README.md             简介和课程列表
+//       └── lesson-08/
+//           ├── index.md          课程简介、知识点、小节和练习
+//           └── error_propagation.md   小节的说明、源代码和真实的运行结果
+//
+// 页面之间有上一节、下一节和所在课程的链接，所有内容都来自课程注册表，使用当前的界面语言
+//
+// `rustlearn readme` 用同样的注册表更新 README.md 中的课程列表，
+// 列表位于两行标记注释之间，`--check` 只检查是否需要更新，不修改文件
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::context::LessonContext;
+use crate::i18n::{self, tr, tr_fmt};
+use crate::lesson::{self, Lesson, Section};
+use crate::source;
+
+// 默认的输出目录
+const DEFAULT_DIR: &str = "book";
+
+// 仓库中源代码的地址，README 中的链接指向这里
+const REPOSITORY: &str = "https://github.com/zxymax/rustlearn/blob/main/";
+
+// README 中课程列表的起止标记
+const README_START: &str = "<!-- 以下课程列表由 `rustlearn readme` 根据课程注册表生成，请不要手动修改 -->";
+const README_END: &str = "<!-- 课程列表结束 -->";
+
+// book 子命令，返回进程退出码
+pub fn run_cli(args: &[String]) -> i32 {
+    let dir = match args {
+        [] => PathBuf::from(DEFAULT_DIR),
+        [flag, dir] if flag == "--out" => PathBuf::from(dir),
+        _ => {
+            eprintln!("{}", tr("用法: rustlearn book [--out <目录>]"));
+            return 2;
+        }
+    };
+
+    match export(&dir) {
+        Ok(pages) => {
+            println!("{}", tr_fmt("已导出 {} 个页面到 {}", &[&pages, &dir.display()]));
+            0
+        }
+        Err(error) => {
+            eprintln!("{}", tr_fmt("无法导出书籍: {}", &[&error]));
+            1
+        }
+    }
+}
+
+// 导出整本书，返回生成的页面数
+fn export(dir: &Path) -> io::Result<usize> {
+    let src = dir.join("src");
+    fs::create_dir_all(&src)?;
+
+    fs::write(
+        dir.join("book.toml"),
+        format!(
+            "[book]\ntitle = \"{}\"\nlanguage = \"{}\"\nsrc = \"src\"\n",
+            tr("Rust 学习示例程序"),
+            i18n::current().code()
+        ),
+    )?;
+    fs::write(src.join("SUMMARY.md"), summary())?;
+    fs::write(src.join("README.md"), introduction())?;
+    let mut pages = 1;
+
+    for (index, lesson) in lesson::LESSONS.iter().enumerate() {
+        let lesson_dir = src.join(lesson_dir(*lesson));
+        fs::create_dir_all(&lesson_dir)?;
+        fs::write(lesson_dir.join("index.md"), lesson_page(index))?;
+        pages += 1;
+
+        for section_index in 0..lesson.sections().len() {
+            let section = &lesson.sections()[section_index];
+            fs::write(
+                lesson_dir.join(format!("{}.md", section.id)),
+                section_page(index, section_index),
+            )?;
+            pages += 1;
+        }
+    }
+
+    Ok(pages)
+}
+
+// 目录：简介、每一课以及课程中的每一节
+fn summary() -> String {
+    let mut text = format!("# {}\n\n[{}](README.md)\n\n", tr("目录"), tr("简介"));
+    for lesson in lesson::LESSONS {
+        text.push_str(&format!("- [{}]({}/index.md)\n", lesson_title(*lesson), lesson_dir(*lesson)));
+        for section in lesson.sections() {
+            text.push_str(&format!(
+                "  - [{}]({}/{}.md)\n",
+                tr(section.title),
+                lesson_dir(*lesson),
+                section.id
+            ));
+        }
+    }
+    text
+}
+
+// 简介页面
+fn introduction() -> String {
+    let mut text = format!(
+        "# {}\n\n{}\n\n",
+        tr("Rust 学习示例程序"),
+        tr("本书由 `rustlearn book` 根据课程自动生成，每一节都包含说明、完整的源代码和真实的运行结果。")
+    );
+    for lesson in lesson::LESSONS {
+        text.push_str(&format!(
+            "- [{}]({}/index.md)：{}\n",
+            lesson_title(*lesson),
+            lesson_dir(*lesson),
+            tr(lesson.summary())
+        ));
+    }
+    text
+}
+
+// 课程页面：简介、知识点、小节列表和练习
+fn lesson_page(index: usize) -> String {
+    let lesson = lesson::LESSONS[index];
+    let mut text = format!("# {}\n\n{}\n\n", lesson_title(lesson), tr(lesson.summary()));
+
+    text.push_str(&format!("## {}\n\n", tr("知识点")));
+    for topic in lesson.topics() {
+        text.push_str(&format!("- {}\n", tr(topic)));
+    }
+
+    text.push_str(&format!("\n## {}\n\n", tr("小节")));
+    for (number, section) in lesson.sections().iter().enumerate() {
+        text.push_str(&format!("{}. [{}]({}.md)\n", number + 1, tr(section.title), section.id));
+    }
+
+    if !lesson.exercises().is_empty() {
+        text.push_str(&format!("\n## {}\n\n", tr("练习")));
+        for (number, exercise) in lesson.exercises().iter().enumerate() {
+            text.push_str(&format!(
+                "- **{}.{} {}**：{}\n",
+                lesson.id(),
+                number + 1,
+                tr(exercise.title),
+                tr(exercise.description)
+            ));
+        }
+        text.push_str(&format!(
+            "\n{}\n",
+            tr_fmt("运行 `rustlearn exercise {}.1` 开始练习。", &[&lesson.id()])
+        ));
+    }
+
+    // 前后两课
+    let previous = index.checked_sub(1).map(|index| lesson::LESSONS[index]);
+    let next = lesson::LESSONS.get(index + 1).copied();
+    text.push_str(&pager(
+        previous.map(|lesson| (format!("../{}/index.md", lesson_dir(lesson)), lesson_title(lesson))),
+        None,
+        next.map(|lesson| (format!("../{}/index.md", lesson_dir(lesson)), lesson_title(lesson))),
+    ));
+    text
+}
+
+// 小节页面：说明、源代码和运行结果
+fn section_page(lesson_index: usize, index: usize) -> String {
+    let lesson = lesson::LESSONS[lesson_index];
+    let sections = lesson.sections();
+    let section = &sections[index];
+
+    let mut text = format!(
+        "# {}\n\n> {}\n\n",
+        tr(section.title),
+        tr_fmt(
+            "{} · 第 {}/{} 节",
+            &[&lesson_title(lesson), &(index + 1), &sections.len()]
+        )
+    );
+
+    let explanation = source::explanation(lesson, section);
+    if !explanation.is_empty() {
+        let lines: Vec<String> = explanation.iter().map(|line| tr(line).into_owned()).collect();
+        text.push_str(&lines.join("\n"));
+        text.push_str("\n\n");
+    }
+
+    if let Some(code) = source::plain(lesson, section) {
+        text.push_str(&format!(
+            "## {}\n\n{}\n\n",
+            tr("源代码"),
+            tr_fmt("摘自 `{}`：", &[&lesson.source_path()])
+        ));
+        text.push_str(&code_block("rust", &code));
+    }
+
+    text.push_str(&format!("\n## {}\n\n", tr("运行结果")));
+    text.push_str(&code_block("text", &section_output(section)));
+
+    // 上一节和下一节可以跨越课程
+    let current = lesson.id();
+    let link = |lesson: &dyn Lesson, section: &Section| {
+        let path = if lesson.id() == current {
+            format!("{}.md", section.id)
+        } else {
+            format!("../{}/{}.md", lesson_dir(lesson), section.id)
+        };
+        (path, tr(section.title).into_owned())
+    };
+    let previous = match index.checked_sub(1) {
+        Some(previous) => Some(link(lesson, &sections[previous])),
+        None => lesson_index
+            .checked_sub(1)
+            .map(|previous| lesson::LESSONS[previous])
+            .and_then(|lesson| lesson.sections().last().map(|section| link(lesson, section))),
+    };
+    let next = match sections.get(index + 1) {
+        Some(next) => Some(link(lesson, next)),
+        None => lesson::LESSONS
+            .get(lesson_index + 1)
+            .and_then(|lesson| lesson.sections().first().map(|section| link(*lesson, section))),
+    };
+    text.push_str(&pager(
+        previous,
+        Some((String::from("index.md"), lesson_title(lesson))),
+        next,
+    ));
+    text
+}
+
+// 页面底部的导航：上一页、所在的课程和下一页
+fn pager(previous: Option<(String, String)>, up: Option<(String, String)>, next: Option<(String, String)>) -> String {
+    let mut links = Vec::new();
+    if let Some((path, title)) = previous {
+        links.push(format!("[← {}]({})", title, path));
+    }
+    if let Some((path, title)) = up {
+        links.push(format!("[↑ {}]({})", title, path));
+    }
+    if let Some((path, title)) = next {
+        links.push(format!("[{} →]({})", title, path));
+    }
+    format!("\n---\n\n{}\n", links.join(" | "))
+}
+
+// Markdown 代码块，围栏比内容中最长的连续反引号还要长
+fn code_block(language: &str, code: &str) -> String {
+    let longest = code
+        .split(|c| c != '`')
+        .map(str::len)
+        .max()
+        .unwrap_or(0);
+    let fence = "`".repeat(longest.max(2) + 1);
+    format!("{}{}\n{}\n{}\n", fence, language, code, fence)
+}
+
+// 运行一个小节，返回不带颜色的运行结果，去掉首尾的空行
+pub fn section_output(section: &Section) -> String {
+    let mut buffer = Vec::new();
+    let mut ctx = LessonContext::new(&mut buffer);
+    (section.run)(&mut ctx);
+    // 写入内存缓冲区不会失败
+    let _ = ctx.finish();
+    String::from_utf8_lossy(&buffer).trim_matches('\n').to_string()
+}
+
+pub fn lesson_title(lesson: &dyn Lesson) -> String {
+    tr_fmt("第{}课：{}", &[&lesson.id(), &tr(lesson.title())])
+}
+
+// 课程在书中的目录名，例如 "lesson-08"，按编号排序时顺序不变
+fn lesson_dir(lesson: &dyn Lesson) -> String {
+    format!("lesson-{:0>2}", lesson.id())
+}
+
+// readme 子命令：更新或检查 README 中的课程列表，返回进程退出码
+pub fn readme_cli(args: &[String]) -> i32 {
+    let mut check = false;
+    let mut path = None;
+    for arg in args {
+        match arg.as_str() {
+            "--check" => check = true,
+            other if other.starts_with('-') || path.is_some() => {
+                eprintln!("{}", tr("用法: rustlearn readme [--check] [<README 路径>]"));
+                return 2;
+            }
+            other => path = Some(PathBuf::from(other)),
+        }
+    }
+    let path = path.unwrap_or_else(|| PathBuf::from("README.md"));
+
+    let content = match fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(error) => {
+            eprintln!("{}", tr_fmt("无法读取 {}: {}", &[&path.display(), &error]));
+            return 1;
+        }
+    };
+
+    let Some(updated) = update_readme(&content) else {
+        eprintln!("{}", tr_fmt("{} 中没有课程列表的标记，请先加上这两行：", &[&path.display()]));
+        eprintln!("{}\n{}", README_START, README_END);
+        return 1;
+    };
+
+    if updated == content {
+        println!("{}", tr_fmt("{} 中的课程列表已是最新", &[&path.display()]));
+        return 0;
+    }
+    if check {
+        println!("{}", tr_fmt("{} 中的课程列表需要更新，请运行 `rustlearn readme`", &[&path.display()]));
+        return 1;
+    }
+
+    match fs::write(&path, updated) {
+        Ok(()) => {
+            println!("{}", tr_fmt("已更新 {} 中的课程列表", &[&path.display()]));
+            0
+        }
+        Err(error) => {
+            eprintln!("{}", tr_fmt("无法写入 {}: {}", &[&path.display(), &error]));
+            1
+        }
+    }
+}
+
+// 替换 README 中两行标记之间的内容，找不到标记时返回 None
+fn update_readme(content: &str) -> Option<String> {
+    let start = content.find(README_START)? + README_START.len();
+    let end = start + content[start..].find(README_END)?;
+    Some(format!("{}\n{}{}", &content[..start], readme_lessons(), &content[end..]))
+}
+
+// README 中的课程列表：课程、源文件和知识点
+// README 本身是中文的，所以这里不翻译
+fn readme_lessons() -> String {
+    let mut text = String::from("\n| 课程 | 源文件 | 知识点 |\n| --- | --- | --- |\n");
+    for lesson in lesson::LESSONS {
+        text.push_str(&format!(
+            "| {}. {} | [{}]({}{}) | {} |\n",
+            lesson.id(),
+            lesson.title(),
+            lesson.source_path(),
+            REPOSITORY,
+            lesson.source_path(),
+            lesson.topics().join("；")
+        ));
+    }
+    text.push('\n');
+    text
+}
@@ -0,0 +1,1722 @@
+// 借用检查模拟器
+// 课程中的代码都必须能通过编译，借用冲突只能写在注释里。这里实现了一个很小的教学语言，语法是 Rust 的一个子集：
+// let、赋值、&、&mut、*、函数和方法调用、println! 这样的宏，以及用 { } 表示的作用域（也可以写在 fn main() { } 中）
+//
+// 模拟器逐条执行语句，记录每个值的所有权和每一次借用。和编译器一样，借用从创建开始，
+// 一直持续到持有它的引用最后一次被使用为止（非词法生命周期），然后：
+// 1. 画出每个引用的生命周期时间线
+// 2. 列出所有权的转移
+// 3. 按照 rustc 的格式报告冲突，错误代码和编译器一致，例如 E0382、E0499、E0502、E0505、E0597
+//
+// 模拟器不知道变量的具体类型，而是根据值的来源判断：
+// - 数字、字符串字面量以及算术和比较的结果实现了 Copy
+// - String::from(..)、Vec::new()、vec![..]、format!(..)、clone() 创建的值，以及函数调用的返回值没有实现 Copy
+// - first()、get()、iter() 等方法返回借用了接收者的引用；push()、insert() 会把参数中的引用保存到接收者中
+//
+// `rustlearn borrow --check` 检查内置的示例：模拟器报告的错误代码和本地 rustc 编译同一段代码得到的一致
+
+use std::fs;
+use std::io::{self, Read};
+
+use crate::cli;
+use crate::compile_error;
+use crate::context::Style;
+use crate::glossary::Place;
+use crate::i18n::{tr, tr_fmt};
+use crate::screen;
+use crate::theme;
+
+// 内置的示例
+pub struct Example {
+    // 名称，用于 --example 参数
+    pub name: &'static str,
+    pub title: &'static str,
+    // 模拟器和 rustc 都应该报告的错误代码，能够通过检查的示例为空
+    pub codes: &'static [&'static str],
+    // 讲解相关内容的小节：课程编号和小节标识
+    pub sections: &'static [(&'static str, &'static str)],
+    pub code: &'static str,
+}
+
+pub static EXAMPLES: &[Example] = &[
+    Example {
+        name: "nll",
+        title: "引用在最后一次使用之后就不再借用",
+        codes: &[],
+        sections: &[("10", "lifetimes_basics")],
+        code: r#"let mut s = String::from("hello");
+let r1 = &s;
+let r2 = &s;
+println!("{} and {}", r1, r2);
+// r1 和 r2 之后不再使用，它们的借用已经结束
+let r3 = &mut s;
+r3.push_str(", world");
+println!("{}", r3);"#,
+    },
+    Example {
+        name: "push_while_borrowed",
+        title: "持有元素的引用时修改 Vector",
+        codes: &["E0502"],
+        sections: &[("6", "ownership_issues")],
+        code: r#"let mut v = vec![1, 2, 3, 4, 5];
+let first = &v[0];
+v.push(6);
+println!("第一个元素是 {}", first);"#,
+    },
+    Example {
+        name: "two_mutable_borrows",
+        title: "同时存在两个可变借用",
+        codes: &["E0499"],
+        sections: &[("6", "ownership_issues")],
+        code: r#"let mut s = String::from("hello");
+let r1 = &mut s;
+let r2 = &mut s;
+r1.push_str(" world");
+println!("{}", r2);"#,
+    },
+    Example {
+        name: "references_in_vec",
+        title: "Vector 中保存的引用让原来的值一直被借用",
+        codes: &["E0502"],
+        sections: &[("6", "ownership_issues")],
+        code: r#"let mut s3 = String::from("rust");
+let mut v_refs = Vec::new();
+v_refs.push(&s3);
+s3.push_str("acean");
+println!("{:?}", v_refs);"#,
+    },
+    Example {
+        name: "move_while_borrowed",
+        title: "值还被借用着的时候就被移动了",
+        codes: &["E0505"],
+        sections: &[("6", "ownership_issues"), ("10", "lifetimes_basics")],
+        code: r#"let v = vec![String::from("rust")];
+let first = &v[0];
+let moved = v;
+println!("{}", first);"#,
+    },
+    Example {
+        name: "use_after_move",
+        title: "使用已经被移动的值",
+        codes: &["E0382"],
+        sections: &[("6", "ownership_issues")],
+        code: r#"let s1 = String::from("hello");
+let s2 = s1;
+let mut v = Vec::new();
+v.push(s2);
+println!("{} {:?}", s1, v);"#,
+    },
+    Example {
+        name: "dangling_reference",
+        title: "引用比它指向的值活得更久",
+        codes: &["E0597"],
+        sections: &[("10", "lifetimes_basics")],
+        code: r#"let r;
+{
+    let x = 5;
+    r = &x;
+}
+println!("r = {}", r);"#,
+    },
+    Example {
+        name: "assign_while_borrowed",
+        title: "值被借用时给它赋值",
+        codes: &["E0506"],
+        sections: &[("10", "lifetimes_basics")],
+        code: r#"let mut count = 1;
+let r = &count;
+count = 2;
+println!("{}", r);"#,
+    },
+    Example {
+        name: "missing_mut",
+        title: "修改没有声明为可变的变量",
+        codes: &["E0596"],
+        sections: &[("6", "vector_collection")],
+        code: r#"let v = Vec::new();
+v.push(1);
+println!("{:?}", v);"#,
+    },
+];
+
+// 代码中的一个位置：行号从 1 开始，列是从行首开始的显示宽度，中日韩文字占两列
+#[derive(Clone, Copy)]
+struct Span {
+    line: usize,
+    start: usize,
+    end: usize,
+}
+
+impl Span {
+    // 从这个位置开始到 other 结束，跨行时只标注第一行
+    fn to(self, other: Span) -> Span {
+        if other.line == self.line { Span { end: other.end, ..self } } else { Span { end: usize::MAX, ..self } }
+    }
+}
+
+// 词法单元
+#[derive(Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    // 数字和字符字面量
+    Number,
+    // 字符串字面量的内容
+    Str(String),
+    Punct(&'static str),
+}
+
+struct Spanned {
+    token: Token,
+    span: Span,
+}
+
+// 标点符号，多个字符的写在前面
+const PUNCTUATION: [&str; 25] = [
+    "::", "==", "!=", "<=", ">=", "..", "&", "*", "=", ";", "(", ")", "{", "}", "[", "]", ",", ".", "!", ":", "<", ">",
+    "+", "-", "/",
+];
+
+// 一段文字的显示宽度
+fn width(text: &str) -> usize {
+    text.chars().map(screen::char_width).sum()
+}
+
+// 按照显示宽度截取一行中的一段
+fn slice(line: &str, start: usize, end: usize) -> String {
+    let mut column = 0;
+    let mut result = String::new();
+    for c in line.chars() {
+        if column >= start && column < end {
+            result.push(c);
+        }
+        column += screen::char_width(c);
+    }
+    result
+}
+
+// 第几行的错误
+fn at_line(line: usize, message: &str) -> String {
+    tr_fmt("第 {} 行：{}", &[&line, &message])
+}
+
+// 把代码切分成词法单元，// 之后的注释被忽略
+fn tokenize(code: &str) -> Result<Vec<Spanned>, String> {
+    let mut tokens = Vec::new();
+    for (index, line) in code.lines().enumerate() {
+        let chars: Vec<char> = line.chars().collect();
+        let mut i = 0;
+        let mut column = 0;
+        while i < chars.len() {
+            let c = chars[i];
+            if c.is_whitespace() {
+                column += screen::char_width(c);
+                i += 1;
+                continue;
+            }
+            if c == '/' && chars.get(i + 1) == Some(&'/') {
+                break;
+            }
+
+            let start = i;
+            let token = if c.is_alphabetic() || c == '_' {
+                while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                    i += 1;
+                }
+                Token::Ident(chars[start..i].iter().collect())
+            } else if c.is_ascii_digit() {
+                while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                    i += 1;
+                }
+                // 小数
+                if chars.get(i) == Some(&'.') && chars.get(i + 1).is_some_and(char::is_ascii_digit) {
+                    i += 1;
+                    while i < chars.len() && chars[i].is_alphanumeric() {
+                        i += 1;
+                    }
+                }
+                Token::Number
+            } else if c == '"' {
+                i += 1;
+                while i < chars.len() && chars[i] != '"' {
+                    i += if chars[i] == '\\' { 2 } else { 1 };
+                }
+                if i >= chars.len() {
+                    return Err(at_line(index + 1, &tr("字符串没有结束")));
+                }
+                i += 1;
+                Token::Str(chars[start + 1..i - 1].iter().collect())
+            } else if c == '\'' {
+                // 字符字面量，例如 'a' 或 '\n'
+                let close = (start + 2..chars.len().min(start + 4)).find(|&end| chars[end] == '\'');
+                let Some(close) = close else {
+                    return Err(at_line(index + 1, &tr("暂不支持生命周期标注")));
+                };
+                i = close + 1;
+                Token::Number
+            } else {
+                let rest: String = chars[i..].iter().collect();
+                let punct = PUNCTUATION
+                    .iter()
+                    .find(|punct| rest.starts_with(**punct))
+                    .ok_or_else(|| at_line(index + 1, &tr_fmt("无法识别的字符：{}", &[&c])))?;
+                i += punct.chars().count();
+                Token::Punct(punct)
+            };
+
+            let text: String = chars[start..i].iter().collect();
+            let span = Span { line: index + 1, start: column, end: column + width(&text) };
+            column = span.end;
+            tokens.push(Spanned { token, span });
+        }
+    }
+    Ok(tokens)
+}
+
+// 表达式
+enum Expr {
+    // 数字、字符和字符串字面量
+    Literal(Span),
+    Var(String, Span),
+    // *r
+    Deref(String, Span),
+    // &x、&mut x、&v[0]、&*r
+    Borrow { mutable: bool, place: Box<Expr>, span: Span },
+    // v[0]、&s[1..3] 中的 s[1..3]
+    Index { base: String, index: Vec<Expr>, span: Span },
+    // drop(x)、String::from("hi")
+    Call { name: String, args: Vec<Expr>, span: Span },
+    // println!(..)、vec![..]，格式字符串中的 {name} 也算作参数
+    Macro { name: String, args: Vec<Expr>, span: Span },
+    Method { receiver: Box<Expr>, name: String, args: Vec<Expr>, span: Span },
+    Binary { left: Box<Expr>, right: Box<Expr>, add: bool, span: Span },
+}
+
+impl Expr {
+    fn span(&self) -> Span {
+        match self {
+            Expr::Literal(span) | Expr::Var(_, span) | Expr::Deref(_, span) => *span,
+            Expr::Borrow { span, .. }
+            | Expr::Index { span, .. }
+            | Expr::Call { span, .. }
+            | Expr::Macro { span, .. }
+            | Expr::Method { span, .. }
+            | Expr::Binary { span, .. } => *span,
+        }
+    }
+}
+
+// 语句
+enum Statement {
+    Let { name: String, mutable: bool, span: Span, init: Option<Expr> },
+    // x = ..、*r = ..、v[0] = ..，compound 表示 += 这样的复合赋值
+    Assign { target: Expr, value: Expr, compound: bool, span: Span },
+    Expr(Expr),
+    // 作用域的开始和结束
+    Open,
+    Close(Span),
+}
+
+// 输出时会读取参数的宏，格式字符串中的 {name} 也会被读取
+const FORMAT_MACROS: [&str; 8] = ["println", "print", "eprintln", "eprint", "format", "panic", "assert", "assert_eq"];
+
+// 代码的解析器
+struct Parser {
+    tokens: Vec<Spanned>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek_at(&self, offset: usize) -> Option<&Token> {
+        self.tokens.get(self.pos + offset).map(|spanned| &spanned.token)
+    }
+
+    fn is_at(&self, offset: usize, text: &str) -> bool {
+        match self.peek_at(offset) {
+            Some(Token::Punct(punct)) => *punct == text,
+            Some(Token::Ident(word)) => word == text,
+            _ => false,
+        }
+    }
+
+    fn is(&self, text: &str) -> bool {
+        self.is_at(0, text)
+    }
+
+    fn eat(&mut self, text: &str) -> bool {
+        let found = self.is(text);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    // 当前词法单元的位置
+    fn span(&self) -> Span {
+        match self.tokens.get(self.pos).or(self.tokens.last()) {
+            Some(spanned) => spanned.span,
+            None => Span { line: 1, start: 0, end: 0 },
+        }
+    }
+
+    // 上一个词法单元的位置
+    fn previous(&self) -> Span {
+        self.tokens[self.pos.saturating_sub(1)].span
+    }
+
+    fn unexpected(&self, expected: &str) -> String {
+        match self.tokens.get(self.pos) {
+            Some(spanned) => {
+                let found = match &spanned.token {
+                    Token::Ident(word) => format!("\"{}\"", word),
+                    Token::Number => tr("数字").into_owned(),
+                    Token::Str(_) => tr("字符串").into_owned(),
+                    Token::Punct(punct) => format!("\"{}\"", punct),
+                };
+                at_line(spanned.span.line, &tr_fmt("应该是 {}，但是遇到了 {}", &[&expected, &found]))
+            }
+            None => at_line(self.span().line, &tr_fmt("应该是 {}，但是代码已经结束了", &[&expected])),
+        }
+    }
+
+    fn expect(&mut self, text: &str) -> Result<Span, String> {
+        let span = self.span();
+        if self.eat(text) { Ok(span) } else { Err(self.unexpected(&format!("\"{}\"", text))) }
+    }
+
+    fn ident(&mut self) -> Result<(String, Span), String> {
+        match self.peek_at(0) {
+            Some(Token::Ident(name)) => {
+                let name = name.clone();
+                let span = self.span();
+                self.pos += 1;
+                Ok((name, span))
+            }
+            _ => Err(self.unexpected(&tr("名称"))),
+        }
+    }
+
+    fn program(&mut self) -> Result<Vec<Statement>, String> {
+        let mut statements = Vec::new();
+        let mut depth = 0;
+        while self.pos < self.tokens.len() {
+            let statement = self.statement()?;
+            match statement {
+                Statement::Open => depth += 1,
+                Statement::Close(span) if depth == 0 => return Err(at_line(span.line, &tr("多余的 }"))),
+                Statement::Close(_) => depth -= 1,
+                _ => {}
+            }
+            statements.push(statement);
+        }
+        if depth > 0 {
+            return Err(at_line(self.span().line, &tr("缺少 }")));
+        }
+        Ok(statements)
+    }
+
+    fn statement(&mut self) -> Result<Statement, String> {
+        if self.eat("{") {
+            return Ok(Statement::Open);
+        }
+        if self.is("}") {
+            let span = self.span();
+            self.pos += 1;
+            return Ok(Statement::Close(span));
+        }
+
+        // fn main() { 看作一个作用域
+        if self.is("fn") {
+            self.pos += 1;
+            self.ident()?;
+            self.expect("(")?;
+            self.expect(")")?;
+            self.expect("{")?;
+            return Ok(Statement::Open);
+        }
+
+        if self.eat("let") {
+            let mutable = self.eat("mut");
+            let (name, span) = self.ident()?;
+            // 类型标注不参与检查
+            if self.eat(":") {
+                while !self.is("=") && !self.is(";") && self.pos < self.tokens.len() {
+                    self.pos += 1;
+                }
+            }
+            let init = if self.eat("=") { Some(self.expr()?) } else { None };
+            self.expect(";")?;
+            return Ok(Statement::Let { name, mutable, span, init });
+        }
+
+        let expr = self.expr()?;
+        let compound = ["+", "-", "*", "/"].iter().any(|op| self.is(op)) && self.is_at(1, "=");
+        if compound {
+            self.pos += 1;
+        }
+        if self.eat("=") {
+            if !matches!(expr, Expr::Var(..) | Expr::Deref(..) | Expr::Index { .. }) {
+                return Err(at_line(expr.span().line, &tr("只能给变量、*引用或者 v[i] 赋值")));
+            }
+            let value = self.expr()?;
+            let span = expr.span().to(value.span());
+            self.expect(";")?;
+            return Ok(Statement::Assign { target: expr, value, compound, span });
+        }
+        self.expect(";")?;
+        Ok(Statement::Expr(expr))
+    }
+
+    fn expr(&mut self) -> Result<Expr, String> {
+        let mut left = self.unary()?;
+        loop {
+            let op = ["+", "-", "*", "/", "==", "!=", "<=", ">=", "<", ">"].into_iter().find(|op| self.is(op));
+            // += 这样的复合赋值由 statement() 处理
+            let Some(op) = op.filter(|_| !self.is_at(1, "=")) else {
+                return Ok(left);
+            };
+            self.pos += 1;
+            let right = self.unary()?;
+            let span = left.span().to(right.span());
+            left = Expr::Binary { left: Box::new(left), right: Box::new(right), add: op == "+", span };
+        }
+    }
+
+    fn unary(&mut self) -> Result<Expr, String> {
+        let start = self.span();
+        if self.eat("&") {
+            let mutable = self.eat("mut");
+            let place = self.unary()?;
+            let span = start.to(place.span());
+            return Ok(Expr::Borrow { mutable, place: Box::new(place), span });
+        }
+        if self.eat("*") {
+            let (name, span) = self.ident()?;
+            return Ok(Expr::Deref(name, start.to(span)));
+        }
+        self.postfix()
+    }
+
+    fn postfix(&mut self) -> Result<Expr, String> {
+        let mut expr = self.primary()?;
+        loop {
+            if self.eat(".") {
+                let (name, _) = self.ident()?;
+                if !self.is("(") {
+                    return Err(at_line(self.previous().line, &tr("暂不支持字段访问，只能调用方法")));
+                }
+                let args = self.args("(", ")")?;
+                let span = expr.span().to(self.previous());
+                expr = Expr::Method { receiver: Box::new(expr), name, args, span };
+            } else if self.is("[") {
+                let Expr::Var(base, span) = expr else {
+                    return Err(at_line(self.span().line, &tr("只能对变量使用索引")));
+                };
+                self.pos += 1;
+                let mut index = Vec::new();
+                while !self.eat("]") {
+                    if !self.eat("..") {
+                        index.push(self.expr()?);
+                    }
+                    if self.pos >= self.tokens.len() {
+                        return Err(self.unexpected("\"]\""));
+                    }
+                }
+                expr = Expr::Index { base, index, span: span.to(self.previous()) };
+            } else {
+                return Ok(expr);
+            }
+        }
+    }
+
+    fn primary(&mut self) -> Result<Expr, String> {
+        let start = self.span();
+        match self.peek_at(0).cloned() {
+            Some(Token::Number | Token::Str(_)) => {
+                self.pos += 1;
+                Ok(Expr::Literal(start))
+            }
+            Some(Token::Punct("(")) => {
+                self.pos += 1;
+                let expr = self.expr()?;
+                self.expect(")")?;
+                Ok(expr)
+            }
+            Some(Token::Ident(_)) => {
+                let (mut name, _) = self.ident()?;
+                if self.eat("!") {
+                    let close = if self.is("[") { "]" } else { ")" };
+                    let open = if close == "]" { "[" } else { "(" };
+                    let args = self.macro_args(&name, open, close)?;
+                    return Ok(Expr::Macro { name, args, span: start.to(self.previous()) });
+                }
+                while self.eat("::") {
+                    let (segment, _) = self.ident()?;
+                    name = format!("{}::{}", name, segment);
+                }
+                if self.is("(") {
+                    let args = self.args("(", ")")?;
+                    return Ok(Expr::Call { name, args, span: start.to(self.previous()) });
+                }
+                if name.contains("::") {
+                    return Err(self.unexpected("\"(\""));
+                }
+                Ok(Expr::Var(name, start))
+            }
+            _ => Err(self.unexpected(&tr("表达式"))),
+        }
+    }
+
+    // 括号中用逗号分隔的参数
+    fn args(&mut self, open: &str, close: &str) -> Result<Vec<Expr>, String> {
+        self.expect(open)?;
+        let mut args = Vec::new();
+        while !self.eat(close) {
+            args.push(self.expr()?);
+            if !self.eat(",") {
+                self.expect(close)?;
+                break;
+            }
+        }
+        Ok(args)
+    }
+
+    // 宏的参数，输出类的宏会把格式字符串中的 {name} 当作读取变量 name
+    fn macro_args(&mut self, name: &str, open: &str, close: &str) -> Result<Vec<Expr>, String> {
+        self.expect(open)?;
+        let mut args = Vec::new();
+        while !self.eat(close) {
+            match self.peek_at(0).cloned() {
+                Some(Token::Str(text)) if FORMAT_MACROS.contains(&name) => {
+                    let span = self.span();
+                    self.pos += 1;
+                    args.extend(captures(&text).into_iter().map(|(name, offset)| {
+                        // 标注格式字符串中的变量名，字符串的内容从引号之后开始
+                        let start = span.start + 1 + offset;
+                        let span = Span { start, end: start + width(&name), ..span };
+                        Expr::Var(name, span)
+                    }));
+                }
+                _ => args.push(self.expr()?),
+            }
+            // vec![0; 5] 中的分号
+            if !self.eat(",") && !self.eat(";") {
+                self.expect(close)?;
+                break;
+            }
+        }
+        Ok(args)
+    }
+}
+
+// 格式字符串中直接写出的变量名，例如 "{name}" 和 "{count:>3}"，以及变量名在字符串中的显示宽度偏移
+fn captures(format: &str) -> Vec<(String, usize)> {
+    let mut names: Vec<(String, usize)> = Vec::new();
+    let mut offset = 0;
+    let mut rest = format;
+    while let Some(open) = rest.find('{') {
+        offset += width(&rest[..open]) + 1;
+        rest = &rest[open + 1..];
+        if let Some(stripped) = rest.strip_prefix('{') {
+            offset += 1;
+            rest = stripped;
+            continue;
+        }
+        let end = rest.find(['}', ':']).unwrap_or(rest.len());
+        let name = &rest[..end];
+        if name.starts_with(|c: char| c.is_alphabetic() || c == '_') && !names.iter().any(|(known, _)| known == name) {
+            names.push((name.to_string(), offset));
+        }
+    }
+    names
+}
+
+// 解析代码
+fn parse(code: &str) -> Result<Vec<Statement>, String> {
+    let tokens = tokenize(code)?;
+    Parser { tokens, pos: 0 }.program()
+}
+
+// 值的种类
+#[derive(Clone, Copy, PartialEq)]
+enum Kind {
+    // 实现了 Copy 的值
+    Copy,
+    // 没有实现 Copy、拥有所有权的值，例如 String 和 Vec
+    Owned,
+    // 共享引用，也实现了 Copy
+    Shared,
+    // 可变引用，赋值和传参时会被移动
+    Mutable,
+}
+
+// 一个值，loans 是它直接或间接持有的借用
+#[derive(Clone)]
+struct Value {
+    kind: Kind,
+    loans: Vec<usize>,
+    // 引用指向的值没有实现 Copy，不能通过 * 移出
+    owned_target: bool,
+}
+
+impl Value {
+    fn new(kind: Kind) -> Value {
+        Value { kind, loans: Vec::new(), owned_target: false }
+    }
+
+    fn is_copy(&self) -> bool {
+        matches!(self.kind, Kind::Copy | Kind::Shared)
+    }
+
+    fn is_reference(&self) -> bool {
+        matches!(self.kind, Kind::Shared | Kind::Mutable)
+    }
+}
+
+// 变量的状态
+#[derive(Clone, Copy)]
+enum State {
+    Uninit,
+    Init,
+    // 值在这里被移动走了
+    Moved(Span),
+}
+
+struct Variable {
+    name: String,
+    mutable: bool,
+    depth: usize,
+    // 声明的位置
+    declared: Span,
+    // 第一次赋值的位置
+    assigned: Option<Span>,
+    state: State,
+    value: Value,
+    // 离开作用域之后不能再使用
+    alive: bool,
+}
+
+// 一次借用
+struct Loan {
+    // 被借用的变量
+    var: usize,
+    mutable: bool,
+    seq: usize,
+    span: Span,
+    // 借用的代码，例如 "&v[0]"
+    text: String,
+    // 持有这个借用的变量，用作时间线的列名
+    holder: Option<String>,
+    // 最后一次使用
+    last_use: Option<(usize, Span)>,
+}
+
+// 对变量的一次访问
+#[derive(Clone, Copy, PartialEq)]
+enum AccessKind {
+    // 读取，borrow 表示通过共享借用读取（例如 println! 读取 String），否则是复制
+    Read { borrow: bool },
+    // 可变借用
+    Write,
+    Move,
+    Assign,
+    // 离开作用域
+    Drop,
+}
+
+struct Access {
+    var: usize,
+    kind: AccessKind,
+    seq: usize,
+    span: Span,
+}
+
+// 所有权的一次转移
+struct Move {
+    var: usize,
+    span: Span,
+    // 新的所有者，例如变量名或者 drop()
+    target: Option<String>,
+}
+
+// 诊断信息中的一处标注
+struct Label {
+    span: Span,
+    primary: bool,
+    text: String,
+}
+
+// 一条错误
+struct Diagnostic {
+    code: &'static str,
+    message: String,
+    labels: Vec<Label>,
+    notes: Vec<String>,
+    // 用于按照发生的顺序排列
+    seq: usize,
+}
+
+impl Diagnostic {
+    fn new(code: &'static str, message: String, seq: usize) -> Diagnostic {
+        Diagnostic { code, message, labels: Vec::new(), notes: Vec::new(), seq }
+    }
+
+    fn primary(mut self, span: Span, text: String) -> Diagnostic {
+        self.labels.push(Label { span, primary: true, text });
+        self
+    }
+
+    fn secondary(mut self, span: Span, text: String) -> Diagnostic {
+        self.labels.push(Label { span, primary: false, text });
+        self
+    }
+
+    fn note(mut self, text: String) -> Diagnostic {
+        self.notes.push(text);
+        self
+    }
+
+    // 主要标注所在的行
+    fn line(&self) -> usize {
+        self.labels.iter().find(|label| label.primary).map_or(0, |label| label.span.line)
+    }
+}
+
+// 方法对接收者的使用方式
+#[derive(Clone, Copy, PartialEq)]
+enum Receiver {
+    // &mut self，参数中的引用会被保存到接收者中
+    Mutating,
+    // &self，返回借用了接收者的引用
+    SharedRef,
+    // &mut self，返回借用了接收者的可变引用
+    MutRef,
+    // self
+    Consuming,
+    // &self，返回新创建的值
+    Owned,
+    // &self，返回实现了 Copy 的值，例如 len()
+    Plain,
+}
+
+fn receiver(method: &str) -> Receiver {
+    match method {
+        "push" | "push_str" | "insert" | "extend" | "append" | "pop" | "remove" | "clear" | "truncate" | "sort"
+        | "reverse" | "retain" | "dedup" => Receiver::Mutating,
+        "first" | "last" | "get" | "iter" | "as_str" | "as_slice" | "as_ref" | "keys" | "values" | "chars" | "bytes"
+        | "lines" | "trim" | "split_whitespace" => Receiver::SharedRef,
+        "first_mut" | "last_mut" | "get_mut" | "iter_mut" | "as_mut" | "values_mut" | "as_mut_str" => Receiver::MutRef,
+        "clone" | "to_string" | "to_owned" | "to_vec" | "to_uppercase" | "to_lowercase" => Receiver::Owned,
+        // 迭代器适配器同样按值接收迭代器，返回的值仍然借用着被迭代的集合
+        "unwrap" | "expect" | "max" | "min" | "find" | "nth" | "map" | "filter" | "rev" | "skip" | "take"
+        | "enumerate" | "zip" | "peekable" => Receiver::Consuming,
+        name if name.starts_with("into_") => Receiver::Consuming,
+        _ => Receiver::Plain,
+    }
+}
+
+// 模拟执行的结果
+struct Simulation {
+    variables: Vec<Variable>,
+    depth: usize,
+    loans: Vec<Loan>,
+    accesses: Vec<Access>,
+    moves: Vec<Move>,
+    diagnostics: Vec<Diagnostic>,
+    seq: usize,
+    // 代码的每一行，用于截取借用的代码
+    lines: Vec<String>,
+}
+
+impl Simulation {
+    fn run(code: &str, statements: &[Statement]) -> Simulation {
+        let mut simulation = Simulation {
+            variables: Vec::new(),
+            depth: 0,
+            loans: Vec::new(),
+            accesses: Vec::new(),
+            moves: Vec::new(),
+            diagnostics: Vec::new(),
+            seq: 0,
+            lines: code.lines().map(str::to_string).collect(),
+        };
+        for statement in statements {
+            simulation.statement(statement);
+        }
+        simulation.conflicts();
+        simulation.diagnostics.sort_by_key(|diagnostic| diagnostic.seq);
+        simulation
+    }
+
+    fn next(&mut self) -> usize {
+        self.seq += 1;
+        self.seq
+    }
+
+    fn text(&self, span: Span) -> String {
+        self.lines.get(span.line - 1).map(|line| slice(line, span.start, span.end)).unwrap_or_default()
+    }
+
+    // 查找作用域中的变量，找不到时报告 E0425
+    fn lookup(&mut self, name: &str, span: Span) -> Option<usize> {
+        let found = self.variables.iter().rposition(|var| var.alive && var.name == name);
+        if found.is_none() {
+            let seq = self.next();
+            self.diagnostics.push(
+                Diagnostic::new("E0425", tr_fmt("在这个作用域中找不到变量 `{}`", &[&name]), seq)
+                    .primary(span, tr("没有找到这个变量").into_owned()),
+            );
+        }
+        found
+    }
+
+    // 值被使用，它持有的借用至少要持续到这里
+    fn touch(&mut self, value: &Value, seq: usize, span: Span) {
+        for &loan in &value.loans {
+            let loan = &mut self.loans[loan];
+            if loan.last_use.is_none_or(|(last, _)| last < seq) {
+                loan.last_use = Some((seq, span));
+            }
+        }
+    }
+
+    // 参数在调用时被使用；直接写出的变量在读取时已经记录过了，这样标注的是变量本身而不是整个调用
+    fn touch_args(&mut self, args: &[Expr], values: &[Value], span: Span) {
+        let seq = self.next();
+        for (arg, value) in args.iter().zip(values) {
+            if !matches!(arg, Expr::Var(..)) {
+                self.touch(value, seq, span);
+            }
+        }
+    }
+
+    // 使用变量之前检查它已经初始化，并且没有被移动走
+    fn check_state(&mut self, var: usize, span: Span, borrow: bool) {
+        let name = self.variables[var].name.clone();
+        let seq = self.next();
+        match self.variables[var].state {
+            State::Init => {}
+            State::Uninit => self.diagnostics.push(
+                Diagnostic::new("E0381", tr_fmt("使用了没有初始化的变量 `{}`", &[&name]), seq)
+                    .secondary(self.variables[var].declared, tr("变量在这里声明，但是没有初始化").into_owned())
+                    .primary(span, tr_fmt("`{}` 在这里被使用，但是还没有初始化", &[&name])),
+            ),
+            State::Moved(moved) => {
+                let (message, label) = if borrow {
+                    (tr_fmt("借用了已经被移动的值 `{}`", &[&name]), tr("值在移动之后在这里被借用"))
+                } else {
+                    (tr_fmt("使用了已经被移动的值 `{}`", &[&name]), tr("值在移动之后在这里被使用"))
+                };
+                self.diagnostics.push(
+                    Diagnostic::new("E0382", message, seq)
+                        .secondary(moved, tr("值被移动到这里").into_owned())
+                        .primary(span, label.into_owned())
+                        .note(tr_fmt("移动发生是因为 `{}` 的类型没有实现 Copy trait", &[&name])),
+                );
+            }
+        }
+    }
+
+    fn access(&mut self, var: usize, kind: AccessKind, span: Span) -> usize {
+        let seq = self.next();
+        self.accesses.push(Access { var, kind, seq, span });
+        seq
+    }
+
+    // 读取变量：实现了 Copy 的值被复制，其余的值通过共享借用读取
+    fn read(&mut self, var: usize, span: Span) -> Value {
+        let borrow = !self.variables[var].value.is_copy();
+        self.check_state(var, span, borrow);
+        let seq = self.access(var, AccessKind::Read { borrow }, span);
+        let value = self.variables[var].value.clone();
+        self.touch(&value, seq, span);
+        value
+    }
+
+    // 检查变量可以被可变借用
+    fn check_mutable(&mut self, var: usize, span: Span) {
+        let variable = &self.variables[var];
+        if variable.mutable {
+            return;
+        }
+        let name = variable.name.clone();
+        let declared = variable.declared;
+        let seq = self.next();
+        self.diagnostics.push(
+            Diagnostic::new("E0596", tr_fmt("不能把 `{}` 借用为可变的，因为它没有声明为可变的", &[&name]), seq)
+                .secondary(declared, tr_fmt("考虑把它改成可变的：`mut {}`", &[&name]))
+                .primary(span, tr("不能借用为可变的").into_owned()),
+        );
+    }
+
+    // 引用指向的值不能被修改时报告错误，返回引用是不是可变的
+    fn check_mutable_reference(&mut self, var: usize, span: Span, assign: bool) -> bool {
+        if self.variables[var].value.kind == Kind::Mutable {
+            return true;
+        }
+        let name = self.variables[var].name.clone();
+        let seq = self.next();
+        let diagnostic = if assign {
+            Diagnostic::new("E0594", tr_fmt("不能给 `*{}` 赋值，因为它在一个 `&` 引用之后", &[&name]), seq)
+                .primary(span, tr_fmt("`{}` 是一个 `&` 引用，不能通过它修改数据", &[&name]))
+        } else {
+            Diagnostic::new("E0596", tr_fmt("不能把 `*{}` 借用为可变的，因为它在一个 `&` 引用之后", &[&name]), seq)
+                .primary(span, tr_fmt("`{}` 是一个 `&` 引用，不能通过它把数据借用为可变的", &[&name]))
+        };
+        self.diagnostics.push(diagnostic);
+        false
+    }
+
+    // 借用变量，返回引用
+    fn borrow(&mut self, var: usize, mutable: bool, span: Span) -> Value {
+        self.check_state(var, span, true);
+        if mutable {
+            self.check_mutable(var, span);
+        }
+        let kind = if mutable { AccessKind::Write } else { AccessKind::Read { borrow: true } };
+        let seq = self.access(var, kind, span);
+        let target = self.variables[var].value.clone();
+        self.touch(&target, seq, span);
+
+        self.loans.push(Loan { var, mutable, seq, span, text: self.text(span), holder: None, last_use: None });
+        let mut loans = vec![self.loans.len() - 1];
+        // 借用保存着引用的值（例如 Vec<&str>）时，这些引用也必须继续有效
+        loans.extend(target.loans);
+        Value {
+            kind: if mutable { Kind::Mutable } else { Kind::Shared },
+            loans,
+            owned_target: target.kind == Kind::Owned,
+        }
+    }
+
+    // 把值移动走；实现了 Copy 的值只是被复制
+    fn take(&mut self, var: usize, span: Span) -> Value {
+        if self.variables[var].value.is_copy() {
+            return self.read(var, span);
+        }
+        self.check_state(var, span, false);
+        let seq = self.access(var, AccessKind::Move, span);
+        let value = self.variables[var].value.clone();
+        self.touch(&value, seq, span);
+        self.moves.push(Move { var, span, target: None });
+        self.variables[var].state = State::Moved(span);
+        value
+    }
+
+    // 使用引用变量本身
+    fn use_reference(&mut self, var: usize, span: Span) -> Option<Value> {
+        self.check_state(var, span, false);
+        let seq = self.access(var, AccessKind::Read { borrow: false }, span);
+        let value = self.variables[var].value.clone();
+        self.touch(&value, seq, span);
+        if value.is_reference() {
+            return Some(value);
+        }
+        let name = self.variables[var].name.clone();
+        let seq = self.next();
+        self.diagnostics.push(
+            Diagnostic::new("E0614", tr_fmt("`{}` 不是引用，不能用 * 解引用", &[&name]), seq)
+                .primary(span, tr("不能解引用").into_owned()),
+        );
+        None
+    }
+
+    // 计算表达式的值；value 为 false 时只读取，例如 println! 的参数和比较的两边
+    fn eval(&mut self, expr: &Expr, value: bool) -> Value {
+        match expr {
+            Expr::Literal(_) => Value::new(Kind::Copy),
+            Expr::Var(name, span) => match self.lookup(name, *span) {
+                Some(var) if value => self.take(var, *span),
+                Some(var) => self.read(var, *span),
+                None => Value::new(Kind::Copy),
+            },
+            Expr::Deref(name, span) => {
+                let Some(var) = self.lookup(name, *span) else {
+                    return Value::new(Kind::Copy);
+                };
+                if let Some(reference) = self.use_reference(var, *span)
+                    && value
+                    && reference.owned_target
+                {
+                    let seq = self.next();
+                    self.diagnostics.push(
+                        Diagnostic::new("E0507", tr_fmt("不能从 `*{}` 中移出值，因为它在一个引用之后", &[name]), seq)
+                            .primary(*span, tr("值被移动到这里，它的类型没有实现 Copy trait").into_owned()),
+                    );
+                }
+                Value::new(Kind::Copy)
+            }
+            Expr::Borrow { mutable, place, span } => self.eval_borrow(*mutable, place, *span),
+            Expr::Index { base, index, span } => {
+                for expr in index {
+                    self.eval(expr, false);
+                }
+                if let Some(var) = self.lookup(base, *span) {
+                    self.read(var, *span);
+                }
+                Value::new(Kind::Copy)
+            }
+            Expr::Call { name, args, span } => {
+                let values = self.eval_args(args, &format!("{}()", name));
+                self.touch_args(args, &values, *span);
+                Value::new(if name == "drop" { Kind::Copy } else { Kind::Owned })
+            }
+            Expr::Macro { name, args, span } => {
+                let values: Vec<Value> = if name == "vec" {
+                    self.eval_args(args, "vec![]")
+                } else {
+                    args.iter().map(|arg| self.eval(arg, false)).collect()
+                };
+                self.touch_args(args, &values, *span);
+                match name.as_str() {
+                    "vec" => Value { loans: values.into_iter().flat_map(|value| value.loans).collect(), ..Value::new(Kind::Owned) },
+                    "format" => Value::new(Kind::Owned),
+                    _ => Value::new(Kind::Copy),
+                }
+            }
+            Expr::Method { receiver, name, args, span } => self.eval_method(receiver, name, args, *span),
+            Expr::Binary { left, right, add, span } => {
+                // String + &str 会移动左边的 String
+                let moves_left = *add
+                    && matches!(left.as_ref(), Expr::Var(name, _)
+                        if self.variables.iter().rev().find(|var| var.alive && var.name == *name).is_some_and(|var| var.value.kind == Kind::Owned));
+                let left = self.eval(left, moves_left);
+                let right = self.eval(right, false);
+                let seq = self.next();
+                self.touch(&right, seq, *span);
+                Value::new(if moves_left { left.kind } else { Kind::Copy })
+            }
+        }
+    }
+
+    // 函数和 vec! 的参数按值传递，被移动的值转移给 target
+    fn eval_args(&mut self, args: &[Expr], target: &str) -> Vec<Value> {
+        let moves = self.moves.len();
+        let values = args.iter().map(|arg| self.eval(arg, true)).collect();
+        for moved in &mut self.moves[moves..] {
+            moved.target.get_or_insert_with(|| target.to_string());
+        }
+        values
+    }
+
+    fn eval_borrow(&mut self, mutable: bool, place: &Expr, span: Span) -> Value {
+        match place {
+            Expr::Var(name, _) => match self.lookup(name, span) {
+                Some(var) => self.borrow(var, mutable, span),
+                None => Value::new(Kind::Shared),
+            },
+            Expr::Index { base, index, .. } => {
+                for expr in index {
+                    self.eval(expr, false);
+                }
+                match self.lookup(base, span) {
+                    Some(var) => self.borrow(var, mutable, span),
+                    None => Value::new(Kind::Shared),
+                }
+            }
+            // 通过引用再借用：&*r、&mut *r
+            Expr::Deref(name, _) => {
+                let Some(var) = self.lookup(name, span) else {
+                    return Value::new(Kind::Shared);
+                };
+                if mutable && self.variables[var].value.kind == Kind::Shared {
+                    self.check_mutable_reference(var, span, false);
+                }
+                let Some(reference) = self.use_reference(var, span) else {
+                    return Value::new(Kind::Shared);
+                };
+                if !mutable {
+                    return Value { kind: Kind::Shared, ..reference };
+                }
+                // 可变的再借用期间，原来的引用也不能使用
+                let mut reborrow = self.borrow(var, false, span);
+                self.loans[reborrow.loans[0]].mutable = true;
+                reborrow.loans.extend(reference.loans);
+                Value { kind: Kind::Mutable, ..reborrow }
+            }
+            // 借用临时值
+            other => {
+                let value = self.eval(other, true);
+                Value { kind: if mutable { Kind::Mutable } else { Kind::Shared }, ..value }
+            }
+        }
+    }
+
+    fn eval_method(&mut self, receiver_expr: &Expr, name: &str, args: &[Expr], span: Span) -> Value {
+        let kind = receiver(name);
+        // 先计算参数，再借用接收者，这和编译器的两阶段借用一致，所以 v.push(v.len()) 可以通过检查
+        let values = self.eval_args(args, &format!("{}()", name));
+        self.touch_args(args, &values, span);
+        let arg_loans: Vec<usize> = values.iter().flat_map(|value| value.loans.clone()).collect();
+
+        let (var, receiver_span) = match receiver_expr {
+            Expr::Var(base, var_span) => (self.lookup(base, *var_span), *var_span),
+            Expr::Index { base, index, span } => {
+                for expr in index {
+                    self.eval(expr, false);
+                }
+                (self.lookup(base, *span), *span)
+            }
+            other => {
+                // 在临时值上调用方法，例如 s.trim().len()
+                let value = self.eval(other, true);
+                return match kind {
+                    Receiver::SharedRef => Value { kind: Kind::Shared, ..value },
+                    Receiver::MutRef => Value { kind: Kind::Mutable, ..value },
+                    // v.last().unwrap() 得到的仍然是借用了 v 的引用
+                    Receiver::Consuming => value,
+                    Receiver::Owned => Value::new(Kind::Owned),
+                    Receiver::Mutating | Receiver::Plain => Value::new(Kind::Copy),
+                };
+            }
+        };
+        let Some(var) = var else {
+            return Value::new(Kind::Copy);
+        };
+
+        // 接收者持有的借用在调用时被使用，和编译器一样标注在接收者上，而不是整个调用上
+        let held = self.variables[var].value.clone();
+        let value = self.call_method(var, kind, name, arg_loans, receiver_span.to(span));
+        let seq = self.next();
+        self.touch(&held, seq, receiver_span);
+        value
+    }
+
+    // 在变量上调用方法，span 是整个调用
+    fn call_method(&mut self, var: usize, kind: Receiver, name: &str, arg_loans: Vec<usize>, span: Span) -> Value {
+        // 通过引用调用方法
+        if self.variables[var].value.is_reference() {
+            if matches!(kind, Receiver::Mutating | Receiver::MutRef) {
+                self.check_mutable_reference(var, span, false);
+            }
+            let Some(reference) = self.use_reference(var, span) else {
+                return Value::new(Kind::Copy);
+            };
+            return match kind {
+                Receiver::SharedRef => Value { kind: Kind::Shared, ..reference },
+                Receiver::MutRef => reference,
+                Receiver::Owned | Receiver::Consuming => Value::new(Kind::Owned),
+                Receiver::Mutating | Receiver::Plain => Value::new(Kind::Copy),
+            };
+        }
+
+        match kind {
+            Receiver::Mutating => {
+                self.borrow(var, true, span);
+                self.loans.pop();
+                // push 的参数中的引用保存到了接收者中，由接收者继续持有
+                let holder = self.variables[var].name.clone();
+                for &loan in &arg_loans {
+                    self.loans[loan].holder.get_or_insert_with(|| holder.clone());
+                }
+                self.variables[var].value.loans.extend(arg_loans);
+                Value::new(Kind::Copy)
+            }
+            Receiver::SharedRef => self.borrow(var, false, span),
+            Receiver::MutRef => self.borrow(var, true, span),
+            Receiver::Consuming => {
+                let moves = self.moves.len();
+                let value = self.take(var, span);
+                for moved in &mut self.moves[moves..] {
+                    moved.target = Some(format!("{}()", name));
+                }
+                value
+            }
+            Receiver::Owned => {
+                self.read(var, span);
+                Value::new(Kind::Owned)
+            }
+            Receiver::Plain => {
+                self.read(var, span);
+                Value::new(Kind::Copy)
+            }
+        }
+    }
+
+    // 新创建的借用由 name 持有，被移动的值转移给 name
+    fn store(&mut self, name: &str, value: &Value, loans: usize, moves: usize) {
+        for &loan in &value.loans {
+            if loan >= loans {
+                self.loans[loan].holder.get_or_insert_with(|| name.to_string());
+            }
+        }
+        for moved in &mut self.moves[moves..] {
+            moved.target.get_or_insert_with(|| format!("`{}`", name));
+        }
+    }
+
+    fn statement(&mut self, statement: &Statement) {
+        let (loans, moves) = (self.loans.len(), self.moves.len());
+        match statement {
+            Statement::Let { name, mutable, span, init } => {
+                let value = init.as_ref().map(|init| self.eval(init, true));
+                if let Some(value) = &value {
+                    self.store(name, value, loans, moves);
+                }
+                self.variables.push(Variable {
+                    name: name.clone(),
+                    mutable: *mutable,
+                    depth: self.depth,
+                    declared: *span,
+                    assigned: value.as_ref().map(|_| *span),
+                    state: if value.is_some() { State::Init } else { State::Uninit },
+                    value: value.unwrap_or(Value::new(Kind::Copy)),
+                    alive: true,
+                });
+            }
+            Statement::Assign { target, value, compound, span } => self.assign(target, value, *compound, *span),
+            Statement::Expr(expr) => {
+                self.eval(expr, true);
+            }
+            Statement::Open => self.depth += 1,
+            Statement::Close(span) => {
+                // 作用域中声明的变量按照相反的顺序离开作用域
+                for var in (0..self.variables.len()).rev() {
+                    let variable = &self.variables[var];
+                    if !variable.alive || variable.depth < self.depth {
+                        continue;
+                    }
+                    if matches!(variable.state, State::Init) {
+                        self.access(var, AccessKind::Drop, *span);
+                    }
+                    self.variables[var].alive = false;
+                }
+                self.depth -= 1;
+            }
+        }
+    }
+
+    fn assign(&mut self, target: &Expr, value: &Expr, compound: bool, span: Span) {
+        let (loans, moves) = (self.loans.len(), self.moves.len());
+        let new_value = self.eval(value, !compound);
+        match target {
+            Expr::Var(name, var_span) => {
+                let Some(var) = self.lookup(name, *var_span) else {
+                    return;
+                };
+                if compound {
+                    self.read(var, *var_span);
+                }
+                let variable = &self.variables[var];
+                if !variable.mutable && (compound || !matches!(variable.state, State::Uninit)) {
+                    let first = variable.assigned.unwrap_or(variable.declared);
+                    let seq = self.next();
+                    self.diagnostics.push(
+                        Diagnostic::new("E0384", tr_fmt("不能给不可变变量 `{}` 赋值两次", &[name]), seq)
+                            .secondary(first, tr_fmt("第一次给 `{}` 赋值", &[name]))
+                            .primary(span, tr("不能给不可变变量赋值两次").into_owned())
+                            .note(tr_fmt("考虑把它改成可变的：`mut {}`", &[name])),
+                    );
+                }
+                self.access(var, AccessKind::Assign, span);
+                if !compound {
+                    self.store(name, &new_value, loans, moves);
+                    let variable = &mut self.variables[var];
+                    variable.value = new_value;
+                    variable.state = State::Init;
+                    variable.assigned.get_or_insert(span);
+                }
+            }
+            Expr::Deref(name, deref_span) => {
+                if let Some(var) = self.lookup(name, *deref_span) {
+                    if self.variables[var].value.kind == Kind::Shared {
+                        self.check_mutable_reference(var, span, true);
+                    }
+                    self.use_reference(var, *deref_span);
+                }
+            }
+            Expr::Index { base, index, span: index_span } => {
+                for expr in index {
+                    self.eval(expr, false);
+                }
+                if let Some(var) = self.lookup(base, *index_span) {
+                    self.borrow(var, true, *index_span);
+                    self.loans.pop();
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // 根据借用的存活范围找出冲突：借用创建之后、最后一次使用之前，被借用的变量不能以冲突的方式访问
+    fn conflicts(&mut self) {
+        for access in &self.accesses {
+            let Some(loan) = self.loans.iter().find(|loan| {
+                loan.var == access.var
+                    && loan.seq < access.seq
+                    && loan.last_use.is_some_and(|(last, _)| last > access.seq)
+                    && (loan.mutable || !matches!(access.kind, AccessKind::Read { .. }))
+            }) else {
+                continue;
+            };
+            let (_, used) = loan.last_use.unwrap_or((0, loan.span));
+            let name = &self.variables[access.var].name;
+            let later = tr("借用之后在这里被使用").into_owned();
+            let borrowed_here = tr_fmt("`{}` 被借用在这里", &[name]);
+
+            let diagnostic = match access.kind {
+                AccessKind::Read { borrow: true } => Diagnostic::new(
+                    "E0502",
+                    tr_fmt("不能把 `{}` 借用为不可变的，因为它同时被借用为可变的", &[name]),
+                    access.seq,
+                )
+                .secondary(loan.span, tr("可变借用发生在这里").into_owned())
+                .primary(access.span, tr("不可变借用发生在这里").into_owned())
+                .secondary(used, tr("可变借用之后在这里被使用").into_owned()),
+                AccessKind::Read { borrow: false } => {
+                    Diagnostic::new("E0503", tr_fmt("不能使用 `{}`，因为它已经被可变借用了", &[name]), access.seq)
+                        .secondary(loan.span, borrowed_here)
+                        .primary(access.span, tr_fmt("使用了被借用的 `{}`", &[name]))
+                        .secondary(used, later)
+                }
+                AccessKind::Write if loan.mutable => {
+                    Diagnostic::new("E0499", tr_fmt("不能同时把 `{}` 借用为可变的多于一次", &[name]), access.seq)
+                        .secondary(loan.span, tr("第一次可变借用发生在这里").into_owned())
+                        .primary(access.span, tr("第二次可变借用发生在这里").into_owned())
+                        .secondary(used, tr("第一次借用之后在这里被使用").into_owned())
+                }
+                AccessKind::Write => Diagnostic::new(
+                    "E0502",
+                    tr_fmt("不能把 `{}` 借用为可变的，因为它同时被借用为不可变的", &[name]),
+                    access.seq,
+                )
+                .secondary(loan.span, tr("不可变借用发生在这里").into_owned())
+                .primary(access.span, tr("可变借用发生在这里").into_owned())
+                .secondary(used, tr("不可变借用之后在这里被使用").into_owned()),
+                AccessKind::Move => {
+                    Diagnostic::new("E0505", tr_fmt("不能移出 `{}`，因为它还被借用着", &[name]), access.seq)
+                        .secondary(loan.span, borrowed_here)
+                        .primary(access.span, tr_fmt("`{}` 在这里被移出", &[name]))
+                        .secondary(used, later)
+                }
+                AccessKind::Assign => {
+                    Diagnostic::new("E0506", tr_fmt("不能给 `{}` 赋值，因为它还被借用着", &[name]), access.seq)
+                        .secondary(loan.span, borrowed_here)
+                        .primary(access.span, tr_fmt("`{}` 在被借用时被赋值", &[name]))
+                        .secondary(used, later)
+                }
+                AccessKind::Drop => Diagnostic::new("E0597", tr_fmt("`{}` 活得不够久", &[name]), loan.seq)
+                    .primary(loan.span, tr("借用的值活得不够久").into_owned())
+                    .secondary(access.span, tr_fmt("`{}` 在这里离开作用域时仍然被借用着", &[name]))
+                    .secondary(used, later),
+            };
+            self.diagnostics.push(diagnostic);
+        }
+    }
+
+    // 报告的错误代码，按照出现的顺序排列，不重复
+    fn codes(&self) -> Vec<&'static str> {
+        let mut codes: Vec<&'static str> = Vec::new();
+        for diagnostic in &self.diagnostics {
+            if !codes.contains(&diagnostic.code) {
+                codes.push(diagnostic.code);
+            }
+        }
+        codes
+    }
+
+    // 每个引用的时间线，左边是代码，右边每一列是一个由变量持有的借用
+    fn timeline(&self) -> String {
+        let columns: Vec<&Loan> = self.loans.iter().filter(|loan| loan.holder.is_some()).collect();
+        let range = |loan: &Loan| {
+            let end = loan.last_use.map_or(loan.span.line, |(_, span)| span.line.max(loan.span.line));
+            (loan.span.line, end)
+        };
+
+        let number_width = self.lines.len().to_string().len().max(width(&tr("行")));
+        let code_width = self.lines.iter().map(|line| width(line)).chain([width(&tr("代码"))]).max().unwrap_or(0);
+        let widths: Vec<usize> = columns.iter().map(|loan| width(loan.holder.as_deref().unwrap_or("")).max(1) + 2).collect();
+        let pad = |text: &str, total: usize| format!("{}{}", text, " ".repeat(total.saturating_sub(width(text))));
+
+        let mut header = format!("{}  {}  ", pad(&tr("行"), number_width), pad(&tr("代码"), code_width));
+        for (loan, column) in columns.iter().zip(&widths) {
+            header.push_str(&pad(loan.holder.as_deref().unwrap_or(""), *column));
+        }
+        let mut text = format!("{}\n", header.trim_end());
+
+        for (index, line) in self.lines.iter().enumerate() {
+            let number = index + 1;
+            let mut row = format!("{:>width$}  {}  ", number, pad(line, code_width), width = number_width);
+            for (loan, column) in columns.iter().zip(&widths) {
+                let (start, end) = range(loan);
+                let mark = match number {
+                    n if n == start && n == end => "●",
+                    n if n == start => "┬",
+                    n if n == end => "┴",
+                    n if n > start && n < end => "│",
+                    _ => " ",
+                };
+                row.push_str(&pad(mark, *column));
+            }
+            let errors: Vec<&str> = self
+                .diagnostics
+                .iter()
+                .filter(|diagnostic| diagnostic.line() == number)
+                .map(|diagnostic| diagnostic.code)
+                .collect();
+            if !errors.is_empty() {
+                row.push_str(&format!("✗ {}", errors.join(" ")));
+            }
+            text.push_str(row.trim_end());
+            text.push('\n');
+        }
+
+        if !columns.is_empty() {
+            text.push_str(&format!("\n{}\n", tr("┬ 借用开始  │ 借用仍然有效  ┴ 最后一次使用  ● 借用只在这一行有效")));
+        }
+        text
+    }
+
+    // 每个引用的生命周期
+    fn lifetimes(&self) -> String {
+        let mut text = String::new();
+        for loan in self.loans.iter().filter(|loan| loan.holder.is_some()) {
+            let holder = loan.holder.as_deref().unwrap_or("");
+            let end = loan.last_use.map_or(loan.span.line, |(_, span)| span.line.max(loan.span.line));
+            let range = if end == loan.span.line {
+                tr_fmt("只在第 {} 行", &[&end])
+            } else {
+                tr_fmt("第 {} 行到第 {} 行", &[&loan.span.line, &end])
+            };
+            let kind = if loan.mutable { tr("可变借用") } else { tr("共享借用") };
+            text.push_str(&format!("  {}  {}\n", holder, tr_fmt("{}（{}）：{}", &[&loan.text, &kind, &range])));
+        }
+        text
+    }
+
+    // 所有权的转移
+    fn ownership(&self) -> String {
+        let mut text = String::new();
+        for moved in &self.moves {
+            let name = &self.variables[moved.var].name;
+            let line = match &moved.target {
+                Some(target) => tr_fmt("第 {} 行：`{}` 的所有权转移给了 {}", &[&moved.span.line, name, target]),
+                None => tr_fmt("第 {} 行：`{}` 的所有权被转移", &[&moved.span.line, name]),
+            };
+            text.push_str(&format!("  {}\n", line));
+        }
+        text
+    }
+
+    // 按照 rustc 的格式显示一条错误
+    fn render(&self, diagnostic: &Diagnostic, source: &str) -> String {
+        let mut lines: Vec<usize> = diagnostic.labels.iter().map(|label| label.span.line).collect();
+        lines.sort_unstable();
+        lines.dedup();
+        let gutter = lines.last().map_or(1, |line| line.to_string().len());
+        let blank = " ".repeat(gutter);
+
+        let mut text = format!("error[{}]: {}\n", diagnostic.code, diagnostic.message);
+        if let Some(primary) = diagnostic.labels.iter().find(|label| label.primary) {
+            text.push_str(&format!("{}--> {}:{}:{}\n", blank, source, primary.span.line, primary.span.start + 1));
+        }
+        text.push_str(&format!("{} |\n", blank));
+
+        let mut previous = None;
+        for line in lines {
+            if previous.is_some_and(|previous| line > previous + 1) {
+                text.push_str("...\n");
+            }
+            previous = Some(line);
+            let code = self.lines.get(line - 1).map(String::as_str).unwrap_or("");
+            text.push_str(&format!("{:>gutter$} | {}\n", line, code, gutter = gutter));
+
+            let mut labels: Vec<&Label> = diagnostic.labels.iter().filter(|label| label.span.line == line).collect();
+            labels.sort_by_key(|label| label.span.start);
+            for label in labels {
+                let end = label.span.end.min(width(code)).max(label.span.start + 1);
+                let marker = if label.primary { "^" } else { "-" };
+                let row = format!(
+                    "{} | {}{} {}",
+                    blank,
+                    " ".repeat(label.span.start),
+                    marker.repeat(end - label.span.start),
+                    label.text
+                );
+                text.push_str(row.trim_end());
+                text.push('\n');
+            }
+        }
+        if !diagnostic.notes.is_empty() {
+            text.push_str(&format!("{} |\n", blank));
+        }
+        for note in &diagnostic.notes {
+            text.push_str(&format!("{} = {}\n", blank, tr_fmt("注意：{}", &[note])));
+        }
+        text
+    }
+}
+
+// 模拟执行一段代码，返回完整的报告
+fn report(code: &str, source: &str) -> Result<(String, Vec<&'static str>), String> {
+    let statements = parse(code)?;
+    let simulation = Simulation::run(code, &statements);
+    let style = theme::current();
+
+    let mut text = format!("{}\n{}", style.label(&tr("时间线：")), simulation.timeline());
+    let lifetimes = simulation.lifetimes();
+    if !lifetimes.is_empty() {
+        text.push_str(&format!("\n{}\n{}", style.label(&tr("引用的生命周期：")), lifetimes));
+    }
+    let ownership = simulation.ownership();
+    if !ownership.is_empty() {
+        text.push_str(&format!("\n{}\n{}", style.label(&tr("所有权的转移：")), ownership));
+    }
+
+    for diagnostic in &simulation.diagnostics {
+        text.push_str(&format!("\n{}", simulation.render(diagnostic, source)));
+    }
+    let count = simulation.diagnostics.len();
+    if count == 0 {
+        text.push_str(&format!("\n{}\n", tr("✓ 借用检查通过，没有发现错误")));
+    } else {
+        text.push_str(&format!("\n{}\n", tr_fmt("✗ 发现 {} 个错误", &[&count])));
+    }
+    Ok((text, simulation.codes()))
+}
+
+// borrow 子命令，返回进程退出码
+// 用法：
+//   rustlearn borrow                     列出内置的示例
+//   rustlearn borrow <文件>              模拟检查文件中的代码，文件是 - 时从标准输入读取，发现借用错误时返回 1
+//   rustlearn borrow --example <名称>    模拟检查内置的示例
+//   rustlearn borrow --check             确认内置示例的检查结果和 rustc 一致
+pub fn run_cli(args: &[String]) -> i32 {
+    match args {
+        [] => list(),
+        [flag] if flag == "--check" => check(),
+        [flag, name] if flag == "--example" => match EXAMPLES.iter().find(|example| example.name == name) {
+            Some(example) => show_example(example),
+            None => {
+                eprintln!("{}", tr_fmt("没有名为 {} 的示例，可以使用 `rustlearn borrow` 查看所有示例", &[name]));
+                2
+            }
+        },
+        [path] if path == "-" || !path.starts_with('-') => {
+            let mut code = String::new();
+            let read = if path == "-" { io::stdin().read_to_string(&mut code).map(|_| ()) } else { fs::read_to_string(path).map(|text| code = text) };
+            if let Err(error) = read {
+                eprintln!("{}", tr_fmt("无法读取 {}: {}", &[path, &error]));
+                return 1;
+            }
+            let source = if path == "-" { "<stdin>" } else { path.as_str() };
+            simulate(&code, source)
+        }
+        _ => {
+            eprintln!("{}", tr("用法: rustlearn borrow [<文件> | - | --example <名称> | --check]"));
+            2
+        }
+    }
+}
+
+// 模拟检查一段代码并输出报告，发现借用错误时返回 1，方便在脚本中使用；代码无法解析时返回 2
+fn simulate(code: &str, source: &str) -> i32 {
+    match report(code, source) {
+        Ok((text, codes)) => match cli::print(&text) {
+            0 if !codes.is_empty() => 1,
+            status => status,
+        },
+        Err(message) => {
+            eprintln!("{}", tr_fmt("无法解析代码：{}", &[&message]));
+            2
+        }
+    }
+}
+
+// 列出内置的示例
+fn list() -> i32 {
+    let mut text = String::new();
+    let name_width = EXAMPLES.iter().map(|example| example.name.len()).max().unwrap_or(0);
+    for example in EXAMPLES {
+        let codes = if example.codes.is_empty() { String::from("✓") } else { example.codes.join(" ") };
+        text.push_str(&format!("  {:<name_width$}  {:<5}  {}\n", example.name, codes, tr(example.title), name_width = name_width));
+    }
+    text.push_str(&format!("\n{}\n", tr("使用 `rustlearn borrow --example <名称>` 查看示例的检查过程，或者使用 `rustlearn borrow <文件>` 检查自己写的代码")));
+    cli::print(&text)
+}
+
+// 显示一个内置示例的检查过程
+fn show_example(example: &Example) -> i32 {
+    let style = theme::current();
+    let mut text = format!("{}\n", style.lesson_heading(&tr(example.title)));
+    for place in example.sections.iter().filter_map(|section| Place::find(*section)) {
+        text.push_str(&format!("{}\n", tr_fmt("相关小节：{}（{}）", &[&place.title(), &place.command()])));
+    }
+    match report(example.code, example.name) {
+        Ok((report, _)) => {
+            text.push('\n');
+            text.push_str(&report);
+            cli::print(&text)
+        }
+        Err(message) => {
+            eprintln!("{}", tr_fmt("无法解析代码：{}", &[&message]));
+            1
+        }
+    }
+}
+
+// 把示例放进 fn main 中，交给 rustc 编译
+fn as_program(code: &str) -> String {
+    let mut program = String::from("fn main() {\n");
+    for line in code.lines() {
+        program.push_str(&format!("    {}\n", line).replace("    \n", "\n"));
+    }
+    program.push_str("}\n");
+    program
+}
+
+// 检查内置的示例：相关的小节存在，模拟器和 rustc 报告的错误代码都和预期一致
+fn check() -> i32 {
+    let mut problems = 0;
+    for example in EXAMPLES {
+        let mut issues = Vec::new();
+        for (lesson, section) in example.sections {
+            if Place::find((lesson, section)).is_none() {
+                issues.push(tr_fmt("找不到相关的小节（第{}课 {}）", &[lesson, section]));
+            }
+        }
+
+        let mut expected: Vec<&str> = example.codes.to_vec();
+        expected.sort_unstable();
+        let describe = |codes: &[&str]| if codes.is_empty() { tr("没有错误").into_owned() } else { codes.join(", ") };
+
+        match report(example.code, example.name) {
+            Ok((_, mut codes)) => {
+                codes.sort_unstable();
+                if codes != expected {
+                    issues.push(tr_fmt("模拟器报告的是 {}，预期是 {}", &[&describe(&codes), &describe(&expected)]));
+                }
+            }
+            Err(message) => issues.push(message),
+        }
+
+        match compile_error::error_codes(&format!("borrow_{}", example.name), &as_program(example.code)) {
+            Ok(mut codes) => {
+                codes.sort_unstable();
+                codes.dedup();
+                let codes: Vec<&str> = codes.iter().map(String::as_str).collect();
+                if codes != expected {
+                    issues.push(tr_fmt("rustc 报告的是 {}，预期是 {}", &[&describe(&codes), &describe(&expected)]));
+                }
+            }
+            Err(error) => {
+                eprintln!("{}", tr_fmt("无法编译示例代码: {}", &[&error]));
+                return 1;
+            }
+        }
+
+        if issues.is_empty() {
+            println!("✓ {}", example.name);
+        } else {
+            println!("✗ {}：{}", example.name, issues.join(tr("；").as_ref()));
+            problems += 1;
+        }
+    }
+
+    if problems == 0 {
+        println!("\n{}", tr_fmt("{} 个示例的检查结果都和 rustc 一致", &[&EXAMPLES.len()]));
+        0
+    } else {
+        println!("\n{}", tr_fmt("{} 个示例中有 {} 个和预期不一致", &[&EXAMPLES.len(), &problems]));
+        1
+    }
+}
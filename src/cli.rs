@@ -0,0 +1,314 @@
+// 非交互式命令行模式
+// 不带参数运行时进入交互式菜单；带参数时直接执行对应的子命令，执行完毕后以退出码结束，
+// 方便在脚本、管道中使用，或者把课程输出保存成讲义
+//
+// 退出码：
+// 0 - 执行成功
+// 1 - 执行过程中出错，例如输出失败
+// 2 - 命令行参数有误，例如课程或小节不存在
+
+use std::io::{self, ErrorKind, Write};
+
+use crate::book;
+use crate::borrow;
+use crate::compile_error;
+use crate::context::LessonContext;
+use crate::elision;
+use crate::epub;
+use crate::exercise;
+use crate::explain;
+use crate::glossary;
+use crate::golden;
+use crate::graph;
+use crate::i18n::{self, tr, tr_fmt};
+use crate::lesson::{self, Lesson, Section};
+use crate::progress;
+use crate::quiz;
+use crate::review;
+use crate::search;
+use crate::theme;
+use crate::tui;
+use crate::web;
+
+// 帮助信息，按行翻译
+pub const USAGE: &str = "用法:
+  rustlearn                                  进入交互式菜单
+  rustlearn tui                              进入全屏界面，左侧是课程目录，右侧是源代码和运行结果
+  rustlearn tui --replay [<行数>x<列数>]      不需要终端，从标准输入读取按键，输出处理完所有按键之后的界面
+  rustlearn serve [--port <端口号>]           在 127.0.0.1 上启动网页界面，默认端口是 7878
+  rustlearn book [--out <目录>]              把所有课程导出成 mdBook 格式的 Markdown 书籍，默认导出到 book 目录
+  rustlearn readme [--check] [<README 路径>] 根据课程注册表更新 README 中的课程列表，--check 只检查
+  rustlearn export --epub [--out <文件>]     把所有课程导出成 EPUB 电子书，默认是 rustlearn.epub
+  rustlearn list                             列出所有课程
+  rustlearn list <课程编号>                   列出某一课的知识点和小节
+  rustlearn run <课程编号>                    运行某一课的全部小节
+  rustlearn run <课程编号> --section <小节>   只运行某一个小节，小节可以是编号、标识或标识的一部分
+  rustlearn run --all                        依次运行所有课程
+  rustlearn run ... --code                   运行每个小节之前先显示它的源代码和行号
+  rustlearn search <关键词>... [--limit <数量>] [--run]
+                                             在小节标题、说明、源代码和运行结果中搜索，--run 直接运行最相关的小节
+  rustlearn glossary [<术语> | --check]      查看术语表或某个术语，--check 检查术语是否在介绍之前就被用到
+  rustlearn graph [--dot | --check]          显示课程之间的依赖关系，--dot 输出 Graphviz DOT 格式
+  rustlearn path [<路线>]                    列出推荐的学习路线，或者按照学习进度查看某一条路线
+  rustlearn verify [--lesson <课程编号> [--source <文件>]] [--bless]
+                                             校验各小节的运行结果注释
+  rustlearn quiz <课程编号>                   参加某一课的测验，成绩记录到学习进度中
+  rustlearn review [--limit <数量>] [--status]
+                                             复习到期的测验题，--status 查看复习计划
+  rustlearn exercise [<课程编号>.<练习编号>] [--reset] [--solution]
+                                             列出所有练习，或者准备并检查某个练习
+  rustlearn errors [<示例编号> | <错误代码> | --check]
+                                             用本地的 rustc 编译故意写错的代码，对照显示编译器的错误信息和修改方法
+  rustlearn explain [<错误代码> | --check]   查看常见编译错误代码的原因、复现代码、修改方法和相关的小节
+  rustlearn elision [<函数签名>]             输入函数或方法的签名，一步一步演示生命周期省略规则的推断过程
+  rustlearn borrow [<文件> | - | --example <名称> | --check]
+                                             用一个小型教学语言模拟借用检查，画出每个引用的生命周期并报告借用冲突
+  rustlearn progress [--user <用户名> | --all]
+                                             查看学习进度，--all 列出所有学习者
+  rustlearn check-translations [<语言代码>]  列出消息目录中缺少译文的消息
+  rustlearn help                             显示本帮助信息
+
+选项:
+  --lang <语言代码>                          界面语言，可以是 zh 或 en，默认根据 LANG 环境变量选择
+  --theme <主题>                             终端配色主题，可以是 light、dark 或 high-contrast，默认是 dark
+                                             设置了 NO_COLOR 或者输出不是终端时不使用颜色";
+
+// 执行命令行参数对应的子命令，返回进程退出码
+pub fn run(args: &[String]) -> i32 {
+    let (command, rest) = match args.split_first() {
+        Some((command, rest)) => (command.as_str(), rest),
+        None => return usage_error(&tr("缺少子命令")),
+    };
+
+    match command {
+        "list" => list(rest),
+        "run" => run_lessons(rest),
+        "verify" => golden::run_cli(rest),
+        "quiz" => quiz::run_cli(rest),
+        "review" => review::run_cli(rest),
+        "exercise" => exercise::run_cli(rest),
+        "errors" => compile_error::run_cli(rest),
+        "explain" => explain::run_cli(rest),
+        "elision" => elision::run_cli(rest),
+        "borrow" => borrow::run_cli(rest),
+        "tui" => tui::run_cli(rest),
+        "serve" => web::run_cli(rest),
+        "book" => book::run_cli(rest),
+        "readme" => book::readme_cli(rest),
+        "export" => epub::run_cli(rest),
+        "search" => search::run_cli(rest),
+        "glossary" => glossary::run_cli(rest),
+        "graph" => graph::run_cli(rest),
+        "path" => graph::path_cli(rest),
+        "progress" => progress::run_cli(rest),
+        "check-translations" => i18n::check_cli(rest),
+        "help" | "--help" | "-h" => print(&format!("{}\n", usage())),
+        other => usage_error(&tr_fmt("无法识别的子命令: {}", &[&other])),
+    }
+}
+
+// list 子命令
+fn list(args: &[String]) -> i32 {
+    match args {
+        [] => print_to_stdout(|ctx| {
+            for lesson in lesson::LESSONS {
+                writeln!(ctx, "{:>2}. {}", lesson.id(), tr(lesson.title()));
+                writeln!(ctx, "    {}", tr(lesson.summary()));
+            }
+        }),
+        [id] => match find_lesson(id) {
+            Ok(lesson) => print_to_stdout(|ctx| {
+                writeln!(ctx, "{}", tr_fmt("第{}课：{}", &[&lesson.id(), &tr(lesson.title())]));
+                writeln!(ctx, "{}", tr(lesson.summary()));
+
+                writeln!(ctx, "\n{}", tr("知识点："));
+                for topic in lesson.topics() {
+                    writeln!(ctx, "- {}", tr(topic));
+                }
+
+                writeln!(ctx, "\n{}", tr("小节："));
+                for (index, section) in lesson.sections().iter().enumerate() {
+                    writeln!(ctx, "{:>2}. {} ({})", index + 1, tr(section.title), section.id);
+                }
+
+                if !lesson.exercises().is_empty() {
+                    writeln!(ctx, "\n{}", tr("练习："));
+                    for (index, exercise) in lesson.exercises().iter().enumerate() {
+                        writeln!(ctx, "{}.{} {}", lesson.id(), index + 1, tr(exercise.title));
+                    }
+                }
+
+                if !lesson.compile_errors().is_empty() {
+                    writeln!(ctx, "\n{}", tr("编译错误示例："));
+                    for (index, example) in lesson.compile_errors().iter().enumerate() {
+                        writeln!(ctx, "{}.{} {} {}", lesson.id(), index + 1, example.code, tr(example.title));
+                    }
+                }
+            }),
+            Err(code) => code,
+        },
+        _ => usage_error(&tr("list 最多接受一个课程编号")),
+    }
+}
+
+// run 子命令
+fn run_lessons(args: &[String]) -> i32 {
+    let mut all = false;
+    let mut code = false;
+    let mut lesson_id = None;
+    let mut section_query = None;
+    let mut iter = args.iter();
+
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--all" => all = true,
+            "--code" => code = true,
+            "--section" => match iter.next() {
+                Some(query) => section_query = Some(query.as_str()),
+                None => return usage_error(&tr("--section 需要一个小节编号或标识")),
+            },
+            other if other.starts_with('-') => {
+                return usage_error(&tr_fmt("无法识别的参数: {}", &[&other]));
+            }
+            other => {
+                if lesson_id.is_some() {
+                    return usage_error(&tr("run 只接受一个课程编号"));
+                }
+                lesson_id = Some(other);
+            }
+        }
+    }
+
+    match (all, lesson_id, section_query) {
+        (true, None, None) => print_to_stdout(|ctx| {
+            for lesson in lesson::LESSONS {
+                if code {
+                    ctx.show_code(*lesson);
+                }
+                lesson.run(ctx);
+                writeln!(ctx);
+            }
+        }),
+        (true, _, _) => usage_error(&tr("--all 不能与课程编号或 --section 一起使用")),
+        (false, Some(id), None) => match find_lesson(id) {
+            Ok(lesson) => print_to_stdout(|ctx| {
+                if code {
+                    ctx.show_code(lesson);
+                }
+                lesson.run(ctx);
+            }),
+            Err(code) => code,
+        },
+        (false, Some(id), Some(query)) => {
+            let lesson = match find_lesson(id) {
+                Ok(lesson) => lesson,
+                Err(code) => return code,
+            };
+            match find_section(lesson, query) {
+                Ok(section) => print_to_stdout(|ctx| {
+                    if code {
+                        ctx.show_code(lesson);
+                    }
+                    (section.run)(ctx);
+                }),
+                Err(code) => code,
+            }
+        }
+        (false, None, _) => usage_error(&tr("run 需要一个课程编号，或者使用 --all 运行所有课程")),
+    }
+}
+
+// 根据课程编号查找课程，找不到时输出错误信息并返回退出码
+fn find_lesson(id: &str) -> Result<&'static dyn Lesson, i32> {
+    lesson::find(id).ok_or_else(|| {
+        eprintln!("{}", tr_fmt("没有编号为 {} 的课程，可以使用 `rustlearn list` 查看所有课程", &[&id]));
+        2
+    })
+}
+
+// 根据小节编号、完整标识或者标识的一部分查找小节
+// 例如第 6 课中 "3"、"hashmap_collection" 和 "hashmap" 都表示同一个小节
+fn find_section(lesson: &dyn Lesson, query: &str) -> Result<&'static Section, i32> {
+    let sections = lesson.sections();
+
+    if let Ok(number) = query.parse::<usize>() {
+        return match number.checked_sub(1).and_then(|index| sections.get(index)) {
+            Some(section) => Ok(section),
+            None => {
+                eprintln!("{}", tr_fmt("第{}课只有 {} 个小节", &[&lesson.id(), &sections.len()]));
+                Err(2)
+            }
+        };
+    }
+
+    if let Some(section) = sections.iter().find(|section| section.id == query) {
+        return Ok(section);
+    }
+
+    let candidates: Vec<&Section> = sections
+        .iter()
+        .filter(|section| section.id.contains(query))
+        .collect();
+
+    match candidates.as_slice() {
+        [section] => Ok(section),
+        [] => {
+            eprintln!(
+                "{}",
+                tr_fmt(
+                    "第{}课中没有与 \"{}\" 匹配的小节，可以使用 `rustlearn list {}` 查看所有小节",
+                    &[&lesson.id(), &query, &lesson.id()]
+                )
+            );
+            Err(2)
+        }
+        _ => {
+            let ids: Vec<&str> = candidates.iter().map(|section| section.id).collect();
+            eprintln!("{}", tr_fmt("\"{}\" 匹配到多个小节：{}", &[&query, &ids.join(tr("、").as_ref())]));
+            Err(2)
+        }
+    }
+}
+
+// 把课程内容输出到标准输出，返回退出码
+// 输出被管道的另一端提前关闭（例如 `rustlearn run --all | head`）不算错误
+pub fn print_to_stdout(run: impl FnOnce(&mut LessonContext)) -> i32 {
+    let mut stdout = io::stdout();
+    let mut ctx = LessonContext::with_style(&mut stdout, theme::current());
+    run(&mut ctx);
+
+    match ctx.finish() {
+        Ok(()) => 0,
+        Err(error) if error.kind() == ErrorKind::BrokenPipe => 0,
+        Err(error) => {
+            eprintln!("{}", tr_fmt("无法输出课程内容: {}", &[&error]));
+            1
+        }
+    }
+}
+
+// 把一段已经生成好的文字输出到标准输出，返回退出码
+// 和 print_to_stdout 一样，输出被管道的另一端提前关闭不算错误
+pub fn print(text: &str) -> i32 {
+    let mut stdout = io::stdout();
+    match stdout.write_all(text.as_bytes()).and_then(|()| stdout.flush()) {
+        Ok(()) => 0,
+        Err(error) if error.kind() == ErrorKind::BrokenPipe => 0,
+        Err(error) => {
+            eprintln!("{}", tr_fmt("无法输出课程内容: {}", &[&error]));
+            1
+        }
+    }
+}
+
+// 当前语言的帮助信息
+fn usage() -> String {
+    let lines: Vec<_> = USAGE.lines().map(tr).collect();
+    lines.join("\n")
+}
+
+// 输出参数错误信息和帮助信息，返回退出码
+fn usage_error(message: &str) -> i32 {
+    eprintln!("{}\n\n{}", message, usage());
+    2
+}
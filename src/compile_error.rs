@@ -0,0 +1,314 @@
+// 编译错误示例
+// 每一课都通过 Lesson::compile_errors() 提供几段故意写错的代码，文件保存在项目根目录的 compile_errors/ 中，
+// 在编译时嵌入程序：
+// 1. <名称>.rs        无法通过编译的代码
+// 2. <名称>_fixed.rs  修改之后的代码
+//
+// `rustlearn errors 10.1` 会调用本地的 rustc --error-format=json 编译有错误的代码，
+// 把编译器真实的错误信息和修改前后的代码对照显示出来，再给出错误原因的说明
+// 这样学习者看到的就是自己以后会遇到的编译器输出，而不是课程中转述的文字
+// `rustlearn errors --check` 检查每段代码都会产生预期的错误代码，并且修改之后能够通过编译
+// 可以通过 RUSTC 环境变量指定使用的 rustc
+
+use std::env;
+use std::fs;
+use std::io::{self, ErrorKind};
+use std::path::Path;
+use std::process::{self, Command};
+
+use crate::cli;
+use crate::i18n::{tr, tr_fmt};
+use crate::json;
+use crate::lesson::{self, Lesson};
+use crate::screen;
+
+// 一段故意写错的代码
+pub struct CompileError {
+    // 名称，也是代码文件的名字，例如 "longest"
+    pub name: &'static str,
+    // 标题
+    pub title: &'static str,
+    // 相关的小节标识
+    pub section: &'static str,
+    // 预期的错误代码，例如 "E0106"
+    pub code: &'static str,
+    // 无法通过编译的代码
+    pub broken: &'static str,
+    // 修改之后的代码
+    pub fixed: &'static str,
+    // 错误原因和修改方法
+    pub explanation: &'static str,
+}
+
+// 编译器输出的一条诊断信息
+struct Diagnostic {
+    // "error"、"warning"、"failure-note" 等
+    level: String,
+    // 错误代码，例如 "E0106"，有些诊断信息没有错误代码
+    code: Option<String>,
+    // 编译器在终端中显示的完整文字
+    rendered: String,
+}
+
+// errors 子命令，返回进程退出码
+// 用法：
+//   rustlearn errors              列出所有编译错误示例
+//   rustlearn errors 10.1         编译第 10 课的第 1 段代码，显示错误信息和修改方法
+//   rustlearn errors E0502        显示所有预期产生 E0502 错误的代码
+//   rustlearn errors --check      检查每段代码都产生预期的错误，修改之后都能通过编译
+pub fn run_cli(args: &[String]) -> i32 {
+    match args {
+        [] => cli::print(&list()),
+        [flag] if flag == "--check" => check_all(),
+        [query] if !query.starts_with('-') => {
+            let found = find(query);
+            if found.is_empty() {
+                eprintln!(
+                    "{}",
+                    tr_fmt("没有编号或错误代码为 {} 的示例，可以使用 `rustlearn errors` 查看所有示例", &[&query])
+                );
+                return 2;
+            }
+
+            let mut text = String::new();
+            for (index, (lesson, number, example)) in found.into_iter().enumerate() {
+                if index > 0 {
+                    text.push('\n');
+                }
+                match show(lesson, number, example) {
+                    Ok(shown) => text.push_str(&shown),
+                    Err(error) => {
+                        // 先输出已经编译好的示例
+                        cli::print(&text);
+                        eprintln!("{}", tr_fmt("无法编译示例代码: {}", &[&error]));
+                        return 1;
+                    }
+                }
+            }
+            cli::print(&text)
+        }
+        _ => {
+            eprintln!("{}", tr("用法: rustlearn errors [<课程编号>.<示例编号> | <错误代码> | --check]"));
+            2
+        }
+    }
+}
+
+// 列出所有课程的编译错误示例
+fn list() -> String {
+    let mut text = String::new();
+    for lesson in lesson::LESSONS {
+        for (index, example) in lesson.compile_errors().iter().enumerate() {
+            text.push_str(&format!(
+                "{:>5}  {}  {}\n",
+                format!("{}.{}", lesson.id(), index + 1),
+                example.code,
+                tr(example.title)
+            ));
+        }
+    }
+    text
+}
+
+// 根据 "10.1" 这样的编号或者 "E0106" 这样的错误代码查找示例
+// 返回课程、示例在本课中的序号（从 1 开始）和示例
+fn find(query: &str) -> Vec<(&'static dyn Lesson, usize, &'static CompileError)> {
+    let mut found = Vec::new();
+    for lesson in lesson::LESSONS {
+        for (index, example) in lesson.compile_errors().iter().enumerate() {
+            let id = format!("{}.{}", lesson.id(), index + 1);
+            if id == query || example.code.eq_ignore_ascii_case(query) {
+                found.push((*lesson, index + 1, example));
+            }
+        }
+    }
+    found
+}
+
+// 编译有错误的代码，返回修改前后的代码、编译器的输出和说明
+fn show(lesson: &dyn Lesson, number: usize, example: &CompileError) -> io::Result<String> {
+    let mut text = format!("{}\n", tr_fmt("示例 {}.{}：{}", &[&lesson.id(), &number, &tr(example.title)]));
+
+    if let Some(section) = lesson.sections().iter().find(|section| section.id == example.section) {
+        text.push_str(&format!(
+            "{}\n",
+            tr_fmt(
+                "相关小节：第{}课 · {}（rustlearn run {} --section {}）",
+                &[&lesson.id(), &tr(section.title), &lesson.id(), &section.id]
+            )
+        ));
+    }
+
+    text.push_str(&format!(
+        "\n{}\n",
+        side_by_side(&tr("有错误的代码"), example.broken, &tr("修改之后的代码"), example.fixed)
+    ));
+
+    let diagnostics = compile(example.name, example.broken)?;
+    text.push_str(&format!("\n{}\n", tr_fmt("编译器的输出（{}）：", &[&rustc_version()])));
+    for diagnostic in &diagnostics {
+        text.push_str(&diagnostic.rendered);
+    }
+
+    text.push_str(&format!("\n{}\n", tr("说明：")));
+    text.push_str(&format!("{}\n", tr(example.explanation)));
+    Ok(text)
+}
+
+// 把两段代码左右并排，左边一列按照最长的一行补齐，中日韩文字占两列
+// 标题下面的分隔线在每一列中都和这一列最长的一行一样长
+fn side_by_side(left_title: &str, left: &str, right_title: &str, right: &str) -> String {
+    let width = |text: &str| text.chars().map(screen::char_width).sum::<usize>();
+    let left_lines: Vec<&str> = left.lines().collect();
+    let right_lines: Vec<&str> = right.lines().collect();
+    let column_width = |title: &str, lines: &[&str]| lines.iter().map(|line| width(line)).chain([width(title)]).max();
+    let column = column_width(left_title, &left_lines).unwrap_or(0);
+    let right_column = column_width(right_title, &right_lines).unwrap_or(0);
+
+    let mut result = format!("{}{} │ {}\n", left_title, " ".repeat(column - width(left_title)), right_title);
+    result.push_str(&format!("{}─┼─{}\n", "─".repeat(column), "─".repeat(right_column)));
+    for row in 0..left_lines.len().max(right_lines.len()) {
+        let left_line = left_lines.get(row).copied().unwrap_or("");
+        let right_line = right_lines.get(row).copied().unwrap_or("");
+        let line = format!("{}{} │ {}", left_line, " ".repeat(column - width(left_line)), right_line);
+        result.push_str(line.trim_end());
+        result.push('\n');
+    }
+    result.pop();
+    result
+}
+
+// 用本地的 rustc 编译一段代码，返回编译器输出的诊断信息
+// 代码写入临时目录中的 <名称>.rs，错误信息中的文件名就是示例的名称
+fn compile(name: &str, code: &str) -> io::Result<Vec<Diagnostic>> {
+    let dir = env::temp_dir().join(format!("rustlearn-errors-{}-{}", process::id(), name));
+    fs::create_dir_all(&dir)?;
+    let file = format!("{}.rs", name);
+    fs::write(dir.join(&file), code)?;
+
+    let output = rustc(&dir)
+        .args(["--edition", "2024", "--crate-type", "lib", "--emit=metadata", "--error-format=json", "-A", "warnings"])
+        .args(["--out-dir", "."])
+        .arg(&file)
+        .output();
+    let _ = fs::remove_dir_all(&dir);
+    let output = output.map_err(not_found)?;
+
+    Ok(String::from_utf8_lossy(&output.stderr).lines().filter_map(parse_diagnostic).collect())
+}
+
+// 调用 rustc 的命令
+fn rustc(dir: &Path) -> Command {
+    let mut command = Command::new(env::var_os("RUSTC").unwrap_or_else(|| "rustc".into()));
+    command.current_dir(dir);
+    command
+}
+
+// 找不到 rustc 时给出安装提示
+fn not_found(error: io::Error) -> io::Error {
+    if error.kind() == ErrorKind::NotFound {
+        io::Error::new(
+            ErrorKind::NotFound,
+            tr("找不到 rustc，请先安装 Rust 工具链，或者通过 RUSTC 环境变量指定").into_owned(),
+        )
+    } else {
+        error
+    }
+}
+
+// rustc 的版本，例如 "rustc 1.85.0 (4d91de4e4 2025-02-17)"
+fn rustc_version() -> String {
+    rustc(&env::temp_dir())
+        .arg("--version")
+        .output()
+        .ok()
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .filter(|version| !version.is_empty())
+        .unwrap_or_else(|| String::from("rustc"))
+}
+
+// 解析编译器输出的一行 JSON，"aborting due to ..." 这样的汇总信息不显示
+fn parse_diagnostic(line: &str) -> Option<Diagnostic> {
+    let value = json::parse(line).ok()?;
+    let message = value.get("message")?.as_str()?;
+    if message.starts_with("aborting due to") {
+        return None;
+    }
+
+    Some(Diagnostic {
+        level: value.get("level")?.as_str()?.to_string(),
+        code: value.get("code").and_then(|code| code.get("code")).and_then(|code| code.as_str()).map(str::to_string),
+        rendered: value.get("rendered")?.as_str()?.to_string(),
+    })
+}
+
+// 检查所有示例，返回进程退出码
+fn check_all() -> i32 {
+    let mut problems = 0;
+    let mut total = 0;
+    let mut text = String::new();
+
+    for lesson in lesson::LESSONS {
+        for (index, example) in lesson.compile_errors().iter().enumerate() {
+            total += 1;
+            let id = format!("{}.{}", lesson.id(), index + 1);
+            match check(example) {
+                Ok(None) => text.push_str(&format!("✓ {} {}\n", id, example.code)),
+                Ok(Some(problem)) => {
+                    text.push_str(&format!("✗ {} {}：{}\n", id, example.code, problem));
+                    problems += 1;
+                }
+                Err(error) => {
+                    cli::print(&text);
+                    eprintln!("{}", tr_fmt("无法编译示例代码: {}", &[&error]));
+                    return 1;
+                }
+            }
+        }
+    }
+
+    if problems == 0 {
+        text.push_str(&format!("\n{}\n", tr_fmt("{} 段示例代码都符合预期", &[&total])));
+        cli::print(&text)
+    } else {
+        text.push_str(&format!("\n{}\n", tr_fmt("{} 段示例代码中有 {} 段不符合预期", &[&total, &problems])));
+        cli::print(&text);
+        1
+    }
+}
+
+// 检查一段示例：有错误的代码产生预期的错误代码，修改之后的代码没有错误
+fn check(example: &CompileError) -> io::Result<Option<String>> {
+    verify(example.name, example.code, example.broken, example.fixed)
+}
+
+// 编译一段有错误的代码和修改之后的代码，有错误的代码没有产生错误代码 code，
+// 或者修改之后的代码仍然有错误时，返回问题的说明
+pub fn verify(name: &str, code: &str, broken: &str, fixed: &str) -> io::Result<Option<String>> {
+    let broken = error_codes(name, broken)?;
+    if !broken.iter().any(|actual| actual == code) {
+        let actual = if broken.is_empty() { tr("没有错误").into_owned() } else { broken.join(", ") };
+        return Ok(Some(tr_fmt("有错误的代码没有产生 {}，实际是 {}", &[&code, &actual])));
+    }
+
+    let fixed = error_codes(&format!("{}_fixed", name), fixed)?;
+    if !fixed.is_empty() {
+        return Ok(Some(tr_fmt("修改之后的代码仍然有错误：{}", &[&fixed.join(", ")])));
+    }
+
+    Ok(None)
+}
+
+// 编译一段代码，返回编译器报告的错误代码，没有错误代码的错误记作 "error"
+pub fn error_codes(name: &str, code: &str) -> io::Result<Vec<String>> {
+    Ok(compile(name, code)?
+        .into_iter()
+        .filter(|diagnostic| diagnostic.level == "error")
+        .map(|diagnostic| diagnostic.code.unwrap_or_else(|| String::from("error")))
+        .collect())
+}
+
+// 某个错误代码对应的示例编号，例如 E0502 对应 ["6.2"]
+pub fn examples_for(code: &str) -> Vec<String> {
+    find(code).into_iter().map(|(lesson, number, _)| format!("{}.{}", lesson.id(), number)).collect()
+}
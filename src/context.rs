@@ -0,0 +1,169 @@
+// 课程输出上下文
+// 所有课程的示例代码都不直接使用 println! 打印到标准输出，而是写入 LessonContext
+// 这样命令行菜单、测试以及其他前端都可以决定输出写到哪里、以什么样式显示：
+// 写到终端、写到内存缓冲区里捕获下来，或者给标题加上颜色
+// 选择中文以外的语言时，输出会以行为单位通过消息目录翻译，课程代码中仍然只写中文
+// 打开显示代码之后，每个小节标题的下面会先显示这个小节的源代码，然后才是运行结果
+
+use std::fmt;
+use std::io::{self, Write};
+
+use crate::highlight::TokenKind;
+use crate::i18n::{self, Locale, tr};
+use crate::lesson::Lesson;
+use crate::source;
+
+// 输出样式钩子
+// 课程中的标题和显示的代码通过这里生成最终显示的文本，不同前端可以提供自己的实现，
+// 例如 theme::Theme 按终端配色主题加上颜色
+pub trait Style {
+    // 课程标题，例如 "=== 第1课：变量和数据类型 ==="
+    fn lesson_heading(&self, text: &str) -> String {
+        format!("=== {} ===", text)
+    }
+
+    // 小节标题，例如 "--- 变量的可变性与不可变性 ---"
+    fn section_heading(&self, text: &str) -> String {
+        format!("--- {} ---", text)
+    }
+
+    // 标签，例如显示代码时的 "运行结果："
+    fn label(&self, text: &str) -> String {
+        text.to_string()
+    }
+
+    // 源代码的行号栏，例如 "226 |"
+    fn gutter(&self, text: &str) -> String {
+        text.to_string()
+    }
+
+    // 代码中的一个片段，kind 是语法高亮时的类别
+    fn code(&self, _kind: TokenKind, text: &str) -> String {
+        text.to_string()
+    }
+}
+
+// 不带任何修饰的默认样式
+pub struct PlainStyle;
+
+impl Style for PlainStyle {}
+
+// 课程运行时使用的输出上下文
+pub struct LessonContext<'a> {
+    out: &'a mut dyn Write,
+    style: &'a dyn Style,
+    // 输出使用的语言
+    locale: Locale,
+    // 需要翻译时，还没有写完的一行先暂存在这里，等到换行时整行翻译
+    pending: String,
+    // 第一次写入失败时的错误，之后的写入都会被忽略
+    error: Option<io::Error>,
+    // 需要在小节标题下面显示源代码的课程
+    code: Option<&'a dyn Lesson>,
+}
+
+impl<'a> LessonContext<'a> {
+    // 使用默认样式创建上下文
+    pub fn new(out: &'a mut dyn Write) -> Self {
+        Self::with_style(out, &PlainStyle)
+    }
+
+    // 使用指定的样式创建上下文
+    pub fn with_style(out: &'a mut dyn Write, style: &'a dyn Style) -> Self {
+        LessonContext {
+            out,
+            style,
+            locale: i18n::current(),
+            pending: String::new(),
+            error: None,
+            code: None,
+        }
+    }
+
+    // 指定输出使用的语言，默认使用程序启动时选择的语言
+    pub fn with_locale(mut self, locale: Locale) -> Self {
+        self.locale = locale;
+        self
+    }
+
+    // 在接下来运行的小节标题下面显示这一课的源代码
+    pub fn show_code(&mut self, lesson: &'a dyn Lesson) {
+        self.code = Some(lesson);
+    }
+
+    // 供 write! 和 writeln! 宏调用
+    // 课程代码中的 writeln!(ctx, ...) 不需要处理返回值，写入错误会记录下来，
+    // 由运行课程的前端通过 finish() 统一处理
+    pub fn write_fmt(&mut self, args: fmt::Arguments) {
+        if self.locale == Locale::Zh {
+            self.emit(args);
+            return;
+        }
+
+        self.pending.push_str(&args.to_string());
+        while let Some(end) = self.pending.find('\n') {
+            let line: String = self.pending.drain(..=end).collect();
+            let translated = self.translate(&line[..end]);
+            self.emit(format_args!("{}\n", translated));
+        }
+    }
+
+    // 输出课程标题，标题文字先翻译再套用样式
+    pub fn lesson_heading(&mut self, text: &str) {
+        let heading = self.style.lesson_heading(&self.translate(text));
+        self.flush_pending();
+        self.emit(format_args!("{}\n", heading));
+    }
+
+    // 输出小节标题，标题前空一行
+    pub fn section_heading(&mut self, text: &str) {
+        let heading = self.style.section_heading(&self.translate(text));
+        self.flush_pending();
+        self.emit(format_args!("\n{}\n", heading));
+
+        // 小节标题与注册表中的小节标题一致，据此找到对应的小节
+        let code = self.code.and_then(|lesson| {
+            let section = lesson.sections().iter().find(|section| section.title == text)?;
+            source::render(lesson, section, self.style)
+        });
+        if let Some(code) = code {
+            let label = self.style.label(&tr("运行结果："));
+            self.emit(format_args!("{}{}\n", code, label));
+        }
+    }
+
+    // 结束输出，刷新缓冲区并返回过程中遇到的第一个写入错误
+    pub fn finish(mut self) -> io::Result<()> {
+        self.flush_pending();
+        match self.error.take() {
+            Some(error) => Err(error),
+            None => self.out.flush(),
+        }
+    }
+
+    // 把一行文字翻译成当前语言，没有译文时保留原文
+    fn translate(&self, text: &str) -> String {
+        if self.locale == Locale::Zh || !i18n::needs_translation(text) {
+            return text.to_string();
+        }
+        i18n::translate(self.locale, text).unwrap_or_else(|| text.to_string())
+    }
+
+    // 输出暂存的不完整的一行
+    fn flush_pending(&mut self) {
+        if !self.pending.is_empty() {
+            let line = std::mem::take(&mut self.pending);
+            let translated = self.translate(&line);
+            self.emit(format_args!("{}", translated));
+        }
+    }
+
+    // 直接写入输出，不再翻译
+    fn emit(&mut self, args: fmt::Arguments) {
+        if self.error.is_none()
+            && let Err(error) = self.out.write_fmt(args)
+        {
+            self.error = Some(error);
+        }
+    }
+}
@@ -0,0 +1,1065 @@
+// 生命周期省略规则的演示
+// 学习者输入一个函数或方法的签名，例如 `fn get_name(&self, other: &str) -> &str`，
+// 这里解析签名，按照编译器的三条省略规则一步一步推断生命周期，最后给出补全了生命周期的完整签名；
+// 推断不出来时（例如 longest），说明是哪一条规则都不适用，以及应该怎样手动标注
+//
+// 三条省略规则：
+// 1. 参数中每一个省略了生命周期的位置（&T、&mut T 以及 '_）都得到一个独立的生命周期参数
+// 2. 如果参数中只有一个生命周期（无论是省略的还是写出来的），它被赋给返回值中所有省略的生命周期
+// 3. 如果有 &self 或 &mut self 参数，self 的生命周期被赋给返回值中所有省略的生命周期
+//
+// 签名由这里自己的解析器解析，支持引用、裸指针、路径和泛型参数、元组、切片、数组、dyn/impl Trait、
+// fn 指针和 Fn(..) 语法；fn 指针和 Fn(..) 中的生命周期属于它们自己，不参与外层函数的省略
+// 结构体隐藏的生命周期参数（例如写成 Formatter 的 Formatter<'_>）无法从签名中看出来，不在推断范围内
+
+use std::fmt::{self, Display};
+
+use crate::i18n::{tr, tr_fmt};
+use crate::menu;
+
+// 词法单元
+#[derive(Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    // 不带前面的单引号，例如 'a 保存为 "a"
+    Lifetime(String),
+    // 数字或字符串字面量，保留原始写法
+    Literal(String),
+    Punct(&'static str),
+}
+
+impl Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Token::Ident(text) | Token::Literal(text) => write!(f, "\"{}\"", text),
+            Token::Lifetime(name) => write!(f, "\"'{}\"", name),
+            Token::Punct(text) => write!(f, "\"{}\"", text),
+        }
+    }
+}
+
+// 词法单元和它在签名中的字节范围
+struct Spanned {
+    token: Token,
+    start: usize,
+    end: usize,
+}
+
+// 标点符号，多个字符的写在前面
+const PUNCTUATION: [&str; 22] = [
+    "->", "::", "&", "(", ")", "<", ">", ",", ":", ";", "[", "]", "{", "}", "+", "=", "!", "*", "?", "#", ".", "-",
+];
+
+// 把签名切分成词法单元
+fn tokenize(text: &str) -> Result<Vec<Spanned>, String> {
+    let mut tokens = Vec::new();
+    let mut rest = text.char_indices().peekable();
+
+    while let Some(&(start, c)) = rest.peek() {
+        if c.is_whitespace() {
+            rest.next();
+            continue;
+        }
+
+        let token = if c.is_alphanumeric() || c == '_' || c == '\'' {
+            // 标识符、生命周期和数字
+            let mut word = String::new();
+            word.push(c);
+            rest.next();
+            while let Some(&(_, c)) = rest.peek() {
+                if !(c.is_alphanumeric() || c == '_') {
+                    break;
+                }
+                word.push(c);
+                rest.next();
+            }
+            match word.strip_prefix('\'') {
+                Some("") => return Err(tr("单引号后面缺少生命周期的名称").into_owned()),
+                Some(name) => Token::Lifetime(name.to_string()),
+                None if c.is_ascii_digit() => Token::Literal(word),
+                None => Token::Ident(word),
+            }
+        } else if c == '"' {
+            let mut literal = String::from('"');
+            rest.next();
+            for (_, c) in rest.by_ref() {
+                literal.push(c);
+                if c == '"' {
+                    break;
+                }
+            }
+            Token::Literal(literal)
+        } else {
+            let punct = PUNCTUATION
+                .iter()
+                .find(|punct| text[start..].starts_with(**punct))
+                .ok_or_else(|| tr_fmt("无法识别的字符：{}", &[&c]))?;
+            for _ in 0..punct.len() {
+                rest.next();
+            }
+            Token::Punct(punct)
+        };
+
+        let end = rest.peek().map_or(text.len(), |&(end, _)| end);
+        tokens.push(Spanned { token, start, end });
+    }
+
+    Ok(tokens)
+}
+
+// 一个生命周期位置
+#[derive(Clone, PartialEq)]
+enum Lifetime {
+    // 省略不写，例如 &str
+    Elided,
+    // 写成 '_
+    Anonymous,
+    Named(String),
+}
+
+impl Lifetime {
+    // 需要由省略规则推断的位置
+    fn is_elided(&self) -> bool {
+        matches!(self, Lifetime::Elided | Lifetime::Anonymous)
+    }
+}
+
+impl Display for Lifetime {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Lifetime::Elided => Ok(()),
+            Lifetime::Anonymous => write!(f, "'_"),
+            Lifetime::Named(name) => write!(f, "'{}", name),
+        }
+    }
+}
+
+// 类型
+#[derive(Clone)]
+enum Type {
+    Reference { lifetime: Lifetime, mutable: bool, inner: Box<Type> },
+    Pointer { mutable: bool, inner: Box<Type> },
+    Path { global: bool, segments: Vec<Segment> },
+    Tuple(Vec<Type>),
+    // 括号中的类型，例如 &(dyn Display + Send)
+    Paren(Box<Type>),
+    Slice(Box<Type>),
+    // 数组的长度保留原始写法
+    Array(Box<Type>, String),
+    // dyn Trait 或 impl Trait
+    Bounds { keyword: &'static str, bounds: Vec<Bound> },
+    // fn 指针
+    Function { inputs: Vec<Type>, output: Option<Box<Type>> },
+    Never,
+    Infer,
+}
+
+// 路径中的一段，例如 Vec<&str> 或 Fn(&str) -> bool
+#[derive(Clone)]
+struct Segment {
+    name: String,
+    args: GenericArgs,
+}
+
+#[derive(Clone)]
+enum GenericArgs {
+    None,
+    Angle(Vec<GenericArg>),
+    // Fn、FnMut、FnOnce 的括号写法
+    Parenthesized { inputs: Vec<Type>, output: Option<Box<Type>> },
+}
+
+#[derive(Clone)]
+enum GenericArg {
+    Lifetime(Lifetime),
+    Type(Type),
+    // 关联类型，例如 Item = &str
+    Binding(String, Type),
+    Const(String),
+}
+
+#[derive(Clone)]
+enum Bound {
+    Lifetime(Lifetime),
+    Trait { maybe: bool, path: Box<Type> },
+}
+
+impl Type {
+    // 对属于这个函数签名的每一个生命周期位置调用 visit
+    // fn 指针和 Fn(..) 中的生命周期属于它们自己，不包括在内
+    fn visit_lifetimes(&mut self, visit: &mut dyn FnMut(&mut Lifetime)) {
+        match self {
+            Type::Reference { lifetime, inner, .. } => {
+                visit(lifetime);
+                inner.visit_lifetimes(visit);
+            }
+            Type::Pointer { inner, .. } | Type::Paren(inner) | Type::Slice(inner) | Type::Array(inner, _) => {
+                inner.visit_lifetimes(visit)
+            }
+            Type::Path { segments, .. } => {
+                for segment in segments {
+                    if let GenericArgs::Angle(args) = &mut segment.args {
+                        for arg in args {
+                            match arg {
+                                GenericArg::Lifetime(lifetime) => visit(lifetime),
+                                GenericArg::Type(ty) | GenericArg::Binding(_, ty) => ty.visit_lifetimes(visit),
+                                GenericArg::Const(_) => {}
+                            }
+                        }
+                    }
+                }
+            }
+            Type::Tuple(items) => {
+                for item in items {
+                    item.visit_lifetimes(visit);
+                }
+            }
+            Type::Bounds { bounds, .. } => {
+                for bound in bounds {
+                    match bound {
+                        Bound::Lifetime(lifetime) => visit(lifetime),
+                        Bound::Trait { path, .. } => path.visit_lifetimes(visit),
+                    }
+                }
+            }
+            Type::Function { .. } | Type::Never | Type::Infer => {}
+        }
+    }
+
+    // 是否是 Self
+    fn is_self(&self) -> bool {
+        matches!(self, Type::Path { global: false, segments } if segments.len() == 1 && segments[0].name == "Self")
+    }
+}
+
+// 用逗号分隔的列表
+fn comma_list<T: Display>(items: &[T]) -> String {
+    items.iter().map(|item| item.to_string()).collect::<Vec<_>>().join(", ")
+}
+
+// 括号写法的参数和返回值，例如 (&str, i32) -> bool
+fn fn_sugar(inputs: &[Type], output: &Option<Box<Type>>) -> String {
+    match output {
+        Some(output) => format!("({}) -> {}", comma_list(inputs), output),
+        None => format!("({})", comma_list(inputs)),
+    }
+}
+
+impl Display for Type {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Type::Reference { lifetime, mutable, inner } => {
+                write!(f, "&")?;
+                if *lifetime != Lifetime::Elided {
+                    write!(f, "{} ", lifetime)?;
+                }
+                if *mutable {
+                    write!(f, "mut ")?;
+                }
+                write!(f, "{}", inner)
+            }
+            Type::Pointer { mutable, inner } => write!(f, "*{} {}", if *mutable { "mut" } else { "const" }, inner),
+            Type::Path { global, segments } => {
+                if *global {
+                    write!(f, "::")?;
+                }
+                let segments: Vec<String> = segments.iter().map(|segment| segment.to_string()).collect();
+                write!(f, "{}", segments.join("::"))
+            }
+            Type::Tuple(items) if items.len() == 1 => write!(f, "({},)", items[0]),
+            Type::Tuple(items) => write!(f, "({})", comma_list(items)),
+            Type::Paren(inner) => write!(f, "({})", inner),
+            Type::Slice(inner) => write!(f, "[{}]", inner),
+            Type::Array(inner, length) => write!(f, "[{}; {}]", inner, length),
+            Type::Bounds { keyword, bounds } => {
+                let bounds: Vec<String> = bounds.iter().map(|bound| bound.to_string()).collect();
+                write!(f, "{} {}", keyword, bounds.join(" + "))
+            }
+            Type::Function { inputs, output } => write!(f, "fn{}", fn_sugar(inputs, output)),
+            Type::Never => write!(f, "!"),
+            Type::Infer => write!(f, "_"),
+        }
+    }
+}
+
+impl Display for Segment {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.args {
+            GenericArgs::None => write!(f, "{}", self.name),
+            GenericArgs::Angle(args) => write!(f, "{}<{}>", self.name, comma_list(args)),
+            GenericArgs::Parenthesized { inputs, output } => write!(f, "{}{}", self.name, fn_sugar(inputs, output)),
+        }
+    }
+}
+
+impl Display for GenericArg {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GenericArg::Lifetime(lifetime) => write!(f, "{}", lifetime),
+            GenericArg::Type(ty) => write!(f, "{}", ty),
+            GenericArg::Binding(name, ty) => write!(f, "{} = {}", name, ty),
+            GenericArg::Const(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+impl Display for Bound {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Bound::Lifetime(lifetime) => write!(f, "{}", lifetime),
+            Bound::Trait { maybe: true, path } => write!(f, "?{}", path),
+            Bound::Trait { maybe: false, path } => write!(f, "{}", path),
+        }
+    }
+}
+
+// 函数的泛型参数
+#[derive(Clone)]
+enum GenericParam {
+    // 生命周期参数的名称和原始写法，例如 ("a", "'a: 'b")
+    Lifetime(String, String),
+    // 类型参数和常量参数保留原始写法
+    Other(String),
+}
+
+// 一个参数
+#[derive(Clone)]
+struct Param {
+    // 参数的模式，例如 "name"、"mut count" 或者 "self"
+    pattern: String,
+    ty: Type,
+    // self、&self、&mut self 这样的简写
+    shorthand: bool,
+}
+
+impl Param {
+    // &self、&mut self 或者 self: &Self 这样借用 self 的参数
+    fn borrows_self(&self) -> Option<&Lifetime> {
+        match &self.ty {
+            Type::Reference { lifetime, inner, .. } if self.pattern.ends_with("self") && inner.is_self() => Some(lifetime),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Param {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match (&self.ty, self.shorthand) {
+            (Type::Reference { lifetime, mutable, .. }, true) => {
+                write!(f, "&")?;
+                if *lifetime != Lifetime::Elided {
+                    write!(f, "{} ", lifetime)?;
+                }
+                write!(f, "{}{}", if *mutable { "mut " } else { "" }, self.pattern)
+            }
+            (_, true) => write!(f, "{}", self.pattern),
+            (ty, false) => write!(f, "{}: {}", self.pattern, ty),
+        }
+    }
+}
+
+// 函数签名
+#[derive(Clone)]
+struct Signature {
+    // fn 之前的 pub、const、async、unsafe 等
+    prefix: String,
+    name: String,
+    generics: Vec<GenericParam>,
+    params: Vec<Param>,
+    output: Option<Type>,
+    // where 子句保留原始写法
+    where_clause: String,
+}
+
+impl Signature {
+    // 参数中出现的所有生命周期位置
+    fn visit_input_lifetimes(&mut self, visit: &mut dyn FnMut(&mut Lifetime)) {
+        for param in &mut self.params {
+            param.ty.visit_lifetimes(visit);
+        }
+    }
+
+    fn visit_output_lifetimes(&mut self, visit: &mut dyn FnMut(&mut Lifetime)) {
+        if let Some(output) = &mut self.output {
+            output.visit_lifetimes(visit);
+        }
+    }
+
+    // 参数中用到的生命周期，按照出现的顺序排列，重复的只算一次
+    fn input_lifetimes(&self) -> Vec<String> {
+        let mut names: Vec<String> = Vec::new();
+        self.clone().visit_input_lifetimes(&mut |lifetime| {
+            if let Lifetime::Named(name) = lifetime
+                && !names.contains(name)
+            {
+                names.push(name.clone());
+            }
+        });
+        names
+    }
+
+    // 返回值中需要推断的生命周期位置的数量
+    fn elided_outputs(&self) -> usize {
+        let mut count = 0;
+        self.clone().visit_output_lifetimes(&mut |lifetime| {
+            if lifetime.is_elided() {
+                count += 1;
+            }
+        });
+        count
+    }
+
+    // 签名中写出来的所有生命周期名称，包括泛型参数中声明的
+    fn named_lifetimes(&self) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .generics
+            .iter()
+            .filter_map(|param| match param {
+                GenericParam::Lifetime(name, _) => Some(name.clone()),
+                GenericParam::Other(_) => None,
+            })
+            .collect();
+        let mut copy = self.clone();
+        let mut collect = |lifetime: &mut Lifetime| {
+            if let Lifetime::Named(name) = lifetime
+                && !names.contains(name)
+            {
+                names.push(name.clone());
+            }
+        };
+        copy.visit_input_lifetimes(&mut collect);
+        copy.visit_output_lifetimes(&mut collect);
+        names
+    }
+
+    // 把生命周期参数加到泛型参数中，放在已有的生命周期参数之后
+    fn declare_lifetime(&mut self, name: &str) {
+        let position = self
+            .generics
+            .iter()
+            .rposition(|param| matches!(param, GenericParam::Lifetime(..)))
+            .map_or(0, |index| index + 1);
+        self.generics.insert(position, GenericParam::Lifetime(name.to_string(), format!("'{}", name)));
+    }
+}
+
+impl Display for Signature {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if !self.prefix.is_empty() {
+            write!(f, "{} ", self.prefix)?;
+        }
+        write!(f, "fn {}", self.name)?;
+        if !self.generics.is_empty() {
+            let generics: Vec<&str> = self
+                .generics
+                .iter()
+                .map(|param| match param {
+                    GenericParam::Lifetime(_, text) | GenericParam::Other(text) => text.as_str(),
+                })
+                .collect();
+            write!(f, "<{}>", generics.join(", "))?;
+        }
+        write!(f, "({})", comma_list(&self.params))?;
+        if let Some(output) = &self.output {
+            write!(f, " -> {}", output)?;
+        }
+        if !self.where_clause.is_empty() {
+            write!(f, " {}", self.where_clause)?;
+        }
+        Ok(())
+    }
+}
+
+// 签名的解析器
+struct Parser<'s> {
+    source: &'s str,
+    tokens: Vec<Spanned>,
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn peek_at(&self, offset: usize) -> Option<&Token> {
+        self.tokens.get(self.pos + offset).map(|spanned| &spanned.token)
+    }
+
+    // 当前的词法单元是不是某个标点符号或关键字
+    fn is_at(&self, offset: usize, text: &str) -> bool {
+        match self.peek_at(offset) {
+            Some(Token::Punct(punct)) => *punct == text,
+            Some(Token::Ident(word)) => word == text,
+            _ => false,
+        }
+    }
+
+    fn is(&self, text: &str) -> bool {
+        self.is_at(0, text)
+    }
+
+    fn eat(&mut self, text: &str) -> bool {
+        let found = self.is(text);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn expect(&mut self, text: &str) -> Result<(), String> {
+        if self.eat(text) { Ok(()) } else { Err(self.unexpected(&format!("\"{}\"", text))) }
+    }
+
+    // 遇到了不应该出现的内容
+    fn unexpected(&self, expected: &str) -> String {
+        match self.peek_at(0) {
+            Some(token) => tr_fmt("应该是 {}，但是遇到了 {}", &[&expected, token]),
+            None => tr_fmt("应该是 {}，但是签名已经结束了", &[&expected]),
+        }
+    }
+
+    fn ident(&mut self) -> Result<String, String> {
+        match self.peek_at(0) {
+            Some(Token::Ident(name)) => {
+                let name = name.clone();
+                self.pos += 1;
+                Ok(name)
+            }
+            _ => Err(self.unexpected(&tr("名称"))),
+        }
+    }
+
+    fn lifetime(&mut self) -> Option<Lifetime> {
+        match self.peek_at(0) {
+            Some(Token::Lifetime(name)) => {
+                let lifetime = if name == "_" { Lifetime::Anonymous } else { Lifetime::Named(name.clone()) };
+                self.pos += 1;
+                Some(lifetime)
+            }
+            _ => None,
+        }
+    }
+
+    // 当前词法单元开始的位置
+    fn offset(&self) -> usize {
+        self.tokens.get(self.pos).map_or(self.source.len(), |spanned| spanned.start)
+    }
+
+    // 跳过括号之外遇到 stops 中任何一个之前的内容，返回跳过部分的原始写法
+    fn skip_until(&mut self, stops: &[&str]) -> String {
+        let start = self.offset();
+        let mut end = start;
+        let mut depth = 0;
+        while let Some(spanned) = self.tokens.get(self.pos) {
+            if depth == 0 && stops.iter().any(|stop| self.is(stop)) {
+                break;
+            }
+            match spanned.token {
+                Token::Punct("<" | "(" | "[" | "{") => depth += 1,
+                Token::Punct(">" | ")" | "]" | "}") => depth -= 1,
+                _ => {}
+            }
+            end = spanned.end;
+            self.pos += 1;
+        }
+        self.source[start..end].trim().to_string()
+    }
+
+    fn signature(&mut self) -> Result<Signature, String> {
+        // fn 之前的 pub、pub(crate)、const、async、unsafe、extern "C" 原样保留
+        let mut prefix_end = 0;
+        while !self.is("fn") {
+            let Some(spanned) = self.tokens.get(self.pos) else {
+                return Err(tr("签名中缺少 fn 关键字").into_owned());
+            };
+            prefix_end = spanned.end;
+            self.pos += 1;
+        }
+        let prefix = self.source[..prefix_end].trim().to_string();
+        self.expect("fn")?;
+        let name = self.ident()?;
+        let generics = if self.is("<") { self.generics()? } else { Vec::new() };
+
+        self.expect("(")?;
+        let mut params = Vec::new();
+        while !self.eat(")") {
+            params.push(self.param()?);
+            if !self.eat(",") {
+                self.expect(")")?;
+                break;
+            }
+        }
+
+        let output = if self.eat("->") { Some(self.ty()?) } else { None };
+        let where_clause = if self.is("where") { self.skip_until(&["{", ";"]) } else { String::new() };
+
+        // 签名后面可以带着分号或者函数体的开头
+        if !self.eat(";")
+            && !self.is("{")
+            && let Some(token) = self.peek_at(0)
+        {
+            return Err(tr_fmt("签名之后还有多余的内容：{}", &[token]));
+        }
+
+        Ok(Signature { prefix, name, generics, params, output, where_clause })
+    }
+
+    fn generics(&mut self) -> Result<Vec<GenericParam>, String> {
+        self.expect("<")?;
+        let mut generics = Vec::new();
+        while !self.eat(">") {
+            let start = self.offset();
+            let param = match self.lifetime() {
+                Some(Lifetime::Named(name)) => {
+                    self.skip_until(&[",", ">"]);
+                    let text = self.source[start..self.offset()].trim().trim_end_matches(',').trim().to_string();
+                    GenericParam::Lifetime(name, text)
+                }
+                Some(_) => return Err(self.unexpected(&tr("生命周期参数的名称"))),
+                None => {
+                    let text = self.skip_until(&[",", ">"]);
+                    if text.is_empty() {
+                        return Err(self.unexpected(&tr("泛型参数")));
+                    }
+                    GenericParam::Other(text)
+                }
+            };
+            generics.push(param);
+            if !self.eat(",") {
+                self.expect(">")?;
+                break;
+            }
+        }
+        Ok(generics)
+    }
+
+    fn param(&mut self) -> Result<Param, String> {
+        let self_type = || Box::new(Type::Path { global: false, segments: vec![Segment { name: String::from("Self"), args: GenericArgs::None }] });
+
+        // &self、&'a mut self
+        if self.is("&") {
+            let start = self.pos;
+            self.pos += 1;
+            let lifetime = self.lifetime().unwrap_or(Lifetime::Elided);
+            let mutable = self.eat("mut");
+            if self.eat("self") {
+                let ty = Type::Reference { lifetime, mutable, inner: self_type() };
+                return Ok(Param { pattern: String::from("self"), ty, shorthand: true });
+            }
+            self.pos = start;
+        }
+
+        // self、mut self，或者 self: Box<Self> 这样写出类型的 self
+        if self.is("self") || (self.is("mut") && self.is_at(1, "self")) {
+            let pattern = if self.eat("mut") { "mut self" } else { "self" };
+            self.pos += 1;
+            if self.eat(":") {
+                return Ok(Param { pattern: pattern.to_string(), ty: self.ty()?, shorthand: false });
+            }
+            return Ok(Param { pattern: pattern.to_string(), ty: *self_type(), shorthand: true });
+        }
+
+        let pattern = self.skip_until(&[":", ",", ")"]);
+        if pattern.is_empty() {
+            return Err(self.unexpected(&tr("参数")));
+        }
+        self.expect(":")?;
+        Ok(Param { pattern, ty: self.ty()?, shorthand: false })
+    }
+
+    fn ty(&mut self) -> Result<Type, String> {
+        if self.eat("&") {
+            let lifetime = self.lifetime().unwrap_or(Lifetime::Elided);
+            let mutable = self.eat("mut");
+            return Ok(Type::Reference { lifetime, mutable, inner: Box::new(self.ty()?) });
+        }
+        if self.eat("*") {
+            let mutable = self.eat("mut");
+            if !mutable {
+                self.expect("const")?;
+            }
+            return Ok(Type::Pointer { mutable, inner: Box::new(self.ty()?) });
+        }
+        if self.eat("(") {
+            let mut items = Vec::new();
+            let mut trailing_comma = false;
+            while !self.eat(")") {
+                items.push(self.ty()?);
+                trailing_comma = self.eat(",");
+                if !trailing_comma {
+                    self.expect(")")?;
+                    break;
+                }
+            }
+            return Ok(match items.len() {
+                1 if !trailing_comma => Type::Paren(Box::new(items.remove(0))),
+                _ => Type::Tuple(items),
+            });
+        }
+        if self.eat("[") {
+            let inner = Box::new(self.ty()?);
+            if self.eat(";") {
+                let length = self.skip_until(&["]"]);
+                self.expect("]")?;
+                return Ok(Type::Array(inner, length));
+            }
+            self.expect("]")?;
+            return Ok(Type::Slice(inner));
+        }
+        if self.eat("!") {
+            return Ok(Type::Never);
+        }
+        if self.eat("_") {
+            return Ok(Type::Infer);
+        }
+        for keyword in ["dyn", "impl"] {
+            if self.eat(keyword) {
+                return Ok(Type::Bounds { keyword, bounds: self.bounds()? });
+            }
+        }
+        if self.eat("fn") {
+            let (inputs, output) = self.fn_sugar()?;
+            return Ok(Type::Function { inputs, output });
+        }
+        if self.is("for") {
+            return Err(tr_fmt("暂不支持 {} 这样的写法", &[&"for<'a>"]));
+        }
+        if self.is("<") {
+            return Err(tr_fmt("暂不支持 {} 这样的写法", &[&"<T as Trait>::Item"]));
+        }
+        if self.is("::") || matches!(self.peek_at(0), Some(Token::Ident(_))) {
+            return self.path();
+        }
+        Err(self.unexpected(&tr("类型")))
+    }
+
+    // 括号中的参数类型和可选的返回值类型
+    fn fn_sugar(&mut self) -> Result<(Vec<Type>, Option<Box<Type>>), String> {
+        self.expect("(")?;
+        let mut inputs = Vec::new();
+        while !self.eat(")") {
+            inputs.push(self.ty()?);
+            if !self.eat(",") {
+                self.expect(")")?;
+                break;
+            }
+        }
+        let output = if self.eat("->") { Some(Box::new(self.ty()?)) } else { None };
+        Ok((inputs, output))
+    }
+
+    fn path(&mut self) -> Result<Type, String> {
+        let global = self.eat("::");
+        let mut segments = Vec::new();
+        loop {
+            let name = self.ident()?;
+            let args = if self.is("<") || (self.is("::") && self.is_at(1, "<")) {
+                self.eat("::");
+                GenericArgs::Angle(self.generic_args()?)
+            } else if self.is("(") {
+                let (inputs, output) = self.fn_sugar()?;
+                GenericArgs::Parenthesized { inputs, output }
+            } else {
+                GenericArgs::None
+            };
+            segments.push(Segment { name, args });
+
+            if !(self.is("::") && matches!(self.peek_at(1), Some(Token::Ident(_)))) {
+                break;
+            }
+            self.pos += 1;
+        }
+        Ok(Type::Path { global, segments })
+    }
+
+    fn generic_args(&mut self) -> Result<Vec<GenericArg>, String> {
+        self.expect("<")?;
+        let mut args = Vec::new();
+        while !self.eat(">") {
+            let arg = if let Some(lifetime) = self.lifetime() {
+                GenericArg::Lifetime(lifetime)
+            } else if let Some(Token::Literal(value)) = self.peek_at(0) {
+                let value = value.clone();
+                self.pos += 1;
+                GenericArg::Const(value)
+            } else if matches!(self.peek_at(0), Some(Token::Ident(_))) && self.is_at(1, "=") {
+                let name = self.ident()?;
+                self.pos += 1;
+                GenericArg::Binding(name, self.ty()?)
+            } else {
+                GenericArg::Type(self.ty()?)
+            };
+            args.push(arg);
+            if !self.eat(",") {
+                self.expect(">")?;
+                break;
+            }
+        }
+        Ok(args)
+    }
+
+    fn bounds(&mut self) -> Result<Vec<Bound>, String> {
+        let mut bounds = Vec::new();
+        loop {
+            let bound = match self.lifetime() {
+                Some(lifetime) => Bound::Lifetime(lifetime),
+                None => {
+                    let maybe = self.eat("?");
+                    Bound::Trait { maybe, path: Box::new(self.path()?) }
+                }
+            };
+            bounds.push(bound);
+            if !self.eat("+") {
+                return Ok(bounds);
+            }
+        }
+    }
+}
+
+// 解析一个函数或方法的签名
+fn parse(text: &str) -> Result<Signature, String> {
+    let tokens = tokenize(text)?;
+    Parser { source: text, tokens, pos: 0 }.signature()
+}
+
+// 还没有用过的生命周期名称：'a、'b、……、'z，之后是 'a1、'b1、……
+fn fresh_name(used: &[String]) -> String {
+    (0..)
+        .flat_map(|round| {
+            ('a'..='z').map(move |c| if round == 0 { c.to_string() } else { format!("{}{}", c, round) })
+        })
+        .find(|name| !used.contains(name))
+        .unwrap_or_default()
+}
+
+// 生命周期的列表，例如 "'a、'b"
+fn lifetime_list(names: &[String]) -> String {
+    let names: Vec<String> = names.iter().map(|name| format!("'{}", name)).collect();
+    names.join(tr("、").as_ref())
+}
+
+// 按照三条省略规则推断签名中的生命周期，返回每一步的说明
+pub fn explain(text: &str) -> Result<String, String> {
+    let original = parse(text)?;
+    let mut signature = original.clone();
+    let mut used = signature.named_lifetimes();
+    let mut out = format!("{}\n", tr_fmt("函数签名：{}", &[&signature]));
+
+    // 用到了却没有声明的生命周期只能来自外层的 impl 块
+    let declared: Vec<&str> = signature
+        .generics
+        .iter()
+        .filter_map(|param| match param {
+            GenericParam::Lifetime(name, _) => Some(name.as_str()),
+            GenericParam::Other(_) => None,
+        })
+        .collect();
+    let undeclared: Vec<String> = used
+        .iter()
+        .filter(|name| *name != "static" && !declared.contains(&name.as_str()))
+        .cloned()
+        .collect();
+    if !undeclared.is_empty() {
+        out.push_str(&format!(
+            "{}\n",
+            tr_fmt(
+                "注意：{} 没有在函数的泛型参数中声明，需要由外层的 impl 块声明，否则会产生 E0261 错误",
+                &[&lifetime_list(&undeclared)]
+            )
+        ));
+    }
+
+    // 规则一
+    out.push_str(&format!("\n{}\n", tr("规则一：参数中每一个省略的生命周期都得到一个独立的生命周期参数")));
+    let mut fresh = Vec::new();
+    for param in &mut signature.params {
+        let before = param.to_string();
+        param.ty.visit_lifetimes(&mut |lifetime| {
+            if lifetime.is_elided() {
+                let name = fresh_name(&used);
+                used.push(name.clone());
+                fresh.push(name.clone());
+                *lifetime = Lifetime::Named(name);
+            }
+        });
+        let after = param.to_string();
+        if before != after {
+            out.push_str(&format!("  {} → {}\n", before, after));
+        }
+    }
+    for name in &fresh {
+        signature.declare_lifetime(name);
+    }
+
+    let inputs = signature.input_lifetimes();
+    if inputs.is_empty() {
+        out.push_str(&format!("  {}\n", tr("参数中没有任何生命周期")));
+    } else {
+        if fresh.is_empty() {
+            out.push_str(&format!("  {}\n", tr("参数中没有省略的生命周期")));
+        }
+        out.push_str(&format!("  {}\n", tr_fmt("输入生命周期：{}", &[&lifetime_list(&inputs)])));
+    }
+
+    if signature.elided_outputs() == 0 {
+        out.push_str(&format!("\n{}\n", tr("返回值中没有省略的生命周期，不需要使用规则二和规则三")));
+        out.push_str(&format!("\n{}\n  {}\n", tr("完整的签名："), signature));
+        return Ok(out);
+    }
+    // 规则二
+    out.push_str(&format!("\n{}\n", tr("规则二：如果参数中只有一个生命周期，它被赋给返回值中所有省略的生命周期")));
+    let mut resolved = false;
+    match inputs.as_slice() {
+        [only] => {
+            out.push_str(&format!("  {}\n", tr_fmt("适用：唯一的输入生命周期是 '{}", &[only])));
+            out.push_str(&assign_output(&mut signature, only));
+            resolved = true;
+        }
+        [] => out.push_str(&format!("  {}\n", tr("不适用：参数中没有生命周期"))),
+        _ => out.push_str(&format!(
+            "  {}\n",
+            tr_fmt("不适用：参数中有 {} 个生命周期（{}）", &[&inputs.len(), &lifetime_list(&inputs)])
+        )),
+    }
+
+    // 规则三
+    out.push_str(&format!("\n{}\n", tr("规则三：如果有 &self 或 &mut self 参数，self 的生命周期被赋给返回值中所有省略的生命周期")));
+    let receiver = signature.params.iter().find_map(Param::borrows_self).and_then(|lifetime| match lifetime {
+        Lifetime::Named(name) => Some(name.clone()),
+        _ => None,
+    });
+    match receiver {
+        None => out.push_str(&format!("  {}\n", tr("不适用：没有 &self 或 &mut self 参数"))),
+        Some(name) if resolved => out.push_str(&format!(
+            "  {}\n",
+            tr_fmt("不需要：self 的生命周期 '{} 就是唯一的输入生命周期，规则二已经确定了返回值的生命周期", &[&name])
+        )),
+        Some(name) => {
+            out.push_str(&format!("  {}\n", tr_fmt("适用：self 的生命周期是 '{}", &[&name])));
+            out.push_str(&assign_output(&mut signature, &name));
+            resolved = true;
+        }
+    }
+
+    if !resolved {
+        out.push_str(&failure(&original, &inputs));
+        return Ok(out);
+    }
+    out.push_str(&format!("\n{}\n  {}\n", tr("完整的签名："), signature));
+    Ok(out)
+}
+
+// 把返回值中省略的生命周期都换成 name，返回说明这一步的文字
+fn assign_output(signature: &mut Signature, name: &str) -> String {
+    let render = |signature: &Signature| signature.output.as_ref().map(|output| output.to_string()).unwrap_or_default();
+    let before = render(signature);
+    signature.visit_output_lifetimes(&mut |lifetime| {
+        if lifetime.is_elided() {
+            *lifetime = Lifetime::Named(name.to_string());
+        }
+    });
+    format!("  {}\n", tr_fmt("返回值：{} → {}", &[&before, &render(signature)]))
+}
+
+// 三条规则都不适用时，说明原因并给出一种手动标注的写法
+fn failure(original: &Signature, inputs: &[String]) -> String {
+    let mut out = format!("\n{}\n", tr("✗ 无法省略返回值的生命周期，编译器会报告 E0106 错误"));
+    let mut suggestion = original.clone();
+
+    let name = if inputs.is_empty() {
+        out.push_str(&format!(
+            "  {}\n  {}\n",
+            tr("参数中没有任何引用，返回的引用不能借用参数，只能借用静态数据或者函数中创建的值，而后者在函数返回时就被释放了"),
+            tr("返回函数中创建的值时，应该返回拥有所有权的类型，例如用 String 代替 &str；返回静态数据时可以写成：")
+        ));
+        String::from("static")
+    } else {
+        out.push_str(&format!(
+            "  {}\n  {}\n",
+            tr_fmt(
+                "参数中有 {} 个生命周期，也没有 &self 或 &mut self，编译器不知道返回值借用自哪一个参数",
+                &[&inputs.len()]
+            ),
+            tr("需要手动标注，例如让返回值和它可能借用的参数使用同一个生命周期：")
+        ));
+
+        // 每个参数中第一个省略的生命周期（通常就是参数本身的引用）和返回值使用同一个生命周期，
+        // 其余的仍然各自独立，避免把 &mut Vec<&str> 写成 &'a mut Vec<&'a str>
+        let mut used = original.named_lifetimes();
+        let shared = fresh_name(&used);
+        used.push(shared.clone());
+        let mut declared = Vec::new();
+        for param in &mut suggestion.params {
+            let mut first = true;
+            param.ty.visit_lifetimes(&mut |lifetime| {
+                if lifetime.is_elided() {
+                    let name = if first { shared.clone() } else { fresh_name(&used) };
+                    first = false;
+                    used.push(name.clone());
+                    if !declared.contains(&name) {
+                        declared.push(name.clone());
+                    }
+                    *lifetime = Lifetime::Named(name);
+                }
+            });
+        }
+        for name in &declared {
+            suggestion.declare_lifetime(name);
+        }
+
+        // 参数中的生命周期都已经写出来了，就让返回值使用第一个
+        if declared.is_empty() { inputs[0].clone() } else { shared }
+    };
+
+    suggestion.visit_output_lifetimes(&mut |lifetime| {
+        if lifetime.is_elided() {
+            *lifetime = Lifetime::Named(name.clone());
+        }
+    });
+    out.push_str(&format!("    {}\n", suggestion));
+    if !inputs.is_empty() {
+        out.push_str(&format!("  {}\n", tr("这只是其中一种写法，应该让返回值实际借用的那些参数和返回值使用同一个生命周期")));
+    }
+    out.push_str(&format!("  {}\n", tr("更多说明：rustlearn explain E0106")));
+    out
+}
+
+// elision 子命令，返回进程退出码
+// 带着签名时只推断这一个签名，不带参数时进入交互模式，逐行读取签名
+pub fn run_cli(args: &[String]) -> i32 {
+    match args {
+        [] => interactive(),
+        [flag, ..] if flag.starts_with('-') => {
+            eprintln!("{}", tr("用法: rustlearn elision [<函数签名>]"));
+            2
+        }
+        words => match explain(&words.join(" ")) {
+            Ok(text) => {
+                print!("{}", text);
+                0
+            }
+            Err(message) => {
+                eprintln!("{}", tr_fmt("无法解析函数签名：{}", &[&message]));
+                2
+            }
+        },
+    }
+}
+
+// 交互模式，输入结束、输入空行或者 q 时退出
+fn interactive() -> i32 {
+    println!("{}", tr("输入一个函数或方法的签名，查看生命周期省略规则是怎样一步一步应用的"));
+    println!("{}", tr("例如：fn get_name(&self, other: &str) -> &str"));
+
+    loop {
+        println!("\n{}", tr("请输入函数签名（直接回车结束）:"));
+        match menu::read_choice() {
+            Ok(Some(line)) if !line.is_empty() && line != "q" => match explain(&line) {
+                Ok(text) => print!("{}", text),
+                Err(message) => println!("{}", tr_fmt("无法解析函数签名：{}", &[&message])),
+            },
+            Ok(_) => return 0,
+            Err(error) => {
+                eprintln!("{}", tr_fmt("无法读取输入: {}", &[&error]));
+                return 1;
+            }
+        }
+    }
+}
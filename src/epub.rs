@@ -0,0 +1,371 @@
+// 导出 EPUB 电子书
+// `rustlearn export --epub` 把所有课程打包成一本 EPUB 3 电子书，方便在电子阅读器上离线阅读：
+// 每一课是一章，包含课程简介、知识点，以及每一节的说明、源代码和真实的运行结果
+//
+//   mimetype                  固定为 application/epub+zip，必须是第一个文件且不压缩
+//   META-INF/container.xml    指向 OEBPS/content.opf
+//   OEBPS/content.opf         书籍的元数据、文件清单和阅读顺序
+//   OEBPS/nav.xhtml           导航文档，列出每一课和每一节
+//   OEBPS/style.css           样式表，正文和代码都指定了包含中文字形的字体
+//   OEBPS/lesson-01.xhtml     每一课一章
+//
+// EPUB 是一个 ZIP 文件，这里自己写出不压缩的 ZIP 格式，不依赖任何外部工具
+
+use std::fs;
+use std::path::PathBuf;
+
+use crate::book;
+use crate::i18n::{self, tr, tr_fmt};
+use crate::lesson::{self, Lesson};
+use crate::progress;
+use crate::source;
+use crate::web::escape;
+
+// 默认的输出文件
+const DEFAULT_FILE: &str = "rustlearn.epub";
+
+// 样式表：正文和代码分别列出常见的中文字体，阅读器没有这些字体时使用系统默认的字体
+const STYLE: &str = "body { font-family: \"Noto Serif CJK SC\", \"Source Han Serif SC\", \"Songti SC\", serif; line-height: 1.6; }
+h1, h2, h3 { font-family: \"Noto Sans CJK SC\", \"Source Han Sans SC\", \"PingFang SC\", sans-serif; }
+code, pre { font-family: \"Noto Sans Mono CJK SC\", \"Sarasa Mono SC\", \"DejaVu Sans Mono\", monospace; }
+pre { font-size: 0.8em; white-space: pre-wrap; word-wrap: break-word; border: 1px solid #999; padding: 0.5em; }
+pre.output { background: #eee; }
+p.source { font-size: 0.9em; color: #555; }
+";
+
+// export 子命令，返回进程退出码
+pub fn run_cli(args: &[String]) -> i32 {
+    let mut epub = false;
+    let mut file = PathBuf::from(DEFAULT_FILE);
+    let mut rest = args.iter();
+    while let Some(arg) = rest.next() {
+        match (arg.as_str(), rest.as_slice().first()) {
+            ("--epub", _) => epub = true,
+            ("--out", Some(path)) => {
+                file = PathBuf::from(path);
+                rest.next();
+            }
+            _ => return usage_error(),
+        }
+    }
+    // 目前只支持 EPUB 格式，Markdown 书籍由 book 子命令导出
+    if !epub {
+        return usage_error();
+    }
+
+    match fs::write(&file, package()) {
+        Ok(()) => {
+            println!(
+                "{}",
+                tr_fmt("已导出 EPUB 电子书 {}，共 {} 章", &[&file.display(), &lesson::LESSONS.len()])
+            );
+            0
+        }
+        Err(error) => {
+            eprintln!("{}", tr_fmt("无法导出 EPUB 电子书: {}", &[&error]));
+            1
+        }
+    }
+}
+
+fn usage_error() -> i32 {
+    eprintln!("{}", tr("用法: rustlearn export --epub [--out <文件>]"));
+    2
+}
+
+// 生成整本电子书的 ZIP 数据
+fn package() -> Vec<u8> {
+    let mut files: Vec<(String, String)> = vec![
+        (String::from("mimetype"), String::from("application/epub+zip")),
+        (String::from("META-INF/container.xml"), String::from(CONTAINER)),
+        (String::from("OEBPS/content.opf"), package_document()),
+        (String::from("OEBPS/nav.xhtml"), navigation()),
+        (String::from("OEBPS/style.css"), String::from(STYLE)),
+    ];
+    for lesson in lesson::LESSONS {
+        files.push((format!("OEBPS/{}", chapter_file(*lesson)), chapter(*lesson)));
+    }
+
+    let mut zip = Zip::new(progress::now());
+    for (name, content) in &files {
+        zip.add(name, content.as_bytes());
+    }
+    zip.finish()
+}
+
+const CONTAINER: &str = "<?xml version=\"1.0\" encoding=\"UTF-8\"?>
+<container version=\"1.0\" xmlns=\"urn:oasis:names:tc:opendocument:xmlns:container\">
+  <rootfiles>
+    <rootfile full-path=\"OEBPS/content.opf\" media-type=\"application/oebps-package+xml\"/>
+  </rootfiles>
+</container>
+";
+
+// 包文档：元数据、文件清单和阅读顺序
+fn package_document() -> String {
+    let language = i18n::current().code();
+    let mut manifest = String::from(
+        "    <item id=\"nav\" href=\"nav.xhtml\" media-type=\"application/xhtml+xml\" properties=\"nav\"/>\n    <item id=\"style\" href=\"style.css\" media-type=\"text/css\"/>\n",
+    );
+    let mut spine = String::new();
+    for lesson in lesson::LESSONS {
+        manifest.push_str(&format!(
+            "    <item id=\"lesson-{}\" href=\"{}\" media-type=\"application/xhtml+xml\"/>\n",
+            lesson.id(),
+            chapter_file(*lesson)
+        ));
+        spine.push_str(&format!("    <itemref idref=\"lesson-{}\"/>\n", lesson.id()));
+    }
+
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>
+<package xmlns=\"http://www.idpf.org/2007/opf\" version=\"3.0\" unique-identifier=\"book-id\" xml:lang=\"{language}\">
+  <metadata xmlns:dc=\"http://purl.org/dc/elements/1.1/\">
+    <dc:identifier id=\"book-id\">urn:rustlearn:{version}:{language}</dc:identifier>
+    <dc:title>{title}</dc:title>
+    <dc:language>{language}</dc:language>
+    <dc:creator>rustlearn</dc:creator>
+    <meta property=\"dcterms:modified\">{modified}</meta>
+  </metadata>
+  <manifest>
+{manifest}  </manifest>
+  <spine page-progression-direction=\"ltr\">
+{spine}  </spine>
+</package>
+",
+        version = env!("CARGO_PKG_VERSION"),
+        title = escape(&tr("Rust 学习示例程序")),
+        modified = modified(progress::now()),
+    )
+}
+
+// 导航文档：每一课以及课程中的每一节
+fn navigation() -> String {
+    let mut items = String::new();
+    for lesson in lesson::LESSONS {
+        items.push_str(&format!(
+            "      <li><a href=\"{}\">{}</a>\n        <ol>\n",
+            chapter_file(*lesson),
+            escape(&book::lesson_title(*lesson))
+        ));
+        for section in lesson.sections() {
+            items.push_str(&format!(
+                "          <li><a href=\"{}#{}\">{}</a></li>\n",
+                chapter_file(*lesson),
+                section.id,
+                escape(&tr(section.title))
+            ));
+        }
+        items.push_str("        </ol>\n      </li>\n");
+    }
+
+    let body = format!(
+        "  <nav epub:type=\"toc\" id=\"toc\">\n    <h1>{}</h1>\n    <ol>\n{}    </ol>\n  </nav>\n",
+        escape(&tr("目录")),
+        items
+    );
+    xhtml(&tr("目录"), &body)
+}
+
+// 一课的内容：简介、知识点和每一节
+fn chapter(lesson: &dyn Lesson) -> String {
+    let mut body = format!(
+        "  <section epub:type=\"chapter\">\n  <h1>{}</h1>\n  <p>{}</p>\n  <h2>{}</h2>\n  <ul>\n",
+        escape(&book::lesson_title(lesson)),
+        escape(&tr(lesson.summary())),
+        escape(&tr("知识点"))
+    );
+    for topic in lesson.topics() {
+        body.push_str(&format!("    <li>{}</li>\n", escape(&tr(topic))));
+    }
+    body.push_str("  </ul>\n");
+
+    for section in lesson.sections() {
+        body.push_str(&format!(
+            "  <section id=\"{}\">\n  <h2>{}</h2>\n",
+            section.id,
+            escape(&tr(section.title))
+        ));
+        for line in source::explanation(lesson, section) {
+            body.push_str(&format!("  <p>{}</p>\n", escape(&tr(line))));
+        }
+        if let Some(code) = source::plain(lesson, section) {
+            body.push_str(&format!(
+                "  <h3>{}</h3>\n  <p class=\"source\">{}</p>\n  <pre class=\"code\"><code>{}</code></pre>\n",
+                escape(&tr("源代码")),
+                // 与 Markdown 书籍使用同一条消息，把其中的 `...` 换成 <code> 元素
+                escape(&tr_fmt("摘自 `{}`：", &[&lesson.source_path()]))
+                    .replacen('`', "<code>", 1)
+                    .replacen('`', "</code>", 1),
+                text(&code)
+            ));
+        }
+        body.push_str(&format!(
+            "  <h3>{}</h3>\n  <pre class=\"output\">{}</pre>\n  </section>\n",
+            escape(&tr("运行结果")),
+            text(&book::section_output(section))
+        ));
+    }
+    body.push_str("  </section>\n");
+
+    xhtml(&book::lesson_title(lesson), &body)
+}
+
+// 完整的 XHTML 文档，阅读器根据 xml:lang 选择中文或英文的字形
+fn xhtml(title: &str, body: &str) -> String {
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>
+<!DOCTYPE html>
+<html xmlns=\"http://www.w3.org/1999/xhtml\" xmlns:epub=\"http://www.idpf.org/2007/ops\" xml:lang=\"{language}\" lang=\"{language}\">
+<head>
+  <meta charset=\"UTF-8\"/>
+  <title>{title}</title>
+  <link rel=\"stylesheet\" type=\"text/css\" href=\"style.css\"/>
+</head>
+<body>
+{body}</body>
+</html>
+",
+        language = i18n::current().code(),
+        title = escape(title),
+    )
+}
+
+// 代码和运行结果中的文字，XML 不允许出现换行和制表符以外的控制字符
+fn text(content: &str) -> String {
+    let content: String = content
+        .chars()
+        .filter(|c| !c.is_control() || matches!(c, '\n' | '\t'))
+        .collect();
+    escape(&content)
+}
+
+fn chapter_file(lesson: &dyn Lesson) -> String {
+    format!("lesson-{:0>2}.xhtml", lesson.id())
+}
+
+// dcterms:modified 要求的时间格式，例如 "2024-03-01T09:30:00Z"
+fn modified(timestamp: u64) -> String {
+    let (year, month, day) = progress::civil_from_days((timestamp / 86_400) as i64);
+    let seconds = timestamp % 86_400;
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        seconds / 3600,
+        seconds % 3600 / 60,
+        seconds % 60
+    )
+}
+
+// 只存储、不压缩的 ZIP 文件
+// 格式参见 PKWARE 的 APPNOTE.TXT：每个文件先写本地文件头和内容，最后写中央目录和目录结束记录
+struct Zip {
+    data: Vec<u8>,
+    directory: Vec<u8>,
+    entries: u16,
+    // MS-DOS 格式的修改时间和日期
+    time: u16,
+    date: u16,
+}
+
+impl Zip {
+    fn new(timestamp: u64) -> Zip {
+        let (year, month, day) = progress::civil_from_days((timestamp / 86_400) as i64);
+        let seconds = timestamp % 86_400;
+        let time = (seconds / 3600) << 11 | (seconds % 3600 / 60) << 5 | (seconds % 60 / 2);
+        // MS-DOS 日期从 1980 年开始
+        let date = ((year.max(1980) - 1980) as u32) << 9 | month << 5 | day;
+
+        Zip {
+            data: Vec::new(),
+            directory: Vec::new(),
+            entries: 0,
+            time: time as u16,
+            date: date as u16,
+        }
+    }
+
+    fn add(&mut self, name: &str, content: &[u8]) {
+        let offset = self.data.len() as u32;
+        let crc = crc32(content);
+        let size = content.len() as u32;
+
+        // 本地文件头
+        put32(&mut self.data, 0x0403_4b50);
+        self.put_header(true, name, crc, size);
+        self.data.extend_from_slice(name.as_bytes());
+        self.data.extend_from_slice(content);
+
+        // 中央目录中的记录
+        put32(&mut self.directory, 0x0201_4b50);
+        // 创建文件使用的版本
+        put16(&mut self.directory, 20);
+        self.put_header(false, name, crc, size);
+        // 注释长度、磁盘编号、内部属性、外部属性和本地文件头的位置
+        put16(&mut self.directory, 0);
+        put16(&mut self.directory, 0);
+        put16(&mut self.directory, 0);
+        put32(&mut self.directory, 0);
+        put32(&mut self.directory, offset);
+        self.directory.extend_from_slice(name.as_bytes());
+
+        self.entries += 1;
+    }
+
+    // 本地文件头和中央目录记录中相同的部分
+    fn put_header(&mut self, local: bool, name: &str, crc: u32, size: u32) {
+        let out = if local { &mut self.data } else { &mut self.directory };
+        // 解压需要的版本、标志和压缩方法，0 表示不压缩
+        put16(out, 10);
+        put16(out, 0);
+        put16(out, 0);
+        put16(out, self.time);
+        put16(out, self.date);
+        put32(out, crc);
+        // 不压缩时压缩前后的大小相同
+        put32(out, size);
+        put32(out, size);
+        put16(out, name.len() as u16);
+        // 扩展字段的长度
+        put16(out, 0);
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        let offset = self.data.len() as u32;
+        let size = self.directory.len() as u32;
+        self.data.extend_from_slice(&self.directory);
+
+        // 目录结束记录
+        put32(&mut self.data, 0x0605_4b50);
+        put16(&mut self.data, 0);
+        put16(&mut self.data, 0);
+        put16(&mut self.data, self.entries);
+        put16(&mut self.data, self.entries);
+        put32(&mut self.data, size);
+        put32(&mut self.data, offset);
+        put16(&mut self.data, 0);
+        self.data
+    }
+}
+
+fn put16(out: &mut Vec<u8>, value: u16) {
+    out.extend_from_slice(&value.to_le_bytes());
+}
+
+fn put32(out: &mut Vec<u8>, value: u32) {
+    out.extend_from_slice(&value.to_le_bytes());
+}
+
+// ZIP 使用的 CRC-32 校验码（多项式 0xEDB88320）
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for byte in data {
+        crc ^= u32::from(*byte);
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 { (crc >> 1) ^ 0xEDB8_8320 } else { crc >> 1 };
+        }
+    }
+    !crc
+}
@@ -0,0 +1,369 @@
+// 动手练习
+// 每一课都通过 Lesson::exercises() 提供几个练习，练习的文件保存在项目根目录的 exercises/ 中，
+// 在编译时嵌入程序：
+// 1. <名称>.rs           发给学习者的初始代码，需要完成的地方写成 todo!()
+// 2. <名称>_tests.rs     隐藏的测试，检查答案时附加在学习者的代码后面一起编译
+// 3. <名称>_solution.rs  参考答案，`rustlearn exercise <编号> --solution` 可以查看
+//
+// `rustlearn exercise 3.2` 会把第 3 课的第 2 个练习复制到练习目录中（已经存在时保留学习者的修改），
+// 然后调用本地的 rustc 把学习者的代码和隐藏的测试编译成测试程序并运行，整个过程不需要联网
+// 练习目录默认在 $XDG_DATA_HOME/rustlearn/exercises/<用户名>/<编号>，也可以用 --dir 指定
+// 可以通过 RUSTC 环境变量指定使用的 rustc
+
+use std::env;
+use std::fs::{self, File};
+use std::io::{self, ErrorKind};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::cli;
+use crate::i18n::{tr, tr_fmt};
+use crate::lesson::{self, Lesson};
+use crate::progress;
+
+// 一个练习
+pub struct Exercise {
+    // 练习的名称，也是练习文件的名字，例如 "can_hold"
+    pub name: &'static str,
+    // 练习标题
+    pub title: &'static str,
+    // 练习要求
+    pub description: &'static str,
+    // 初始代码
+    pub starter: &'static str,
+    // 隐藏的测试
+    pub tests: &'static str,
+    // 参考答案
+    pub solution: &'static str,
+    // 没有通过时显示的提示
+    pub hints: &'static [&'static str],
+}
+
+// 测试程序的最长运行时间，超过时认为代码中有死循环
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+// 检查结果
+enum Outcome {
+    // 全部测试通过
+    Passed,
+    // 编译失败，附带编译器的错误信息
+    CompileError(String),
+    // 有测试没有通过
+    Failed,
+}
+
+// exercise 子命令，返回进程退出码
+// 用法：
+//   rustlearn exercise                        列出所有练习
+//   rustlearn exercise 3.2                    准备并检查第 3 课的第 2 个练习
+//   rustlearn exercise 3.2 --dir <目录>       使用指定的练习目录
+//   rustlearn exercise 3.2 --reset            用初始代码覆盖练习目录中的代码
+//   rustlearn exercise 3.2 --solution         显示参考答案
+pub fn run_cli(args: &[String]) -> i32 {
+    let mut id = None;
+    let mut dir = None;
+    let mut reset = false;
+    let mut solution = false;
+    let mut iter = args.iter();
+
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--reset" => reset = true,
+            "--solution" => solution = true,
+            "--dir" => match iter.next() {
+                Some(path) => dir = Some(PathBuf::from(path)),
+                None => return usage_error(),
+            },
+            other if other.starts_with('-') || id.is_some() => return usage_error(),
+            other => id = Some(other),
+        }
+    }
+
+    let Some(id) = id else {
+        if dir.is_some() || reset || solution {
+            return usage_error();
+        }
+        return cli::print(&list());
+    };
+
+    let Some((lesson, number, exercise)) = find(id) else {
+        eprintln!(
+            "{}",
+            tr_fmt("没有编号为 {} 的练习，可以使用 `rustlearn exercise` 查看所有练习", &[&id])
+        );
+        return 2;
+    };
+
+    if solution {
+        return cli::print(&format!(
+            "{}\n\n{}\n",
+            tr_fmt("练习 {}.{} 的参考答案：", &[&lesson.id(), &number]),
+            exercise.solution
+        ));
+    }
+
+    let dir = match dir.or_else(|| default_dir(id)) {
+        Some(dir) => dir,
+        None => {
+            eprintln!("{}", tr("无法确定练习目录，请使用 --dir 指定"));
+            return 2;
+        }
+    };
+
+    // 出错之前已经生成的报告也要输出
+    let mut report = String::new();
+    let outcome = prepare_and_check(lesson, number, exercise, &dir, reset, &mut report);
+    let printed = cli::print(&report);
+
+    match outcome {
+        Ok(Outcome::Passed) => printed,
+        Ok(_) => 1,
+        Err(error) => {
+            eprintln!("{}", tr_fmt("无法检查练习: {}", &[&error]));
+            1
+        }
+    }
+}
+
+// 列出所有课程的练习
+fn list() -> String {
+    let mut text = String::new();
+    for lesson in lesson::LESSONS {
+        for (index, exercise) in lesson.exercises().iter().enumerate() {
+            text.push_str(&format!("{:>5}  {}\n", format!("{}.{}", lesson.id(), index + 1), tr(exercise.title)));
+        }
+    }
+    text
+}
+
+// 根据 "3.2" 这样的编号查找练习，返回课程、练习在本课中的序号（从 1 开始）和练习
+fn find(id: &str) -> Option<(&'static dyn Lesson, usize, &'static Exercise)> {
+    let (lesson_id, number) = id.split_once('.')?;
+    let lesson = lesson::find(lesson_id)?;
+    let number: usize = number.parse().ok()?;
+    let exercise = lesson.exercises().get(number.checked_sub(1)?)?;
+
+    Some((lesson, number, exercise))
+}
+
+// 默认的练习目录，每位学习者、每个练习各自一个目录
+fn default_dir(id: &str) -> Option<PathBuf> {
+    let user = progress::file_name(&progress::current_user());
+    Some(progress::data_dir()?.join("exercises").join(user).join(id))
+}
+
+// 准备练习目录并检查学习者的代码，检查的过程和结果写入 report
+fn prepare_and_check(
+    lesson: &dyn Lesson,
+    number: usize,
+    exercise: &Exercise,
+    dir: &Path,
+    reset: bool,
+    report: &mut String,
+) -> io::Result<Outcome> {
+    let source = dir.join("src").join("lib.rs");
+
+    report.push_str(&format!("{}\n", tr_fmt("练习 {}.{}：{}", &[&lesson.id(), &number, &tr(exercise.title)])));
+    report.push_str(&format!("{}\n", tr(exercise.description)));
+
+    if reset || !source.exists() {
+        scaffold(lesson, number, exercise, dir)?;
+        report.push_str(&format!("\n{}\n", tr_fmt("已经把初始代码写入 {}", &[&source.display()])));
+    } else {
+        report.push_str(&format!("\n{}\n", tr_fmt("检查 {}", &[&source.display()])));
+    }
+
+    let outcome = check(exercise, dir, &source, report)?;
+    match &outcome {
+        Outcome::Passed => {
+            report.push_str(&format!("\n{}\n", tr("✓ 所有测试都通过了，练习完成！")));
+        }
+        Outcome::CompileError(message) => {
+            report.push_str(&format!("\n{}\n", tr("✗ 代码无法通过编译：")));
+            report.push_str(&format!("{}\n", message.trim_end()));
+            report.push_str(&hints(exercise));
+        }
+        Outcome::Failed => report.push_str(&hints(exercise)),
+    }
+
+    if !matches!(outcome, Outcome::Passed) {
+        report.push_str(&format!(
+            "\n{}\n",
+            tr_fmt(
+                "修改代码后再次运行 `rustlearn exercise {}.{}` 检查答案",
+                &[&lesson.id(), &number]
+            )
+        ));
+    }
+
+    Ok(outcome)
+}
+
+// 把初始代码和 Cargo.toml 写入练习目录，可以直接用编辑器或者 cargo build 打开
+fn scaffold(lesson: &dyn Lesson, number: usize, exercise: &Exercise, dir: &Path) -> io::Result<()> {
+    fs::create_dir_all(dir.join("src"))?;
+    fs::write(dir.join("src").join("lib.rs"), exercise.starter)?;
+
+    let manifest = format!(
+        "[package]\nname = \"exercise-{}-{}-{}\"\nversion = \"0.1.0\"\nedition = \"2024\"\n\n[dependencies]\n",
+        lesson.id(),
+        number,
+        exercise.name.replace('_', "-")
+    );
+    fs::write(dir.join("Cargo.toml"), manifest)
+}
+
+// 把学习者的代码和隐藏的测试编译成测试程序并运行，每个测试的结果写入 report
+fn check(exercise: &Exercise, dir: &Path, source: &Path, report: &mut String) -> io::Result<Outcome> {
+    let build = dir.join("target").join("rustlearn");
+    fs::create_dir_all(&build)?;
+
+    // 学习者的代码通过 include! 引入，这样编译错误仍然指向学习者自己的文件
+    let source = fs::canonicalize(source)?;
+    let harness = build.join("check.rs");
+    fs::write(
+        &harness,
+        format!(
+            "// 由 rustlearn 生成，每次检查时都会重新生成\ninclude!({:?});\n\n#[cfg(test)]\nmod hidden_tests {{\n    use super::*;\n\n{}}}\n",
+            source.to_string_lossy(),
+            indent(exercise.tests)
+        ),
+    )?;
+
+    let binary = build.join(if cfg!(windows) { "check.exe" } else { "check" });
+    let rustc = env::var_os("RUSTC").unwrap_or_else(|| "rustc".into());
+    let compiled = Command::new(&rustc)
+        .args(["--edition", "2024", "--test", "--crate-name", "exercise", "-A", "warnings", "-o"])
+        .arg(&binary)
+        .arg(&harness)
+        .output();
+
+    let compiled = match compiled {
+        Ok(output) => output,
+        Err(error) if error.kind() == ErrorKind::NotFound => {
+            return Err(io::Error::new(
+                ErrorKind::NotFound,
+                tr("找不到 rustc，请先安装 Rust 工具链，或者通过 RUSTC 环境变量指定").into_owned(),
+            ));
+        }
+        Err(error) => return Err(error),
+    };
+
+    if !compiled.status.success() {
+        return Ok(Outcome::CompileError(String::from_utf8_lossy(&compiled.stderr).into_owned()));
+    }
+
+    let output = run_tests(&binary, &build.join("output.txt"), report)?;
+    let results = parse_results(&output);
+
+    report.push('\n');
+    for (name, passed) in &results {
+        if *passed {
+            report.push_str(&format!("✓ {}\n", name));
+        } else {
+            report.push_str(&format!("✗ {}\n", name));
+            for line in failure_message(&output, name) {
+                report.push_str(&format!("    {}\n", line));
+            }
+        }
+    }
+
+    let passed = results.iter().filter(|(_, passed)| *passed).count();
+    report.push_str(&format!("\n{}\n", tr_fmt("通过了 {}/{} 个测试", &[&passed, &results.len()])));
+
+    if output.contains("not yet implemented") {
+        report.push_str(&format!("{}\n", tr("代码中还有没有完成的 todo!()")));
+    }
+
+    if !results.is_empty() && passed == results.len() {
+        Ok(Outcome::Passed)
+    } else {
+        Ok(Outcome::Failed)
+    }
+}
+
+// 运行测试程序，返回它的输出，运行时间过长时结束测试程序，并在 report 中说明
+// 输出先写入文件，避免测试输出太多时管道被写满
+fn run_tests(binary: &Path, output_path: &Path, report: &mut String) -> io::Result<String> {
+    let output = File::create(output_path)?;
+    let mut child = Command::new(binary)
+        .arg("--test-threads=1")
+        .env("RUST_BACKTRACE", "0")
+        .stdin(Stdio::null())
+        .stdout(output.try_clone()?)
+        .stderr(output)
+        .spawn()?;
+
+    let start = Instant::now();
+    while child.try_wait()?.is_none() {
+        if start.elapsed() > TIMEOUT {
+            child.kill()?;
+            child.wait()?;
+            report.push_str(&format!(
+                "{}\n",
+                tr_fmt("测试运行超过了 {} 秒，代码中可能有死循环", &[&TIMEOUT.as_secs()])
+            ));
+            break;
+        }
+        thread::sleep(Duration::from_millis(20));
+    }
+
+    fs::read_to_string(output_path)
+}
+
+// 从测试程序的输出中找出每个测试的结果，例如 "test hidden_tests::origin ... ok"
+fn parse_results(output: &str) -> Vec<(String, bool)> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let rest = line.strip_prefix("test hidden_tests::")?;
+            let (name, result) = rest.split_once(" ... ")?;
+            Some((name.to_string(), result.trim() == "ok"))
+        })
+        .collect()
+}
+
+// 某个测试失败时输出的信息，也就是 "---- hidden_tests::<名称> stdout ----" 之后的几行
+fn failure_message<'a>(output: &'a str, name: &str) -> Vec<&'a str> {
+    let header = format!("---- hidden_tests::{} stdout ----", name);
+
+    output
+        .lines()
+        .skip_while(|line| *line != header)
+        .skip(1)
+        .skip_while(|line| line.is_empty())
+        .take_while(|line| !line.is_empty() && !line.starts_with("---- "))
+        .filter(|line| !line.starts_with("note: run with `RUST_BACKTRACE"))
+        .collect()
+}
+
+// 练习的提示，没有提示时是空字符串
+fn hints(exercise: &Exercise) -> String {
+    if exercise.hints.is_empty() {
+        return String::new();
+    }
+
+    let mut text = format!("\n{}\n", tr("提示："));
+    for hint in exercise.hints {
+        text.push_str(&format!("- {}\n", tr(hint)));
+    }
+    text
+}
+
+// 把隐藏的测试缩进一层，放进 hidden_tests 模块中
+fn indent(code: &str) -> String {
+    code.lines()
+        .map(|line| if line.is_empty() { String::from("\n") } else { format!("    {}\n", line) })
+        .collect()
+}
+
+// 输出用法并返回退出码
+fn usage_error() -> i32 {
+    eprintln!(
+        "{}",
+        tr("用法: rustlearn exercise [<课程编号>.<练习编号>] [--dir <目录>] [--reset] [--solution]")
+    );
+    2
+}
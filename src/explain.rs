@@ -0,0 +1,599 @@
+// 编译错误代码说明
+// 在工作中遇到 E0499、E0597 这样的编译错误时，`rustlearn explain E0499` 可以离线查看这个错误的中英文说明、
+// 一段最小的复现代码和修改之后的代码，并且列出课程中讲解相关内容的小节
+//
+// 收录的是学习所有权、借用、生命周期、trait 约束和模式匹配时最常见的错误代码，
+// 没有收录的错误代码可以使用 `rustc --explain <错误代码>` 查看编译器自带的英文说明
+//
+// `rustlearn explain --check` 检查相关的小节都存在，并且调用本地的 rustc 确认复现代码产生的就是这个错误代码、
+// 修改之后的代码能够通过编译
+
+use std::borrow::Cow;
+
+use crate::cli;
+use crate::compile_error;
+use crate::context::Style;
+use crate::glossary::Place;
+use crate::highlight::Highlighter;
+use crate::i18n::{tr, tr_fmt};
+use crate::theme;
+
+// 错误代码的分类
+#[derive(Clone, Copy, PartialEq)]
+pub enum Category {
+    Ownership,
+    Borrowing,
+    Lifetime,
+    TraitBound,
+    Exhaustiveness,
+}
+
+impl Category {
+    // 所有分类，按照列出的顺序排列
+    const ALL: [Category; 5] = [
+        Category::Ownership,
+        Category::Borrowing,
+        Category::Lifetime,
+        Category::TraitBound,
+        Category::Exhaustiveness,
+    ];
+
+    fn title(self) -> Cow<'static, str> {
+        match self {
+            Category::Ownership => tr("所有权"),
+            Category::Borrowing => tr("借用"),
+            Category::Lifetime => tr("生命周期"),
+            Category::TraitBound => tr("trait 约束"),
+            Category::Exhaustiveness => tr("模式的穷尽性"),
+        }
+    }
+}
+
+// 一个错误代码的说明
+pub struct Explanation {
+    // 错误代码，例如 "E0499"
+    pub code: &'static str,
+    pub category: Category,
+    // 一句话概括错误，英文译文在消息目录中
+    pub title: &'static str,
+    // 错误的原因
+    pub cause: &'static str,
+    // 最小的复现代码
+    pub example: &'static str,
+    // 修改的方法
+    pub fix: &'static str,
+    // 修改之后的代码
+    pub fixed: &'static str,
+    // 讲解相关内容的小节：课程编号和小节标识
+    pub sections: &'static [(&'static str, &'static str)],
+}
+
+// 所有收录的错误代码，同一分类中按照错误代码排列
+pub static EXPLANATIONS: &[Explanation] = &[
+    Explanation {
+        code: "E0382",
+        category: Category::Ownership,
+        title: "使用了已经被移动的值",
+        cause: "String、Vec 这样没有实现 Copy 的值在赋值、传参或者放进集合时，所有权会转移给新的所有者，原来的变量从此不能再使用",
+        example: r#"fn main() {
+    let v = vec![1, 2, 3];
+    let w = v;
+    println!("{:?}", v);
+}"#,
+        fix: "还需要使用原来的值时，传递引用或者克隆一份；也可以调整代码的顺序，在移动之前用完它",
+        fixed: r#"fn main() {
+    let v = vec![1, 2, 3];
+    let w = v.clone();
+    println!("{:?} {:?}", v, w);
+}"#,
+        sections: &[("6", "ownership_issues"), ("3", "struct_update_syntax")],
+    },
+    Explanation {
+        code: "E0505",
+        category: Category::Ownership,
+        title: "值还被借用着的时候就被移动了",
+        cause: "引用在最后一次使用之前，被引用的值必须一直有效；在这期间把值移动走，引用就会指向已经不属于原来变量的数据",
+        example: r#"fn consume(v: Vec<i32>) {}
+
+fn main() {
+    let v = vec![1, 2, 3];
+    let first = &v[0];
+    consume(v);
+    println!("{}", first);
+}"#,
+        fix: "先用完引用再移动值，或者把需要的数据复制出来，不再保留引用",
+        fixed: r#"fn consume(v: Vec<i32>) {}
+
+fn main() {
+    let v = vec![1, 2, 3];
+    let first = &v[0];
+    println!("{}", first);
+    consume(v);
+}"#,
+        sections: &[("6", "ownership_issues"), ("10", "lifetimes_basics")],
+    },
+    Explanation {
+        code: "E0507",
+        category: Category::Ownership,
+        title: "不能从引用中把值移出来",
+        cause: "通过引用只是借用了值，不能把其中的字段或元素的所有权拿走，否则原来的所有者手里就只剩下一个不完整的值",
+        example: r#"struct Person {
+    name: String,
+}
+
+fn take_name(person: &Person) -> String {
+    person.name
+}"#,
+        fix: "返回克隆的值，或者返回引用；确实需要拿走所有权时，让函数接收值本身而不是引用",
+        fixed: r#"struct Person {
+    name: String,
+}
+
+fn take_name(person: &Person) -> String {
+    person.name.clone()
+}"#,
+        sections: &[("6", "ownership_issues"), ("3", "struct_methods")],
+    },
+    Explanation {
+        code: "E0499",
+        category: Category::Borrowing,
+        title: "同时存在两个可变借用",
+        cause: "同一时间一个值只能有一个可变引用，这样就不会有两处代码同时修改同一份数据。可变借用从创建开始，一直持续到最后一次使用",
+        example: r#"fn main() {
+    let mut v = vec![1, 2, 3];
+    let a = &mut v;
+    let b = &mut v;
+    a.push(4);
+    b.push(5);
+}"#,
+        fix: "调整代码的顺序，在第一个可变引用最后一次使用之后再创建第二个；或者把修改放进不同的作用域",
+        fixed: r#"fn main() {
+    let mut v = vec![1, 2, 3];
+    let a = &mut v;
+    a.push(4);
+    let b = &mut v;
+    b.push(5);
+}"#,
+        sections: &[("6", "ownership_issues")],
+    },
+    Explanation {
+        code: "E0502",
+        category: Category::Borrowing,
+        title: "可变借用和不可变借用同时存在",
+        cause: "存在不可变引用的时候不能再创建可变引用。例如持有 Vector 中元素的引用时调用 push，push 可能重新分配内存，让之前的引用指向已经释放的内存",
+        example: r#"fn main() {
+    let mut v = vec![1, 2, 3];
+    let first = &v[0];
+    v.push(4);
+    println!("{}", first);
+}"#,
+        fix: "在不可变引用最后一次使用之后再修改，或者把需要的值复制出来，不再保留引用",
+        fixed: r#"fn main() {
+    let mut v = vec![1, 2, 3];
+    let first = v[0];
+    v.push(4);
+    println!("{}", first);
+}"#,
+        sections: &[("6", "vector_collection"), ("6", "ownership_issues")],
+    },
+    Explanation {
+        code: "E0506",
+        category: Category::Borrowing,
+        title: "给被借用的变量赋值",
+        cause: "变量被借用期间不能给它赋新的值，否则引用读到的值会在不知不觉中改变",
+        example: r#"fn main() {
+    let mut x = 5;
+    let r = &x;
+    x = 6;
+    println!("{}", r);
+}"#,
+        fix: "在引用最后一次使用之后再赋值",
+        fixed: r#"fn main() {
+    let mut x = 5;
+    let r = &x;
+    println!("{}", r);
+    x = 6;
+    println!("{}", x);
+}"#,
+        sections: &[("1", "variable_mutability"), ("10", "lifetimes_basics")],
+    },
+    Explanation {
+        code: "E0596",
+        category: Category::Borrowing,
+        title: "对不可变的值进行可变借用",
+        cause: "调用 push 这样需要 &mut self 的方法时，会对值进行可变借用，而用 let 声明的变量和 & 引用都是不可变的",
+        example: r#"fn main() {
+    let v = Vec::new();
+    v.push(1);
+}"#,
+        fix: "用 let mut 声明变量；在函数参数中需要修改时使用 &mut 引用",
+        fixed: r#"fn main() {
+    let mut v = Vec::new();
+    v.push(1);
+}"#,
+        sections: &[("1", "variable_mutability"), ("6", "vector_collection")],
+    },
+    Explanation {
+        code: "E0106",
+        category: Category::Lifetime,
+        title: "缺少生命周期标注",
+        cause: "函数返回引用时，编译器需要知道返回值借用自哪个参数。只有一个引用参数，或者是 &self 方法时，省略规则可以推断出来；有多个引用参数时就需要写出生命周期参数",
+        example: r#"fn longest(x: &str, y: &str) -> &str {
+    if x.len() > y.len() { x } else { y }
+}"#,
+        fix: "声明生命周期参数 'a，标注返回值和哪些参数的生命周期相同",
+        fixed: r#"fn longest<'a>(x: &'a str, y: &'a str) -> &'a str {
+    if x.len() > y.len() { x } else { y }
+}"#,
+        sections: &[("10", "lifetime_annotations"), ("10", "lifetime_elision")],
+    },
+    Explanation {
+        code: "E0515",
+        category: Category::Lifetime,
+        title: "返回了局部变量的引用",
+        cause: "局部变量在函数返回时就被释放了，返回它的引用会得到悬垂引用",
+        example: r#"fn make_greeting(name: &str) -> &str {
+    let greeting = format!("你好，{}", name);
+    &greeting
+}"#,
+        fix: "直接返回拥有所有权的值，例如 String，而不是它的引用",
+        fixed: r#"fn make_greeting(name: &str) -> String {
+    let greeting = format!("你好，{}", name);
+    greeting
+}"#,
+        sections: &[("10", "function_signatures"), ("10", "lifetimes_basics")],
+    },
+    Explanation {
+        code: "E0597",
+        category: Category::Lifetime,
+        title: "被借用的值活得不够久",
+        cause: "引用不能比被引用的值活得更久。值在作用域结束时被释放，之后还要使用的引用就成了悬垂引用",
+        example: r#"fn main() {
+    let r;
+    {
+        let x = 5;
+        r = &x;
+    }
+    println!("{}", r);
+}"#,
+        fix: "让被引用的值的作用域覆盖引用的所有使用，或者不再借用，直接移动或复制值",
+        fixed: r#"fn main() {
+    let x = 5;
+    let r;
+    {
+        r = &x;
+    }
+    println!("{}", r);
+}"#,
+        sections: &[("10", "lifetimes_basics"), ("10", "lifetime_annotations")],
+    },
+    Explanation {
+        code: "E0621",
+        category: Category::Lifetime,
+        title: "参数的类型中缺少生命周期",
+        cause: "函数签名约定返回值的生命周期是 'a，而实际返回的引用来自一个没有标注 'a 的参数，编译器无法保证它活得足够久",
+        example: r#"fn pick<'a>(x: &str, y: &'a str) -> &'a str {
+    x
+}"#,
+        fix: "给返回值可能来自的参数也加上同样的生命周期标注",
+        fixed: r#"fn pick<'a>(x: &'a str, y: &'a str) -> &'a str {
+    x
+}"#,
+        sections: &[("10", "function_signatures")],
+    },
+    Explanation {
+        code: "E0716",
+        category: Category::Lifetime,
+        title: "临时值在借用期间被释放",
+        cause: "表达式中创建的临时值在语句结束时就被释放，如果借用了它的引用在之后还要使用，就会指向已经释放的值",
+        example: r#"fn main() {
+    let words: Vec<&str>;
+    words = String::from("a b c").split(' ').collect();
+    println!("{:?}", words);
+}"#,
+        fix: "先用 let 把临时值保存到变量中，让它活得和引用一样久",
+        fixed: r#"fn main() {
+    let text = String::from("a b c");
+    let words: Vec<&str> = text.split(' ').collect();
+    println!("{:?}", words);
+}"#,
+        sections: &[("10", "lifetimes_basics"), ("6", "string_collection")],
+    },
+    Explanation {
+        code: "E0277",
+        category: Category::TraitBound,
+        title: "类型没有实现需要的 trait",
+        cause: "泛型函数的约束要求类型参数实现某个 trait，传入的类型没有实现它；? 运算符要求函数返回 Result 或 Option 时报告的也是这个错误",
+        example: r#"use std::fmt::Display;
+
+fn show<T: Display>(item: T) {
+    println!("{}", item);
+}
+
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+fn main() {
+    show(Point { x: 1, y: 2 });
+}"#,
+        fix: "为类型实现需要的 trait，可以派生的 trait 用 #[derive(...)]，或者改用满足约束的类型",
+        fixed: r#"use std::fmt::{self, Display};
+
+fn show<T: Display>(item: T) {
+    println!("{}", item);
+}
+
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+impl Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+fn main() {
+    show(Point { x: 1, y: 2 });
+}"#,
+        sections: &[("9", "generic_constraints"), ("9", "generics_with_traits")],
+    },
+    Explanation {
+        code: "E0369",
+        category: Category::TraitBound,
+        title: "泛型参数缺少运算符需要的约束",
+        cause: "在泛型函数中使用 >、+ 这样的运算符时，编译器只知道类型参数满足约束中列出的 trait，没有约束就不能使用运算符",
+        example: r#"fn largest<T>(list: &[T]) -> &T {
+    let mut largest = &list[0];
+    for item in list {
+        if item > largest {
+            largest = item;
+        }
+    }
+    largest
+}"#,
+        fix: "给类型参数加上运算符对应的 trait 约束，比较大小需要 PartialOrd，加法需要 Add",
+        fixed: r#"fn largest<T: PartialOrd>(list: &[T]) -> &T {
+    let mut largest = &list[0];
+    for item in list {
+        if item > largest {
+            largest = item;
+        }
+    }
+    largest
+}"#,
+        sections: &[("9", "generic_functions"), ("9", "generic_constraints")],
+    },
+    Explanation {
+        code: "E0599",
+        category: Category::TraitBound,
+        title: "类型上没有这个方法",
+        cause: "方法只为某些具体类型实现，或者需要类型满足某个 trait 约束时才存在，当前的类型不满足条件",
+        example: r#"struct Point<T> {
+    x: T,
+    y: T,
+}
+
+impl Point<f64> {
+    fn distance_from_origin(&self) -> f64 {
+        (self.x * self.x + self.y * self.y).sqrt()
+    }
+}
+
+fn main() {
+    let p = Point { x: 3, y: 4 };
+    println!("{}", p.distance_from_origin());
+}"#,
+        fix: "使用实现了这个方法的类型，或者为需要的类型实现方法；需要 trait 时记得导入或实现它",
+        fixed: r#"struct Point<T> {
+    x: T,
+    y: T,
+}
+
+impl Point<f64> {
+    fn distance_from_origin(&self) -> f64 {
+        (self.x * self.x + self.y * self.y).sqrt()
+    }
+}
+
+fn main() {
+    let p = Point { x: 3.0, y: 4.0 };
+    println!("{}", p.distance_from_origin());
+}"#,
+        sections: &[("9", "generic_methods"), ("9", "generics_with_traits")],
+    },
+    Explanation {
+        code: "E0004",
+        category: Category::Exhaustiveness,
+        title: "match 没有覆盖所有可能的值",
+        cause: "match 必须穷尽所有可能的值，编译器会列出没有覆盖的模式，这样新增枚举变体时不会漏掉处理",
+        example: r#"enum Direction {
+    North,
+    South,
+    East,
+    West,
+}
+
+fn turn(direction: Direction) -> &'static str {
+    match direction {
+        Direction::North => "北",
+        Direction::South => "南",
+        Direction::East => "东",
+    }
+}"#,
+        fix: "补上缺少的分支；确实不关心其余的值时，使用 _ 通配分支",
+        fixed: r#"enum Direction {
+    North,
+    South,
+    East,
+    West,
+}
+
+fn turn(direction: Direction) -> &'static str {
+    match direction {
+        Direction::North => "北",
+        Direction::South => "南",
+        Direction::East => "东",
+        Direction::West => "西",
+    }
+}"#,
+        sections: &[("5", "match_basics"), ("4", "enum_pattern_matching"), ("5", "wildcards")],
+    },
+    Explanation {
+        code: "E0005",
+        category: Category::Exhaustiveness,
+        title: "let 中使用了可能匹配失败的模式",
+        cause: "let 语句中的模式必须总是能够匹配，Some(x) 这样的模式在值是 None 时匹配失败，不能直接用在 let 中",
+        example: r#"fn main() {
+    let maybe: Option<i32> = Some(3);
+    let Some(x) = maybe;
+    println!("{}", x);
+}"#,
+        fix: "使用 if let 或 match 处理匹配失败的情况，或者使用 let ... else 在匹配失败时提前返回",
+        fixed: r#"fn main() {
+    let maybe: Option<i32> = Some(3);
+    let Some(x) = maybe else {
+        return;
+    };
+    println!("{}", x);
+}"#,
+        sections: &[("5", "let_statements_with_patterns"), ("5", "if_let_expressions")],
+    },
+];
+
+// explain 子命令，返回进程退出码
+pub fn run_cli(args: &[String]) -> i32 {
+    match args {
+        [] => list(),
+        [flag] if flag == "--check" => check(),
+        [code] if !code.starts_with('-') => show(code),
+        _ => {
+            eprintln!("{}", tr("用法: rustlearn explain [<错误代码> | --check]"));
+            2
+        }
+    }
+}
+
+// 根据错误代码查找说明，"E0499"、"e0499" 和 "0499" 都可以
+fn find(query: &str) -> Option<&'static Explanation> {
+    let digits = query.strip_prefix(['E', 'e']).unwrap_or(query);
+    let number: u32 = digits.parse().ok()?;
+    EXPLANATIONS
+        .iter()
+        .find(|explanation| explanation.code[1..].parse() == Ok(number))
+}
+
+// 按分类列出所有收录的错误代码
+fn list() -> i32 {
+    let style = theme::current();
+    let mut text = String::new();
+    for category in Category::ALL {
+        text.push_str(&format!("{}\n", style.label(&category.title())));
+        for explanation in EXPLANATIONS.iter().filter(|explanation| explanation.category == category) {
+            text.push_str(&format!("  {}  {}\n", explanation.code, tr(explanation.title)));
+        }
+        text.push('\n');
+    }
+    text.push_str(&format!("{}\n", tr("使用 `rustlearn explain <错误代码>` 查看错误的原因、复现代码、修改方法和相关的小节")));
+    cli::print(&text)
+}
+
+// 显示一个错误代码的说明
+fn show(query: &str) -> i32 {
+    let Some(explanation) = find(query) else {
+        eprintln!(
+            "{}",
+            tr_fmt(
+                "没有收录 {} 的说明，可以使用 `rustlearn explain` 查看收录的错误代码，或者使用 `rustc --explain {}` 查看编译器自带的说明",
+                &[&query, &query]
+            )
+        );
+        return 2;
+    };
+
+    let style = theme::current();
+    let mut text = format!(
+        "{}\n{}\n\n{}\n",
+        style.lesson_heading(&format!("{}: {}", explanation.code, tr(explanation.title))),
+        tr_fmt("分类：{}", &[&explanation.category.title()]),
+        tr(explanation.cause)
+    );
+
+    text.push_str(&format!("\n{}\n", style.label(&tr("复现代码："))));
+    push_code(&mut text, explanation.example, style);
+    text.push_str(&format!("\n{}\n{}\n", style.label(&tr("修改方法：")), tr(explanation.fix)));
+    push_code(&mut text, explanation.fixed, style);
+
+    let places: Vec<Place> = explanation.sections.iter().filter_map(|section| Place::find(*section)).collect();
+    if !places.is_empty() {
+        text.push_str(&format!("\n{}\n", style.label(&tr("相关的小节："))));
+        for place in places {
+            text.push_str(&format!("  {} ({})\n    {}\n", place.title(), place.path(), place.command()));
+        }
+    }
+
+    let examples = compile_error::examples_for(explanation.code);
+    if !examples.is_empty() {
+        text.push_str(&format!(
+            "\n{}\n",
+            tr_fmt("课程中的编译错误示例：{}，可以使用 `rustlearn errors {}` 查看本地 rustc 的真实输出", &[&examples.join(tr("、").as_ref()), &explanation.code])
+        ));
+    }
+    text.push_str(&format!("\n{}\n", tr_fmt("编译器自带的说明：rustc --explain {}", &[&explanation.code])));
+
+    cli::print(&text)
+}
+
+// 缩进并高亮一段代码
+fn push_code(text: &mut String, code: &str, style: &dyn Style) {
+    let mut highlighter = Highlighter::default();
+    for line in code.lines() {
+        if line.is_empty() {
+            text.push('\n');
+        } else {
+            text.push_str(&format!("    {}\n", highlighter.line(line, style)));
+        }
+    }
+}
+
+// 检查所有说明：相关的小节都存在，复现代码产生这个错误代码，修改之后的代码能够通过编译
+fn check() -> i32 {
+    let mut problems = 0;
+
+    for explanation in EXPLANATIONS {
+        let mut issues = Vec::new();
+        for (lesson, section) in explanation.sections {
+            if Place::find((lesson, section)).is_none() {
+                issues.push(tr_fmt("找不到相关的小节（第{}课 {}）", &[lesson, section]));
+            }
+        }
+
+        let name = format!("explain_{}", explanation.code.to_lowercase());
+        match compile_error::verify(&name, explanation.code, explanation.example, explanation.fixed) {
+            Ok(None) => {}
+            Ok(Some(problem)) => issues.push(problem),
+            Err(error) => {
+                eprintln!("{}", tr_fmt("无法编译示例代码: {}", &[&error]));
+                return 1;
+            }
+        }
+
+        if issues.is_empty() {
+            println!("✓ {}", explanation.code);
+        } else {
+            println!("✗ {}：{}", explanation.code, issues.join(tr("；").as_ref()));
+            problems += 1;
+        }
+    }
+
+    if problems == 0 {
+        println!("\n{}", tr_fmt("{} 个错误代码的说明都符合预期", &[&EXPLANATIONS.len()]));
+        0
+    } else {
+        println!("\n{}", tr_fmt("{} 个错误代码的说明中有 {} 个不符合预期", &[&EXPLANATIONS.len(), &problems]));
+        1
+    }
+}
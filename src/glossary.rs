@@ -0,0 +1,441 @@
+// 术语表
+// 所有权、借用、泛型约束、关联类型这样的术语分散在各课中，这里集中给出每个术语的中英文名称、定义和示例，
+// 并且记录介绍这个术语的小节；用到这个术语的其他小节通过扫描课程内容自动找出
+//
+// 扫描的范围是小节的标题、说明、运行结果以及源代码中的注释，也就是学习者会读到的文字，
+// 代码本身不算在内，例如 `use crate::...` 不算用到了 "crate" 这个术语
+//
+// `rustlearn glossary --check` 按课程顺序检查每个术语：
+// 1. 介绍术语的小节必须存在，并且小节中确实出现了这个术语
+// 2. 在介绍之前就用到术语的小节会被列出来，除非这个小节已经登记在 preview 中，
+//    表示课程有意提前提到这个术语，并且在那里给出了足够的解释
+
+use crate::cli;
+use crate::context::{LessonContext, Style};
+use crate::highlight::Highlighter;
+use crate::i18n::{self, tr, tr_fmt, Locale};
+use crate::lesson::{self, Lesson, Section};
+use crate::source;
+use crate::theme;
+
+// 一个术语
+pub struct Term {
+    // 中文名称
+    pub name: &'static str,
+    // 英文名称
+    pub english: &'static str,
+    // 其他写法，查找术语和扫描课程内容时与名称一样使用，英文不区分大小写
+    pub aliases: &'static [&'static str],
+    // 定义，英文译文在消息目录中
+    pub definition: &'static str,
+    // 一小段示例代码
+    pub example: &'static str,
+    // 介绍这个术语的小节：课程编号和小节标识
+    pub introduced: (&'static str, &'static str),
+    // 有意在介绍之前提到这个术语的小节
+    pub preview: &'static [(&'static str, &'static str)],
+}
+
+// 所有术语，按照介绍的顺序排列
+pub static TERMS: &[Term] = &[
+    Term {
+        name: "变量遮蔽",
+        english: "shadowing",
+        aliases: &["遮蔽"],
+        definition: "用 let 声明一个与已有变量同名的新变量，新变量会遮住旧变量；与 mut 不同，遮蔽可以改变变量的类型",
+        example: "let spaces = \"   \";\nlet spaces = spaces.len();",
+        introduced: ("1", "variable_mutability"),
+        preview: &[],
+    },
+    Term {
+        name: "类型转换",
+        english: "type conversion",
+        aliases: &["type conversion"],
+        definition: "把一种类型的值转换成另一种类型，基本类型之间使用 as，其他类型通常使用 From、Into 或 parse",
+        example: "let x = 3.9_f64 as i32; // 3\nlet n: i32 = \"42\".parse().unwrap();",
+        introduced: ("1", "type_conversions"),
+        preview: &[],
+    },
+    Term {
+        name: "关联函数",
+        english: "associated function",
+        aliases: &["associated function"],
+        definition: "定义在 impl 块中、第一个参数不是 self 的函数，通过 类型名::函数名 调用，常用作构造函数",
+        example: "impl Rectangle {\n    fn square(size: u32) -> Self {\n        Self { width: size, height: size }\n    }\n}\nlet sq = Rectangle::square(3);",
+        introduced: ("3", "associated_functions"),
+        preview: &[("3", "struct_methods")],
+    },
+    Term {
+        name: "解构",
+        english: "destructuring",
+        aliases: &["destructuring"],
+        definition: "用模式把结构体、元组或枚举拆开，把其中的字段分别绑定到变量上",
+        example: "let Point { x, y } = point;\nlet (a, b) = (1, 2);",
+        introduced: ("3", "destructuring_structs"),
+        preview: &[],
+    },
+    Term {
+        name: "Option 枚举",
+        english: "Option",
+        aliases: &[],
+        definition: "标准库中表示 \"可能没有值\" 的枚举，Some(T) 表示有值，None 表示没有值，Rust 用它代替空指针",
+        example: "let found: Option<usize> = names.iter().position(|n| n == \"Bob\");\nif let Some(index) = found { /* ... */ }",
+        introduced: ("4", "option_enum"),
+        preview: &[],
+    },
+    Term {
+        name: "模式匹配",
+        english: "pattern matching",
+        aliases: &["pattern matching"],
+        definition: "把一个值依次与若干个模式比较，执行第一个匹配的分支；match 要求覆盖所有可能的值",
+        example: "match number {\n    1 => println!(\"一\"),\n    2 | 3 => println!(\"二或三\"),\n    _ => println!(\"其他\"),\n}",
+        introduced: ("5", "match_basics"),
+        preview: &[("3", "destructuring_structs"), ("4", "enum_pattern_matching")],
+    },
+    Term {
+        name: "所有权",
+        english: "ownership",
+        aliases: &["ownership"],
+        definition: "Rust 管理内存的规则：每个值都有唯一的所有者，所有者离开作用域时值被释放；赋值或传参时所有权会转移",
+        example: "let v = vec![1, 2, 3];\nlet w = v;          // 所有权转移给 w\n// println!(\"{:?}\", v); // 错误：v 已经被移动",
+        introduced: ("6", "ownership_issues"),
+        preview: &[("3", "struct_update_syntax")],
+    },
+    Term {
+        name: "借用",
+        english: "borrowing",
+        aliases: &["borrowing"],
+        definition: "通过引用使用一个值而不取得它的所有权；同一时间可以有多个不可变引用，或者只有一个可变引用",
+        example: "fn total(v: &Vec<i32>) -> i32 { v.iter().sum() }\nlet sum = total(&numbers); // numbers 仍然可用",
+        introduced: ("6", "ownership_issues"),
+        preview: &[],
+    },
+    Term {
+        name: "模块",
+        english: "module",
+        aliases: &["module"],
+        definition: "用 mod 定义的命名空间，用来组织代码并控制可见性，模块中的项默认是私有的",
+        example: "mod math {\n    pub fn add(a: i32, b: i32) -> i32 { a + b }\n}\nlet sum = math::add(1, 2);",
+        introduced: ("7", "module_definition"),
+        preview: &[("3", "struct_definition"), ("3", "struct_field_visibility"), ("7", "package_and_crate_concepts")],
+    },
+    Term {
+        name: "错误传播",
+        english: "error propagation",
+        aliases: &["? 运算符", "? 操作符", "error propagation"],
+        definition: "函数不自己处理错误，而是把错误返回给调用者；在返回 Result 的函数中，? 会在出错时提前返回错误",
+        example: "fn read_number(text: &str) -> Result<i32, ParseIntError> {\n    let n = text.trim().parse::<i32>()?;\n    Ok(n * 2)\n}",
+        introduced: ("8", "error_propagation"),
+        preview: &[("4", "result_enum")],
+    },
+    Term {
+        name: "泛型",
+        english: "generics",
+        aliases: &["generics"],
+        definition: "用类型参数代替具体类型编写的函数、结构体、枚举和方法，同一份代码可以用于多种类型",
+        example: "fn largest<T: PartialOrd + Copy>(list: &[T]) -> T { /* ... */ }",
+        introduced: ("9", "generics_basics"),
+        preview: &[],
+    },
+    Term {
+        name: "泛型约束",
+        english: "trait bound",
+        aliases: &["trait bound", "特征约束"],
+        definition: "要求泛型参数实现某些 trait，写在类型参数后面或者 where 子句中，这样函数体才能使用这些 trait 的方法",
+        example: "fn print_all<T>(items: &[T])\nwhere\n    T: std::fmt::Display,\n{ /* ... */ }",
+        introduced: ("9", "generic_constraints"),
+        preview: &[],
+    },
+    Term {
+        name: "单态化",
+        english: "monomorphization",
+        aliases: &["monomorphization"],
+        definition: "编译器为泛型代码用到的每一种具体类型生成一份专门的代码，所以泛型在运行时没有额外开销",
+        example: "let a = largest(&[1, 2, 3]);       // 生成 largest::<i32>\nlet b = largest(&[1.0, 2.0, 3.0]); // 生成 largest::<f64>",
+        introduced: ("9", "performance_considerations"),
+        preview: &[("9", "generics_basics")],
+    },
+    Term {
+        name: "关联类型",
+        english: "associated type",
+        aliases: &["associated type"],
+        definition: "在 trait 中声明、由实现者指定的类型占位符，例如 Iterator 的 Item；每个类型只能为它选择一种具体类型",
+        example: "trait Container {\n    type Item;\n    fn get(&self, i: usize) -> Option<&Self::Item>;\n}",
+        introduced: ("9", "advanced_generics"),
+        preview: &[],
+    },
+    Term {
+        name: "生命周期",
+        english: "lifetime",
+        aliases: &["lifetime"],
+        definition: "引用保持有效的范围，编译器用它保证引用不会比它指向的值活得更久",
+        example: "fn longest<'a>(x: &'a str, y: &'a str) -> &'a str {\n    if x.len() > y.len() { x } else { y }\n}",
+        introduced: ("10", "lifetimes_basics"),
+        preview: &[("1", "constants_and_statics"), ("9", "generic_constraints")],
+    },
+    Term {
+        name: "生命周期省略",
+        english: "lifetime elision",
+        aliases: &["省略规则", "lifetime elision"],
+        definition: "编译器按三条固定的规则推断函数签名中的生命周期，符合规则时可以不写生命周期注解",
+        example: "fn first_word(s: &str) -> &str        // 省略的写法\nfn first_word<'a>(s: &'a str) -> &'a str // 编译器推断出的签名",
+        introduced: ("10", "lifetime_elision"),
+        preview: &[],
+    },
+];
+
+impl Term {
+    // 当前语言的标题，例如 "关联类型 (associated type)"，英文界面中是 "associated type (关联类型)"
+    pub fn title(&self) -> String {
+        if self.name == self.english {
+            return self.name.to_string();
+        }
+        match i18n::current() {
+            Locale::Zh => format!("{} ({})", self.name, self.english),
+            Locale::En => format!("{} ({})", self.english, self.name),
+        }
+    }
+
+    // 名称和其他写法
+    fn spellings(&self) -> impl Iterator<Item = &'static str> {
+        [self.name, self.english].into_iter().chain(self.aliases.iter().copied())
+    }
+
+    // 查找术语时是否与输入一致
+    fn matches(&self, query: &str) -> bool {
+        self.spellings().any(|spelling| spelling.eq_ignore_ascii_case(query))
+    }
+
+    // 一段文字中是否提到了这个术语，英文名称太常见（例如 Option），只用中文名称和其他写法
+    fn mentioned_in(&self, text: &str) -> bool {
+        [self.name]
+            .into_iter()
+            .chain(self.aliases.iter().copied())
+            .any(|spelling| text.contains(spelling) || text.to_lowercase().contains(&spelling.to_lowercase()))
+    }
+}
+
+// 课程中按顺序排列的一个小节
+#[derive(Clone, Copy)]
+pub struct Place {
+    lesson: &'static dyn Lesson,
+    index: usize,
+}
+
+impl Place {
+    // 根据课程编号和小节标识找到小节
+    pub fn find((lesson, section): (&str, &str)) -> Option<Place> {
+        let lesson = lesson::find(lesson)?;
+        let index = lesson.sections().iter().position(|candidate| candidate.id == section)?;
+        Some(Place { lesson, index })
+    }
+
+    fn section(&self) -> &'static Section {
+        &self.lesson.sections()[self.index]
+    }
+
+    fn is(&self, (lesson, section): (&str, &str)) -> bool {
+        self.lesson.id() == lesson && self.section().id == section
+    }
+
+    // 例如 "第9课 第10节：泛型的高级用法"
+    pub fn title(&self) -> String {
+        tr_fmt(
+            "第{}课 第{}节：{}",
+            &[&self.lesson.id(), &(self.index + 1), &tr(self.section().title)],
+        )
+    }
+
+    pub fn command(&self) -> String {
+        format!("rustlearn run {} --section {}", self.lesson.id(), self.section().id)
+    }
+
+    // 小节函数在源代码中的路径，例如 "_09_generics::advanced_generics"
+    pub fn path(&self) -> String {
+        let module = self.lesson.source_path().trim_start_matches("src/").trim_end_matches(".rs");
+        format!("{}::{}", module, self.section().id)
+    }
+}
+
+// 学习者在每个小节中读到的中文文字：标题、说明、运行结果和源代码中的注释
+struct Corpus {
+    places: Vec<(Place, String)>,
+}
+
+impl Corpus {
+    fn build() -> Corpus {
+        let mut places = Vec::new();
+        for lesson in lesson::LESSONS {
+            for (index, section) in lesson.sections().iter().enumerate() {
+                let mut text = format!("{}\n", section.title);
+                for line in source::explanation(*lesson, section) {
+                    text.push_str(line);
+                    text.push('\n');
+                }
+                for line in source::plain(*lesson, section).unwrap_or_default().lines() {
+                    if let Some((_, comment)) = line.split_once("//") {
+                        text.push_str(comment);
+                        text.push('\n');
+                    }
+                }
+                text.push_str(&chinese_output(section));
+                places.push((Place { lesson: *lesson, index }, text));
+            }
+        }
+        Corpus { places }
+    }
+
+    // 提到这个术语的小节，按课程顺序排列
+    fn mentions(&self, term: &Term) -> Vec<Place> {
+        self.places
+            .iter()
+            .filter(|(_, text)| term.mentioned_in(text))
+            .map(|(place, _)| *place)
+            .collect()
+    }
+
+    fn position(&self, (lesson, section): (&str, &str)) -> Option<usize> {
+        self.places.iter().position(|(place, _)| place.is((lesson, section)))
+    }
+}
+
+// 小节的中文运行结果，术语以中文原文为准，与界面语言无关
+fn chinese_output(section: &Section) -> String {
+    let mut buffer = Vec::new();
+    let mut ctx = LessonContext::new(&mut buffer).with_locale(Locale::Zh);
+    (section.run)(&mut ctx);
+    // 写入内存缓冲区不会失败
+    let _ = ctx.finish();
+    String::from_utf8_lossy(&buffer).into_owned()
+}
+
+// glossary 子命令，返回进程退出码
+pub fn run_cli(args: &[String]) -> i32 {
+    match args {
+        [] => list(),
+        [flag] if flag == "--check" => check(),
+        [flag, ..] if flag.starts_with('-') => {
+            eprintln!("{}", tr("用法: rustlearn glossary [<术语> | --check]"));
+            2
+        }
+        words => show(&words.join(" ")),
+    }
+}
+
+// 列出所有术语
+fn list() -> i32 {
+    let mut text = format!("{}\n", tr("术语表："));
+    for term in TERMS {
+        match Place::find(term.introduced) {
+            Some(place) => text.push_str(&format!("  {} — {}\n", term.title(), place.title())),
+            None => text.push_str(&format!("  {}\n", term.title())),
+        }
+    }
+    text.push_str(&format!("\n{}\n", tr("使用 `rustlearn glossary <术语>` 查看术语的定义、示例和相关的小节")));
+    cli::print(&text)
+}
+
+// 显示一个术语
+fn show(query: &str) -> i32 {
+    let term = match TERMS.iter().find(|term| term.matches(query)) {
+        Some(term) => term,
+        None => {
+            // 输入的是术语的一部分时，唯一匹配的术语直接显示，多个时列出候选
+            let query = query.to_lowercase();
+            let candidates: Vec<&'static Term> = TERMS
+                .iter()
+                .filter(|term| term.spellings().any(|spelling| spelling.to_lowercase().contains(&query)))
+                .collect();
+            match candidates.as_slice() {
+                [term] => *term,
+                [] => {
+                    eprintln!(
+                        "{}",
+                        tr_fmt("术语表中没有 \"{}\"，可以使用 `rustlearn glossary` 查看所有术语", &[&query])
+                    );
+                    return 2;
+                }
+                _ => {
+                    let names: Vec<String> = candidates.iter().map(|term| term.title()).collect();
+                    eprintln!("{}", tr_fmt("\"{}\" 匹配到多个术语：{}", &[&query, &names.join(tr("、").as_ref())]));
+                    return 2;
+                }
+            }
+        }
+    };
+
+    let corpus = Corpus::build();
+    let style = theme::current();
+    let mut text = format!("{}\n\n{}\n\n{}\n", style.lesson_heading(&term.title()), tr(term.definition), style.label(&tr("示例：")));
+    let mut highlighter = Highlighter::default();
+    for line in term.example.lines() {
+        text.push_str(&format!("    {}\n", highlighter.line(line, style)));
+    }
+
+    if let Some(place) = Place::find(term.introduced) {
+        text.push_str(&format!(
+            "\n{}\n  {}\n    {}\n",
+            style.label(&tr("介绍这个术语的小节：")),
+            place.title(),
+            place.command()
+        ));
+    }
+    let uses: Vec<Place> = corpus
+        .mentions(term)
+        .into_iter()
+        .filter(|place| !place.is(term.introduced))
+        .collect();
+    if !uses.is_empty() {
+        text.push_str(&format!("\n{}\n", style.label(&tr("用到这个术语的其他小节："))));
+        for place in uses {
+            text.push_str(&format!("  {}\n    {}\n", place.title(), place.command()));
+        }
+    }
+
+    cli::print(&text)
+}
+
+// 检查术语表：介绍术语的小节是否存在，以及有没有在介绍之前就用到术语的小节
+fn check() -> i32 {
+    let corpus = Corpus::build();
+    let mut problems = 0;
+
+    for term in TERMS {
+        let (lesson, section) = term.introduced;
+        let Some(introduced) = corpus.position(term.introduced) else {
+            println!("{}", tr_fmt("{}：找不到介绍这个术语的小节（第{}课 {}）", &[&term.title(), &lesson, &section]));
+            problems += 1;
+            continue;
+        };
+        if !term.mentioned_in(&corpus.places[introduced].1) {
+            println!(
+                "{}",
+                tr_fmt("{}：介绍这个术语的小节中没有出现这个术语（{}）", &[&term.title(), &corpus.places[introduced].0.title()])
+            );
+            problems += 1;
+        }
+
+        for place in corpus.mentions(term) {
+            let position = corpus.position((place.lesson.id(), place.section().id)).unwrap_or_default();
+            let previewed = term.preview.iter().any(|preview| place.is(*preview));
+            if position < introduced && !previewed {
+                println!(
+                    "{}",
+                    tr_fmt(
+                        "{}：{} 在介绍之前就用到了这个术语，这个术语在 {} 中介绍",
+                        &[&term.title(), &place.title(), &corpus.places[introduced].0.title()]
+                    )
+                );
+                problems += 1;
+            }
+        }
+    }
+
+    if problems == 0 {
+        println!("{}", tr_fmt("术语表检查通过，共 {} 个术语", &[&TERMS.len()]));
+        0
+    } else {
+        println!("\n{}", tr_fmt("发现 {} 个问题", &[&problems]));
+        1
+    }
+}
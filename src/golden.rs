@@ -0,0 +1,496 @@
+// 运行结果校验
+// 课程文件中的 "// 运行结果：" 注释块记录了每个小节应该输出的内容
+// 本模块逐节运行示例、捕获真实输出，并与注释中的预期结果进行比较，
+// 这样这些注释就变成了一组回归测试，也可以用 --bless 根据真实输出重新生成注释
+//
+// 注释块的写法：
+// 1. "// 运行结果：" 之后连续的 "// " 注释行就是预期输出，不包括小节开头的 "--- 标题 ---"，
+//    注释块的第一行也可以先写一行小节标题，比较时会跳过这一行
+// 2. 写成 "// 运行结果（顺序不固定）：" 时按无序方式比较，用于遍历 HashMap、HashSet 的小节
+// 3. 以 "而定]" 结尾的方括号内容（例如 "[具体时间，根据运行环境而定]"）可以匹配任意输出
+//
+// 测验中的预测输出题也在这里校验：正确答案必须是对应小节真实输出中的某一行
+//
+// 默认校验编译进程序的课程源代码，--source 可以换成磁盘上的另一个文件，
+// 例如正在修改、还没有重新编译的课程文件，或者测试中准备好的注释块
+
+use std::cmp::Reverse;
+use std::fs;
+use std::io;
+use std::iter;
+use std::path::{Path, PathBuf};
+
+use crate::context::LessonContext;
+use crate::i18n::{tr, tr_fmt, Locale};
+use crate::lesson::{self, Lesson, Section};
+use crate::quiz::QuestionKind;
+
+// 注释块的开头
+const BLOCK_MARKER: &str = "// 运行结果";
+
+// 表示输出顺序不固定的标记
+const UNORDERED_MARKER: &str = "（顺序不固定）";
+
+// 注释中的一个 "运行结果" 块
+struct ExpectedBlock {
+    // 注释块第一行（"// 运行结果：" 所在行）在源文件中的行号，从 0 开始
+    header_line: usize,
+    // 注释块最后一行之后的行号
+    end_line: usize,
+    // 注释行的缩进
+    indent: String,
+    // 是否按无序方式比较
+    unordered: bool,
+    // 注释块的第一行是小节标题时，保存这一行原来的写法，例如 "// --- 标题 ---"
+    title_line: Option<String>,
+    // 预期输出的各行，不包括标题行
+    lines: Vec<String>,
+}
+
+// 单个小节的校验结果
+enum Outcome {
+    // 输出与注释一致
+    Matched,
+    // 输出与注释不一致，附带真实输出
+    Mismatched(Vec<String>),
+    // 小节中没有 "运行结果" 注释
+    Undocumented,
+}
+
+// verify 子命令的入口，返回进程退出码
+// 用法：
+//   rustlearn verify                    校验所有小节
+//   rustlearn verify --lesson 6         只校验第 6 课
+//   rustlearn verify --bless            用真实输出重新生成不一致的注释
+//   rustlearn verify --lesson 6 --source <文件>
+//                                       用指定文件中的注释块校验第 6 课，--bless 时也写回这个文件
+pub fn run_cli(args: &[String]) -> i32 {
+    let mut bless = false;
+    let mut only_lesson = None;
+    let mut source_file = None;
+    let mut iter = args.iter();
+
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--bless" => bless = true,
+            "--lesson" => match iter.next() {
+                Some(id) => only_lesson = Some(id.clone()),
+                None => {
+                    eprintln!("{}", tr("--lesson 需要一个课程编号"));
+                    return 2;
+                }
+            },
+            "--source" => match iter.next() {
+                Some(path) => source_file = Some(PathBuf::from(path)),
+                None => {
+                    eprintln!("{}", tr("--source 需要一个文件路径"));
+                    return 2;
+                }
+            },
+            other => {
+                eprintln!("{}", tr_fmt("无法识别的参数: {}", &[&other]));
+                return 2;
+            }
+        }
+    }
+
+    // 一个源文件只对应一课
+    if source_file.is_some() && only_lesson.is_none() {
+        eprintln!("{}", tr("--source 需要和 --lesson 一起使用"));
+        return 2;
+    }
+
+    let lessons: Vec<&dyn Lesson> = match &only_lesson {
+        Some(id) => match lesson::find(id) {
+            Some(lesson) => vec![lesson],
+            None => {
+                eprintln!("{}", tr_fmt("没有编号为 {} 的课程", &[id]));
+                return 2;
+            }
+        },
+        None => lesson::LESSONS.to_vec(),
+    };
+
+    match verify(&lessons, source_file.as_deref(), bless) {
+        Ok(true) => 0,
+        Ok(false) => 1,
+        Err(error) => {
+            eprintln!("{}", tr_fmt("校验失败: {}", &[&error]));
+            2
+        }
+    }
+}
+
+// 校验给定课程的所有小节，全部一致（或者已经重新生成）时返回 true
+// source_file 为 None 时使用课程自己的源文件
+fn verify(lessons: &[&dyn Lesson], source_file: Option<&Path>, bless: bool) -> io::Result<bool> {
+    let mut matched = 0;
+    let mut mismatched = 0;
+    let mut undocumented = Vec::new();
+    let mut stale_questions = 0;
+
+    for &lesson in lessons {
+        let (source, path) = match source_file {
+            Some(path) => (fs::read_to_string(path)?, path.to_path_buf()),
+            None => (
+                lesson.source().to_string(),
+                Path::new(env!("CARGO_MANIFEST_DIR")).join(lesson.source_path()),
+            ),
+        };
+        let mut outcomes = Vec::new();
+
+        for section in lesson.sections() {
+            let outcome = check_section(&source, section)?;
+
+            match &outcome {
+                Outcome::Matched => matched += 1,
+                Outcome::Mismatched(actual) => {
+                    mismatched += 1;
+                    let block = find_block(&source, section)
+                        .expect("不一致的小节一定带有注释块");
+                    println!("✗ {}", tr_fmt("第{}课 {}（{}）", &[&lesson.id(), &section.id, &tr(section.title)]));
+                    for line in diff(&block.lines, actual) {
+                        println!("    {}", line);
+                    }
+                }
+                Outcome::Undocumented => {
+                    undocumented.push(tr_fmt("第{}课 {}", &[&lesson.id(), &section.id]));
+                }
+            }
+
+            outcomes.push((section, outcome));
+        }
+
+        if bless {
+            bless_file(&path, &outcomes)?;
+        }
+
+        for problem in check_quiz(lesson)? {
+            stale_questions += 1;
+            println!("✗ {}", problem);
+        }
+    }
+
+    println!(
+        "\n{}",
+        tr_fmt(
+            "运行结果校验：{} 个小节一致，{} 个小节不一致，{} 个小节没有记录运行结果",
+            &[&matched, &mismatched, &undocumented.len()]
+        )
+    );
+
+    if !undocumented.is_empty() {
+        println!("{}", tr_fmt("没有记录运行结果的小节：{}", &[&undocumented.join(tr("、").as_ref())]));
+    }
+
+    // 测验题目需要手动修改，--bless 不会重新生成
+    if stale_questions > 0 {
+        println!("{}", tr_fmt("测验题目校验：{} 道题目与运行结果不一致", &[&stale_questions]));
+    }
+
+    if bless && mismatched > 0 {
+        println!("{}", tr_fmt("已根据真实输出重新生成 {} 个注释块", &[&mismatched]));
+        return Ok(stale_questions == 0);
+    }
+
+    Ok(mismatched == 0 && stale_questions == 0)
+}
+
+// 校验一课的测验题目，返回发现的问题
+// 每道题的正确答案都必须是其中一个选项；预测输出题的正确答案还必须出现在对应小节的真实输出中
+// 复习计划按题干的哈希记录题目，所以同一课中的题干不能相同
+fn check_quiz(lesson: &dyn Lesson) -> io::Result<Vec<String>> {
+    let mut problems = Vec::new();
+
+    for (index, question) in lesson.quiz().iter().enumerate() {
+        if let Some(earlier) = lesson.quiz()[..index].iter().position(|other| other.id() == question.id()) {
+            problems.push(tr_fmt(
+                "第{}课测验第{}题：题干和第{}题相同，复习计划无法区分这两道题",
+                &[&lesson.id(), &(index + 1), &(earlier + 1)]
+            ));
+        }
+
+        let Some(answer) = question.options().get(question.answer()) else {
+            problems.push(tr_fmt("第{}课测验第{}题：正确答案的编号超出了选项范围", &[&lesson.id(), &(index + 1)]));
+            continue;
+        };
+
+        let QuestionKind::PredictOutput { section, .. } = question.kind else {
+            continue;
+        };
+
+        let problem = match lesson.sections().iter().find(|candidate| candidate.id == section) {
+            Some(section) => {
+                let output = capture_section(section)?;
+                if output.lines().any(|line| line.trim() == answer.trim()) {
+                    continue;
+                }
+                tr_fmt("正确答案 \"{}\" 没有出现在小节 {} 的输出中", &[answer, &section.id])
+            }
+            None => tr_fmt("对应的小节 {} 不存在", &[&section]),
+        };
+        problems.push(tr_fmt("第{}课测验第{}题：{}", &[&lesson.id(), &(index + 1), &problem]));
+    }
+
+    Ok(problems)
+}
+
+// 运行一个小节并与源代码注释中的预期输出比较
+fn check_section(source: &str, section: &Section) -> io::Result<Outcome> {
+    let block = match find_block(source, section) {
+        Some(block) => block,
+        None => return Ok(Outcome::Undocumented),
+    };
+
+    let output = capture_section(section)?;
+    let actual = actual_lines(&output, section);
+
+    if outputs_match(&block.lines, &actual, block.unordered) {
+        Ok(Outcome::Matched)
+    } else {
+        Ok(Outcome::Mismatched(actual))
+    }
+}
+
+// 运行小节并把它的输出捕获到内存中
+// 运行结果注释是中文写的，所以无论界面语言是什么都以中文运行
+fn capture_section(section: &Section) -> io::Result<String> {
+    let mut buffer = Vec::new();
+    let mut ctx = LessonContext::new(&mut buffer).with_locale(Locale::Zh);
+    (section.run)(&mut ctx);
+    ctx.finish()?;
+
+    Ok(String::from_utf8_lossy(&buffer).into_owned())
+}
+
+// 把捕获的输出整理成逐行的列表：去掉小节开头的标题行、行尾空白以及末尾的空行
+fn actual_lines(output: &str, section: &Section) -> Vec<String> {
+    let heading = format!("--- {} ---", section.title);
+    let mut lines: Vec<String> = output.lines().map(|line| line.trim_end().to_string()).collect();
+
+    // 小节的输出以一个空行和 "--- 标题 ---" 开头
+    if let Some(position) = lines.iter().position(|line| *line == heading)
+        && lines[..position].iter().all(|line| line.is_empty())
+    {
+        lines.drain(..=position);
+    }
+
+    while lines.last().is_some_and(|line| line.is_empty()) {
+        lines.pop();
+    }
+
+    lines
+}
+
+// 在课程源代码中查找某个小节的 "运行结果" 注释块
+fn find_block(source: &str, section: &Section) -> Option<ExpectedBlock> {
+    let lines: Vec<&str> = source.lines().collect();
+    // 只在本小节的函数体内查找，避免误用其他小节的注释块
+    let (start, end) = function_range(&lines, section.id)?;
+    let header_line = (start..end).find(|&index| is_block_header(lines[index]))?;
+    let header = lines[header_line];
+    let indent = header[..header.len() - header.trim_start().len()].to_string();
+
+    let mut expected = Vec::new();
+    let mut end_line = header_line + 1;
+
+    while end_line < end {
+        let trimmed = lines[end_line].trim_start();
+        match trimmed.strip_prefix("//") {
+            Some(text) => {
+                expected.push(text.strip_prefix(' ').unwrap_or(text).trim_end().to_string());
+                end_line += 1;
+            }
+            None => break,
+        }
+    }
+
+    while expected.last().is_some_and(|line| line.is_empty()) {
+        expected.pop();
+    }
+
+    // 有些注释块会先写一行小节标题
+    let title_line = expected
+        .first()
+        .is_some_and(|line| *line == section.title || *line == format!("--- {} ---", section.title))
+        .then(|| lines[header_line + 1].to_string());
+    if title_line.is_some() {
+        expected.remove(0);
+    }
+
+    Some(ExpectedBlock {
+        header_line,
+        end_line,
+        indent,
+        unordered: header.contains(UNORDERED_MARKER),
+        title_line,
+        lines: expected,
+    })
+}
+
+// 判断一行是否是 "运行结果" 注释块的开头
+fn is_block_header(line: &str) -> bool {
+    let trimmed = line.trim();
+    trimmed.starts_with(BLOCK_MARKER) && trimmed.ends_with('：')
+}
+
+// 找到小节函数的起止行号：从 "fn 名称(ctx: &mut LessonContext) {" 开始，到与之缩进相同的 "}" 结束
+fn function_range(lines: &[&str], section_id: &str) -> Option<(usize, usize)> {
+    let signature = format!("fn {}(", section_id);
+    let start = lines.iter().position(|line| line.trim_start().starts_with(&signature))?;
+    let indent = &lines[start][..lines[start].len() - lines[start].trim_start().len()];
+    let closing = format!("{}}}", indent);
+    let end = (start + 1..lines.len())
+        .find(|&index| lines[index].trim_end() == closing)
+        .unwrap_or(lines.len());
+
+    Some((start, end))
+}
+
+// 比较预期输出和真实输出
+fn outputs_match(expected: &[String], actual: &[String], unordered: bool) -> bool {
+    if expected.len() != actual.len() {
+        return false;
+    }
+
+    if unordered {
+        // 无序比较：先把每一行里 {...} 中的元素排序，再把所有行排序后比较
+        let mut expected: Vec<String> = expected.iter().map(|line| sort_braced_items(line)).collect();
+        let mut actual: Vec<String> = actual.iter().map(|line| sort_braced_items(line)).collect();
+        expected.sort();
+        actual.sort();
+
+        return expected.iter().zip(&actual).all(|(e, a)| line_matches(e, a));
+    }
+
+    expected.iter().zip(actual).all(|(e, a)| line_matches(e, a))
+}
+
+// 比较单行输出，支持 "[...而定]" 形式的占位符
+fn line_matches(expected: &str, actual: &str) -> bool {
+    match find_placeholder(expected) {
+        None => expected == actual,
+        Some((start, end)) => {
+            let Some(rest) = actual.strip_prefix(&expected[..start]) else {
+                return false;
+            };
+
+            // 占位符可以匹配任意长度的内容，尝试每一个可能的切分位置
+            rest.char_indices()
+                .map(|(index, _)| index)
+                .chain(iter::once(rest.len()))
+                .any(|index| line_matches(&expected[end..], &rest[index..]))
+        }
+    }
+}
+
+// 查找形如 "[具体时间，根据运行环境而定]" 的占位符，返回它的字节范围
+fn find_placeholder(line: &str) -> Option<(usize, usize)> {
+    let end = line.find("而定]")? + "而定]".len();
+    let start = line[..end].rfind('[')?;
+    Some((start, end))
+}
+
+// 把一行中 {...} 里用 ", " 分隔的元素排序，用于比较 HashMap、HashSet 的调试输出
+fn sort_braced_items(line: &str) -> String {
+    let (Some(open), Some(close)) = (line.find('{'), line.rfind('}')) else {
+        return line.to_string();
+    };
+
+    if open > close {
+        return line.to_string();
+    }
+
+    let mut items: Vec<&str> = line[open + 1..close].split(", ").collect();
+    items.sort();
+    format!("{}{{{}}}{}", &line[..open], items.join(", "), &line[close + 1..])
+}
+
+// 生成预期输出和真实输出之间的逐行差异，"-" 表示注释中的行，"+" 表示真实输出中的行
+fn diff(expected: &[String], actual: &[String]) -> Vec<String> {
+    // 用最长公共子序列找出两边相同的行
+    let n = expected.len();
+    let m = actual.len();
+    let mut lcs = vec![vec![0usize; m + 1]; n + 1];
+
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if line_matches(&expected[i], &actual[j]) {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut result = Vec::new();
+    let (mut i, mut j) = (0, 0);
+
+    while i < n || j < m {
+        if i < n && j < m && line_matches(&expected[i], &actual[j]) {
+            result.push(format!("  {}", actual[j]));
+            i += 1;
+            j += 1;
+        } else if j < m && (i == n || lcs[i][j + 1] >= lcs[i + 1][j]) {
+            result.push(format!("+ {}", actual[j]));
+            j += 1;
+        } else {
+            result.push(format!("- {}", expected[i]));
+            i += 1;
+        }
+    }
+
+    result
+}
+
+// 用真实输出重新生成一课中所有不一致的注释块，并写回源文件
+fn bless_file(path: &Path, outcomes: &[(&Section, Outcome)]) -> io::Result<()> {
+    let source = fs::read_to_string(path)?;
+    let mut lines: Vec<String> = source.lines().map(str::to_string).collect();
+    let mut changed = false;
+
+    // 从后往前替换，这样前面注释块的行号不会因为替换而失效
+    let mut replacements: Vec<(ExpectedBlock, &Vec<String>)> = outcomes
+        .iter()
+        .filter_map(|(section, outcome)| match outcome {
+            Outcome::Mismatched(actual) => {
+                find_block(&source, section).map(|block| (block, actual))
+            }
+            _ => None,
+        })
+        .collect();
+    replacements.sort_by_key(|(block, _)| Reverse(block.header_line));
+
+    for (block, actual) in replacements {
+        let mut new_lines = Vec::new();
+
+        // 原样保留注释块的标题行
+        if let Some(title_line) = block.title_line {
+            new_lines.push(title_line);
+        }
+
+        for line in actual {
+            if line.is_empty() {
+                new_lines.push(format!("{}// ", block.indent));
+            } else {
+                new_lines.push(format!("{}// {}", block.indent, line));
+            }
+        }
+
+        lines.splice(block.header_line + 1..block.end_line, new_lines);
+        changed = true;
+    }
+
+    if changed {
+        let mut content = lines.join("\n");
+        if source.ends_with('\n') {
+            content.push('\n');
+        }
+        fs::write(path, content)?;
+        // 课程自己的源文件显示相对于项目目录的路径
+        let shown = path.strip_prefix(env!("CARGO_MANIFEST_DIR")).unwrap_or(path);
+        println!("{}", tr_fmt("已更新 {}", &[&shown.display()]));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,477 @@
+// 课程依赖图和学习路线
+// 课程按编号排列，但是课程之间真正的依赖关系是一张图：例如第10课的生命周期约束建立在第9课的泛型约束之上，
+// 第5课的 match 沿用了第4课的 Coin 枚举。每一课通过 Lesson trait 声明需要先学的课程，
+// 以及本课中哪些小节依赖其他课程中的哪些小节
+//
+// `rustlearn graph` 以文字或者 Graphviz DOT 格式显示这张图，
+// `rustlearn path` 列出面向不同读者的学习路线，菜单根据学习进度和这张图推荐下一课
+//
+// `rustlearn graph --check` 检查：
+// 1. 依赖的课程和小节都存在，课程之间没有循环依赖
+// 2. 小节依赖的课程也是本课依赖的课程（可以是间接依赖）
+// 3. 每条学习路线在安排一个小节之前，已经安排了它依赖的内容
+
+use std::collections::BTreeSet;
+
+use crate::cli;
+use crate::i18n::{tr, tr_fmt};
+use crate::lesson::{self, Lesson, Section};
+use crate::progress::Progress;
+
+// 小节之间的依赖：本课的某个小节需要先学习另一课的某个小节
+pub struct Prerequisite {
+    // 本课中需要前置知识的小节
+    pub section: &'static str,
+    // 需要先学习的课程编号
+    pub lesson: &'static str,
+    // 需要先学习的小节标识
+    pub requires: &'static str,
+    // 为什么需要先学那一节
+    pub reason: &'static str,
+}
+
+// 一条学习路线
+pub struct LearningPath {
+    // 路线标识，在命令行中使用，例如 "cpp"
+    pub id: &'static str,
+    pub title: &'static str,
+    pub description: &'static str,
+    // 这条路线假定读者已经掌握的课程
+    pub assumes: &'static [&'static str],
+    // 按学习顺序排列的步骤
+    pub steps: &'static [Step],
+}
+
+// 学习路线中的一步：一整课或者一课中的几个小节
+pub struct Step {
+    pub lesson: &'static str,
+    // 只学习其中的这些小节，为空时学习整课
+    pub sections: &'static [&'static str],
+}
+
+// 所有学习路线
+pub static PATHS: &[LearningPath] = &[
+    LearningPath {
+        id: "cpp",
+        title: "C++ 开发者快速通道",
+        description: "已经熟悉 C++ 的开发者可以跳过语法基础，重点学习与 C++ 差别最大的枚举、模式匹配、错误处理、泛型约束和生命周期",
+        assumes: &[],
+        steps: &[
+            Step { lesson: "1", sections: &["variable_mutability", "type_conversions"] },
+            Step { lesson: "2", sections: &["loop_statements", "match_expressions"] },
+            Step {
+                lesson: "3",
+                sections: &["struct_definition", "struct_methods", "associated_functions", "destructuring_structs"],
+            },
+            Step { lesson: "4", sections: &[] },
+            Step {
+                lesson: "5",
+                sections: &["match_basics", "pattern_destructuring", "if_let_expressions", "advanced_pattern_matching"],
+            },
+            Step { lesson: "6", sections: &["ownership_issues"] },
+            Step { lesson: "8", sections: &[] },
+            Step {
+                lesson: "9",
+                sections: &["generic_constraints", "performance_considerations", "generics_with_traits"],
+            },
+            Step { lesson: "10", sections: &[] },
+        ],
+    },
+    LearningPath {
+        id: "errors",
+        title: "错误处理专题",
+        description: "学过基本语法之后，集中学习 Option 和 Result、用模式匹配处理它们，以及错误传播、自定义错误类型和错误处理库",
+        assumes: &["1", "2", "3"],
+        steps: &[
+            Step {
+                lesson: "4",
+                sections: &["enum_definition", "enum_pattern_matching", "enum_with_data", "option_enum", "result_enum"],
+            },
+            Step { lesson: "5", sections: &["match_basics", "pattern_destructuring", "if_let_expressions"] },
+            Step { lesson: "8", sections: &[] },
+            Step { lesson: "9", sections: &["generic_enums"] },
+        ],
+    },
+];
+
+impl Step {
+    // 这一步要学习的小节，找不到的小节标识会被跳过（由 --check 报告）
+    fn sections(&self) -> Vec<&'static Section> {
+        let Some(lesson) = lesson::find(self.lesson) else {
+            return Vec::new();
+        };
+        if self.sections.is_empty() {
+            return lesson.sections().iter().collect();
+        }
+        self.sections
+            .iter()
+            .filter_map(|id| lesson.sections().iter().find(|section| section.id == *id))
+            .collect()
+    }
+}
+
+// 某一课的某个小节
+fn find_section(lesson: &str, section: &str) -> Option<&'static Section> {
+    lesson::find(lesson)?.sections().iter().find(|candidate| candidate.id == section)
+}
+
+// "第3课、第9课" 这样的课程列表
+fn lesson_list(ids: &[&str]) -> String {
+    let names: Vec<String> = ids.iter().map(|id| tr_fmt("第{}课", &[id])).collect();
+    names.join(tr("、").as_ref())
+}
+
+// 某一课直接和间接依赖的所有课程
+fn all_prerequisites(lesson: &dyn Lesson) -> BTreeSet<&'static str> {
+    let mut found = BTreeSet::new();
+    let mut pending: Vec<&'static str> = lesson.prerequisites().to_vec();
+    while let Some(id) = pending.pop() {
+        if found.insert(id)
+            && let Some(prerequisite) = lesson::find(id)
+        {
+            pending.extend(prerequisite.prerequisites());
+        }
+    }
+    found
+}
+
+// 课程之间的循环依赖，没有时返回 None
+// 找到时返回组成循环的课程编号，首尾是同一课
+fn find_cycle() -> Option<Vec<&'static str>> {
+    fn visit(id: &'static str, stack: &mut Vec<&'static str>, done: &mut BTreeSet<&'static str>) -> Option<Vec<&'static str>> {
+        if let Some(start) = stack.iter().position(|item| *item == id) {
+            let mut cycle = stack[start..].to_vec();
+            cycle.push(id);
+            return Some(cycle);
+        }
+        if done.contains(id) {
+            return None;
+        }
+
+        stack.push(id);
+        for prerequisite in lesson::find(id).map_or(&[][..], |lesson| lesson.prerequisites()) {
+            if let Some(cycle) = visit(prerequisite, stack, done) {
+                return Some(cycle);
+            }
+        }
+        stack.pop();
+        done.insert(id);
+        None
+    }
+
+    let mut done = BTreeSet::new();
+    lesson::LESSONS
+        .iter()
+        .find_map(|lesson| visit(lesson.id(), &mut Vec::new(), &mut done))
+}
+
+// 检查依赖图和学习路线，返回发现的问题
+fn problems() -> Vec<String> {
+    let mut problems = Vec::new();
+
+    for lesson in lesson::LESSONS {
+        for id in lesson.prerequisites() {
+            if lesson::find(id).is_none() {
+                problems.push(tr_fmt("第{}课依赖的第{}课不存在", &[&lesson.id(), id]));
+            }
+        }
+
+        let required = all_prerequisites(*lesson);
+        for prerequisite in lesson.section_prerequisites() {
+            if !lesson.sections().iter().any(|section| section.id == prerequisite.section) {
+                problems.push(tr_fmt("第{}课中没有 {} 这个小节", &[&lesson.id(), &prerequisite.section]));
+            }
+            if find_section(prerequisite.lesson, prerequisite.requires).is_none() {
+                problems.push(tr_fmt(
+                    "第{}课的 {} 依赖的第{}课 {} 不存在",
+                    &[&lesson.id(), &prerequisite.section, &prerequisite.lesson, &prerequisite.requires],
+                ));
+            } else if !required.contains(prerequisite.lesson) {
+                problems.push(tr_fmt(
+                    "第{}课的 {} 依赖第{}课，但是第{}课没有把第{}课列为需要先学的课程",
+                    &[&lesson.id(), &prerequisite.section, &prerequisite.lesson, &lesson.id(), &prerequisite.lesson],
+                ));
+            }
+        }
+    }
+
+    if let Some(cycle) = find_cycle() {
+        problems.push(tr_fmt("课程之间存在循环依赖：{}", &[&cycle.join(" → ")]));
+    }
+
+    for path in PATHS {
+        problems.extend(path_problems(path));
+    }
+    problems
+}
+
+// 检查一条学习路线：每一步之前都已经安排了它依赖的课程和小节
+fn path_problems(path: &LearningPath) -> Vec<String> {
+    let mut problems = Vec::new();
+    let mut lessons: BTreeSet<&str> = path.assumes.iter().copied().collect();
+    let mut sections: BTreeSet<(&str, &str)> = BTreeSet::new();
+    for id in path.assumes {
+        if let Some(lesson) = lesson::find(id) {
+            sections.extend(lesson.sections().iter().map(|section| (lesson.id(), section.id)));
+        }
+    }
+
+    for step in path.steps {
+        let Some(lesson) = lesson::find(step.lesson) else {
+            problems.push(tr_fmt("路线 {}：第{}课不存在", &[&path.id, &step.lesson]));
+            continue;
+        };
+        for id in step.sections {
+            if find_section(step.lesson, id).is_none() {
+                problems.push(tr_fmt("路线 {}：第{}课中没有 {} 这个小节", &[&path.id, &step.lesson, id]));
+            }
+        }
+        for id in lesson.prerequisites() {
+            if !lessons.contains(id) {
+                problems.push(tr_fmt("路线 {}：第{}课安排在它依赖的第{}课之前", &[&path.id, &step.lesson, id]));
+            }
+        }
+
+        let chosen = step.sections();
+        for prerequisite in lesson.section_prerequisites() {
+            let included = chosen.iter().any(|section| section.id == prerequisite.section);
+            if included && !sections.contains(&(prerequisite.lesson, prerequisite.requires)) {
+                problems.push(tr_fmt(
+                    "路线 {}：第{}课的 {} 依赖第{}课的 {}，但是路线中没有提前安排",
+                    &[&path.id, &step.lesson, &prerequisite.section, &prerequisite.lesson, &prerequisite.requires],
+                ));
+            }
+        }
+
+        lessons.insert(lesson.id());
+        sections.extend(chosen.iter().map(|section| (lesson.id(), section.id)));
+    }
+    problems
+}
+
+// 推荐的下一课：按编号顺序第一个还没有学完、并且依赖的课程都已经学完的课程
+pub fn recommend(progress: &Progress) -> Option<&'static dyn Lesson> {
+    let completed = |id: &str| lesson::find(id).is_some_and(|lesson| progress.lesson_completed_at(lesson).is_some());
+    lesson::LESSONS.iter().copied().find(|lesson| {
+        !completed(lesson.id()) && lesson.prerequisites().iter().all(|id| completed(id))
+    })
+}
+
+// 推荐下一课时给出的理由，没有依赖的课程时为空
+pub fn recommend_reason(lesson: &dyn Lesson) -> String {
+    if lesson.prerequisites().is_empty() {
+        String::new()
+    } else {
+        tr_fmt("（已学完它依赖的{}）", &[&lesson_list(lesson.prerequisites())])
+    }
+}
+
+// 以文字显示依赖图：每一课后面列出需要先学的课程，下面再列出依赖其他课程的小节
+fn render_text() -> String {
+    let mut text = format!("{}\n", tr("课程依赖图（← 后面是需要先学的内容）："));
+    for lesson in lesson::LESSONS {
+        let title = tr_fmt("第{}课：{}", &[&lesson.id(), &tr(lesson.title())]);
+        if lesson.prerequisites().is_empty() {
+            text.push_str(&format!("{}\n", title));
+        } else {
+            text.push_str(&format!("{} ← {}\n", title, lesson_list(lesson.prerequisites())));
+        }
+
+        for prerequisite in lesson.section_prerequisites() {
+            let (Some(section), Some(required)) = (
+                find_section(lesson.id(), prerequisite.section),
+                find_section(prerequisite.lesson, prerequisite.requires),
+            ) else {
+                continue;
+            };
+            let line = tr_fmt("{} ← 第{}课 {}", &[&tr(section.title), &prerequisite.lesson, &tr(required.title)]);
+            text.push_str(&format!("    {}\n", line));
+            text.push_str(&format!("      {}\n", tr(prerequisite.reason)));
+        }
+    }
+    text
+}
+
+// DOT 字符串中的引号和反斜杠需要转义
+fn quote(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+// 以 Graphviz DOT 格式输出依赖图
+// 每一课是一个方框，有小节依赖的课程放在一个虚线框中，和它涉及的小节放在一起；
+// 实线箭头从需要先学的课程指向后面的课程，虚线箭头表示小节之间的依赖，鼠标悬停时显示原因
+fn render_dot() -> String {
+    // 每一课中出现在小节依赖中的小节
+    let mut involved: Vec<(&str, &str)> = Vec::new();
+    for lesson in lesson::LESSONS {
+        for prerequisite in lesson.section_prerequisites() {
+            involved.push((lesson.id(), prerequisite.section));
+            involved.push((prerequisite.lesson, prerequisite.requires));
+        }
+    }
+    involved.sort();
+    involved.dedup();
+
+    let mut dot = String::from("digraph rustlearn {\n");
+    dot.push_str("    rankdir=LR;\n");
+    dot.push_str("    node [shape=box, fontname=\"sans-serif\"];\n");
+    dot.push_str("    edge [fontname=\"sans-serif\"];\n\n");
+
+    for lesson in lesson::LESSONS {
+        let node = format!(
+            "{} [label={}];",
+            quote(lesson.id()),
+            quote(&tr_fmt("第{}课：{}", &[&lesson.id(), &tr(lesson.title())]))
+        );
+        let sections: Vec<&Section> = involved
+            .iter()
+            .filter(|(id, _)| *id == lesson.id())
+            .filter_map(|(id, section)| find_section(id, section))
+            .collect();
+        if sections.is_empty() {
+            dot.push_str(&format!("    {}\n", node));
+            continue;
+        }
+
+        dot.push_str(&format!("    subgraph cluster_{} {{\n", lesson.id()));
+        dot.push_str("        style=dashed;\n");
+        dot.push_str(&format!("        {}\n", node));
+        for section in sections {
+            dot.push_str(&format!(
+                "        {} [label={}, shape=ellipse];\n",
+                quote(&format!("{}/{}", lesson.id(), section.id)),
+                quote(&tr(section.title))
+            ));
+        }
+        dot.push_str("    }\n");
+    }
+    dot.push('\n');
+
+    for lesson in lesson::LESSONS {
+        for id in lesson.prerequisites() {
+            dot.push_str(&format!("    {} -> {};\n", quote(id), quote(lesson.id())));
+        }
+    }
+    for lesson in lesson::LESSONS {
+        for prerequisite in lesson.section_prerequisites() {
+            dot.push_str(&format!(
+                "    {} -> {} [style=dashed, tooltip={}];\n",
+                quote(&format!("{}/{}", prerequisite.lesson, prerequisite.requires)),
+                quote(&format!("{}/{}", lesson.id(), prerequisite.section)),
+                quote(&tr(prerequisite.reason))
+            ));
+        }
+    }
+    dot.push_str("}\n");
+    dot
+}
+
+// graph 子命令，返回进程退出码
+pub fn run_cli(args: &[String]) -> i32 {
+    match args {
+        [] => cli::print(&render_text()),
+        [flag] if flag == "--dot" => cli::print(&render_dot()),
+        [flag] if flag == "--check" => {
+            let problems = problems();
+            if problems.is_empty() {
+                println!("{}", tr_fmt("课程依赖图检查通过，共 {} 条学习路线", &[&PATHS.len()]));
+                return 0;
+            }
+            for problem in &problems {
+                println!("{}", problem);
+            }
+            println!("\n{}", tr_fmt("发现 {} 个问题", &[&problems.len()]));
+            1
+        }
+        _ => {
+            eprintln!("{}", tr("用法: rustlearn graph [--dot | --check]"));
+            2
+        }
+    }
+}
+
+// path 子命令：列出学习路线，或者按照学习进度显示某一条路线，返回进程退出码
+pub fn path_cli(args: &[String]) -> i32 {
+    match args {
+        [] => list_paths(),
+        [id] if !id.starts_with('-') => match PATHS.iter().find(|path| path.id == id) {
+            Some(path) => show_path(path),
+            None => {
+                let ids: Vec<&str> = PATHS.iter().map(|path| path.id).collect();
+                eprintln!("{}", tr_fmt("没有名为 {} 的学习路线，可以选择：{}", &[id, &ids.join(tr("、").as_ref())]));
+                2
+            }
+        },
+        _ => {
+            eprintln!("{}", tr("用法: rustlearn path [<路线>]"));
+            2
+        }
+    }
+}
+
+// 列出所有学习路线
+fn list_paths() -> i32 {
+    let mut text = format!("{}\n", tr("学习路线："));
+    for path in PATHS {
+        text.push_str(&format!("  {:<8}{}\n", path.id, tr(path.title)));
+        text.push_str(&format!("          {}\n", tr(path.description)));
+    }
+    text.push_str(&format!("\n{}\n", tr("使用 `rustlearn path <路线>` 查看路线中的每一步和学习进度")));
+    cli::print(&text)
+}
+
+// 按照当前学习者的进度显示一条学习路线
+fn show_path(path: &LearningPath) -> i32 {
+    let progress = Progress::load_current_or_unsaved();
+
+    let mut text = format!("{}\n{}\n", tr(path.title), tr(path.description));
+    if !path.assumes.is_empty() {
+        text.push_str(&format!("{}\n", tr_fmt("假定已经掌握：{}", &[&lesson_list(path.assumes)])));
+    }
+    text.push('\n');
+
+    let mut next = None;
+    for (number, step) in path.steps.iter().enumerate() {
+        let Some(lesson) = lesson::find(step.lesson) else {
+            continue;
+        };
+        let sections = step.sections();
+        let done = sections
+            .iter()
+            .filter(|section| progress.is_section_completed(lesson, section.id))
+            .count();
+        let mark = if done == sections.len() {
+            String::from(" ✓")
+        } else if done > 0 {
+            format!(" ({}/{})", done, sections.len())
+        } else {
+            String::new()
+        };
+        let title = tr_fmt("第{}课：{}", &[&lesson.id(), &tr(lesson.title())]);
+        text.push_str(&format!("{:>2}. {}{}\n", number + 1, title, mark));
+        if !step.sections.is_empty() {
+            let titles: Vec<String> = sections.iter().map(|section| tr(section.title).into_owned()).collect();
+            text.push_str(&format!("    {}\n", tr_fmt("只学这些小节：{}", &[&titles.join(tr("、").as_ref())])));
+        }
+
+        if next.is_none() {
+            next = sections
+                .iter()
+                .find(|section| !progress.is_section_completed(lesson, section.id))
+                .map(|section| (lesson, *section));
+        }
+    }
+
+    match next {
+        Some((lesson, section)) => {
+            let index = lesson.sections().iter().position(|candidate| candidate.id == section.id).unwrap_or(0);
+            let line = tr_fmt("下一步: 第{}课 第{}节：{}", &[&lesson.id(), &(index + 1), &tr(section.title)]);
+            text.push_str(&format!("\n{}\n", line));
+            text.push_str(&format!("    rustlearn run {} --section {}\n", lesson.id(), section.id));
+        }
+        None => {
+            text.push_str(&format!("\n{}\n", tr("这条学习路线已经全部学完")));
+        }
+    }
+    cli::print(&text)
+}
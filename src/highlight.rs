@@ -0,0 +1,257 @@
+// Rust 代码的语法高亮
+// 把一行代码切分成关键字、类型名、宏、生命周期、字符串、数字、注释和属性等片段，
+// 每个片段再交给输出样式（context::Style）决定显示的颜色，没有颜色时原样输出
+//
+// 字符串和块注释可能跨越多行，所以高亮器会在行与行之间保留状态，
+// 同一段代码需要用同一个 Highlighter 逐行处理
+//
+// 这里的切分也是程序中唯一的 Rust 词法分析：source 模块查找代码范围时，
+// 通过 code_chars 跳过字符串、字符和注释中的括号
+
+use crate::context::Style;
+
+// 代码片段的类别
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TokenKind {
+    // 标识符、运算符、空白等不需要特别显示的内容
+    Plain,
+    // 关键字，例如 fn、let、match
+    Keyword,
+    // 以大写字母开头的类型名，例如 String、Rectangle
+    Type,
+    // 宏调用，例如 println!、vec!
+    Macro,
+    // 生命周期，例如 'a、'static
+    Lifetime,
+    // 字符串和字符字面量
+    Str,
+    // 数字字面量
+    Number,
+    // 注释
+    Comment,
+    // 属性，例如 #[derive(Debug)]
+    Attribute,
+}
+
+// Rust 的关键字
+const KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern", "false",
+    "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref", "return",
+    "self", "Self", "static", "struct", "super", "trait", "true", "type", "unsafe", "use", "where", "while",
+];
+
+// 跨行的状态
+#[derive(Default, Clone, Copy, PartialEq)]
+enum State {
+    #[default]
+    Code,
+    // 普通字符串
+    Str,
+    // 原始字符串，记录结尾需要的 # 个数
+    RawStr(usize),
+    // 块注释，记录嵌套的层数
+    BlockComment(usize),
+}
+
+// 逐行高亮一段代码
+#[derive(Default)]
+pub struct Highlighter {
+    state: State,
+}
+
+impl Highlighter {
+    // 按照样式高亮一行代码，返回带颜色的文本
+    pub fn line(&mut self, line: &str, style: &dyn Style) -> String {
+        self.tokens(line)
+            .into_iter()
+            .map(|(kind, text)| style.code(kind, &text))
+            .collect()
+    }
+
+    // 把一行代码切分成片段，相邻的同类片段会合并在一起
+    pub fn tokens(&mut self, line: &str) -> Vec<(TokenKind, String)> {
+        let chars: Vec<char> = line.chars().collect();
+        let mut tokens: Vec<(TokenKind, String)> = Vec::new();
+        let mut index = 0;
+
+        while index < chars.len() {
+            let (kind, end) = self.next_token(&chars, index);
+            let text: String = chars[index..end].iter().collect();
+
+            match tokens.last_mut() {
+                Some((last, existing)) if *last == kind => existing.push_str(&text),
+                _ => tokens.push((kind, text)),
+            }
+            index = end;
+        }
+
+        tokens
+    }
+
+    // 返回这一行中属于代码的字符，字符串、字符字面量和注释中的字符都不算
+    pub fn code_chars(&mut self, line: &str) -> Vec<char> {
+        self.tokens(line)
+            .into_iter()
+            .filter(|(kind, _)| !matches!(kind, TokenKind::Str | TokenKind::Comment))
+            .flat_map(|(_, text)| text.chars().collect::<Vec<char>>())
+            .collect()
+    }
+
+    // 从 start 开始的下一个片段，返回片段的类别和结束位置
+    fn next_token(&mut self, chars: &[char], start: usize) -> (TokenKind, usize) {
+        match self.state {
+            State::Str => return (TokenKind::Str, self.string_end(chars, start)),
+            State::RawStr(hashes) => return (TokenKind::Str, self.raw_string_end(chars, start, hashes)),
+            State::BlockComment(depth) => return (TokenKind::Comment, self.block_comment_end(chars, start, depth)),
+            State::Code => {}
+        }
+
+        let c = chars[start];
+        let next = chars.get(start + 1).copied();
+
+        match c {
+            '/' if next == Some('/') => (TokenKind::Comment, chars.len()),
+            '/' if next == Some('*') => (TokenKind::Comment, self.block_comment_end(chars, start + 2, 1)),
+            '"' => (TokenKind::Str, self.string_end(chars, start + 1)),
+            '\'' => quote_token(chars, start),
+            '#' if matches!(next, Some('[' | '!')) => (TokenKind::Attribute, attribute_end(chars, start)),
+            c if c.is_ascii_digit() => (TokenKind::Number, number_end(chars, start)),
+            c if c.is_alphabetic() || c == '_' => {
+                let end = word_end(chars, start);
+                let word: String = chars[start..end].iter().collect();
+
+                // 原始字符串 r"..." 和 r#"..."#，字节字符串 b"..."
+                if matches!(word.as_str(), "r" | "br") && matches!(chars.get(end), Some('"' | '#')) {
+                    let hashes = chars[end..].iter().take_while(|c| **c == '#').count();
+                    if chars.get(end + hashes) == Some(&'"') {
+                        return (TokenKind::Str, self.raw_string_end(chars, end + hashes + 1, hashes));
+                    }
+                }
+                if word == "b" && chars.get(end) == Some(&'"') {
+                    return (TokenKind::Str, self.string_end(chars, end + 1));
+                }
+
+                if chars.get(end) == Some(&'!') && chars.get(end + 1) != Some(&'=') {
+                    (TokenKind::Macro, end + 1)
+                } else if KEYWORDS.contains(&word.as_str()) {
+                    (TokenKind::Keyword, end)
+                } else if c.is_uppercase() {
+                    (TokenKind::Type, end)
+                } else {
+                    (TokenKind::Plain, end)
+                }
+            }
+            _ => (TokenKind::Plain, start + 1),
+        }
+    }
+
+    // 普通字符串从 start 开始的剩余部分，字符串在这一行没有结束时保留状态
+    fn string_end(&mut self, chars: &[char], start: usize) -> usize {
+        let mut index = start;
+        while index < chars.len() {
+            match chars[index] {
+                '\\' => index += 2,
+                '"' => {
+                    self.state = State::Code;
+                    return index + 1;
+                }
+                _ => index += 1,
+            }
+        }
+        self.state = State::Str;
+        chars.len()
+    }
+
+    // 原始字符串从 start 开始的剩余部分
+    fn raw_string_end(&mut self, chars: &[char], start: usize, hashes: usize) -> usize {
+        for index in start..chars.len() {
+            if chars[index] == '"' && chars[index + 1..].iter().take_while(|c| **c == '#').count() >= hashes {
+                self.state = State::Code;
+                return index + 1 + hashes;
+            }
+        }
+        self.state = State::RawStr(hashes);
+        chars.len()
+    }
+
+    // 块注释从 start 开始的剩余部分，块注释可以嵌套
+    fn block_comment_end(&mut self, chars: &[char], start: usize, mut depth: usize) -> usize {
+        let mut index = start;
+        while index < chars.len() {
+            let next = chars.get(index + 1).copied();
+            if chars[index] == '*' && next == Some('/') {
+                depth -= 1;
+                index += 2;
+                if depth == 0 {
+                    self.state = State::Code;
+                    return index;
+                }
+            } else if chars[index] == '/' && next == Some('*') {
+                depth += 1;
+                index += 2;
+            } else {
+                index += 1;
+            }
+        }
+        self.state = State::BlockComment(depth);
+        chars.len()
+    }
+}
+
+// 以 ' 开头的片段：字符字面量（'a'、'\n'）或者生命周期（'a、'static）
+fn quote_token(chars: &[char], start: usize) -> (TokenKind, usize) {
+    match chars.get(start + 1) {
+        Some('\\') => {
+            let end = chars[start + 2..]
+                .iter()
+                .skip(1)
+                .position(|c| *c == '\'')
+                .map_or(chars.len(), |position| start + position + 4);
+            (TokenKind::Str, end.min(chars.len()))
+        }
+        Some(_) if chars.get(start + 2) == Some(&'\'') => (TokenKind::Str, start + 3),
+        Some(c) if c.is_alphabetic() || *c == '_' => (TokenKind::Lifetime, word_end(chars, start + 1)),
+        _ => (TokenKind::Plain, start + 1),
+    }
+}
+
+// 标识符的结束位置
+fn word_end(chars: &[char], start: usize) -> usize {
+    chars[start..]
+        .iter()
+        .position(|c| !(c.is_alphanumeric() || *c == '_'))
+        .map_or(chars.len(), |position| start + position)
+}
+
+// 数字的结束位置，包括 1_000、3.14、0xff、5u32 这样的写法，但不包括范围 0..10 中的 ..
+fn number_end(chars: &[char], start: usize) -> usize {
+    let mut index = start;
+    while index < chars.len() {
+        let c = chars[index];
+        let fraction = c == '.' && chars.get(index + 1).is_some_and(|next| next.is_ascii_digit());
+        if c.is_alphanumeric() || c == '_' || fraction {
+            index += 1;
+        } else {
+            break;
+        }
+    }
+    index
+}
+
+// 属性的结束位置：与 #[ 匹配的 ]，这一行没有结束时到行尾为止
+fn attribute_end(chars: &[char], start: usize) -> usize {
+    let mut depth = 0;
+    for (index, c) in chars.iter().enumerate().skip(start) {
+        match c {
+            '[' => depth += 1,
+            ']' => {
+                depth -= 1;
+                if depth == 0 {
+                    return index + 1;
+                }
+            }
+            _ => {}
+        }
+    }
+    chars.len()
+}
@@ -0,0 +1,518 @@
+// 多语言支持
+// 课程内容和菜单都以中文编写，中文原文同时也是消息目录中的键（类似 gettext 的做法）
+// 其他语言的译文保存在 src/locales/ 下的消息目录中，运行时按照所选语言替换输出：
+// 1. 菜单和命令行中的文字通过 tr() 和 tr_fmt() 翻译
+// 2. 课程输出由 LessonContext 逐行翻译，课程代码本身不需要任何修改
+// 3. 找不到译文时使用中文原文，保证任何时候都有内容可看
+//
+// 语言的选择顺序：命令行参数 --lang，其次是 LANG 环境变量，默认使用中文
+
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::collections::{BTreeSet, HashMap};
+use std::env;
+use std::fmt::Display;
+use std::sync::OnceLock;
+
+use crate::borrow;
+use crate::cli;
+use crate::context::{LessonContext, Style};
+use crate::explain;
+use crate::glossary;
+use crate::graph;
+use crate::lesson;
+use crate::quiz::QuestionKind;
+
+// 支持的语言
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Locale {
+    // 中文，课程的原文
+    Zh,
+    // 英文
+    En,
+}
+
+impl Locale {
+    // 解析 --lang 参数或 LANG 环境变量的值，例如 "en"、"zh"、"en_US.UTF-8"
+    pub fn parse(value: &str) -> Option<Locale> {
+        let language = value
+            .split(['_', '-', '.', '@'])
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+
+        match language.as_str() {
+            "zh" => Some(Locale::Zh),
+            "en" => Some(Locale::En),
+            _ => None,
+        }
+    }
+
+    // 语言代码
+    pub fn code(self) -> &'static str {
+        match self {
+            Locale::Zh => "zh",
+            Locale::En => "en",
+        }
+    }
+}
+
+// 当前使用的语言，在程序启动时确定
+static LOCALE: OnceLock<Locale> = OnceLock::new();
+
+// 英文消息目录
+static EN_CATALOG: OnceLock<Catalog> = OnceLock::new();
+
+// 根据命令行参数和环境变量确定界面语言
+// 会从 args 中移除 --lang 参数，剩下的参数交给子命令处理
+pub fn init(args: &mut Vec<String>) -> Result<(), String> {
+    // LANG 中的语言不受支持时（例如 C.UTF-8），使用默认的中文
+    let default = env::var("LANG")
+        .ok()
+        .and_then(|value| Locale::parse(&value))
+        .unwrap_or(Locale::Zh);
+    let mut requested = None;
+    let mut index = 0;
+
+    while index < args.len() {
+        if args[index] == "--lang" {
+            if index + 1 >= args.len() {
+                // 参数有误时按照 LANG 选择的语言报告错误
+                let _ = LOCALE.set(default);
+                return Err(tr("--lang 需要一个语言代码，例如 zh 或 en").into_owned());
+            }
+            requested = Some(args.remove(index + 1));
+            args.remove(index);
+        } else if let Some(value) = args[index].strip_prefix("--lang=") {
+            requested = Some(value.to_string());
+            args.remove(index);
+        } else {
+            index += 1;
+        }
+    }
+
+    let locale = match requested {
+        Some(value) => match Locale::parse(&value) {
+            Some(locale) => locale,
+            None => {
+                let _ = LOCALE.set(default);
+                return Err(tr_fmt("不支持的语言: {}，可以使用 zh 或 en", &[&value]));
+            }
+        },
+        None => default,
+    };
+
+    // 程序启动时只会调用一次，重复设置时保留第一次的结果
+    let _ = LOCALE.set(locale);
+    Ok(())
+}
+
+// 当前使用的语言
+pub fn current() -> Locale {
+    LOCALE.get().copied().unwrap_or(Locale::Zh)
+}
+
+// 把一段中文翻译成当前语言，没有译文时返回原文
+pub fn tr(text: &str) -> Cow<'_, str> {
+    match translate(current(), text) {
+        Some(translated) => Cow::Owned(translated),
+        None => Cow::Borrowed(text),
+    }
+}
+
+// 翻译带有 {} 占位符的模板，再按顺序填入参数
+// 例如 tr_fmt("第{}课只有 {} 个小节", &[&6, &10])
+pub fn tr_fmt(template: &str, args: &[&dyn Display]) -> String {
+    let translated = tr(template);
+    let mut result = String::new();
+    let mut args = args.iter();
+
+    for (index, part) in translated.split("{}").enumerate() {
+        if index > 0
+            && let Some(arg) = args.next()
+        {
+            result.push_str(&arg.to_string());
+        }
+        result.push_str(part);
+    }
+
+    result
+}
+
+// 把一行中文翻译成指定的语言，没有译文时返回 None
+pub fn translate(locale: Locale, text: &str) -> Option<String> {
+    match locale {
+        // 中文是原文，不需要翻译
+        Locale::Zh => Some(text.to_string()),
+        Locale::En => english().translate(text),
+    }
+}
+
+// 判断一段文字是否需要翻译：只包含 ASCII 字符、数字等内容的行在各种语言下都一样
+pub fn needs_translation(text: &str) -> bool {
+    text.chars().any(|c| {
+        matches!(c,
+            '\u{4e00}'..='\u{9fff}'   // 中日韩统一表意文字
+            | '\u{3000}'..='\u{303f}' // 中文标点，例如 "。"、"、"
+            | '\u{ff00}'..='\u{ffef}' // 全角符号，例如 "："、"（"
+        )
+    })
+}
+
+// 英文消息目录，第一次使用时解析
+fn english() -> &'static Catalog {
+    EN_CATALOG.get_or_init(|| Catalog::parse(include_str!("locales/en.txt")))
+}
+
+// 消息目录
+// 文件格式：以 "zh: " 开头的一行是中文原文，紧接着以 "en: " 开头的一行是译文，
+// 以 # 开头的行是注释，空行会被忽略
+// 原文中包含 {} 时作为模板使用，{} 可以匹配任意内容，匹配到的内容会按顺序填入译文中的 {}
+struct Catalog {
+    // 所有原文到译文的映射
+    messages: HashMap<&'static str, &'static str>,
+    // 带有 {} 的模板，按照在文件中出现的顺序尝试匹配
+    templates: Vec<(&'static str, &'static str)>,
+}
+
+impl Catalog {
+    fn parse(content: &'static str) -> Catalog {
+        let mut messages = HashMap::new();
+        let mut templates = Vec::new();
+        let mut source = None;
+
+        for line in content.lines() {
+            if let Some(text) = line.strip_prefix("zh: ") {
+                source = Some(text);
+            } else if let Some(text) = line.strip_prefix("en: ") {
+                // 译文必须紧跟在原文之后，格式错误的条目直接忽略
+                if let Some(source) = source.take() {
+                    messages.insert(source, text);
+                    if source.contains("{}") {
+                        templates.push((source, text));
+                    }
+                }
+            }
+        }
+
+        Catalog { messages, templates }
+    }
+
+    // 先查找完全相同的原文，再尝试模板
+    fn translate(&self, text: &str) -> Option<String> {
+        if let Some(translated) = self.messages.get(text) {
+            return Some(translated.to_string());
+        }
+
+        self.templates.iter().find_map(|(source, translated)| {
+            let captures = match_template(source, text)?;
+            let mut result = String::new();
+            let mut captures = captures.into_iter();
+
+            for (index, part) in translated.split("{}").enumerate() {
+                // 匹配到的内容本身如果有译文（例如小节标题），也一并翻译
+                if index > 0
+                    && let Some(capture) = captures.next()
+                {
+                    result.push_str(self.messages.get(capture).copied().unwrap_or(capture));
+                }
+                result.push_str(part);
+            }
+
+            Some(result)
+        })
+    }
+}
+
+// 用带有 {} 的模板匹配一行文字，成功时返回每个 {} 匹配到的内容
+fn match_template<'t>(template: &str, text: &'t str) -> Option<Vec<&'t str>> {
+    let parts: Vec<&str> = template.split("{}").collect();
+    let (first, rest) = parts.split_first()?;
+    let (last, middle) = rest.split_last()?;
+
+    let mut remaining = text.strip_prefix(first)?.strip_suffix(last)?;
+    let mut captures = Vec::new();
+
+    for part in middle {
+        let position = remaining.find(part)?;
+        captures.push(&remaining[..position]);
+        remaining = &remaining[position + part.len()..];
+    }
+    captures.push(remaining);
+
+    Some(captures)
+}
+
+// check-translations 子命令：列出指定语言中缺少译文的消息，返回进程退出码
+// 检查的范围包括菜单和命令行中的文字、课程的标题和大纲，以及每个小节实际输出的每一行
+pub fn check_cli(args: &[String]) -> i32 {
+    let locale = match args {
+        [] => Locale::En,
+        [code] => match Locale::parse(code) {
+            Some(locale) => locale,
+            None => {
+                eprintln!("{}", tr_fmt("不支持的语言: {}", &[code]));
+                return 2;
+            }
+        },
+        _ => {
+            eprintln!("{}", tr("用法: rustlearn check-translations [语言代码]"));
+            return 2;
+        }
+    };
+
+    // 没有加入扫描范围的模块中的消息无法检查，单独报告出来
+    let unscanned = unscanned_modules();
+    if !unscanned.is_empty() {
+        println!("{}", tr_fmt("这些模块没有加入 check-translations 的扫描范围：{}", &[&unscanned.join(tr("、").as_ref())]));
+    }
+
+    let mut missing = 0;
+
+    for (origin, messages) in collect_messages() {
+        let untranslated: Vec<&String> = messages
+            .iter()
+            .filter(|message| translate(locale, message).is_none())
+            .collect();
+
+        if !untranslated.is_empty() {
+            println!("{}", tr_fmt("{}中缺少译文的消息：", &[&origin]));
+            for message in &untranslated {
+                println!("  {}", message);
+            }
+            missing += untranslated.len();
+        }
+    }
+
+    if missing > 0 {
+        println!("\n{}", tr_fmt("{} 的消息目录中缺少 {} 条译文", &[&locale.code(), &missing]));
+        1
+    } else if !unscanned.is_empty() {
+        1
+    } else {
+        println!("{}", tr_fmt("{} 的消息目录完整，所有消息都有译文", &[&locale.code()]));
+        0
+    }
+}
+
+// 需要提取 tr()、tr_fmt() 调用的源文件，包括除课程以外的所有模块
+// check-translations 会和 main.rs 中声明的模块对照，新增的模块没有加入这里时会报告出来
+const SOURCES: &[(&str, &str)] = &[
+    ("main.rs", include_str!("main.rs")),
+    ("lesson.rs", include_str!("lesson.rs")),
+    ("context.rs", include_str!("context.rs")),
+    ("golden.rs", include_str!("golden.rs")),
+    ("cli.rs", include_str!("cli.rs")),
+    ("menu.rs", include_str!("menu.rs")),
+    ("i18n.rs", include_str!("i18n.rs")),
+    ("progress.rs", include_str!("progress.rs")),
+    ("quiz.rs", include_str!("quiz.rs")),
+    ("review.rs", include_str!("review.rs")),
+    ("exercise.rs", include_str!("exercise.rs")),
+    ("compile_error.rs", include_str!("compile_error.rs")),
+    ("explain.rs", include_str!("explain.rs")),
+    ("elision.rs", include_str!("elision.rs")),
+    ("borrow.rs", include_str!("borrow.rs")),
+    ("json.rs", include_str!("json.rs")),
+    ("source.rs", include_str!("source.rs")),
+    ("highlight.rs", include_str!("highlight.rs")),
+    ("theme.rs", include_str!("theme.rs")),
+    ("tui.rs", include_str!("tui.rs")),
+    ("screen.rs", include_str!("screen.rs")),
+    ("web.rs", include_str!("web.rs")),
+    ("book.rs", include_str!("book.rs")),
+    ("epub.rs", include_str!("epub.rs")),
+    ("search.rs", include_str!("search.rs")),
+    ("glossary.rs", include_str!("glossary.rs")),
+    ("graph.rs", include_str!("graph.rs")),
+];
+
+// main.rs 中声明了、但是没有加入 SOURCES 的模块
+// 课程模块的输出在运行时逐行收集，不需要加入
+fn unscanned_modules() -> Vec<String> {
+    include_str!("main.rs")
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("mod ")?.strip_suffix(';'))
+        .filter(|module| !module.starts_with('_'))
+        .map(|module| format!("{}.rs", module))
+        .filter(|file| !SOURCES.iter().any(|(name, _)| name == file))
+        .collect()
+}
+
+// 收集所有需要翻译的消息，按照出处分组
+fn collect_messages() -> Vec<(String, BTreeSet<String>)> {
+    let mut groups = Vec::new();
+
+    // 菜单和命令行中通过 tr()、tr_fmt() 翻译的文字
+    let mut interface = BTreeSet::new();
+    for (_, source) in SOURCES {
+        interface.extend(extract_messages(source));
+    }
+    interface.extend(cli::USAGE.lines().map(str::to_string));
+    groups.push((tr("菜单和命令行").into_owned(), interface));
+
+    // 术语表中的定义
+    groups.push((
+        tr("术语表").into_owned(),
+        glossary::TERMS.iter().map(|term| term.definition.to_string()).collect(),
+    ));
+
+    // 错误代码说明的标题、原因和修改方法
+    groups.push((
+        tr("错误代码说明").into_owned(),
+        explain::EXPLANATIONS
+            .iter()
+            .flat_map(|explanation| [explanation.title, explanation.cause, explanation.fix])
+            .map(str::to_string)
+            .collect(),
+    ));
+    groups.push((
+        tr("借用检查模拟器的示例").into_owned(),
+        borrow::EXAMPLES.iter().map(|example| example.title.to_string()).collect(),
+    ));
+
+    // 学习路线的名称和介绍
+    groups.push((
+        tr("学习路线").into_owned(),
+        graph::PATHS
+            .iter()
+            .flat_map(|path| [path.title.to_string(), path.description.to_string()])
+            .collect(),
+    ));
+
+    for lesson in lesson::LESSONS {
+        // 课程的标题、简介、知识点和小节标题
+        let mut metadata = BTreeSet::new();
+        metadata.insert(lesson.title().to_string());
+        metadata.insert(lesson.summary().to_string());
+        metadata.extend(lesson.topics().iter().map(|topic| topic.to_string()));
+        metadata.extend(lesson.sections().iter().map(|section| section.title.to_string()));
+        metadata.extend(lesson.section_prerequisites().iter().map(|prerequisite| prerequisite.reason.to_string()));
+        groups.push((tr_fmt("第{}课的标题和大纲", &[&lesson.id()]), metadata));
+
+        // 测验题目的题干、选项、解析以及预测输出题中的代码
+        let mut quiz = BTreeSet::new();
+        for question in lesson.quiz() {
+            quiz.insert(question.kind_name().to_string());
+            quiz.insert(question.prompt.to_string());
+            quiz.insert(question.explanation.to_string());
+            quiz.extend(question.options().iter().map(|option| option.to_string()));
+            if let QuestionKind::PredictOutput { code, .. } = question.kind {
+                quiz.extend(code.lines().map(|line| line.trim_start().to_string()));
+            }
+        }
+        groups.push((tr_fmt("第{}课的测验", &[&lesson.id()]), quiz));
+
+        // 练习的标题、要求和提示
+        let mut exercises = BTreeSet::new();
+        for exercise in lesson.exercises() {
+            exercises.insert(exercise.title.to_string());
+            exercises.insert(exercise.description.to_string());
+            exercises.extend(exercise.hints.iter().map(|hint| hint.to_string()));
+        }
+        groups.push((tr_fmt("第{}课的练习", &[&lesson.id()]), exercises));
+
+        // 编译错误示例的标题和说明
+        let mut compile_errors = BTreeSet::new();
+        for example in lesson.compile_errors() {
+            compile_errors.insert(example.title.to_string());
+            compile_errors.insert(example.explanation.to_string());
+        }
+        groups.push((tr_fmt("第{}课的编译错误示例", &[&lesson.id()]), compile_errors));
+
+        // 课程实际输出的每一行，包括课程介绍和所有小节，以中文运行并逐行收集
+        // 标题在翻译之后才套用样式，所以单独记录标题文字
+        let recorder = HeadingRecorder::default();
+        let mut buffer = Vec::new();
+        let mut ctx = LessonContext::with_style(&mut buffer, &recorder).with_locale(Locale::Zh);
+        lesson.run(&mut ctx);
+        let _ = ctx.finish();
+
+        let output = String::from_utf8_lossy(&buffer);
+        let mut lines: BTreeSet<String> = output.lines().map(str::to_string).collect();
+        lines.extend(recorder.headings.into_inner());
+        groups.push((tr_fmt("第{}课的输出", &[&lesson.id()]), lines));
+    }
+
+    for (_, messages) in &mut groups {
+        messages.retain(|message| needs_translation(message));
+    }
+
+    groups
+}
+
+// 记录课程中所有标题文字的样式，标题本身不输出
+#[derive(Default)]
+struct HeadingRecorder {
+    headings: RefCell<Vec<String>>,
+}
+
+impl Style for HeadingRecorder {
+    fn lesson_heading(&self, text: &str) -> String {
+        self.headings.borrow_mut().push(text.to_string());
+        String::new()
+    }
+
+    fn section_heading(&self, text: &str) -> String {
+        self.headings.borrow_mut().push(text.to_string());
+        String::new()
+    }
+}
+
+// 从源代码中提取 tr("...") 和 tr_fmt("...", ...) 中的字符串字面量
+fn extract_messages(source: &str) -> Vec<String> {
+    let mut messages = Vec::new();
+
+    for call in ["tr(", "tr_fmt("] {
+        let mut offset = 0;
+        while let Some(position) = source[offset..].find(call) {
+            let start = offset + position;
+            offset = start + call.len();
+
+            // 跳过 "str(" 之类名字以 tr 结尾的其他函数，以及字符串字面量中的 "tr("
+            if source[..start]
+                .chars()
+                .next_back()
+                .is_some_and(|c| c.is_alphanumeric() || c == '_' || c == '"')
+            {
+                continue;
+            }
+
+            // 跳过注释中举例提到的调用
+            let line_start = source[..start].rfind('\n').map_or(0, |index| index + 1);
+            if source[line_start..start].trim_start().starts_with("//") {
+                continue;
+            }
+
+            // 参数可能换行书写，只提取以字符串字面量作为第一个参数的调用
+            let Some(literal) = source[offset..].trim_start().strip_prefix('"') else {
+                continue;
+            };
+            if let Some(end) = find_string_end(literal) {
+                messages.push(unescape(&literal[..end]));
+            }
+        }
+    }
+
+    messages
+}
+
+// 找到字符串字面量结尾的引号
+fn find_string_end(text: &str) -> Option<usize> {
+    let mut escaped = false;
+
+    for (index, c) in text.char_indices() {
+        match c {
+            '\\' if !escaped => escaped = true,
+            '"' if !escaped => return Some(index),
+            _ => escaped = false,
+        }
+    }
+
+    None
+}
+
+// 处理字符串字面量中常见的转义字符
+fn unescape(text: &str) -> String {
+    text.replace("\\n", "\n").replace("\\\"", "\"").replace("\\\\", "\\")
+}
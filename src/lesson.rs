@@ -0,0 +1,82 @@
+// 课程注册表
+// 每一课都在自己的模块中实现 Lesson trait，并在下面的 LESSONS 中登记一次
+// 菜单、搜索以及将来的其他前端都只从这里读取课程信息，
+// 这样新增一课时只需要修改一个地方，编号、标题和入口函数不会互相脱节
+
+use crate::compile_error::CompileError;
+use crate::context::LessonContext;
+use crate::exercise::Exercise;
+use crate::graph::Prerequisite;
+use crate::quiz::Question;
+
+// 课程中的一个小节，对应课程模块中的一个示例函数
+pub struct Section {
+    // 小节标识，与示例函数同名，例如 "hashmap_collection"
+    pub id: &'static str,
+    // 小节标题，与示例输出中的 "--- 标题 ---" 一致
+    pub title: &'static str,
+    // 运行本小节的示例函数，可以单独调用
+    pub run: fn(&mut LessonContext),
+}
+
+// 每一课都需要实现的 trait
+// 要求实现 Sync，这样课程对象才能放进静态的注册表中
+pub trait Lesson: Sync {
+    // 课程编号，也是菜单中输入的选项，例如 "1"
+    fn id(&self) -> &'static str;
+
+    // 课程标题，例如 "变量和数据类型 (Variables and Data Types)"
+    fn title(&self) -> &'static str;
+
+    // 一句话介绍本课的内容
+    fn summary(&self) -> &'static str;
+
+    // 本课的知识点大纲
+    fn topics(&self) -> &'static [&'static str];
+
+    // 本课包含的小节，按照运行顺序排列
+    fn sections(&self) -> &'static [Section];
+
+    // 本课模块的完整源代码，在编译时嵌入程序
+    fn source(&self) -> &'static str;
+
+    // 本课模块相对于项目根目录的路径，例如 "src/_01_variables.rs"
+    fn source_path(&self) -> &'static str;
+
+    // 本课的测验题目，学完之后用来检查学习效果
+    fn quiz(&self) -> &'static [Question];
+
+    // 本课的动手练习，按照练习编号排列
+    fn exercises(&self) -> &'static [Exercise];
+
+    // 本课的编译错误示例，按照示例编号排列
+    fn compile_errors(&self) -> &'static [CompileError];
+
+    // 学习本课之前应该先学完的课程编号，例如第10课依赖第3课和第9课
+    fn prerequisites(&self) -> &'static [&'static str];
+
+    // 本课中依赖其他课程某个小节的小节，同一课中前面的小节不需要列出
+    fn section_prerequisites(&self) -> &'static [Prerequisite];
+
+    // 按顺序运行本课的全部示例
+    fn run(&self, ctx: &mut LessonContext);
+}
+
+// 所有课程，按照课程编号排列
+pub static LESSONS: &[&dyn Lesson] = &[
+    &crate::_01_variables::VariablesLesson,
+    &crate::_02_functions_control_flow::FunctionsControlFlowLesson,
+    &crate::_03_structs::StructsLesson,
+    &crate::_04_enums::EnumsLesson,
+    &crate::_05_pattern_matching::PatternMatchingLesson,
+    &crate::_06_collections::CollectionsLesson,
+    &crate::_07_packages_modules::PackagesModulesLesson,
+    &crate::_08_error_handling::ErrorHandlingLesson,
+    &crate::_09_generics::GenericsLesson,
+    &crate::_10_lifetimes::LifetimesLesson,
+];
+
+// 根据课程编号查找课程
+pub fn find(id: &str) -> Option<&'static dyn Lesson> {
+    LESSONS.iter().copied().find(|lesson| lesson.id() == id)
+}
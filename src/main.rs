@@ -1,69 +1,112 @@
 // Rust 学习示例程序
 // 本文件是一个知识点列表选择器，您可以选择不同的数字运行对应的 Rust 语法示例
 
-// 导入标准输入输出模块
-use std::io;
-
-// 定义每个知识点示例的标题和描述
-const LESSONS: &[(&str, &str)] = &[
-    ("1", "变量和数据类型 (Variables and Data Types)"),
-    ("2", "函数和流程控制 (Functions and Control Flow)"),
-    ("3", "结构体 (Structs)"),
-    ("4", "枚举 (Enums)"),
-    ("5", "模式匹配 (Pattern Matching)"),
-    ("6", "常见集合及其操作 (Collections)"),
-    ("7", "包和模块 (Packages and Modules)"),
-    ("8", "错误处理 (Error Handling)"),
-    ("9", "泛型 (Generics)"),
-    ("10", "生命周期 (Lifetimes)"),
-];
+// 导入标准库中的命令行参数和进程模块
+use std::env;
+use std::process;
 
 fn main() {
-    loop {
-        // 打印欢迎信息
-        println!("=== Rust 学习示例程序 ===");
-        println!("请选择您想学习的知识点:");
-        
-        // 打印所有知识点列表
-        for &(num, title) in LESSONS {
-            println!("{}. {}", num, title);
-        }
-        
-        println!("q. 退出程序");
-        
-        // 读取用户输入
-        let mut choice = String::new();
-        io::stdin().read_line(&mut choice).expect("无法读取输入");
-        
-        // 去除输入字符串中的换行符和空格
-        let choice = choice.trim();
-        
-        // 根据用户选择执行对应的示例
-        match choice {
-            "1" => _01_variables::run(),
-            "2" => _02_functions_control_flow::run(),
-            "3" => _03_structs::run(),
-            "4" => _04_enums::run(),
-            "5" => _05_pattern_matching::run(),
-            "6" => _06_collections::run(),
-            "7" => _07_packages_modules::run(),
-            "8" => _08_error_handling::run(),
-            "9" => _09_generics::run(),
-            "10" => _10_lifetimes::run(),
-            "q" | "Q" => {
-                println!("感谢使用 Rust 学习示例程序！再见！");
-                break;
-            },
-            _ => println!("无效的选择，请重新输入。\n"),
-        }
-        
-        // 等待用户按回车继续
-        println!("\n按回车键继续...");
-        let mut _continue = String::new();
-        io::stdin().read_line(&mut _continue).expect("无法读取输入");
+    // 带命令行参数时执行对应的子命令，不进入交互式菜单
+    let mut args: Vec<String> = env::args().skip(1).collect();
+
+    // 先处理 --lang 参数，确定界面语言
+    if let Err(message) = i18n::init(&mut args) {
+        eprintln!("{}", message);
+        process::exit(2);
+    }
+
+    // 再处理 --theme 参数，确定终端配色
+    if let Err(message) = theme::init(&mut args) {
+        eprintln!("{}", message);
+        process::exit(2);
+    }
+
+    if !args.is_empty() {
+        process::exit(cli::run(&args));
     }
+    
+    // 否则进入交互式菜单，输入结束（例如按下 Ctrl-D）时正常退出
+    process::exit(menu::run());
 }
 
+// 课程注册表
+mod lesson;
+
+// 课程输出上下文
+mod context;
+
+// 运行结果校验
+mod golden;
+
+// 非交互式命令行模式
+mod cli;
+
+// 交互式菜单
+mod menu;
+
+// 多语言支持
+mod i18n;
+
+// 学习进度
+mod progress;
+
+// 课程测验
+mod quiz;
+
+// 测验题的间隔复习
+mod review;
+
+// 动手练习
+mod exercise;
+
+// 编译错误示例
+mod compile_error;
+
+// 编译错误代码说明
+mod explain;
+
+// 生命周期省略规则的演示
+mod elision;
+
+// 借用检查模拟器
+mod borrow;
+
+// 简单的 JSON 解析器
+mod json;
+
+// 小节源代码
+mod source;
+
+// 语法高亮
+mod highlight;
+
+// 终端配色主题
+mod theme;
+
+// 全屏终端界面
+mod tui;
+
+// 全屏界面的状态和绘制
+mod screen;
+
+// 本地网页界面
+mod web;
+
+// 书籍导出
+mod book;
+
+// EPUB 电子书导出
+mod epub;
+
+// 课程全文搜索
+mod search;
+
+// 术语表
+mod glossary;
+
+// 课程依赖图和学习路线
+mod graph;
+
 // 以下模块声明将在创建对应文件后取消注释
 // 第1课：变量和数据类型
 mod _01_variables;
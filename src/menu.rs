@@ -0,0 +1,342 @@
+// 交互式菜单
+// 一级菜单列出所有课程，选择课程后进入小节菜单，可以单独运行某一节，
+// 也可以在小节之间前后切换或者重新运行
+// 运行过的小节会记录到学习进度中，菜单会标出已经完成的课程和小节，
+// 并且提供 "从上次离开的地方继续" 的选项；学完一课后还可以参加本课的测验
+// 在课程列表中还可以搜索课程内容，选择搜索结果后直接打开对应的小节；
+// 菜单还会根据学习进度和课程依赖图推荐下一课，有到期的测验题时提示复习
+//
+// 读取输入时的几种特殊情况：
+// 1. 输入结束（EOF，例如按下 Ctrl-D 或者管道中的脚本已经读完）时，程序正常退出
+// 2. 读取被信号中断（Interrupted）时自动重试
+// 3. 输入不是合法的 UTF-8 时，按无效的选择处理，不会让程序崩溃
+
+use std::io::{self, BufRead};
+
+use crate::cli;
+use crate::graph;
+use crate::i18n::{tr, tr_fmt};
+use crate::lesson::{self, Lesson};
+use crate::progress::Progress;
+use crate::quiz;
+use crate::review;
+use crate::search::{self, Index};
+use crate::source;
+use crate::theme;
+
+// 子菜单结束后接下来要去的地方
+enum Next {
+    // 回到当前课程的小节列表
+    SectionList,
+    // 回到课程列表
+    LessonList,
+    // 退出程序
+    Quit,
+}
+
+// 运行交互式菜单，返回进程退出码
+pub fn run() -> i32 {
+    let mut progress = Progress::load_current_or_unsaved();
+
+    match main_menu(&mut progress) {
+        Ok(()) => {
+            println!("{}", tr("感谢使用 Rust 学习示例程序！再见！"));
+            0
+        }
+        Err(error) => {
+            eprintln!("{}", tr_fmt("无法读取输入: {}", &[&error]));
+            1
+        }
+    }
+}
+
+// 一级菜单：列出所有课程
+fn main_menu(progress: &mut Progress) -> io::Result<()> {
+    // 搜索索引在第一次搜索时才建立
+    let mut index = None;
+
+    loop {
+        // 打印欢迎信息
+        println!("{}", tr("=== Rust 学习示例程序 ==="));
+        println!("{}", tr("请选择您想学习的知识点:"));
+
+        // 打印所有知识点列表，课程信息统一来自课程注册表
+        // 已经学完的课程打上 ✓，学了一部分的课程显示完成的小节数
+        for lesson in lesson::LESSONS {
+            let done = progress.completed_sections(*lesson);
+            let mark = if done == lesson.sections().len() {
+                String::from(" ✓")
+            } else if done > 0 {
+                format!(" ({}/{})", done, lesson.sections().len())
+            } else {
+                String::new()
+            };
+            println!("{}. {}{}", lesson.id(), tr(lesson.title()), mark);
+        }
+
+        let resume_point = progress.resume_point();
+        if let Some((lesson, index)) = resume_point {
+            println!(
+                "{}",
+                tr_fmt(
+                    "c. 从上次离开的地方继续（第{}课 第{}节：{}）",
+                    &[&lesson.id(), &(index + 1), &tr(lesson.sections()[index].title)]
+                )
+            );
+        }
+        // 推荐依赖的课程都已经学完的下一课
+        let recommended = graph::recommend(progress);
+        if let Some(lesson) = recommended {
+            println!(
+                "{}{}",
+                tr_fmt("n. 推荐下一课：第{}课：{}", &[&lesson.id(), &tr(lesson.title())]),
+                graph::recommend_reason(lesson)
+            );
+        }
+        let due = review::due_count(progress);
+        if due > 0 {
+            println!("{}", tr_fmt("r. 复习到期的测验题（{} 道）", &[&due]));
+        }
+        println!("{}", tr("s. 搜索课程内容"));
+        println!("{}", tr("q. 退出程序"));
+
+        // 读取用户输入，输入结束时退出程序
+        let Some(choice) = read_choice()? else {
+            return Ok(());
+        };
+
+        // 从上次离开的地方继续，浏览结束后可以回到这一课的小节列表
+        if let ("c" | "C", Some((lesson, index))) = (choice.as_str(), resume_point) {
+            let next = match section_navigator(lesson, index, progress)? {
+                Next::SectionList => lesson_menu(lesson, progress)?,
+                next => next,
+            };
+            if let Next::Quit = next {
+                return Ok(());
+            }
+            continue;
+        }
+
+        // 打开推荐的下一课
+        if let ("n" | "N", Some(lesson)) = (choice.as_str(), recommended) {
+            if let Next::Quit = lesson_menu(lesson, progress)? {
+                return Ok(());
+            }
+            continue;
+        }
+
+        // 根据用户选择进入对应课程的小节菜单
+        match choice.as_str() {
+            "q" | "Q" => return Ok(()),
+            "r" | "R" if due > 0 => {
+                if !review::session(progress, review::DEFAULT_LIMIT)? {
+                    return Ok(());
+                }
+            }
+            "s" | "S" => {
+                let index = index.get_or_insert_with(Index::build);
+                if let Next::Quit = search_menu(index, progress)? {
+                    return Ok(());
+                }
+            }
+            _ => match lesson::find(&choice) {
+                Some(lesson) => {
+                    if let Next::Quit = lesson_menu(lesson, progress)? {
+                        return Ok(());
+                    }
+                }
+                None => println!("{}\n", tr("无效的选择，请重新输入。")),
+            },
+        }
+    }
+}
+
+// 课程的二级菜单：列出本课的所有小节，可以单独运行某一节或者运行整课
+fn lesson_menu(lesson: &dyn Lesson, progress: &mut Progress) -> io::Result<Next> {
+    loop {
+        println!("\n=== {} ===", tr_fmt("第{}课：{}", &[&lesson.id(), &tr(lesson.title())]));
+        println!("{}", tr(lesson.summary()));
+        println!("{}", tr("请选择要运行的小节:"));
+
+        for (index, section) in lesson.sections().iter().enumerate() {
+            let mark = if progress.is_section_completed(lesson, section.id) { " ✓" } else { "" };
+            println!("{}. {}{}", index + 1, tr(section.title), mark);
+        }
+
+        println!("{}", tr("a. 运行全部小节"));
+        if !lesson.quiz().is_empty() {
+            match progress.quiz(lesson) {
+                Some(score) => println!(
+                    "{}",
+                    tr_fmt("t. 参加本课测验（上次得分 {}/{}）", &[&score.score, &score.total])
+                ),
+                None => println!("{}", tr("t. 参加本课测验")),
+            }
+        }
+        println!("{}", tr("b. 返回课程列表"));
+
+        let Some(choice) = read_choice()? else {
+            return Ok(Next::Quit);
+        };
+
+        match choice.as_str() {
+            "a" | "A" => {
+                if cli::print_to_stdout(|ctx| lesson.run(ctx)) == 0 {
+                    for section in lesson.sections() {
+                        progress.complete_section(lesson, section.id);
+                    }
+                    save(progress);
+                }
+
+                // 等待用户按回车继续
+                println!("\n{}", tr("按回车键继续..."));
+                if read_choice()?.is_none() {
+                    return Ok(Next::Quit);
+                }
+            }
+            "t" | "T" if !lesson.quiz().is_empty() => match quiz::take(lesson)? {
+                Some(result) => quiz::record(progress, lesson, &result),
+                // 测验没有答完输入就结束了，成绩不记录
+                None => return Ok(Next::Quit),
+            },
+            "b" | "B" => return Ok(Next::LessonList),
+            _ => match choice.parse::<usize>() {
+                Ok(number) if (1..=lesson.sections().len()).contains(&number) => {
+                    // 从选中的小节开始逐节浏览
+                    match section_navigator(lesson, number - 1, progress)? {
+                        Next::SectionList => {}
+                        next => return Ok(next),
+                    }
+                }
+                _ => println!("{}", tr("无效的选择，请重新输入。")),
+            },
+        }
+    }
+}
+
+// 逐节浏览：运行当前小节，然后可以前往下一节、上一节、重新运行或者查看小节的源代码
+fn section_navigator(lesson: &dyn Lesson, mut index: usize, progress: &mut Progress) -> io::Result<Next> {
+    let sections = lesson.sections();
+    let mut run_current = true;
+
+    loop {
+        let section = &sections[index];
+
+        if run_current && cli::print_to_stdout(section.run) == 0 {
+            progress.complete_section(lesson, section.id);
+            save(progress);
+        }
+
+        println!(
+            "\n[{}]",
+            tr_fmt(
+                "第{}课 第{}/{}节：{}",
+                &[&lesson.id(), &(index + 1), &sections.len(), &tr(section.title)]
+            )
+        );
+        println!("{}", tr("n. 下一节  p. 上一节  r. 重新运行  c. 查看代码  l. 小节列表  b. 返回课程列表"));
+
+        let Some(choice) = read_choice()? else {
+            return Ok(Next::Quit);
+        };
+        run_current = true;
+
+        match choice.as_str() {
+            "n" | "N" | "" => {
+                if index + 1 < sections.len() {
+                    index += 1;
+                } else {
+                    println!("{}", tr("已经是本课的最后一节了。"));
+                    run_current = false;
+                }
+            }
+            "p" | "P" => {
+                if index > 0 {
+                    index -= 1;
+                } else {
+                    println!("{}", tr("已经是本课的第一节了。"));
+                    run_current = false;
+                }
+            }
+            "r" | "R" => {}
+            "c" | "C" => {
+                if let Some(code) = source::render(lesson, section, theme::current()) {
+                    print!("\n{}", code);
+                }
+                run_current = false;
+            }
+            "l" | "L" => return Ok(Next::SectionList),
+            "b" | "B" => return Ok(Next::LessonList),
+            _ => {
+                println!("{}", tr("无效的选择，请重新输入。"));
+                run_current = false;
+            }
+        }
+    }
+}
+
+// 搜索课程内容，选择一条结果后从对应的小节开始逐节浏览
+fn search_menu(index: &Index, progress: &mut Progress) -> io::Result<Next> {
+    println!("{}", tr("请输入要搜索的内容（直接回车返回）:"));
+    let Some(query) = read_choice()? else {
+        return Ok(Next::Quit);
+    };
+    if query.is_empty() {
+        return Ok(Next::LessonList);
+    }
+
+    let hits = index.search(&query);
+    if hits.is_empty() {
+        println!("{}\n", tr_fmt("没有找到与 \"{}\" 相关的小节", &[&query]));
+        return Ok(Next::LessonList);
+    }
+    let hits = &hits[..hits.len().min(search::DEFAULT_LIMIT)];
+    for (number, hit) in hits.iter().enumerate() {
+        println!("{}. {}", number + 1, search::hit_title(hit));
+        if !hit.snippet.is_empty() {
+            println!("   {}", hit.snippet);
+        }
+    }
+
+    loop {
+        println!("{}", tr("输入编号打开小节，直接回车返回:"));
+        let Some(choice) = read_choice()? else {
+            return Ok(Next::Quit);
+        };
+        if choice.is_empty() {
+            return Ok(Next::LessonList);
+        }
+        match choice.parse::<usize>() {
+            Ok(number) if (1..=hits.len()).contains(&number) => {
+                let hit = &hits[number - 1];
+                return match section_navigator(hit.lesson, hit.index, progress)? {
+                    Next::SectionList => lesson_menu(hit.lesson, progress),
+                    next => Ok(next),
+                };
+            }
+            _ => println!("{}", tr("无效的选择，请重新输入。")),
+        }
+    }
+}
+
+// 保存学习进度，保存失败时只给出提示，不影响继续学习
+fn save(progress: &Progress) {
+    if let Err(error) = progress.save() {
+        eprintln!("{}", tr_fmt("无法保存学习进度: {}", &[&error]));
+    }
+}
+
+// 读取一行用户输入，并去除换行符和首尾空格
+// 输入结束时返回 None；不是合法 UTF-8 的字节会被替换成 U+FFFD，从而被当作无效的选择
+pub fn read_choice() -> io::Result<Option<String>> {
+    let mut buffer = Vec::new();
+
+    loop {
+        match io::stdin().lock().read_until(b'\n', &mut buffer) {
+            Ok(0) if buffer.is_empty() => return Ok(None),
+            Ok(_) => return Ok(Some(String::from_utf8_lossy(&buffer).trim().to_string())),
+            Err(error) if error.kind() == io::ErrorKind::Interrupted => continue,
+            Err(error) => return Err(error),
+        }
+    }
+}
@@ -0,0 +1,483 @@
+// 学习进度
+// 记录每位学习者完成了哪些小节、什么时候完成的，以及各课的测验成绩，
+// 保存在本地的数据目录中，下次运行时可以从上次离开的地方继续学习
+//
+// 进度文件保存在 $XDG_DATA_HOME/rustlearn/progress/<用户名>.txt，
+// 没有设置 XDG_DATA_HOME 时使用 ~/.local/share；用户名取自 USER 或 USERNAME 环境变量
+//
+// 文件是纯文本格式，每行一条记录，字段之间用制表符分隔，方便直接查看或者用脚本汇总：
+// section  <课程编号>  <小节标识>  <完成时间>
+// quiz     <课程编号>  <得分>  <总分>  <完成时间>
+// last     <课程编号>  <小节标识>
+// review   <课程编号>  <题目标识>  <难度系数>  <间隔天数>  <连续答对次数>  <下次复习时间>
+// 时间是 Unix 时间戳（秒），题目标识是题干的哈希（见 Question::id）。以 # 开头的行是注释，无法识别的行会被忽略
+// 旧版本在 review 记录中写的是从 1 开始的题目编号，读取时按当前的题目顺序换算成题目标识
+
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::io::{self, ErrorKind};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::cli;
+use crate::i18n::{tr, tr_fmt};
+use crate::lesson::{self, Lesson};
+use crate::review::ReviewItem;
+
+// 某一课的测验成绩
+pub struct QuizScore {
+    pub score: u32,
+    pub total: u32,
+    // 完成测验的时间
+    pub at: u64,
+}
+
+// 一位学习者的学习进度
+pub struct Progress {
+    user: String,
+    // 进度文件的位置，无法确定数据目录时为 None，此时进度只保存在内存中
+    path: Option<PathBuf>,
+    // 已完成的小节：(课程编号, 小节标识) -> 完成时间
+    sections: BTreeMap<(String, String), u64>,
+    // 各课最近一次的测验成绩
+    quizzes: BTreeMap<String, QuizScore>,
+    // 最近一次学习的小节
+    last: Option<(String, String)>,
+    // 测验题的复习计划：(课程编号, 题目标识) -> 复习计划
+    reviews: BTreeMap<(String, String), ReviewItem>,
+}
+
+impl Progress {
+    // 读取当前用户的学习进度
+    pub fn load_current() -> io::Result<Progress> {
+        Progress::load(&current_user())
+    }
+
+    // 读取当前用户的学习进度，读取失败时显示警告并返回不保存的空进度
+    // 读取不到学习进度时仍然可以正常学习，只是从头开始记录
+    pub fn load_current_or_unsaved() -> Progress {
+        match Progress::load_current() {
+            Ok(progress) => progress,
+            Err(error) => {
+                eprintln!("{}", tr_fmt("无法读取学习进度: {}", &[&error]));
+                Progress::unsaved(&current_user())
+            }
+        }
+    }
+
+    // 读取指定用户的学习进度，还没有进度文件时返回空的进度
+    pub fn load(user: &str) -> io::Result<Progress> {
+        let mut progress = Progress::unsaved(user);
+        progress.path = progress_dir().map(|dir| dir.join(format!("{}.txt", file_name(user))));
+
+        let Some(path) = &progress.path else {
+            return Ok(progress);
+        };
+
+        match fs::read_to_string(path) {
+            Ok(content) => progress.parse(&content),
+            Err(error) if error.kind() == ErrorKind::NotFound => {}
+            Err(error) => return Err(error),
+        }
+
+        Ok(progress)
+    }
+
+    // 不保存到文件的空进度，在进度文件无法读取时使用，避免覆盖原有的记录
+    pub fn unsaved(user: &str) -> Progress {
+        Progress {
+            user: user.to_string(),
+            path: None,
+            sections: BTreeMap::new(),
+            quizzes: BTreeMap::new(),
+            last: None,
+            reviews: BTreeMap::new(),
+        }
+    }
+
+    // 解析进度文件的内容
+    fn parse(&mut self, content: &str) {
+        for line in content.lines() {
+            let fields: Vec<&str> = line.split('\t').collect();
+
+            match fields.as_slice() {
+                ["section", lesson, section, at] => {
+                    if let Ok(at) = at.parse() {
+                        self.sections.insert((lesson.to_string(), section.to_string()), at);
+                    }
+                }
+                ["quiz", lesson, score, total, at] => {
+                    if let (Ok(score), Ok(total), Ok(at)) = (score.parse(), total.parse(), at.parse()) {
+                        self.quizzes.insert(lesson.to_string(), QuizScore { score, total, at });
+                    }
+                }
+                ["last", lesson, section] => {
+                    self.last = Some((lesson.to_string(), section.to_string()));
+                }
+                ["review", lesson, question, ease, interval, repetitions, due] => {
+                    if let (Some(question), Ok(ease), Ok(interval), Ok(repetitions), Ok(due)) =
+                        (question_id(lesson, question), ease.parse(), interval.parse(), repetitions.parse(), due.parse())
+                    {
+                        let item = ReviewItem { ease, interval, repetitions, due };
+                        self.reviews.insert((lesson.to_string(), question), item);
+                    }
+                }
+                // 注释、空行以及无法识别的记录
+                _ => {}
+            }
+        }
+    }
+
+    // 把学习进度写回进度文件
+    // 先写入临时文件再重命名，避免程序中途退出时留下不完整的文件
+    pub fn save(&self) -> io::Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+
+        let mut content = String::from("# rustlearn 学习进度\n");
+        for ((lesson, section), at) in &self.sections {
+            content.push_str(&format!("section\t{}\t{}\t{}\n", lesson, section, at));
+        }
+        for (lesson, quiz) in &self.quizzes {
+            content.push_str(&format!("quiz\t{}\t{}\t{}\t{}\n", lesson, quiz.score, quiz.total, quiz.at));
+        }
+        if let Some((lesson, section)) = &self.last {
+            content.push_str(&format!("last\t{}\t{}\n", lesson, section));
+        }
+        for ((lesson, question), item) in &self.reviews {
+            content.push_str(&format!(
+                "review\t{}\t{}\t{:.2}\t{}\t{}\t{}\n",
+                lesson,
+                question,
+                item.ease,
+                item.interval,
+                item.repetitions,
+                item.due
+            ));
+        }
+
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let temporary = path.with_extension("txt.tmp");
+        fs::write(&temporary, content)?;
+        fs::rename(&temporary, path)
+    }
+
+    // 学习者的用户名
+    pub fn user(&self) -> &str {
+        &self.user
+    }
+
+    // 进度文件的位置
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    // 记录完成了某一个小节，同时把它记为最近一次学习的小节
+    pub fn complete_section(&mut self, lesson: &dyn Lesson, section: &str) {
+        let key = (lesson.id().to_string(), section.to_string());
+        self.sections.insert(key, now());
+        self.last = Some((lesson.id().to_string(), section.to_string()));
+    }
+
+    // 某一个小节是否已经完成
+    pub fn is_section_completed(&self, lesson: &dyn Lesson, section: &str) -> bool {
+        self.sections
+            .contains_key(&(lesson.id().to_string(), section.to_string()))
+    }
+
+    // 某一课已经完成的小节数
+    pub fn completed_sections(&self, lesson: &dyn Lesson) -> usize {
+        lesson
+            .sections()
+            .iter()
+            .filter(|section| self.is_section_completed(lesson, section.id))
+            .count()
+    }
+
+    // 某一课的完成时间：所有小节都完成时，返回最后完成的那个小节的时间
+    pub fn lesson_completed_at(&self, lesson: &dyn Lesson) -> Option<u64> {
+        lesson
+            .sections()
+            .iter()
+            .map(|section| {
+                self.sections
+                    .get(&(lesson.id().to_string(), section.id.to_string()))
+                    .copied()
+            })
+            .collect::<Option<Vec<u64>>>()?
+            .into_iter()
+            .max()
+    }
+
+    // 记录某一课的测验成绩，只保留最近一次的成绩
+    pub fn record_quiz(&mut self, lesson: &dyn Lesson, score: u32, total: u32) {
+        self.quizzes
+            .insert(lesson.id().to_string(), QuizScore { score, total, at: now() });
+    }
+
+    // 某一课最近一次的测验成绩
+    pub fn quiz(&self, lesson: &dyn Lesson) -> Option<&QuizScore> {
+        self.quizzes.get(lesson.id())
+    }
+
+    // 按照这一次作答的评分安排某一道测验题的下一次复习，还不在复习计划中的题目会被加入计划
+    pub fn grade_question(&mut self, lesson: &dyn Lesson, index: usize, quality: u8, now: u64) {
+        self.reviews
+            .entry((lesson.id().to_string(), lesson.quiz()[index].id()))
+            .or_default()
+            .grade(quality, now);
+    }
+
+    // 某一道测验题的复习计划
+    pub fn review_item(&self, lesson: &dyn Lesson, index: usize) -> Option<&ReviewItem> {
+        self.reviews.get(&(lesson.id().to_string(), lesson.quiz()[index].id()))
+    }
+
+    // 到了复习时间的测验题，最早到期的排在前面
+    // 课程或题目已经不存在的记录会被跳过
+    pub fn due_reviews(&self, now: u64) -> Vec<(&'static dyn Lesson, usize)> {
+        let mut due: Vec<(u64, &'static dyn Lesson, usize)> = self
+            .reviews
+            .iter()
+            .filter(|(_, item)| item.due <= now)
+            .filter_map(|((lesson, question), item)| {
+                let lesson = lesson::find(lesson)?;
+                let index = lesson.quiz().iter().position(|candidate| candidate.id() == *question)?;
+                Some((item.due, lesson, index))
+            })
+            .collect();
+        due.sort_by_key(|(due, _, _)| *due);
+        due.into_iter().map(|(_, lesson, index)| (lesson, index)).collect()
+    }
+
+    // 复习计划中最早的复习时间
+    pub fn next_review(&self) -> Option<u64> {
+        self.reviews.values().map(|item| item.due).min()
+    }
+
+    // 继续学习的位置：最近一次学习的小节的下一节
+    // 如果那是一课的最后一节，就从下一课的第一节开始；全部学完时回到最近一次学习的小节
+    pub fn resume_point(&self) -> Option<(&'static dyn Lesson, usize)> {
+        let (lesson_id, section_id) = self.last.as_ref()?;
+        let position = lesson::LESSONS.iter().position(|lesson| lesson.id() == lesson_id)?;
+        let lesson = lesson::LESSONS[position];
+        let index = lesson.sections().iter().position(|section| section.id == section_id)?;
+
+        if index + 1 < lesson.sections().len() {
+            Some((lesson, index + 1))
+        } else if let Some(next) = lesson::LESSONS.get(position + 1) {
+            Some((*next, 0))
+        } else {
+            Some((lesson, index))
+        }
+    }
+}
+
+// progress 子命令：显示学习进度报告，返回进程退出码
+// 导师可以用 --all 查看这台电脑上所有学习者的进度，或者用 --user 查看某一位学习者
+pub fn run_cli(args: &[String]) -> i32 {
+    let users = match args {
+        [] => vec![current_user()],
+        [flag] if flag == "--all" => match all_users() {
+            Ok(users) => users,
+            Err(error) => {
+                eprintln!("{}", tr_fmt("无法读取学习进度: {}", &[&error]));
+                return 1;
+            }
+        },
+        [flag, user] if flag == "--user" => vec![user.clone()],
+        _ => {
+            eprintln!("{}", tr("用法: rustlearn progress [--user <用户名> | --all]"));
+            return 2;
+        }
+    };
+
+    if users.is_empty() {
+        return cli::print(&format!("{}\n", tr("还没有任何学习记录")));
+    }
+
+    let mut text = String::new();
+    for (index, user) in users.iter().enumerate() {
+        if index > 0 {
+            text.push('\n');
+        }
+        match Progress::load(user) {
+            Ok(progress) => text.push_str(&report(&progress)),
+            Err(error) => {
+                // 先输出已经读取到的报告
+                cli::print(&text);
+                eprintln!("{}", tr_fmt("无法读取学习进度: {}", &[&error]));
+                return 1;
+            }
+        }
+    }
+
+    cli::print(&text)
+}
+
+// 生成一位学习者的进度报告
+fn report(progress: &Progress) -> String {
+    let lessons_done = lesson::LESSONS
+        .iter()
+        .filter(|lesson| progress.lesson_completed_at(**lesson).is_some())
+        .count();
+    let sections_total: usize = lesson::LESSONS.iter().map(|lesson| lesson.sections().len()).sum();
+    let sections_done: usize = lesson::LESSONS
+        .iter()
+        .map(|lesson| progress.completed_sections(*lesson))
+        .sum();
+
+    let mut text = format!("{}\n", tr_fmt("学习者: {}", &[&progress.user()]));
+    if let Some(path) = progress.path() {
+        text.push_str(&format!("{}\n", tr_fmt("进度文件: {}", &[&path.display()])));
+    }
+    text.push_str(&format!(
+        "{}\n",
+        tr_fmt(
+            "已完成 {}/{} 课，{}/{} 个小节",
+            &[&lessons_done, &lesson::LESSONS.len(), &sections_done, &sections_total]
+        )
+    ));
+
+    for lesson in lesson::LESSONS {
+        let done = progress.completed_sections(*lesson);
+        let total = lesson.sections().len();
+        let status = match progress.lesson_completed_at(*lesson) {
+            Some(at) => tr_fmt("已完成，完成于 {}", &[&format_time(at)]),
+            None if done > 0 => tr_fmt("已完成 {}/{} 个小节", &[&done, &total]),
+            None => tr("未开始").into_owned(),
+        };
+        text.push_str(&format!("{:>2}. {} - {}\n", lesson.id(), tr(lesson.title()), status));
+
+        if let Some(quiz) = progress.quiz(*lesson) {
+            text.push_str(&format!(
+                "    {}\n",
+                tr_fmt("测验成绩: {}/{}（{}）", &[&quiz.score, &quiz.total, &format_time(quiz.at)])
+            ));
+        }
+    }
+
+    if let Some((lesson, index)) = progress.resume_point() {
+        let section = &lesson.sections()[index];
+        text.push_str(&format!(
+            "{}\n",
+            tr_fmt("下一步: 第{}课 第{}节：{}", &[&lesson.id(), &(index + 1), &tr(section.title)])
+        ));
+    }
+
+    text
+}
+
+// review 记录中的题目标识
+// 旧版本记录的题目编号换算成当前这一课对应位置的题目标识，课程或题目已经不存在时返回 None
+fn question_id(lesson: &str, field: &str) -> Option<String> {
+    if field.len() == 8 && field.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+        return Some(field.to_string());
+    }
+
+    let number = field.parse::<usize>().ok()?;
+    let question = lesson::find(lesson)?.quiz().get(number.checked_sub(1)?)?;
+    Some(question.id())
+}
+
+// 当前学习者的用户名
+pub fn current_user() -> String {
+    ["USER", "USERNAME"]
+        .iter()
+        .filter_map(|name| env::var(name).ok())
+        .find(|user| !user.trim().is_empty())
+        .unwrap_or_else(|| String::from("default"))
+}
+
+// 进度目录中所有学习者的用户名，按字母顺序排列
+fn all_users() -> io::Result<Vec<String>> {
+    let Some(dir) = progress_dir() else {
+        return Ok(Vec::new());
+    };
+
+    let entries = match fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(error) if error.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(error) => return Err(error),
+    };
+
+    let mut users = Vec::new();
+    for entry in entries {
+        let path = entry?.path();
+        if path.extension().is_some_and(|extension| extension == "txt")
+            && let Some(user) = path.file_stem()
+        {
+            users.push(user.to_string_lossy().into_owned());
+        }
+    }
+    users.sort();
+
+    Ok(users)
+}
+
+// rustlearn 的数据目录，学习进度和练习目录都保存在这里
+pub fn data_dir() -> Option<PathBuf> {
+    let data_home = env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .filter(|path| path.is_absolute())
+        .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".local").join("share")))
+        .or_else(|| env::var_os("APPDATA").map(PathBuf::from))?;
+
+    Some(data_home.join("rustlearn"))
+}
+
+// 保存进度文件的目录
+fn progress_dir() -> Option<PathBuf> {
+    Some(data_dir()?.join("progress"))
+}
+
+// 把用户名转换成安全的文件名，只保留字母、数字以及 - _ .
+pub fn file_name(user: &str) -> String {
+    user.chars()
+        .map(|c| if c.is_alphanumeric() || matches!(c, '-' | '_' | '.') { c } else { '_' })
+        .collect()
+}
+
+// 当前时间的 Unix 时间戳
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default()
+}
+
+// 把 Unix 时间戳格式化成 "2024-03-01 09:30 UTC"
+pub fn format_time(timestamp: u64) -> String {
+    let days = timestamp / 86_400;
+    let seconds = timestamp % 86_400;
+    let (year, month, day) = civil_from_days(days as i64);
+
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02} UTC",
+        year,
+        month,
+        day,
+        seconds / 3600,
+        seconds % 3600 / 60
+    )
+}
+
+// 把自 1970-01-01 起的天数换算成公历日期
+// 算法来自 Howard Hinnant 的 "chrono-Compatible Low-Level Date Algorithms"
+pub fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * month_index + 2) / 5 + 1) as u32;
+    let month = if month_index < 10 { month_index + 3 } else { month_index - 9 } as u32;
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    (year, month, day)
+}
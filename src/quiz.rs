@@ -0,0 +1,241 @@
+// 课程测验
+// 每一课都通过 Lesson::quiz() 提供一组题目，学完之后用来检查是否真正理解了本课的内容
+// 题目分为三种：
+// 1. 单选题
+// 2. 判断题
+// 3. 预测输出：展示课程中的一段示例代码，从几个候选结果中选出它真正打印的内容
+//    这类题目记录了代码所在的小节，`rustlearn verify` 会确认正确答案确实出现在该小节的输出中，
+//    修改了示例代码却忘了更新题目时，校验就会失败
+//
+// 所有题目都通过输入选项编号作答，答完后给出得分，并对答错的题目给出解析，成绩记录到学习进度中
+// 每道题的对错还会交给复习计划，之后用 `rustlearn review` 按间隔复习
+
+use std::io;
+
+use crate::highlight::Highlighter;
+use crate::i18n::{tr, tr_fmt};
+use crate::lesson::{self, Lesson};
+use crate::menu;
+use crate::progress::{self, Progress};
+use crate::review;
+use crate::theme;
+
+// 测验中的一道题
+pub struct Question {
+    // 题干
+    pub prompt: &'static str,
+    // 题目类型以及答案
+    pub kind: QuestionKind,
+    // 答错时显示的解析
+    pub explanation: &'static str,
+}
+
+// 题目类型
+pub enum QuestionKind {
+    // 单选题，answer 是正确选项的下标
+    Choice {
+        options: &'static [&'static str],
+        answer: usize,
+    },
+    // 判断题
+    TrueFalse { answer: bool },
+    // 预测输出，section 是示例代码所在的小节，answer 是正确选项的下标
+    PredictOutput {
+        section: &'static str,
+        code: &'static str,
+        options: &'static [&'static str],
+        answer: usize,
+    },
+}
+
+// 判断题的两个选项
+const TRUE_FALSE_OPTIONS: &[&str] = &["对", "错"];
+
+impl Question {
+    // 题目类型的名称
+    pub fn kind_name(&self) -> &'static str {
+        match self.kind {
+            QuestionKind::Choice { .. } => "单选题",
+            QuestionKind::TrueFalse { .. } => "判断题",
+            QuestionKind::PredictOutput { .. } => "预测输出",
+        }
+    }
+
+    // 显示给学习者的选项
+    pub fn options(&self) -> &'static [&'static str] {
+        match self.kind {
+            QuestionKind::Choice { options, .. } | QuestionKind::PredictOutput { options, .. } => options,
+            QuestionKind::TrueFalse { .. } => TRUE_FALSE_OPTIONS,
+        }
+    }
+
+    // 正确选项的下标
+    pub fn answer(&self) -> usize {
+        match self.kind {
+            QuestionKind::Choice { answer, .. } | QuestionKind::PredictOutput { answer, .. } => answer,
+            QuestionKind::TrueFalse { answer } => usize::from(!answer),
+        }
+    }
+
+    // 题目的标识：题干的 FNV-1a 哈希，写成 8 位十六进制数
+    // 复习计划按课程编号和这个标识记录，在测验中插入题目或者调整顺序不会把复习记录移到别的题目上
+    pub fn id(&self) -> String {
+        let hash = self
+            .prompt
+            .bytes()
+            .fold(0x811c_9dc5_u32, |hash, byte| (hash ^ u32::from(byte)).wrapping_mul(0x0100_0193));
+        format!("{:08x}", hash)
+    }
+}
+
+// 一次测验的成绩
+pub struct QuizResult {
+    pub score: u32,
+    pub total: u32,
+    // 每道题是否答对，按题目顺序排列
+    pub answers: Vec<bool>,
+}
+
+// 进行某一课的测验，输入在答完之前结束时返回 None
+pub fn take(lesson: &dyn Lesson) -> io::Result<Option<QuizResult>> {
+    let questions = lesson.quiz();
+    let total = questions.len() as u32;
+    let mut score = 0;
+
+    println!(
+        "\n=== {} ===",
+        tr_fmt("第{}课测验：{}", &[&lesson.id(), &tr(lesson.title())])
+    );
+    println!("{}", tr_fmt("共 {} 题，输入选项编号作答", &[&total]));
+
+    let mut answers = Vec::new();
+    for (index, question) in questions.iter().enumerate() {
+        println!(
+            "\n{}",
+            tr_fmt(
+                "第 {}/{} 题（{}）",
+                &[&(index + 1), &total, &tr(question.kind_name())]
+            )
+        );
+
+        let Some(correct) = ask_question(question)? else {
+            return Ok(None);
+        };
+        if correct {
+            score += 1;
+        }
+        answers.push(correct);
+    }
+
+    println!("\n{}", tr_fmt("测验结束：得分 {}/{}", &[&score, &total]));
+    Ok(Some(QuizResult { score, total, answers }))
+}
+
+// 显示一道题并读取学习者的答案，答错时给出解析
+// 返回是否答对，输入结束时返回 None
+pub fn ask_question(question: &Question) -> io::Result<Option<bool>> {
+    println!("{}", tr(question.prompt));
+
+    if let QuestionKind::PredictOutput { code, .. } = question.kind {
+        println!();
+        // 代码中的字符串和注释按行翻译，保留原来的缩进，再按配色主题高亮
+        let mut highlighter = Highlighter::default();
+        for line in code.lines() {
+            let text = line.trim_start();
+            if text.is_empty() {
+                println!();
+            } else {
+                let indent = &line[..line.len() - text.len()];
+                println!("    {}{}", indent, highlighter.line(&tr(text), theme::current()));
+            }
+        }
+        println!();
+    }
+
+    let options = question.options();
+    for (number, option) in options.iter().enumerate() {
+        println!("  {}. {}", number + 1, tr(option));
+    }
+
+    let Some(choice) = ask(options.len())? else {
+        return Ok(None);
+    };
+
+    let answer = question.answer();
+    if choice == answer {
+        println!("{}", tr("✓ 回答正确！"));
+    } else {
+        println!(
+            "{}",
+            tr_fmt("✗ 回答错误，正确答案是 {}. {}", &[&(answer + 1), &tr(options[answer])])
+        );
+        println!("{}", tr_fmt("解析：{}", &[&tr(question.explanation)]));
+    }
+    Ok(Some(choice == answer))
+}
+
+// 读取学习者选择的选项编号，返回选项的下标；输入结束时返回 None
+pub fn ask(count: usize) -> io::Result<Option<usize>> {
+    loop {
+        println!("{}", tr("你的答案:"));
+
+        let Some(choice) = menu::read_choice()? else {
+            return Ok(None);
+        };
+
+        match choice.parse::<usize>() {
+            Ok(number) if (1..=count).contains(&number) => return Ok(Some(number - 1)),
+            _ => println!("{}", tr_fmt("请输入 1 到 {} 之间的编号", &[&count])),
+        }
+    }
+}
+
+// quiz 子命令：从标准输入作答某一课的测验，返回进程退出码
+pub fn run_cli(args: &[String]) -> i32 {
+    let lesson = match args {
+        [id] => match lesson::find(id) {
+            Some(lesson) => lesson,
+            None => {
+                eprintln!(
+                    "{}",
+                    tr_fmt("没有编号为 {} 的课程，可以使用 `rustlearn list` 查看所有课程", &[id])
+                );
+                return 2;
+            }
+        },
+        _ => {
+            eprintln!("{}", tr("用法: rustlearn quiz <课程编号>"));
+            return 2;
+        }
+    };
+
+    let mut progress = Progress::load_current_or_unsaved();
+
+    match take(lesson) {
+        Ok(Some(result)) => {
+            record(&mut progress, lesson, &result);
+            0
+        }
+        Ok(None) => {
+            println!("{}", tr("测验没有完成，成绩不会被记录"));
+            0
+        }
+        Err(error) => {
+            eprintln!("{}", tr_fmt("无法读取输入: {}", &[&error]));
+            1
+        }
+    }
+}
+
+// 把测验成绩记录到学习进度中，同时按每道题的对错安排复习，保存失败时只给出提示
+pub fn record(progress: &mut Progress, lesson: &dyn Lesson, result: &QuizResult) {
+    progress.record_quiz(lesson, result.score, result.total);
+    let now = progress::now();
+    for (index, correct) in result.answers.iter().enumerate() {
+        let quality = if *correct { review::RECALLED } else { review::FORGOTTEN };
+        progress.grade_question(lesson, index, quality, now);
+    }
+    if let Err(error) = progress.save() {
+        eprintln!("{}", tr_fmt("无法保存学习进度: {}", &[&error]));
+    }
+}
@@ -0,0 +1,230 @@
+// 测验题的间隔复习
+// 参加过的测验题会按照 SM-2 算法安排复习时间：答得越轻松，下一次复习隔得越久；答错的题目第二天再复习
+// 每道题的难度系数、复习间隔、连续答对的次数和下次复习的时间都保存在学习进度中，
+// `rustlearn review` 把所有课程中已经到期的题目放在一起复习
+//
+// SM-2 算法：每次作答得到一个 0 到 5 的评分 q
+// 1. q < 3 视为忘记了，连续答对次数清零，间隔变为 1 天
+// 2. 否则第一次答对的间隔是 1 天，第二次是 6 天，之后每次都是上一次的间隔乘以难度系数
+// 3. 难度系数按 EF + (0.1 - (5 - q) × (0.08 + (5 - q) × 0.02)) 调整，最低是 1.3
+//
+// 测验中答对的题目评分为 4，答错为 1；复习时答对之后学习者还可以自己评价想起来的难易程度
+
+use std::io;
+
+use crate::cli;
+use crate::i18n::{tr, tr_fmt};
+use crate::lesson;
+use crate::progress::{self, Progress};
+use crate::quiz;
+
+// 新题目的难度系数
+const INITIAL_EASE: f64 = 2.5;
+// 难度系数的下限
+const MINIMUM_EASE: f64 = 1.3;
+// 一天的秒数
+const DAY: u64 = 86_400;
+
+// 答错时的评分
+pub const FORGOTTEN: u8 = 1;
+// 测验中答对时的评分
+pub const RECALLED: u8 = 4;
+// 复习时答对之后，三种自我评价 "很吃力"、"想了一下"、"很轻松" 对应的评分
+const RATINGS: [u8; 3] = [3, 4, 5];
+
+// 一次复习最多的题目数
+pub const DEFAULT_LIMIT: usize = 20;
+
+// 一道题的复习计划
+#[derive(Clone, Copy)]
+pub struct ReviewItem {
+    // 难度系数，越大表示越容易记住
+    pub ease: f64,
+    // 复习间隔（天）
+    pub interval: u32,
+    // 连续答对的次数
+    pub repetitions: u32,
+    // 下次复习的时间
+    pub due: u64,
+}
+
+impl Default for ReviewItem {
+    fn default() -> ReviewItem {
+        ReviewItem { ease: INITIAL_EASE, interval: 0, repetitions: 0, due: 0 }
+    }
+}
+
+impl ReviewItem {
+    // 根据这一次作答的评分安排下一次复习
+    pub fn grade(&mut self, quality: u8, now: u64) {
+        let quality = quality.min(5);
+        if quality < 3 {
+            self.repetitions = 0;
+            self.interval = 1;
+        } else {
+            self.interval = match self.repetitions {
+                0 => 1,
+                1 => 6,
+                _ => (f64::from(self.interval) * self.ease).round() as u32,
+            };
+            self.repetitions += 1;
+        }
+
+        let miss = f64::from(5 - quality);
+        self.ease = (self.ease + 0.1 - miss * (0.08 + miss * 0.02)).max(MINIMUM_EASE);
+        self.due = now + u64::from(self.interval) * DAY;
+    }
+}
+
+// review 子命令，返回进程退出码
+pub fn run_cli(args: &[String]) -> i32 {
+    let mut limit = DEFAULT_LIMIT;
+    let mut status = false;
+    let mut iter = args.iter();
+
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--status" => status = true,
+            "--limit" => match iter.next().and_then(|value| value.parse().ok()) {
+                Some(value) if value > 0 => limit = value,
+                _ => return usage_error(),
+            },
+            _ => return usage_error(),
+        }
+    }
+
+    let mut progress = Progress::load_current_or_unsaved();
+
+    if status {
+        return cli::print(&status_report(&progress));
+    }
+
+    match session(&mut progress, limit) {
+        Ok(_) => 0,
+        Err(error) => {
+            eprintln!("{}", tr_fmt("无法读取输入: {}", &[&error]));
+            1
+        }
+    }
+}
+
+fn usage_error() -> i32 {
+    eprintln!("{}", tr("用法: rustlearn review [--limit <数量>] [--status]"));
+    2
+}
+
+// 复习所有到期的题目，最多 limit 道，每答完一题就保存学习进度
+// 返回 false 表示输入在复习结束之前就结束了
+pub fn session(progress: &mut Progress, limit: usize) -> io::Result<bool> {
+    let due = progress.due_reviews(progress::now());
+    if due.is_empty() {
+        println!("{}", tr("现在没有需要复习的测验题"));
+        print!("{}", next_due(progress));
+        return Ok(true);
+    }
+
+    let total = due.len().min(limit);
+    println!("\n=== {} ===", tr("复习测验题"));
+    println!("{}", tr_fmt("有 {} 道题到了复习时间，本次复习 {} 道", &[&due.len(), &total]));
+
+    let mut correct = 0;
+    for (number, (lesson, index)) in due.into_iter().take(total).enumerate() {
+        let question = &lesson.quiz()[index];
+        println!(
+            "\n{}",
+            tr_fmt(
+                "第 {}/{} 题（第{}课 · {}）",
+                &[&(number + 1), &total, &lesson.id(), &tr(question.kind_name())]
+            )
+        );
+
+        let Some(answered) = quiz::ask_question(question)? else {
+            println!("{}", tr("复习没有完成，已经作答的题目都已经记录"));
+            return Ok(false);
+        };
+
+        let quality = if answered {
+            correct += 1;
+            match rate()? {
+                Some(quality) => quality,
+                None => {
+                    println!("{}", tr("复习没有完成，已经作答的题目都已经记录"));
+                    return Ok(false);
+                }
+            }
+        } else {
+            FORGOTTEN
+        };
+
+        progress.grade_question(lesson, index, quality, progress::now());
+        if let Err(error) = progress.save() {
+            eprintln!("{}", tr_fmt("无法保存学习进度: {}", &[&error]));
+        }
+        if let Some(item) = progress.review_item(lesson, index) {
+            println!("{}", tr_fmt("下次复习：{} 天后", &[&item.interval]));
+        }
+    }
+
+    println!("\n{}", tr_fmt("复习结束：答对 {}/{}", &[&correct, &total]));
+    print!("{}", next_due(progress));
+    Ok(true)
+}
+
+// 答对之后请学习者评价想起来的难易程度
+fn rate() -> io::Result<Option<u8>> {
+    println!("{}", tr("这道题想起来怎么样？"));
+    println!("  1. {}", tr("很吃力"));
+    println!("  2. {}", tr("想了一下"));
+    println!("  3. {}", tr("很轻松"));
+    Ok(quiz::ask(RATINGS.len())?.map(|index| RATINGS[index]))
+}
+
+// 下一道题的复习时间，有题目已经到期时提示继续复习，以换行结尾
+fn next_due(progress: &Progress) -> String {
+    let now = progress::now();
+    let line = match progress.next_review() {
+        Some(due) if due <= now => {
+            tr_fmt("还有 {} 道题到了复习时间，可以再次运行 `rustlearn review`", &[&progress.due_reviews(now).len()])
+        }
+        Some(due) => tr_fmt("下一次复习时间: {}", &[&progress::format_time(due)]),
+        None => tr("复习计划中还没有题目，参加测验之后题目会自动加入复习计划").into_owned(),
+    };
+    format!("{}\n", line)
+}
+
+// 复习计划的概况：每一课在计划中的题目数和到期的题目数
+fn status_report(progress: &Progress) -> String {
+    let now = progress::now();
+    let due = progress.due_reviews(now);
+    let mut scheduled = 0;
+    let mut text = String::new();
+
+    text.push_str(&format!("{}\n", tr("复习计划：")));
+    for lesson in lesson::LESSONS {
+        let items = (0..lesson.quiz().len())
+            .filter(|index| progress.review_item(*lesson, *index).is_some())
+            .count();
+        if items == 0 {
+            continue;
+        }
+        scheduled += items;
+        let lesson_due = due.iter().filter(|(candidate, _)| candidate.id() == lesson.id()).count();
+        text.push_str(&format!(
+            "{:>2}. {} - {}\n",
+            lesson.id(),
+            tr(lesson.title()),
+            tr_fmt("{} 道题，{} 道到期", &[&items, &lesson_due])
+        ));
+    }
+
+    if scheduled > 0 {
+        text.push_str(&format!("{}\n", tr_fmt("共 {} 道题，现在需要复习 {} 道", &[&scheduled, &due.len()])));
+    }
+    text.push_str(&next_due(progress));
+    text
+}
+
+// 学习进度中到期题目的数量，用于菜单中的提示
+pub fn due_count(progress: &Progress) -> usize {
+    progress.due_reviews(progress::now()).len()
+}
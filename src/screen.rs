@@ -0,0 +1,651 @@
+// 全屏界面的状态和绘制
+// 目录中的选择、两个窗格的内容和滚动位置、搜索等状态都在这里，按键只改变状态，
+// 绘制时根据状态生成整个界面的每一行，不直接读写终端，所以也可以在没有终端的环境中使用：
+// tui 模块负责切换终端模式、读取按键和输出画好的界面，tui --replay 则把按键和界面都换成管道
+
+use std::io::{self, Read};
+
+use crate::context::{LessonContext, Style};
+use crate::i18n::{tr, tr_fmt};
+use crate::lesson::{self, Lesson};
+use crate::progress::Progress;
+use crate::source;
+use crate::theme;
+
+// 侧边栏的最大宽度
+const SIDEBAR_WIDTH: usize = 34;
+
+// 窗口大小的最小值，再小就无法正常显示
+const MIN_ROWS: usize = 10;
+const MIN_COLS: usize = 40;
+
+// 目录中的一个位置：某一课，或者某一课中的某一节
+#[derive(Clone, Copy, PartialEq)]
+struct Selection {
+    lesson: usize,
+    section: Option<usize>,
+}
+
+impl Selection {
+    fn lesson(self) -> &'static dyn Lesson {
+        lesson::LESSONS[self.lesson]
+    }
+
+    // 显示在窗格标题中的名称
+    fn title(self) -> String {
+        let lesson = self.lesson();
+        match self.section {
+            Some(index) => tr_fmt(
+                "第{}课 第{}/{}节：{}",
+                &[&lesson.id(), &(index + 1), &lesson.sections().len(), &tr(lesson.sections()[index].title)],
+            ),
+            None => tr_fmt("第{}课：{}", &[&lesson.id(), &tr(lesson.title())]),
+        }
+    }
+}
+
+// 可以滚动的窗格
+#[derive(Clone, Copy, PartialEq)]
+enum Pane {
+    Source,
+    Output,
+}
+
+// 窗格中显示的内容以及滚动到的位置
+#[derive(Default)]
+struct Scroll {
+    lines: Vec<String>,
+    // 显示在窗格第一行的行号
+    top: usize,
+}
+
+// 界面的状态
+pub struct App {
+    progress: Progress,
+    // 目录中选中的位置，源代码窗格显示它的代码
+    selected: Selection,
+    // 运行结果窗格中显示的是哪一课或哪一节的输出
+    shown: Option<Selection>,
+    source: Scroll,
+    output: Scroll,
+    // Tab 切换的、当前可以滚动和搜索的窗格
+    focus: Pane,
+    // 正在输入的搜索内容，不在输入时为 None
+    query: Option<String>,
+    // 上一次搜索的内容
+    last_query: String,
+    // 显示在状态栏中的提示，按下下一个键后清除
+    message: String,
+    // 上一次绘制时各窗格的大小，翻页和滚动到结尾时使用
+    source_height: usize,
+    output_height: usize,
+    output_width: usize,
+}
+
+impl App {
+    pub fn new(progress: Progress) -> App {
+        // 从上次离开的地方开始
+        let selected = match progress.resume_point() {
+            Some((lesson, index)) => Selection {
+                lesson: lesson::LESSONS.iter().position(|item| item.id() == lesson.id()).unwrap_or(0),
+                section: Some(index),
+            },
+            None => Selection { lesson: 0, section: None },
+        };
+
+        let mut app = App {
+            progress,
+            selected,
+            shown: None,
+            source: Scroll::default(),
+            output: Scroll {
+                lines: vec![tr("按回车运行选中的课程或小节").into_owned()],
+                top: 0,
+            },
+            focus: Pane::Output,
+            query: None,
+            last_query: String::new(),
+            message: String::new(),
+            source_height: 0,
+            output_height: 0,
+            output_width: 0,
+        };
+        app.load_source();
+        app
+    }
+
+    // 处理一个按键，返回 false 表示退出
+    pub fn handle(&mut self, key: Key) -> bool {
+        self.message.clear();
+
+        if let Some(query) = &mut self.query {
+            match key {
+                Key::Enter => {
+                    let query = self.query.take().unwrap_or_default();
+                    // 直接回车时重复上一次搜索
+                    if !query.is_empty() {
+                        self.last_query = query;
+                    }
+                    self.search();
+                }
+                Key::Esc | Key::Interrupt => self.query = None,
+                Key::Backspace => {
+                    query.pop();
+                }
+                Key::Char(c) => query.push(c),
+                _ => {}
+            }
+            return true;
+        }
+
+        match key {
+            Key::Char('q') | Key::Interrupt => return false,
+            Key::Up | Key::Char('k') => self.move_selection(-1),
+            Key::Down | Key::Char('j') => self.move_selection(1),
+            Key::Enter | Key::Right => self.run_lesson(self.selected),
+            Key::Char('n') => self.step(1),
+            Key::Char('p') => self.step(-1),
+            Key::Char('r') => match self.shown {
+                Some(shown) => self.run_lesson(shown),
+                None => self.run_lesson(self.selected),
+            },
+            Key::Tab => {
+                self.focus = match self.focus {
+                    Pane::Source => Pane::Output,
+                    Pane::Output => Pane::Source,
+                }
+            }
+            Key::PageUp | Key::Char('b') => self.scroll_by(-1),
+            Key::PageDown | Key::Char(' ') => self.scroll_by(1),
+            Key::Home | Key::Char('g') => self.focused().top = 0,
+            Key::End | Key::Char('G') => self.scroll_to_end(),
+            Key::Char('/') => self.query = Some(String::new()),
+            _ => {}
+        }
+        true
+    }
+
+    // 目录中当前显示的所有行：所有课程，以及选中的课程展开后的小节
+    fn rows(&self) -> Vec<Selection> {
+        let mut rows = Vec::new();
+        for (index, lesson) in lesson::LESSONS.iter().enumerate() {
+            rows.push(Selection { lesson: index, section: None });
+            if index == self.selected.lesson {
+                rows.extend((0..lesson.sections().len()).map(|section| Selection {
+                    lesson: index,
+                    section: Some(section),
+                }));
+            }
+        }
+        rows
+    }
+
+    // 在目录中上下移动
+    fn move_selection(&mut self, delta: isize) {
+        let rows = self.rows();
+        let current = rows.iter().position(|row| *row == self.selected).unwrap_or(0);
+        let target = current.saturating_add_signed(delta).min(rows.len() - 1);
+        if rows[target] != self.selected {
+            self.selected = rows[target];
+            self.load_source();
+        }
+    }
+
+    // 运行下一节或上一节，可以跨越课程
+    fn step(&mut self, delta: isize) {
+        let from = self.shown.unwrap_or(self.selected);
+        let sections: Vec<Selection> = lesson::LESSONS
+            .iter()
+            .enumerate()
+            .flat_map(|(index, lesson)| {
+                (0..lesson.sections().len()).map(move |section| Selection {
+                    lesson: index,
+                    section: Some(section),
+                })
+            })
+            .collect();
+
+        let target = match (from.section, delta > 0) {
+            // 选中的是整课时，下一节是这一课的第一节，上一节是前一课的最后一节
+            (None, true) => sections.iter().position(|item| item.lesson == from.lesson),
+            (None, false) => sections.iter().rposition(|item| item.lesson < from.lesson),
+            (Some(_), _) => sections
+                .iter()
+                .position(|item| *item == from)
+                .and_then(|index| index.checked_add_signed(delta))
+                .filter(|index| *index < sections.len()),
+        };
+
+        match target {
+            Some(index) => {
+                self.selected = sections[index];
+                self.load_source();
+                self.run_lesson(sections[index]);
+            }
+            None if delta > 0 => self.message = tr("已经是最后一节了。").into_owned(),
+            None => self.message = tr("已经是第一节了。").into_owned(),
+        }
+    }
+
+    // 运行一整课或者某一节，把输出显示在运行结果窗格中，并记录学习进度
+    fn run_lesson(&mut self, selection: Selection) {
+        let lesson = selection.lesson();
+        let mut buffer = Vec::new();
+        let mut ctx = LessonContext::with_style(&mut buffer, theme::current());
+        match selection.section {
+            Some(index) => (lesson.sections()[index].run)(&mut ctx),
+            None => lesson.run(&mut ctx),
+        }
+        // 写入内存缓冲区不会失败
+        let _ = ctx.finish();
+
+        let output = String::from_utf8_lossy(&buffer);
+        self.output = Scroll {
+            lines: output.trim_start_matches('\n').lines().map(|line| line.replace('\t', "    ")).collect(),
+            top: 0,
+        };
+        self.shown = Some(selection);
+
+        let completed: Vec<&str> = match selection.section {
+            Some(index) => vec![lesson.sections()[index].id],
+            None => lesson.sections().iter().map(|section| section.id).collect(),
+        };
+        for id in completed {
+            self.progress.complete_section(lesson, id);
+        }
+        if let Err(error) = self.progress.save() {
+            self.message = tr_fmt("无法保存学习进度: {}", &[&error]);
+        }
+    }
+
+    // 在源代码窗格中显示选中的课程或小节
+    fn load_source(&mut self) {
+        let lesson = self.selected.lesson();
+        let style = theme::current();
+        let text = match self.selected.section {
+            Some(index) => source::render(lesson, &lesson.sections()[index], style)
+                .unwrap_or_else(|| source::render_file(lesson, style)),
+            None => source::render_file(lesson, style),
+        };
+        self.source = Scroll {
+            lines: text.lines().map(str::to_string).collect(),
+            top: 0,
+        };
+    }
+
+    fn focused(&mut self) -> &mut Scroll {
+        match self.focus {
+            Pane::Source => &mut self.source,
+            Pane::Output => &mut self.output,
+        }
+    }
+
+    // 翻页，pages 为负数时向上
+    fn scroll_by(&mut self, pages: isize) {
+        let page = match self.focus {
+            Pane::Source => self.source_height,
+            Pane::Output => self.output_height,
+        }
+        .max(1);
+        let scroll = self.focused();
+        let last = scroll.lines.len().saturating_sub(1);
+        scroll.top = scroll.top.saturating_add_signed(pages * page as isize).min(last);
+    }
+
+    // 滚动到最后一页
+    fn scroll_to_end(&mut self) {
+        match self.focus {
+            Pane::Source => self.source.top = self.source.lines.len().saturating_sub(self.source_height),
+            Pane::Output => {
+                // 运行结果会自动换行，从最后一行往前数，直到占满整个窗格
+                let mut rows = 0;
+                let mut top = self.output.lines.len();
+                while top > 0 {
+                    let height = wrap(&self.output.lines[top - 1], self.output_width).len();
+                    if rows + height > self.output_height {
+                        break;
+                    }
+                    rows += height;
+                    top -= 1;
+                }
+                self.output.top = top;
+            }
+        }
+    }
+
+    // 从当前位置往后查找上一次搜索的内容，找到结尾后从头开始
+    fn search(&mut self) {
+        let query = self.last_query.clone();
+        if query.is_empty() {
+            return;
+        }
+
+        let scroll = self.focused();
+        let matches: Vec<usize> = scroll
+            .lines
+            .iter()
+            .enumerate()
+            .filter(|(_, line)| strip_escapes(line).contains(&query))
+            .map(|(index, _)| index)
+            .collect();
+
+        let Some(position) = matches
+            .iter()
+            .position(|index| *index > scroll.top)
+            .or((!matches.is_empty()).then_some(0))
+        else {
+            self.message = tr_fmt("没有找到 \"{}\"", &[&query]);
+            return;
+        };
+
+        scroll.top = matches[position];
+        self.message = tr_fmt("\"{}\"：第 {}/{} 处", &[&query, &(position + 1), &matches.len()]);
+    }
+
+    // 绘制整个界面，返回从上到下的每一行，每一行正好占 cols 列（状态栏少一列）
+    pub fn render(&mut self, (rows, cols): (usize, usize)) -> Vec<String> {
+        if rows < MIN_ROWS || cols < MIN_COLS {
+            return vec![fit(&tr("终端窗口太小了，请调大窗口或者按 q 退出"), cols)];
+        }
+
+        let sidebar_width = (cols / 3).min(SIDEBAR_WIDTH);
+        let right_width = cols - sidebar_width - 1;
+        let body = rows - 2;
+        // 右侧两个窗格各有一行标题，剩下的行按 2:3 分给源代码和运行结果
+        self.source_height = (body - 2) * 2 / 5;
+        self.output_height = body - 2 - self.source_height;
+        self.output_width = right_width;
+
+        let mut lines = Vec::with_capacity(rows);
+
+        let title = format!(" {}  |  {}", tr("Rust 学习示例程序"), self.selected.title());
+        lines.push(reverse(&fit(&title, cols)));
+
+        let sidebar = self.sidebar(sidebar_width, body);
+        let right = self.panes(right_width);
+        for (left, right) in sidebar.iter().zip(&right) {
+            lines.push(format!("{}│{}", left, right));
+        }
+
+        // 状态栏少画一列，避免光标停在右下角时终端滚动
+        let status = match &self.query {
+            Some(query) => format!("/{}▏", query),
+            None if !self.message.is_empty() => self.message.clone(),
+            None => tr("↑↓ 选择  回车 运行  n/p 下一节/上一节  r 重新运行  Tab 切换窗格  / 搜索  q 退出").into_owned(),
+        };
+        lines.push(fit(&status, cols - 1));
+
+        lines
+    }
+
+    // 侧边栏的每一行，选中的一行反色显示，并保证它总在可见范围内
+    fn sidebar(&self, width: usize, height: usize) -> Vec<String> {
+        let rows = self.rows();
+        let current = rows.iter().position(|row| *row == self.selected).unwrap_or(0);
+        let top = (current + 1).saturating_sub(height);
+
+        let mut lines: Vec<String> = rows
+            .iter()
+            .skip(top)
+            .take(height)
+            .map(|row| {
+                let lesson = row.lesson();
+                let text = match row.section {
+                    Some(index) => {
+                        let section = &lesson.sections()[index];
+                        let mark = if self.progress.is_section_completed(lesson, section.id) { " ✓" } else { "" };
+                        format!("    {}. {}{}", index + 1, tr(section.title), mark)
+                    }
+                    None => {
+                        let arrow = if row.lesson == self.selected.lesson { "▾" } else { "▸" };
+                        let done = self.progress.completed_sections(lesson) == lesson.sections().len();
+                        let mark = if done { " ✓" } else { "" };
+                        format!("{} {}. {}{}", arrow, lesson.id(), tr(lesson.title()), mark)
+                    }
+                };
+                let text = fit(&text, width);
+                if *row == self.selected { reverse(&text) } else { text }
+            })
+            .collect();
+
+        lines.resize(height, " ".repeat(width));
+        lines
+    }
+
+    // 右侧的源代码窗格和运行结果窗格，逐行排列
+    fn panes(&self, width: usize) -> Vec<String> {
+        let mut lines = Vec::new();
+
+        lines.push(self.pane_title(Pane::Source, &tr("源代码"), width));
+        lines.extend(
+            self.source.lines[self.source.top.min(self.source.lines.len())..]
+                .iter()
+                .take(self.source_height)
+                .map(|line| fit(line, width)),
+        );
+        lines.resize(1 + self.source_height, " ".repeat(width));
+
+        let title = match self.shown {
+            Some(shown) => tr_fmt("运行结果：{}", &[&shown.title()]),
+            None => tr("运行结果").into_owned(),
+        };
+        lines.push(self.pane_title(Pane::Output, &title, width));
+        lines.extend(
+            self.output.lines[self.output.top.min(self.output.lines.len())..]
+                .iter()
+                .flat_map(|line| wrap(line, width))
+                .take(self.output_height)
+                .map(|line| fit(&line, width)),
+        );
+        lines.resize(2 + self.source_height + self.output_height, " ".repeat(width));
+
+        lines
+    }
+
+    // 窗格的标题行，可以滚动的窗格反色显示
+    fn pane_title(&self, pane: Pane, title: &str, width: usize) -> String {
+        let text = fit(&format!("── {} {}", title, "─".repeat(width)), width);
+        if pane == self.focus { reverse(&text) } else { theme::current().label(&text) }
+    }
+}
+
+// 反色显示
+fn reverse(text: &str) -> String {
+    format!("\x1b[7m{}\x1b[0m", text)
+}
+
+// 字符在终端中占据的列数：中日韩文字和全角符号占两列
+pub fn char_width(c: char) -> usize {
+    match c {
+        c if c.is_control() => 0,
+        '\u{1100}'..='\u{115f}'
+        | '\u{2e80}'..='\u{a4cf}'
+        | '\u{ac00}'..='\u{d7a3}'
+        | '\u{f900}'..='\u{faff}'
+        | '\u{fe30}'..='\u{fe4f}'
+        | '\u{ff00}'..='\u{ff60}'
+        | '\u{ffe0}'..='\u{ffe6}'
+        | '\u{1f300}'..='\u{1faff}'
+        | '\u{20000}'..='\u{3fffd}' => 2,
+        _ => 1,
+    }
+}
+
+// 把文字截断或者用空格补齐到正好 width 列，颜色等转义序列不占宽度
+fn fit(text: &str, width: usize) -> String {
+    let mut result = String::new();
+    let mut used = 0;
+    let mut chars = text.chars();
+
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            result.push(c);
+            for c in chars.by_ref() {
+                result.push(c);
+                if c.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+            continue;
+        }
+
+        let c_width = char_width(c);
+        if used + c_width > width {
+            break;
+        }
+        result.push(c);
+        used += c_width;
+    }
+
+    result.push_str("\x1b[0m");
+    result.push_str(&" ".repeat(width - used));
+    result
+}
+
+// 把一行文字按 width 列自动换行，换行后继续使用原来的颜色
+fn wrap(text: &str, width: usize) -> Vec<String> {
+    let mut rows = Vec::new();
+    let mut row = String::new();
+    let mut used = 0;
+    // 当前生效的颜色
+    let mut color = String::new();
+    let mut chars = text.chars();
+
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            let mut sequence = String::from(c);
+            for c in chars.by_ref() {
+                sequence.push(c);
+                if c.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+            row.push_str(&sequence);
+            color = if sequence == "\x1b[0m" { String::new() } else { sequence };
+            continue;
+        }
+
+        let c_width = char_width(c);
+        if used + c_width > width && used > 0 {
+            rows.push(std::mem::replace(&mut row, color.clone()));
+            used = 0;
+        }
+        row.push(c);
+        used += c_width;
+    }
+
+    rows.push(row);
+    rows
+}
+
+// 去掉转义序列，只留下文字
+pub fn strip_escapes(text: &str) -> String {
+    let mut result = String::new();
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            chars.by_ref().find(|c| c.is_ascii_alphabetic());
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
+
+// 按键
+#[derive(Clone, Copy, PartialEq)]
+pub enum Key {
+    Up,
+    Down,
+    Right,
+    Enter,
+    Tab,
+    Backspace,
+    Esc,
+    PageUp,
+    PageDown,
+    Home,
+    End,
+    // Ctrl-C 或 Ctrl-D
+    Interrupt,
+    Char(char),
+}
+
+// 读取一个按键，等待超时或者遇到不认识的按键时返回 None
+pub fn read_key(input: &mut impl Read) -> io::Result<Option<Key>> {
+    let Some(byte) = read_byte(input)? else {
+        return Ok(None);
+    };
+
+    let key = match byte {
+        b'\r' | b'\n' => Key::Enter,
+        b'\t' => Key::Tab,
+        0x7f | 0x08 => Key::Backspace,
+        0x03 | 0x04 => Key::Interrupt,
+        0x1b => return escape_sequence(input),
+        byte if byte < 0x80 => Key::Char(char::from(byte)),
+        // 多字节的 UTF-8 字符，例如搜索时输入的中文
+        byte => {
+            let len = match byte {
+                0xf0.. => 4,
+                0xe0.. => 3,
+                _ => 2,
+            };
+            let mut bytes = vec![byte];
+            for _ in 1..len {
+                match read_byte(input)? {
+                    Some(byte) => bytes.push(byte),
+                    None => return Ok(None),
+                }
+            }
+            match std::str::from_utf8(&bytes).ok().and_then(|text| text.chars().next()) {
+                Some(c) => Key::Char(c),
+                None => return Ok(None),
+            }
+        }
+    };
+    Ok(Some(key))
+}
+
+// 解析 Esc 之后的转义序列，例如方向键 "\x1b[A" 和翻页键 "\x1b[5~"
+fn escape_sequence(input: &mut impl Read) -> io::Result<Option<Key>> {
+    // Esc 后面没有紧跟其他字符时就是单独按下的 Esc
+    match read_byte(input)? {
+        None => return Ok(Some(Key::Esc)),
+        Some(b'[' | b'O') => {}
+        Some(_) => return Ok(None),
+    }
+
+    let mut sequence = Vec::new();
+    while let Some(byte) = read_byte(input)? {
+        sequence.push(byte);
+        if byte.is_ascii_alphabetic() || byte == b'~' {
+            break;
+        }
+    }
+
+    let key = match sequence.as_slice() {
+        b"A" => Key::Up,
+        b"B" => Key::Down,
+        b"C" => Key::Right,
+        b"H" | b"1~" | b"7~" => Key::Home,
+        b"F" | b"4~" | b"8~" => Key::End,
+        b"5~" => Key::PageUp,
+        b"6~" => Key::PageDown,
+        _ => return Ok(None),
+    };
+    Ok(Some(key))
+}
+
+// 读取一个字节，超时时返回 None
+fn read_byte(input: &mut impl Read) -> io::Result<Option<u8>> {
+    let mut byte = [0];
+    loop {
+        match input.read(&mut byte) {
+            Ok(0) => return Ok(None),
+            Ok(_) => return Ok(Some(byte[0])),
+            Err(error) if error.kind() == io::ErrorKind::Interrupted => continue,
+            Err(error) => return Err(error),
+        }
+    }
+}
@@ -0,0 +1,303 @@
+// 课程全文搜索
+// 为每一个小节建立索引，索引的内容包括小节标题、小节说明、源代码和运行结果，
+// `rustlearn search <关键词>` 和交互式菜单中的搜索都使用这里的索引，结果按相关程度排序
+//
+// 分词规则：
+// 1. 英文、数字和下划线组成的单词转换成小写，例如 HashMap 变成 hashmap；
+//    带下划线的标识符同时拆成几个单词，例如 lifetime_elision 还会产生 lifetime 和 elision；
+//    结尾的复数 s 会被去掉，所以 lifetime 也能找到 lifetimes
+// 2. 中文没有空格分隔单词，连续的汉字按相邻两个字切分，例如 "生命周期" 切分成 "生命"、"命周"、"周期"，
+//    这样不需要词典也能找到 "生命周期省略规则" 中的 "生命周期" 和 "省略"
+//    只有一个字的查询也能使用，所以索引中还会记录每一个单字
+//
+// 排序：标题中的匹配比说明更重要，说明又比代码和运行结果更重要，
+// 出现在很多小节中的常见词（例如 "使用"）权重较低，同时匹配更多关键词的小节排在前面
+
+use std::collections::HashMap;
+
+use crate::book;
+use crate::cli;
+use crate::i18n::{tr, tr_fmt};
+use crate::lesson::{self, Lesson};
+use crate::source;
+
+// 默认显示的结果数
+pub const DEFAULT_LIMIT: usize = 10;
+
+// 索引中的字段和它们的权重
+const TITLE: f64 = 8.0;
+const PROSE: f64 = 4.0;
+const LESSON: f64 = 1.5;
+const CODE: f64 = 1.0;
+const OUTPUT: f64 = 1.0;
+
+// 一个小节的索引
+struct Document {
+    lesson: &'static dyn Lesson,
+    index: usize,
+    // 每个词在各个字段中出现的次数，与字段的权重一起保存
+    fields: Vec<(f64, HashMap<String, usize>)>,
+    // 用于显示摘要的文字：小节说明、运行结果和源代码的每一行
+    lines: Vec<String>,
+}
+
+// 所有小节的索引
+pub struct Index {
+    documents: Vec<Document>,
+    // 每个词出现在多少个小节中
+    frequency: HashMap<String, usize>,
+}
+
+// 一条搜索结果
+pub struct Hit {
+    pub lesson: &'static dyn Lesson,
+    pub index: usize,
+    pub score: f64,
+    // 包含关键词的一行文字，没有时为空
+    pub snippet: String,
+}
+
+impl Index {
+    // 为所有课程的所有小节建立索引，需要运行每一个小节来得到运行结果
+    pub fn build() -> Index {
+        let mut documents = Vec::new();
+        let mut frequency: HashMap<String, usize> = HashMap::new();
+
+        for lesson in lesson::LESSONS {
+            for (index, section) in lesson.sections().iter().enumerate() {
+                // 标题和说明同时索引中文原文和当前语言的译文
+                let title = format!("{} {} {}", section.title, tr(section.title), section.id);
+                let lesson_text = format!("{} {}", lesson.title(), tr(lesson.title()));
+                let prose: Vec<String> = source::explanation(*lesson, section)
+                    .iter()
+                    .flat_map(|line| [line.to_string(), tr(line).into_owned()])
+                    .collect();
+                let code = source::plain(*lesson, section).unwrap_or_default();
+                let output = book::section_output(section);
+
+                let fields = vec![
+                    (TITLE, count(&title)),
+                    (PROSE, count(&prose.join("\n"))),
+                    (LESSON, count(&lesson_text)),
+                    (CODE, count(&code)),
+                    (OUTPUT, count(&output)),
+                ];
+                let mut words: Vec<&String> = fields.iter().flat_map(|(_, counts)| counts.keys()).collect();
+                words.sort();
+                words.dedup();
+                for word in words {
+                    *frequency.entry(word.clone()).or_default() += 1;
+                }
+
+                let lines = prose
+                    .iter()
+                    .map(String::as_str)
+                    .chain(output.lines())
+                    .chain(code.lines())
+                    .map(|line| line.trim().to_string())
+                    .filter(|line| !line.is_empty())
+                    .collect();
+
+                documents.push(Document { lesson: *lesson, index, fields, lines });
+            }
+        }
+
+        Index { documents, frequency }
+    }
+
+    // 搜索，返回按相关程度从高到低排列的结果
+    pub fn search(&self, query: &str) -> Vec<Hit> {
+        let mut terms = query_terms(query);
+        terms.sort();
+        terms.dedup();
+        if terms.is_empty() {
+            return Vec::new();
+        }
+
+        let total = self.documents.len() as f64;
+        let mut hits = Vec::new();
+        for document in &self.documents {
+            let mut score = 0.0;
+            let mut matched = 0;
+            for term in &terms {
+                let weight: f64 = document
+                    .fields
+                    .iter()
+                    .filter_map(|(weight, counts)| counts.get(term).map(|count| weight * (1.0 + (*count as f64).ln())))
+                    .sum();
+                if weight > 0.0 {
+                    let documents = self.frequency.get(term).copied().unwrap_or(1) as f64;
+                    score += weight * (1.0 + total / documents).ln();
+                    matched += 1;
+                }
+            }
+
+            // 至少要匹配一半的关键词，匹配得越多排得越靠前
+            let coverage = matched as f64 / terms.len() as f64;
+            if coverage >= 0.5 {
+                hits.push(Hit {
+                    lesson: document.lesson,
+                    index: document.index,
+                    score: score * coverage * coverage,
+                    snippet: snippet(&document.lines, query),
+                });
+            }
+        }
+
+        hits.sort_by(|a, b| b.score.total_cmp(&a.score));
+        hits
+    }
+}
+
+// 把文字切分成索引中的词
+pub fn tokenize(text: &str) -> Vec<String> {
+    split(text, true)
+}
+
+// 查询中的词：和索引使用相同的规则，但是两个字以上的中文只使用相邻两个字的组合
+fn query_terms(query: &str) -> Vec<String> {
+    split(query, false)
+}
+
+fn split(text: &str, unigrams: bool) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let chars: Vec<char> = text.chars().collect();
+    let mut start = 0;
+
+    while start < chars.len() {
+        let c = chars[start];
+        if is_cjk(c) {
+            let end = chars[start..].iter().position(|c| !is_cjk(*c)).map_or(chars.len(), |n| start + n);
+            let run = &chars[start..end];
+            if unigrams || run.len() == 1 {
+                tokens.extend(run.iter().map(char::to_string));
+            }
+            tokens.extend(run.windows(2).map(|pair| pair.iter().collect::<String>()));
+            start = end;
+        } else if c.is_alphanumeric() || c == '_' {
+            let end = chars[start..]
+                .iter()
+                .position(|c| !(c.is_alphanumeric() || *c == '_') || is_cjk(*c))
+                .map_or(chars.len(), |n| start + n);
+            let word: String = chars[start..end].iter().collect::<String>().to_lowercase();
+            if word.contains('_') {
+                tokens.extend(word.split('_').filter(|part| !part.is_empty()).map(normalize));
+            }
+            tokens.push(normalize(&word));
+            start = end;
+        } else {
+            start += 1;
+        }
+    }
+
+    tokens
+}
+
+// 去掉英文单词结尾表示复数的 s
+fn normalize(word: &str) -> String {
+    match word.strip_suffix('s') {
+        Some(stem) if stem.len() >= 3 && !stem.ends_with('s') => stem.to_string(),
+        _ => word.to_string(),
+    }
+}
+
+// 中日韩统一表意文字
+fn is_cjk(c: char) -> bool {
+    matches!(c, '\u{3400}'..='\u{4dbf}' | '\u{4e00}'..='\u{9fff}' | '\u{f900}'..='\u{faff}')
+}
+
+// 统计每个词出现的次数
+fn count(text: &str) -> HashMap<String, usize> {
+    let mut counts = HashMap::new();
+    for token in tokenize(text) {
+        *counts.entry(token).or_default() += 1;
+    }
+    counts
+}
+
+// 第一行包含查询中某个关键词的文字，太长时截短
+fn snippet(lines: &[String], query: &str) -> String {
+    let keywords: Vec<String> = query.split_whitespace().map(str::to_lowercase).collect();
+    let line = lines
+        .iter()
+        .find(|line| {
+            let line = line.to_lowercase();
+            keywords.iter().any(|keyword| line.contains(keyword.as_str()))
+        })
+        .map_or("", String::as_str);
+
+    let mut text: String = line.chars().take(60).collect();
+    if text.len() < line.len() {
+        text.push('…');
+    }
+    text
+}
+
+// 一条结果的标题，例如 "第10课 第6节：生命周期省略规则"
+pub fn hit_title(hit: &Hit) -> String {
+    tr_fmt(
+        "第{}课 第{}节：{}",
+        &[&hit.lesson.id(), &(hit.index + 1), &tr(hit.lesson.sections()[hit.index].title)],
+    )
+}
+
+// search 子命令，返回进程退出码
+pub fn run_cli(args: &[String]) -> i32 {
+    let mut limit = DEFAULT_LIMIT;
+    let mut run = false;
+    let mut words = Vec::new();
+    let mut iter = args.iter();
+
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--run" => run = true,
+            "--limit" => match iter.next().and_then(|value| value.parse().ok()) {
+                Some(value) if value > 0 => limit = value,
+                _ => return usage_error(),
+            },
+            other if other.starts_with('-') => return usage_error(),
+            other => words.push(other),
+        }
+    }
+    if words.is_empty() {
+        return usage_error();
+    }
+    let query = words.join(" ");
+
+    let hits = Index::build().search(&query);
+    let Some(best) = hits.first() else {
+        cli::print(&format!("{}\n", tr_fmt("没有找到与 \"{}\" 相关的小节", &[&query])));
+        return 1;
+    };
+
+    // 直接运行最相关的小节
+    if run {
+        let section = &best.lesson.sections()[best.index];
+        return cli::print_to_stdout(|ctx| (section.run)(ctx));
+    }
+
+    let mut text = format!("{}\n", tr_fmt("找到 {} 个相关的小节：", &[&hits.len()]));
+    for (number, hit) in hits.iter().take(limit).enumerate() {
+        text.push_str(&format!("{}. {}\n", number + 1, hit_title(hit)));
+        text.push_str(&format!(
+            "   rustlearn run {} --section {}\n",
+            hit.lesson.id(),
+            hit.lesson.sections()[hit.index].id
+        ));
+        if !hit.snippet.is_empty() {
+            text.push_str(&format!("   {}\n", hit.snippet));
+        }
+    }
+    if hits.len() > limit {
+        text.push_str(&format!(
+            "{}\n",
+            tr_fmt("还有 {} 个结果没有显示，可以使用 --limit 显示更多", &[&(hits.len() - limit)])
+        ));
+    }
+    cli::print(&text)
+}
+
+fn usage_error() -> i32 {
+    eprintln!("{}", tr("用法: rustlearn search <关键词>... [--limit <数量>] [--run]"));
+    2
+}
@@ -0,0 +1,326 @@
+// 小节源代码
+// 每一课的源代码都通过 Lesson::source() 在编译时嵌入程序，小节标识与示例函数同名，
+// 所以可以直接从源代码中找出小节对应的函数，连同行号一起显示，代码和输出永远不会脱节
+//
+// 除了小节函数本身，还会带上它用到的顶层定义：
+// 1. 小节中提到名字的函数、结构体、枚举、trait 等，例如第 4 课中的 print_direction 和 Direction
+// 2. 定义了小节中调用的方法的 impl 块，例如第 3 课 struct_methods 中调用的 area 所在的 impl Rectangle
+// 这些定义中再用到的其他定义也会一并带上
+//
+// 查找代码范围时会跳过字符串、字符和注释中的括号，所以 writeln!(ctx, "{}") 这样的代码不会干扰匹配
+// 跳过的规则和语法高亮共用 highlight 模块中的词法分析，两边对字符串、原始字符串和嵌套注释的理解始终一致
+
+use std::collections::BTreeSet;
+
+use crate::context::Style;
+use crate::highlight::Highlighter;
+use crate::i18n::tr_fmt;
+use crate::lesson::{Lesson, Section};
+
+// 源代码中的一段连续的行
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Span {
+    // 第一行的下标，从 0 开始
+    pub start: usize,
+    // 最后一行之后的下标
+    pub end: usize,
+}
+
+// 源文件中的一个顶层定义
+struct Item {
+    // 定义的名字，impl 块是实现的类型名
+    name: String,
+    // 是否是 impl 块
+    is_impl: bool,
+    // impl 块中定义的方法名
+    methods: Vec<String>,
+    // 包括定义前面的注释和属性在内的范围
+    span: Span,
+}
+
+// 找出小节需要显示的代码范围，按行号排列；找不到小节函数时返回 None
+pub fn section_spans(lesson: &dyn Lesson, section: &Section) -> Option<Vec<Span>> {
+    let source = lesson.source();
+    let lines: Vec<&str> = source.lines().collect();
+    let items = top_level_items(&lines);
+
+    let function = items.iter().find(|item| !item.is_impl && item.name == section.id)?;
+
+    // 小节函数、课程入口和 Lesson 的实现不算作辅助定义
+    let skipped: BTreeSet<&str> = lesson
+        .sections()
+        .iter()
+        .map(|section| section.id)
+        .chain(["run", "QUIZ", "EXERCISES"])
+        .collect();
+
+    let mut included = vec![function.span];
+    let mut pending = vec![function.span];
+
+    while let Some(span) = pending.pop() {
+        let text = lines[span.start..span.end].join("\n");
+        let words = identifiers(&text);
+
+        for item in &items {
+            if included.contains(&item.span) || skipped.contains(item.name.as_str()) {
+                continue;
+            }
+
+            let used = if item.is_impl {
+                words.contains(item.name.as_str()) && item.methods.iter().any(|method| calls(&text, method))
+            } else {
+                words.contains(item.name.as_str())
+            };
+
+            if used {
+                included.push(item.span);
+                pending.push(item.span);
+            }
+        }
+    }
+
+    included.sort();
+    Some(included)
+}
+
+// 以 "行号 | 代码" 的形式显示小节的源代码，代码按样式高亮；找不到小节函数时返回 None
+pub fn render(lesson: &dyn Lesson, section: &Section, style: &dyn Style) -> Option<String> {
+    let spans = section_spans(lesson, section)?;
+    let lines: Vec<&str> = lesson.source().lines().collect();
+    let width = spans.last().map_or(1, |span| span.end.to_string().len());
+
+    let mut text = style.label(&tr_fmt("源代码（{}）：", &[&lesson.source_path()]));
+    text.push('\n');
+
+    for (index, span) in spans.iter().enumerate() {
+        if index > 0 {
+            text.push_str(&style.gutter(&format!("{:>width$} ⋮", "", width = width)));
+            text.push('\n');
+        }
+        push_lines(&mut text, &lines, *span, width, style);
+    }
+
+    Some(text)
+}
+
+// 以同样的形式显示一课的全部源代码
+pub fn render_file(lesson: &dyn Lesson, style: &dyn Style) -> String {
+    let lines: Vec<&str> = lesson.source().lines().collect();
+    let width = lines.len().to_string().len();
+
+    let mut text = style.label(&tr_fmt("源代码（{}）：", &[&lesson.source_path()]));
+    text.push('\n');
+    push_lines(&mut text, &lines, Span { start: 0, end: lines.len() }, width, style);
+    text
+}
+
+// 不带行号的小节源代码，各段之间空一行，用于导出书籍等需要复制代码的场合
+pub fn plain(lesson: &dyn Lesson, section: &Section) -> Option<String> {
+    let spans = section_spans(lesson, section)?;
+    let lines: Vec<&str> = lesson.source().lines().collect();
+
+    let segments: Vec<String> = spans
+        .iter()
+        .map(|span| {
+            lines[span.start..span.end]
+                .iter()
+                .map(|line| line.trim_end())
+                .collect::<Vec<_>>()
+                .join("\n")
+        })
+        .collect();
+    Some(segments.join("\n\n"))
+}
+
+// 小节函数前面的注释，去掉 "//" 之后按行返回，例如 ["演示错误传播"]
+pub fn explanation(lesson: &dyn Lesson, section: &Section) -> Vec<&'static str> {
+    let source = lesson.source();
+    let lines: Vec<&'static str> = source.lines().collect();
+    let items = top_level_items(&lines);
+
+    let Some(function) = items.iter().find(|item| !item.is_impl && item.name == section.id) else {
+        return Vec::new();
+    };
+
+    let mut comments: Vec<&'static str> = lines[function.span.start..function.span.end]
+        .iter()
+        .map_while(|line| line.trim_start().strip_prefix("//"))
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect();
+    // 个别小节的注释写了两遍（例如第 8 课的 error_libraries），只保留一次
+    comments.dedup();
+    comments
+}
+
+// 输出一段带行号的代码
+// 每一段都从一个完整的顶层定义开始，高亮状态不需要跨段保留
+fn push_lines(text: &mut String, lines: &[&str], span: Span, width: usize, style: &dyn Style) {
+    let mut highlighter = Highlighter::default();
+
+    for (number, line) in lines.iter().enumerate().take(span.end).skip(span.start) {
+        let line = line.trim_end();
+        let gutter = format!("{:>width$} |", number + 1, width = width);
+        text.push_str(&style.gutter(&gutter));
+        if !line.is_empty() {
+            text.push(' ');
+            text.push_str(&highlighter.line(line, style));
+        }
+        text.push('\n');
+    }
+}
+
+// 找出源文件中所有的顶层定义
+fn top_level_items(lines: &[&str]) -> Vec<Item> {
+    let mut items = Vec::new();
+    let mut index = 0;
+
+    while index < lines.len() {
+        let Some((name, is_impl)) = item_name(lines[index]) else {
+            index += 1;
+            continue;
+        };
+
+        let end = item_end(lines, index);
+        let start = comment_start(lines, index);
+        let methods = if is_impl {
+            lines[index..end].iter().filter_map(|line| method_name(line)).collect()
+        } else {
+            Vec::new()
+        };
+
+        items.push(Item {
+            name,
+            is_impl,
+            methods,
+            span: Span { start, end },
+        });
+        index = end;
+    }
+
+    items
+}
+
+// 如果这一行是一个顶层定义的开头，返回定义的名字以及它是否是 impl 块
+// 个别顶层定义带有缩进（例如第 8 课的 error_libraries），所以先去掉行首的空白
+fn item_name(line: &str) -> Option<(String, bool)> {
+    let line = line.trim_start();
+    let rest = line.strip_prefix("pub ").unwrap_or(line);
+
+    if let Some(rest) = rest.strip_prefix("impl") {
+        // impl Type、impl<T> Type<T>、impl Trait for Type 都取实现的类型名
+        let rest = skip_generics(rest);
+        let target = match rest.split_once(" for ") {
+            Some((_, target)) => target,
+            None => rest,
+        };
+        let name = leading_identifier(target.trim_start());
+        return (!name.is_empty()).then(|| (name.to_string(), true));
+    }
+
+    for keyword in ["fn ", "struct ", "enum ", "trait ", "type ", "const ", "static ", "mod "] {
+        if let Some(rest) = rest.strip_prefix(keyword) {
+            let name = leading_identifier(rest);
+            return (!name.is_empty()).then(|| (name.to_string(), false));
+        }
+    }
+
+    None
+}
+
+// impl 块中以 "    fn 名字" 开头的方法
+fn method_name(line: &str) -> Option<String> {
+    let rest = line.trim_start();
+    let rest = rest.strip_prefix("pub ").unwrap_or(rest);
+    let name = leading_identifier(rest.strip_prefix("fn ")?);
+    (!name.is_empty()).then(|| name.to_string())
+}
+
+// 跳过 impl 后面的泛型参数，例如 "<T: Display> Wrapper<T>" 中的 "<T: Display>"
+fn skip_generics(rest: &str) -> &str {
+    let Some(inner) = rest.strip_prefix('<') else {
+        return rest;
+    };
+
+    let mut depth = 1;
+    for (position, c) in inner.char_indices() {
+        match c {
+            '<' => depth += 1,
+            '>' => {
+                depth -= 1;
+                if depth == 0 {
+                    return &inner[position + 1..];
+                }
+            }
+            _ => {}
+        }
+    }
+    ""
+}
+
+// 字符串开头的标识符
+fn leading_identifier(text: &str) -> &str {
+    let end = text
+        .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .unwrap_or(text.len());
+    &text[..end]
+}
+
+// 代码中出现的所有标识符
+fn identifiers(text: &str) -> BTreeSet<&str> {
+    text.split(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .filter(|word| !word.is_empty())
+        .collect()
+}
+
+// 代码中是否以 .name( 或 ::name( 的形式调用了某个方法
+fn calls(text: &str, method: &str) -> bool {
+    [".", "::"].iter().any(|prefix| {
+        text.match_indices(&format!("{}{}", prefix, method)).any(|(position, matched)| {
+            text[position + matched.len()..].trim_start().starts_with('(')
+        })
+    })
+}
+
+// 定义前面紧挨着的注释和属性也算作定义的一部分
+fn comment_start(lines: &[&str], index: usize) -> usize {
+    let mut start = index;
+    while start > 0 {
+        let previous = lines[start - 1].trim_start();
+        if previous.starts_with("//") || previous.starts_with("#[") {
+            start -= 1;
+        } else {
+            break;
+        }
+    }
+    start
+}
+
+// 从定义的第一行开始，找到与第一个 { 匹配的 } 所在的行，返回它的下一行
+// 没有 { 的定义（例如 "struct Unit;"）在遇到 ; 时结束
+fn item_end(lines: &[&str], start: usize) -> usize {
+    let mut highlighter = Highlighter::default();
+    let mut depth = 0;
+    let mut opened = false;
+
+    for (index, line) in lines.iter().enumerate().skip(start) {
+        for c in highlighter.code_chars(line) {
+            match c {
+                '{' | '(' | '[' => {
+                    depth += 1;
+                    opened |= c == '{';
+                }
+                '}' | ')' | ']' => {
+                    depth -= 1;
+                    if opened && depth == 0 {
+                        return index + 1;
+                    }
+                }
+                ';' if depth == 0 => return index + 1,
+                _ => {}
+            }
+        }
+    }
+
+    lines.len()
+}
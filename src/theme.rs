@@ -0,0 +1,189 @@
+// 终端配色主题
+// 课程标题、小节标题以及显示的 Rust 代码在终端中按主题着色，提供三种主题：
+// 1. light - 适合浅色背景的终端
+// 2. dark - 适合深色背景的终端，默认使用
+// 3. high-contrast - 高对比度，只使用粗体和明亮的颜色，不使用暗淡或斜体
+//
+// 主题的选择顺序：命令行参数 --theme，其次是 RUSTLEARN_THEME 环境变量，默认使用 dark
+// 以下情况不输出任何颜色，保证重定向到文件或管道时得到纯文本：
+// 1. 设置了非空的 NO_COLOR 环境变量（参见 https://no-color.org）
+// 2. 标准输出不是终端，或者 TERM 是 dumb；设置了非空的 CLICOLOR_FORCE 时仍然输出颜色
+
+use std::env;
+use std::io::{self, IsTerminal};
+use std::sync::OnceLock;
+
+use crate::context::Style;
+use crate::highlight::TokenKind;
+use crate::i18n::{tr, tr_fmt};
+
+// 配色主题
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Theme {
+    // 不输出颜色
+    Plain,
+    Light,
+    Dark,
+    HighContrast,
+}
+
+impl Theme {
+    // 解析 --theme 参数或 RUSTLEARN_THEME 环境变量的值
+    pub fn parse(value: &str) -> Option<Theme> {
+        match value.to_ascii_lowercase().as_str() {
+            "light" => Some(Theme::Light),
+            "dark" => Some(Theme::Dark),
+            "high-contrast" => Some(Theme::HighContrast),
+            _ => None,
+        }
+    }
+
+    // 各类文字使用的 ANSI 颜色代码（SGR 参数），None 表示不着色
+    fn color(self, role: Role) -> Option<&'static str> {
+        use Role::*;
+        use TokenKind::*;
+
+        let code = match (self, role) {
+            (Theme::Plain, _) | (_, Token(Plain)) => return None,
+
+            (Theme::Light, LessonHeading) => "1;34",
+            (Theme::Light, SectionHeading) => "1;35",
+            (Theme::Light, Label | Gutter) => "90",
+            (Theme::Light, Token(Keyword)) => "1;34",
+            (Theme::Light, Token(Type)) => "36",
+            (Theme::Light, Token(Macro)) => "1;35",
+            (Theme::Light, Token(Lifetime)) => "35",
+            (Theme::Light, Token(Str)) => "32",
+            (Theme::Light, Token(Number)) => "31",
+            (Theme::Light, Token(Comment)) => "3;90",
+            (Theme::Light, Token(Attribute)) => "33",
+
+            (Theme::Dark, LessonHeading) => "1;96",
+            (Theme::Dark, SectionHeading) => "1;93",
+            (Theme::Dark, Label | Gutter) => "90",
+            (Theme::Dark, Token(Keyword)) => "1;95",
+            (Theme::Dark, Token(Type)) => "96",
+            (Theme::Dark, Token(Macro)) => "1;94",
+            (Theme::Dark, Token(Lifetime)) => "93",
+            (Theme::Dark, Token(Str)) => "92",
+            (Theme::Dark, Token(Number)) => "91",
+            (Theme::Dark, Token(Comment)) => "3;90",
+            (Theme::Dark, Token(Attribute)) => "33",
+
+            (Theme::HighContrast, LessonHeading) => "1;4;97",
+            (Theme::HighContrast, SectionHeading) => "1;97",
+            (Theme::HighContrast, Label | Gutter) => "97",
+            (Theme::HighContrast, Token(Keyword)) => "1;93",
+            (Theme::HighContrast, Token(Type)) => "1;96",
+            (Theme::HighContrast, Token(Macro)) => "1;95",
+            (Theme::HighContrast, Token(Lifetime)) => "1;91",
+            (Theme::HighContrast, Token(Str)) => "1;92",
+            (Theme::HighContrast, Token(Number)) => "1;94",
+            (Theme::HighContrast, Token(Comment)) => "97",
+            (Theme::HighContrast, Token(Attribute)) => "1;93",
+        };
+        Some(code)
+    }
+
+    // 给一段文字加上颜色
+    fn paint(self, role: Role, text: &str) -> String {
+        match self.color(role) {
+            Some(code) if !text.is_empty() => format!("\x1b[{}m{}\x1b[0m", code, text),
+            _ => text.to_string(),
+        }
+    }
+}
+
+// 需要着色的文字
+#[derive(Clone, Copy)]
+enum Role {
+    LessonHeading,
+    SectionHeading,
+    // "源代码（...）：" 和 "运行结果：" 这样的标签
+    Label,
+    // 源代码的行号栏
+    Gutter,
+    // 代码片段
+    Token(TokenKind),
+}
+
+impl Style for Theme {
+    fn lesson_heading(&self, text: &str) -> String {
+        self.paint(Role::LessonHeading, &format!("=== {} ===", text))
+    }
+
+    fn section_heading(&self, text: &str) -> String {
+        self.paint(Role::SectionHeading, &format!("--- {} ---", text))
+    }
+
+    fn label(&self, text: &str) -> String {
+        self.paint(Role::Label, text)
+    }
+
+    fn gutter(&self, text: &str) -> String {
+        self.paint(Role::Gutter, text)
+    }
+
+    fn code(&self, kind: TokenKind, text: &str) -> String {
+        self.paint(Role::Token(kind), text)
+    }
+}
+
+// 当前使用的主题，在程序启动时确定
+static THEME: OnceLock<Theme> = OnceLock::new();
+
+// 根据命令行参数和环境变量确定配色主题
+// 会从 args 中移除 --theme 参数，剩下的参数交给子命令处理
+pub fn init(args: &mut Vec<String>) -> Result<(), String> {
+    let mut requested = None;
+    let mut index = 0;
+
+    while index < args.len() {
+        if args[index] == "--theme" {
+            if index + 1 >= args.len() {
+                return Err(tr("--theme 需要一个主题名称，可以是 light、dark 或 high-contrast").into_owned());
+            }
+            requested = Some(args.remove(index + 1));
+            args.remove(index);
+        } else if let Some(value) = args[index].strip_prefix("--theme=") {
+            requested = Some(value.to_string());
+            args.remove(index);
+        } else {
+            index += 1;
+        }
+    }
+
+    let theme = match requested {
+        Some(value) => Theme::parse(&value)
+            .ok_or_else(|| tr_fmt("不支持的配色主题: {}，可以使用 light、dark 或 high-contrast", &[&value]))?,
+        // RUSTLEARN_THEME 中的主题不受支持时，使用默认的主题
+        None => env::var("RUSTLEARN_THEME")
+            .ok()
+            .and_then(|value| Theme::parse(&value))
+            .unwrap_or(Theme::Dark),
+    };
+
+    let theme = if colors_enabled() { theme } else { Theme::Plain };
+
+    // 程序启动时只会调用一次，重复设置时保留第一次的结果
+    let _ = THEME.set(theme);
+    Ok(())
+}
+
+// 当前使用的主题，没有初始化时不输出颜色
+pub fn current() -> &'static Theme {
+    THEME.get().unwrap_or(&Theme::Plain)
+}
+
+// 判断是否应该输出颜色
+fn colors_enabled() -> bool {
+    let set = |name: &str| env::var_os(name).is_some_and(|value| !value.is_empty());
+
+    if set("NO_COLOR") {
+        return false;
+    }
+    if set("CLICOLOR_FORCE") {
+        return true;
+    }
+    io::stdout().is_terminal() && env::var("TERM").ok().as_deref() != Some("dumb")
+}
@@ -0,0 +1,205 @@
+// 全屏终端界面
+// 与交互式菜单、命令行模式使用同一套课程注册表和课程内容，只是换了一种显示方式：
+// 左侧是课程和小节的目录，右侧上方显示选中内容的源代码，下方显示运行结果，两个窗格都可以滚动
+//
+// 按键：
+// ↑/↓ 或 k/j    在目录中移动，源代码窗格随之显示选中的课程或小节
+// 回车          运行选中的课程或小节
+// n/p           运行下一节/上一节
+// r             重新运行
+// Tab           切换可以滚动的窗格
+// PgUp/PgDn     滚动窗格，也可以使用 b/空格；g/G 滚动到开头/结尾
+// /             在窗格中搜索，回车查找下一处，Esc 取消
+// q、Ctrl-C     退出
+//
+// 不依赖任何第三方库：通过 stty 命令切换终端的原始模式、获取窗口大小，用 ANSI 转义序列绘制界面，
+// 所以只能在类 Unix 系统的终端中使用；界面的状态和绘制在 screen 模块中
+//
+// rustlearn tui --replay [<行数>x<列数>] 不需要终端：从标准输入读取按键，输出处理完所有按键之后的界面
+
+use std::io::{self, IsTerminal, Read, Write};
+use std::process::{Command, Stdio};
+
+use crate::cli;
+use crate::i18n::{tr, tr_fmt};
+use crate::progress::{self, Progress};
+use crate::screen::{self, App};
+
+// 没有按键时每隔多少次读取超时（每次 0.1 秒）重新获取一次窗口大小
+const RESIZE_INTERVAL: u32 = 5;
+
+// tui 子命令，返回进程退出码
+pub fn run_cli(args: &[String]) -> i32 {
+    match args {
+        [] => {}
+        [flag] if flag == "--replay" => return replay((24, 80)),
+        [flag, size] if flag == "--replay" => match parse_size(size) {
+            Some(size) => return replay(size),
+            None => {
+                eprintln!("{}", tr_fmt("无法识别的窗口大小: {}，应该写成 <行数>x<列数>，例如 24x80", &[size]));
+                return 2;
+            }
+        },
+        _ => {
+            eprintln!("{}", tr("用法: rustlearn tui [--replay [<行数>x<列数>]]"));
+            return 2;
+        }
+    }
+    if !io::stdin().is_terminal() || !io::stdout().is_terminal() {
+        eprintln!("{}", tr("全屏界面只能在终端中使用，不带参数运行 rustlearn 可以使用普通的菜单"));
+        return 1;
+    }
+
+    let progress = Progress::load_current_or_unsaved();
+
+    match run(&mut App::new(progress)) {
+        Ok(()) => 0,
+        Err(error) => {
+            eprintln!("{}", tr_fmt("全屏界面出错: {}", &[&error]));
+            1
+        }
+    }
+}
+
+// 解析 "24x80" 形式的窗口大小
+fn parse_size(text: &str) -> Option<(usize, usize)> {
+    let (rows, cols) = text.split_once('x')?;
+    Some((rows.parse().ok()?, cols.parse().ok()?))
+}
+
+// 不使用终端：从标准输入读取全部按键，依次处理之后输出最后的界面，不带颜色等转义序列
+// 从头开始，也不记录学习进度，用于测试和在文档中展示界面
+fn replay(size: (usize, usize)) -> i32 {
+    let mut input = Vec::new();
+    if let Err(error) = io::stdin().read_to_end(&mut input) {
+        eprintln!("{}", tr_fmt("无法读取输入: {}", &[&error]));
+        return 1;
+    }
+
+    let mut app = App::new(Progress::unsaved(&progress::current_user()));
+    // 和在终端中一样先绘制一次，翻页和滚动到结尾需要知道窗格的大小
+    app.render(size);
+    let mut keys = input.as_slice();
+    // 读到不认识的按键时跳过，直到所有输入都处理完
+    while !keys.is_empty() {
+        match screen::read_key(&mut keys) {
+            Ok(Some(key)) => {
+                if !app.handle(key) {
+                    break;
+                }
+            }
+            Ok(None) => {}
+            Err(error) => {
+                eprintln!("{}", tr_fmt("无法读取输入: {}", &[&error]));
+                return 1;
+            }
+        }
+    }
+
+    let mut text = String::new();
+    for line in app.render(size) {
+        text.push_str(screen::strip_escapes(&line).trim_end());
+        text.push('\n');
+    }
+    cli::print(&text)
+}
+
+// 进入全屏界面，直到按下 q 退出
+fn run(app: &mut App) -> io::Result<()> {
+    let _terminal = Terminal::enter()?;
+    let stdin = io::stdin();
+    let mut input = stdin.lock();
+
+    let mut size = terminal_size();
+    let mut idle = 0;
+    draw(app, size)?;
+
+    loop {
+        match screen::read_key(&mut input)? {
+            Some(key) => {
+                if !app.handle(key) {
+                    return Ok(());
+                }
+                size = terminal_size();
+                idle = 0;
+                draw(app, size)?;
+            }
+            // 没有按键时也要留意窗口大小的变化
+            None => {
+                idle += 1;
+                if idle >= RESIZE_INTERVAL {
+                    idle = 0;
+                    let current = terminal_size();
+                    if current != size {
+                        size = current;
+                        draw(app, size)?;
+                    }
+                }
+            }
+        }
+    }
+}
+
+// 清屏之后逐行绘制整个界面
+fn draw(app: &mut App, size: (usize, usize)) -> io::Result<()> {
+    let mut screen = String::from("\x1b[H\x1b[2J");
+    for (row, line) in app.render(size).iter().enumerate() {
+        screen.push_str(&format!("\x1b[{};1H{}", row + 1, line));
+    }
+    write_screen(&screen)
+}
+
+// 把画好的界面一次性写到终端
+fn write_screen(screen: &str) -> io::Result<()> {
+    let mut stdout = io::stdout().lock();
+    stdout.write_all(screen.as_bytes())?;
+    stdout.flush()
+}
+
+// 全屏界面使用期间的终端设置，离开时（包括出错和 panic 时）恢复原来的设置
+struct Terminal {
+    saved: String,
+}
+
+impl Terminal {
+    fn enter() -> io::Result<Terminal> {
+        let saved = stty(&["-g"])?.trim().to_string();
+        // 原始模式：按键不回显、不需要回车；每次读取最多等待 0.1 秒，用来区分单独的 Esc 和留意窗口大小的变化
+        stty(&["raw", "-echo", "min", "0", "time", "1"])?;
+        // 切换到备用屏幕并隐藏光标，退出后终端中原来的内容保持不变
+        write_screen("\x1b[?1049h\x1b[?25l")?;
+        Ok(Terminal { saved })
+    }
+}
+
+impl Drop for Terminal {
+    fn drop(&mut self) {
+        let _ = write_screen("\x1b[0m\x1b[?25h\x1b[?1049l");
+        let _ = stty(&[&self.saved]);
+    }
+}
+
+// 以当前终端作为标准输入运行 stty，返回它的输出
+fn stty(args: &[&str]) -> io::Result<String> {
+    let output = Command::new("stty")
+        .args(args)
+        .stdin(Stdio::inherit())
+        .stderr(Stdio::null())
+        .output()?;
+    if !output.status.success() {
+        return Err(io::Error::other(tr_fmt("stty {} 执行失败", &[&args.join(" ")])));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+// 终端窗口的大小（行数，列数），获取不到时使用 24 行 80 列
+fn terminal_size() -> (usize, usize) {
+    stty(&["size"])
+        .ok()
+        .and_then(|size| {
+            let mut numbers = size.split_whitespace().map(|number| number.parse::<usize>().ok());
+            Some((numbers.next()??, numbers.next()??))
+        })
+        .filter(|(rows, cols)| *rows > 0 && *cols > 0)
+        .unwrap_or((24, 80))
+}
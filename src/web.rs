@@ -0,0 +1,554 @@
+// 本地网页界面
+// `rustlearn serve` 在 127.0.0.1 上启动一个简单的 HTTP 服务器，用浏览器学习课程：
+// 1. /                       课程列表，显示每一课的学习进度
+// 2. /lessons/<课程编号>              某一课的简介、知识点和小节列表
+// 3. /lessons/<课程编号>/<小节标识>     小节的源代码和运行结果，以及 "标记为已学完" 按钮
+// 4. POST /lessons/<课程编号>/<小节标识>/complete   按下按钮时把这一节记录到学习进度中，然后回到小节页面
+//
+// 页面内容全部来自课程注册表，和菜单、命令行模式看到的完全一样；
+// 样式直接写在页面中，不引用任何外部的脚本、字体或 CDN，断网时也能正常使用
+// 服务器只监听本机地址，依次处理每个请求，只支持 GET、HEAD 和 POST：
+// 读取页面不会修改学习进度，浏览器预取、刷新页面或者 HEAD 请求都不会把小节记为已学完
+//
+// 其他网站上的页面也能向本机地址发请求，所以：
+// 1. Host 必须是 127.0.0.1:<端口> 或 localhost:<端口>，防止通过 DNS 重绑定把页面当作别的网站读取
+// 2. POST 请求带有 Origin 时必须和 Host 同源，防止别的网站用表单修改学习进度
+// 3. 请求行、请求头和正文都有长度限制，超过时直接拒绝
+
+use std::io::{self, BufRead, BufReader, ErrorKind, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::time::Duration;
+
+use crate::context::{LessonContext, Style};
+use crate::highlight::TokenKind;
+use crate::i18n::{self, tr, tr_fmt};
+use crate::lesson::{self, Lesson, Section};
+use crate::progress::Progress;
+use crate::source;
+
+// 默认监听的端口
+const DEFAULT_PORT: u16 = 7878;
+
+// 读取请求的超时时间，避免一个没有发完请求的连接卡住服务器
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+// 请求行和每一行请求头的最大长度（字节）
+const MAX_LINE: usize = 8 * 1024;
+// 请求头的最大行数
+const MAX_HEADERS: usize = 64;
+// 请求正文的最大长度，这里的 POST 请求用不到正文，读完丢弃
+const MAX_BODY: u64 = 64 * 1024;
+
+// 页面中的样式
+const CSS: &str = "
+body { margin: 0; font-family: sans-serif; line-height: 1.6; color: #1f2328; background: #ffffff; }
+header { padding: 0.6em 1.5em; background: #b7410e; }
+header a { color: #ffffff; font-weight: bold; text-decoration: none; }
+main { max-width: 60em; margin: 0 auto; padding: 1em 1.5em 3em; }
+a { color: #0550ae; }
+nav.pager { display: flex; justify-content: space-between; margin: 1.5em 0; }
+pre { padding: 0.8em 1em; overflow-x: auto; border-radius: 6px; background: #f6f8fa; line-height: 1.45; }
+progress { width: 8em; vertical-align: middle; }
+ol.sections li, ul.lessons li { margin: 0.3em 0; }
+.done { color: #1a7f37; }
+form.complete button { font: inherit; padding: 0.3em 1em; cursor: pointer; }
+.muted { color: #656d76; }
+.gutter, .label { color: #8c959f; }
+.keyword { color: #cf222e; font-weight: bold; }
+.type { color: #953800; }
+.macro { color: #8250df; }
+.lifetime { color: #0a3069; font-style: italic; }
+.string { color: #0a3069; }
+.number { color: #0550ae; }
+.comment { color: #6e7781; font-style: italic; }
+.attribute { color: #116329; }
+@media (prefers-color-scheme: dark) {
+  body { color: #e6edf3; background: #0d1117; }
+  a { color: #4493f8; }
+  pre { background: #161b22; }
+  .done { color: #3fb950; }
+  .muted, .gutter, .label { color: #7d8590; }
+  .keyword { color: #ff7b72; }
+  .type { color: #ffa657; }
+  .macro { color: #d2a8ff; }
+  .lifetime, .string { color: #a5d6ff; }
+  .number { color: #79c0ff; }
+  .comment { color: #8b949e; }
+  .attribute { color: #7ee787; }
+}
+";
+
+// serve 子命令，返回进程退出码
+pub fn run_cli(args: &[String]) -> i32 {
+    let port = match args {
+        [] => DEFAULT_PORT,
+        [flag, port] if flag == "--port" => match port.parse() {
+            Ok(port) => port,
+            Err(_) => {
+                eprintln!("{}", tr_fmt("无效的端口号: {}", &[port]));
+                return 2;
+            }
+        },
+        _ => {
+            eprintln!("{}", tr("用法: rustlearn serve [--port <端口号>]"));
+            return 2;
+        }
+    };
+
+    let listener = match TcpListener::bind(("127.0.0.1", port)) {
+        Ok(listener) => listener,
+        Err(error) => {
+            eprintln!("{}", tr_fmt("无法监听端口 {}: {}", &[&port, &error]));
+            return 1;
+        }
+    };
+
+    let mut progress = Progress::load_current_or_unsaved();
+
+    // 端口号为 0 时由系统分配，显示实际使用的地址
+    match listener.local_addr() {
+        Ok(address) => println!("{}", tr_fmt("在浏览器中打开 http://{}/ ，按 Ctrl-C 停止", &[&address])),
+        Err(error) => {
+            eprintln!("{}", tr_fmt("无法监听端口 {}: {}", &[&port, &error]));
+            return 1;
+        }
+    }
+    let _ = io::stdout().flush();
+
+    for stream in listener.incoming() {
+        // 单个连接出错不影响后续的请求
+        let result = stream.and_then(|stream| handle(stream, &mut progress));
+        if let Err(error) = result {
+            eprintln!("{}", tr_fmt("处理请求时出错: {}", &[&error]));
+        }
+    }
+    0
+}
+
+// 一个 HTTP 请求中用到的部分
+struct Request {
+    method: String,
+    target: String,
+    host: Option<String>,
+    origin: Option<String>,
+}
+
+// 一个 HTTP 响应
+struct Response {
+    status: &'static str,
+    // 重定向的目标地址
+    location: Option<String>,
+    body: String,
+}
+
+impl Response {
+    fn ok(body: String) -> Response {
+        Response { status: "200 OK", location: None, body }
+    }
+
+    // 处理完 POST 请求之后让浏览器重新 GET 页面，刷新时不会重复提交
+    fn see_other(location: String) -> Response {
+        Response { status: "303 See Other", location: Some(location), body: String::new() }
+    }
+
+    fn not_found() -> Response {
+        let body = page(&tr("页面不存在"), &format!("<h1>{}</h1>", escape(&tr("页面不存在"))));
+        Response { status: "404 Not Found", location: None, body }
+    }
+
+    fn method_not_allowed() -> Response {
+        Response::error("405 Method Not Allowed")
+    }
+
+    // 拒绝请求，没有正文
+    fn error(status: &'static str) -> Response {
+        Response { status, location: None, body: String::new() }
+    }
+}
+
+// 处理一个连接中的请求
+fn handle(stream: TcpStream, progress: &mut Progress) -> io::Result<()> {
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    let port = stream.local_addr()?.port();
+    let mut reader = BufReader::new(&stream);
+
+    let request = match read_request(&mut reader)? {
+        Ok(request) => request,
+        Err(response) => return respond(&stream, &response, false),
+    };
+
+    let host = request.host.as_deref().unwrap_or_default();
+    let same_origin = |origin: &String| origin.eq_ignore_ascii_case(&format!("http://{}", host));
+    // 忽略查询字符串
+    let path = request.target.split(['?', '#']).next().unwrap_or_default();
+
+    // Host 必须是本机地址；POST 请求没有 Origin（例如 curl）或者 Origin 同源时才会修改学习进度
+    let trusted = is_local_host(host, port)
+        && (request.method != "POST" || request.origin.as_ref().is_none_or(same_origin));
+
+    let response = if !trusted {
+        Response::error("403 Forbidden")
+    } else {
+        match request.method.as_str() {
+            "GET" | "HEAD" => route(path, progress),
+            "POST" => complete(path, progress),
+            _ => Response::method_not_allowed(),
+        }
+    };
+
+    respond(&stream, &response, request.method == "HEAD")
+}
+
+// 读取请求行和请求头，再读完正文
+// 请求格式不正确或者超过长度限制时返回要发送的错误响应
+fn read_request(reader: &mut impl BufRead) -> io::Result<Result<Request, Response>> {
+    let Some(request_line) = read_line(reader)? else {
+        return Ok(Err(Response::error("400 Bad Request")));
+    };
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_string();
+    let target = parts.next().unwrap_or_default().to_string();
+
+    let mut request = Request { method, target, host: None, origin: None };
+    let mut length = 0;
+    let mut headers = 0;
+    loop {
+        let Some(line) = read_line(reader)? else {
+            return Ok(Err(Response::error("431 Request Header Fields Too Large")));
+        };
+        if line.is_empty() {
+            break;
+        }
+        headers += 1;
+        if headers > MAX_HEADERS {
+            return Ok(Err(Response::error("431 Request Header Fields Too Large")));
+        }
+
+        let Some((name, value)) = line.split_once(':') else {
+            return Ok(Err(Response::error("400 Bad Request")));
+        };
+        let value = value.trim();
+        if name.eq_ignore_ascii_case("host") {
+            request.host = Some(value.to_string());
+        } else if name.eq_ignore_ascii_case("origin") {
+            request.origin = Some(value.to_string());
+        } else if name.eq_ignore_ascii_case("content-length") {
+            match value.parse() {
+                Ok(value) => length = value,
+                Err(_) => return Ok(Err(Response::error("400 Bad Request"))),
+            }
+        }
+    }
+
+    if length > MAX_BODY {
+        return Ok(Err(Response::error("413 Payload Too Large")));
+    }
+    // 正文对这里的页面没有影响，读完丢弃
+    if io::copy(&mut reader.take(length), &mut io::sink())? < length {
+        return Err(ErrorKind::UnexpectedEof.into());
+    }
+
+    Ok(Ok(request))
+}
+
+// 读取一行，去掉行尾的换行符；超过 MAX_LINE 时返回 None，连接已经关闭时返回空行
+fn read_line(reader: &mut impl BufRead) -> io::Result<Option<String>> {
+    let mut line = Vec::new();
+    reader.take(MAX_LINE as u64 + 1).read_until(b'\n', &mut line)?;
+    if line.len() > MAX_LINE {
+        return Ok(None);
+    }
+    Ok(Some(String::from_utf8_lossy(&line).trim_end_matches(['\r', '\n']).to_string()))
+}
+
+// Host 是否是本机地址和服务器实际监听的端口
+fn is_local_host(host: &str, port: u16) -> bool {
+    let Some((name, host_port)) = host.rsplit_once(':') else {
+        return false;
+    };
+    (name == "127.0.0.1" || name.eq_ignore_ascii_case("localhost")) && host_port == port.to_string()
+}
+
+// 发送响应，HEAD 请求只发送响应头
+fn respond(mut stream: &TcpStream, response: &Response, head: bool) -> io::Result<()> {
+    write!(stream, "HTTP/1.1 {}\r\n", response.status)?;
+    if let Some(location) = &response.location {
+        write!(stream, "Location: {}\r\n", location)?;
+    }
+    write!(
+        stream,
+        "Content-Type: text/html; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        response.body.len()
+    )?;
+    if !head {
+        stream.write_all(response.body.as_bytes())?;
+    }
+    stream.flush()
+}
+
+// 根据路径生成页面，不会修改学习进度
+fn route(path: &str, progress: &Progress) -> Response {
+    let segments: Vec<&str> = path.split('/').filter(|segment| !segment.is_empty()).collect();
+
+    match segments.as_slice() {
+        [] => Response::ok(index_page(progress)),
+        ["lessons", id] => match lesson::find(id) {
+            Some(lesson) => Response::ok(lesson_page(lesson, progress)),
+            None => Response::not_found(),
+        },
+        ["lessons", id, section_id] => {
+            let Some(lesson) = lesson::find(id) else {
+                return Response::not_found();
+            };
+            match lesson.sections().iter().position(|section| section.id == *section_id) {
+                Some(index) => Response::ok(section_page(lesson, index, progress)),
+                None => Response::not_found(),
+            }
+        }
+        _ => Response::not_found(),
+    }
+}
+
+// 首页：课程列表和学习进度
+fn index_page(progress: &Progress) -> String {
+    let mut body = format!("<h1>{}</h1>\n", escape(&tr("Rust 学习示例程序")));
+
+    if let Some((lesson, index)) = progress.resume_point() {
+        let section = &lesson.sections()[index];
+        body.push_str(&format!(
+            "<p><a href=\"{}\">{}</a></p>\n",
+            section_url(lesson, section),
+            escape(&tr_fmt(
+                "从上次离开的地方继续（第{}课 第{}节：{}）",
+                &[&lesson.id(), &(index + 1), &tr(section.title)]
+            ))
+        ));
+    }
+
+    body.push_str("<ul class=\"lessons\">\n");
+    for lesson in lesson::LESSONS {
+        body.push_str(&format!(
+            "<li><a href=\"{}\">{}</a> {}</li>\n",
+            lesson_url(*lesson),
+            escape(&tr_fmt("第{}课：{}", &[&lesson.id(), &tr(lesson.title())])),
+            progress_indicator(*lesson, progress)
+        ));
+    }
+    body.push_str("</ul>\n");
+
+    page(&tr("Rust 学习示例程序"), &body)
+}
+
+// 课程页面：简介、知识点和小节列表
+fn lesson_page(lesson: &dyn Lesson, progress: &Progress) -> String {
+    let title = tr_fmt("第{}课：{}", &[&lesson.id(), &tr(lesson.title())]);
+    let mut body = format!(
+        "<h1>{}</h1>\n<p>{} {}</p>\n<p>{}</p>\n",
+        escape(&title),
+        escape(&tr("学习进度")),
+        progress_indicator(lesson, progress),
+        escape(&tr(lesson.summary()))
+    );
+
+    body.push_str(&format!("<h2>{}</h2>\n<ul>\n", escape(&tr("知识点"))));
+    for topic in lesson.topics() {
+        body.push_str(&format!("<li>{}</li>\n", escape(&tr(topic))));
+    }
+    body.push_str("</ul>\n");
+
+    body.push_str(&format!("<h2>{}</h2>\n<ol class=\"sections\">\n", escape(&tr("小节"))));
+    for section in lesson.sections() {
+        body.push_str(&format!(
+            "<li><a href=\"{}\">{}</a>{}</li>\n",
+            section_url(lesson, section),
+            escape(&tr(section.title)),
+            done_mark(progress.is_section_completed(lesson, section.id))
+        ));
+    }
+    body.push_str("</ol>\n");
+
+    // 前后两课
+    let position = lesson::LESSONS.iter().position(|item| item.id() == lesson.id()).unwrap_or(0);
+    let previous = position.checked_sub(1).map(|index| lesson::LESSONS[index]);
+    let next = lesson::LESSONS.get(position + 1).copied();
+    body.push_str(&pager(
+        previous.map(|lesson| (lesson_url(lesson), tr_fmt("第{}课：{}", &[&lesson.id(), &tr(lesson.title())]))),
+        next.map(|lesson| (lesson_url(lesson), tr_fmt("第{}课：{}", &[&lesson.id(), &tr(lesson.title())]))),
+    ));
+
+    page(&title, &body)
+}
+
+// 把小节记录到学习进度中，只接受 POST /lessons/<课程编号>/<小节标识>/complete
+fn complete(path: &str, progress: &mut Progress) -> Response {
+    let segments: Vec<&str> = path.split('/').filter(|segment| !segment.is_empty()).collect();
+    let ["lessons", id, section_id, "complete"] = segments.as_slice() else {
+        return Response::method_not_allowed();
+    };
+    let Some(lesson) = lesson::find(id) else {
+        return Response::not_found();
+    };
+    let Some(section) = lesson.sections().iter().find(|section| section.id == *section_id) else {
+        return Response::not_found();
+    };
+
+    progress.complete_section(lesson, section.id);
+    if let Err(error) = progress.save() {
+        eprintln!("{}", tr_fmt("无法保存学习进度: {}", &[&error]));
+    }
+    Response::see_other(section_url(lesson, section))
+}
+
+// 小节页面：运行小节，显示源代码和运行结果，学完之后可以按按钮记录学习进度
+fn section_page(lesson: &dyn Lesson, index: usize, progress: &Progress) -> String {
+    let sections = lesson.sections();
+    let section = &sections[index];
+
+    let mut buffer = Vec::new();
+    let mut ctx = LessonContext::new(&mut buffer);
+    (section.run)(&mut ctx);
+    // 写入内存缓冲区不会失败
+    let _ = ctx.finish();
+    let output = String::from_utf8_lossy(&buffer);
+
+    let lesson_title = tr_fmt("第{}课：{}", &[&lesson.id(), &tr(lesson.title())]);
+    let mut body = format!(
+        "<p><a href=\"{}\">{}</a> {}</p>\n<h1>{}</h1>\n",
+        lesson_url(lesson),
+        escape(&lesson_title),
+        progress_indicator(lesson, progress),
+        escape(&tr_fmt("第 {}/{} 节：{}", &[&(index + 1), &sections.len(), &tr(section.title)]))
+    );
+
+    if let Some(code) = source::render(lesson, section, &HtmlStyle) {
+        body.push_str(&format!("<h2>{}</h2>\n<pre class=\"code\">{}</pre>\n", escape(&tr("源代码")), code));
+    }
+    body.push_str(&format!(
+        "<h2>{}</h2>\n<pre class=\"output\">{}</pre>\n",
+        escape(&tr("运行结果")),
+        escape(output.trim_start_matches('\n'))
+    ));
+
+    if progress.is_section_completed(lesson, section.id) {
+        body.push_str(&format!("<p class=\"done\">✓ {}</p>\n", escape(&tr("已经学完这一节"))));
+    } else {
+        body.push_str(&format!(
+            "<form class=\"complete\" method=\"post\" action=\"{}/complete\"><button type=\"submit\">{}</button></form>\n",
+            section_url(lesson, section),
+            escape(&tr("标记为已学完"))
+        ));
+    }
+
+    let link = |section: &Section| (section_url(lesson, section), tr(section.title).into_owned());
+    body.push_str(&pager(
+        index.checked_sub(1).map(|index| link(&sections[index])),
+        sections.get(index + 1).map(link),
+    ));
+
+    page(&tr(section.title), &body)
+}
+
+// 完整的 HTML 页面
+fn page(title: &str, body: &str) -> String {
+    format!(
+        "<!DOCTYPE html>
+<html lang=\"{}\">
+<head>
+<meta charset=\"utf-8\">
+<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">
+<title>{}</title>
+<style>{}</style>
+</head>
+<body>
+<header><a href=\"/\">{}</a></header>
+<main>
+{}</main>
+</body>
+</html>
+",
+        i18n::current().code(),
+        escape(title),
+        CSS,
+        escape(&tr("Rust 学习示例程序")),
+        body
+    )
+}
+
+// 上一页和下一页的链接
+fn pager(previous: Option<(String, String)>, next: Option<(String, String)>) -> String {
+    let link = |target: Option<(String, String)>, arrow: &str| match target {
+        Some((url, title)) if arrow == "←" => format!("<a href=\"{}\">← {}</a>", url, escape(&title)),
+        Some((url, title)) => format!("<a href=\"{}\">{} →</a>", url, escape(&title)),
+        None => String::from("<span></span>"),
+    };
+    format!("<nav class=\"pager\">{}{}</nav>\n", link(previous, "←"), link(next, "→"))
+}
+
+// 一课的完成进度，例如 "3/8"，全部学完时打上 ✓
+fn progress_indicator(lesson: &dyn Lesson, progress: &Progress) -> String {
+    let done = progress.completed_sections(lesson);
+    let total = lesson.sections().len();
+    format!(
+        "<progress value=\"{}\" max=\"{}\"></progress> <span class=\"muted\">{}/{}</span>{}",
+        done,
+        total,
+        done,
+        total,
+        done_mark(done == total)
+    )
+}
+
+fn done_mark(done: bool) -> &'static str {
+    if done { " <span class=\"done\">✓</span>" } else { "" }
+}
+
+fn lesson_url(lesson: &dyn Lesson) -> String {
+    format!("/lessons/{}", lesson.id())
+}
+
+fn section_url(lesson: &dyn Lesson, section: &Section) -> String {
+    format!("/lessons/{}/{}", lesson.id(), section.id)
+}
+
+// 转义 HTML 中的特殊字符
+pub fn escape(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => result.push_str("&amp;"),
+            '<' => result.push_str("&lt;"),
+            '>' => result.push_str("&gt;"),
+            '"' => result.push_str("&quot;"),
+            _ => result.push(c),
+        }
+    }
+    result
+}
+
+// 把源代码输出成带 class 的 HTML，颜色由页面中的样式决定
+struct HtmlStyle;
+
+impl Style for HtmlStyle {
+    fn label(&self, text: &str) -> String {
+        format!("<span class=\"label\">{}</span>", escape(text))
+    }
+
+    fn gutter(&self, text: &str) -> String {
+        format!("<span class=\"gutter\">{}</span>", escape(text))
+    }
+
+    fn code(&self, kind: TokenKind, text: &str) -> String {
+        let class = match kind {
+            TokenKind::Plain => return escape(text),
+            TokenKind::Keyword => "keyword",
+            TokenKind::Type => "type",
+            TokenKind::Macro => "macro",
+            TokenKind::Lifetime => "lifetime",
+            TokenKind::Str => "string",
+            TokenKind::Number => "number",
+            TokenKind::Comment => "comment",
+            TokenKind::Attribute => "attribute",
+        };
+        format!("<span class=\"{}\">{}</span>", class, escape(text))
+    }
+}
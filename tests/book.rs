@@ -0,0 +1,126 @@
+// 书籍导出和 README 课程列表的测试
+// 检查导出的书籍包含所有课程和小节、目录和页面之间的链接都指向存在的文件，
+// 以及仓库中的 README 与课程注册表保持一致
+
+mod common;
+
+use std::env;
+use std::fs;
+use std::path::Path;
+
+use common::{rustlearn, temp_path};
+
+// Markdown 中所有相对链接的目标
+fn links(markdown: &str) -> Vec<String> {
+    markdown
+        .split("](")
+        .skip(1)
+        .filter_map(|rest| rest.split(')').next())
+        .filter(|target| !target.starts_with("http"))
+        .map(str::to_string)
+        .collect()
+}
+
+// 检查一个页面中的链接都指向存在的文件，返回检查过的链接数
+fn check_links(page: &Path) -> usize {
+    let content = fs::read_to_string(page).unwrap();
+    let targets = links(&content);
+    for target in &targets {
+        let path = page.parent().unwrap().join(target);
+        assert!(path.is_file(), "{} 中的链接 {} 指向不存在的文件", page.display(), target);
+    }
+    targets.len()
+}
+
+#[test]
+fn book_contains_every_lesson_and_section() {
+    let dir = temp_path("all");
+    let output = rustlearn(&["book", "--out", dir.to_str().unwrap()]);
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains("个页面"));
+
+    assert!(fs::read_to_string(dir.join("book.toml")).unwrap().contains("language = \"zh\""));
+
+    let summary = fs::read_to_string(dir.join("src/SUMMARY.md")).unwrap();
+    for lesson in 1..=10 {
+        assert!(summary.contains(&format!("(lesson-{:02}/index.md)", lesson)), "目录中缺少第{}课", lesson);
+    }
+    assert!(summary.contains("  - [错误传播](lesson-08/error_propagation.md)"));
+
+    // 目录中的每一个页面都存在，每一个页面中的链接也都指向存在的页面
+    let pages = links(&summary);
+    assert!(pages.len() > 50);
+    check_links(&dir.join("src/SUMMARY.md"));
+    let mut checked = 0;
+    for page in &pages {
+        checked += check_links(&dir.join("src").join(page));
+    }
+    assert!(checked > pages.len());
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn section_page_has_code_and_real_output() {
+    let dir = temp_path("section");
+    assert!(rustlearn(&["book", "--out", dir.to_str().unwrap()]).status.success());
+
+    let page = fs::read_to_string(dir.join("src/lesson-08/custom_error_types.md")).unwrap();
+    assert!(page.starts_with("# 自定义错误类型\n"));
+    assert!(page.contains("第 5/10 节"));
+    assert!(page.contains("```rust\n// 演示自定义错误类型\nfn custom_error_types(ctx: &mut LessonContext) {"));
+    // 运行结果是实际运行得到的，而不是源代码中的文字
+    assert!(page.contains("## 运行结果\n\n```text\n--- 自定义错误类型 ---\n"));
+    assert!(page.contains("处理失败: ParseError(ParseIntError { kind: InvalidDigit })"));
+    // 最后一节的下一节是下一课的第一节
+    let last = fs::read_to_string(dir.join("src/lesson-08/error_libraries.md")).unwrap();
+    assert!(last.contains("(../lesson-09/"));
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn book_follows_interface_language() {
+    let dir = temp_path("en");
+    assert!(rustlearn(&["--lang", "en", "book", "--out", dir.to_str().unwrap()]).status.success());
+
+    let summary = fs::read_to_string(dir.join("src/SUMMARY.md")).unwrap();
+    assert!(summary.starts_with("# Summary\n"));
+    assert!(summary.contains("[Lesson 8: Error Handling](lesson-08/index.md)"));
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn repository_readme_is_up_to_date() {
+    let readme = Path::new(env!("CARGO_MANIFEST_DIR")).join("README.md");
+    let output = rustlearn(&["readme", "--check", readme.to_str().unwrap()]);
+    assert!(
+        output.status.success(),
+        "README 中的课程列表需要更新，请运行 cargo run -- readme：{}",
+        String::from_utf8_lossy(&output.stdout)
+    );
+}
+
+#[test]
+fn readme_regenerates_stale_table() {
+    let dir = temp_path("readme");
+    fs::create_dir_all(&dir).unwrap();
+    let readme = dir.join("README.md");
+    let original = fs::read_to_string(Path::new(env!("CARGO_MANIFEST_DIR")).join("README.md")).unwrap();
+    fs::write(&readme, original.replace("| 8. 错误处理", "| 8. 旧的标题")).unwrap();
+
+    let check = rustlearn(&["readme", "--check", readme.to_str().unwrap()]);
+    assert_eq!(check.status.code(), Some(1));
+    assert!(fs::read_to_string(&readme).unwrap().contains("旧的标题"));
+
+    assert!(rustlearn(&["readme", readme.to_str().unwrap()]).status.success());
+    assert_eq!(fs::read_to_string(&readme).unwrap(), original);
+
+    // 没有标记的文件不会被修改
+    fs::write(&readme, "# 标题\n").unwrap();
+    assert_eq!(rustlearn(&["readme", readme.to_str().unwrap()]).status.code(), Some(1));
+    assert_eq!(fs::read_to_string(&readme).unwrap(), "# 标题\n");
+
+    let _ = fs::remove_dir_all(&dir);
+}
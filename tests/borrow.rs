@@ -0,0 +1,211 @@
+// 借用检查模拟器的测试
+// 检查内置示例的错误代码和时间线、和 rustc 的对照、从标准输入读取代码、解析错误的处理以及英文输出
+
+mod common;
+
+use std::process::Output;
+
+use common::{command, run_with_input, rustlearn, stdout};
+
+// 从标准输入模拟检查一段代码
+fn simulate(code: &str) -> Output {
+    run_with_input(&mut command(&["borrow", "-"]), code.as_bytes())
+}
+
+// 报告中的错误代码
+fn codes(text: &str) -> Vec<&str> {
+    text.lines().filter_map(|line| line.strip_prefix("error[")).map(|line| &line[..5]).collect()
+}
+
+#[test]
+fn borrow_check_agrees_with_rustc() {
+    let output = rustlearn(&["borrow", "--check"]);
+    assert!(output.status.success(), "{}", stdout(&output));
+    assert!(stdout(&output).contains("✓ push_while_borrowed"));
+    assert!(!stdout(&output).contains("✗"));
+}
+
+#[test]
+fn lists_examples_with_their_codes() {
+    let output = rustlearn(&["borrow"]);
+    assert!(output.status.success());
+    let text = stdout(&output);
+    assert!(text.contains("  nll                    ✓      引用在最后一次使用之后就不再借用\n"));
+    assert!(text.contains("  two_mutable_borrows    E0499  同时存在两个可变借用\n"));
+}
+
+#[test]
+fn non_lexical_lifetimes_end_at_the_last_use() {
+    let output = rustlearn(&["borrow", "--example", "nll"]);
+    assert!(output.status.success());
+    let text = stdout(&output);
+
+    assert!(text.starts_with("=== 引用在最后一次使用之后就不再借用 ===\n相关小节：第10课 第1节：生命周期的基本概念（rustlearn run 10 --section lifetimes_basics）\n"));
+    assert!(text.contains(" 2  let r1 = &s;                                  ┬\n"));
+    assert!(text.contains(" 4  println!(\"{} and {}\", r1, r2);                ┴   ┴\n"));
+    assert!(text.contains(" 6  let r3 = &mut s;                                      ┬\n"));
+    assert!(text.contains("  r3  &mut s（可变借用）：第 6 行到第 8 行\n"));
+    assert!(text.ends_with("✓ 借用检查通过，没有发现错误\n"));
+}
+
+#[test]
+fn push_while_borrowed_is_reported_like_rustc() {
+    let text = stdout(&rustlearn(&["borrow", "--example", "push_while_borrowed"]));
+
+    assert!(text.contains(" 3  v.push(6);                           │      ✗ E0502\n"));
+    assert!(text.contains(
+        "error[E0502]: 不能把 `v` 借用为可变的，因为它同时被借用为不可变的
+ --> push_while_borrowed:3:1
+  |
+2 | let first = &v[0];
+  |             ----- 不可变借用发生在这里
+3 | v.push(6);
+  | ^^^^^^^^^ 可变借用发生在这里
+4 | println!(\"第一个元素是 {}\", first);
+  |                             ----- 不可变借用之后在这里被使用
+"
+    ));
+    assert!(text.ends_with("✗ 发现 1 个错误\n"));
+}
+
+#[test]
+fn later_uses_are_labelled_on_the_receiver() {
+    // 通过引用调用方法时，标注的是引用本身而不是整个调用
+    let text = stdout(&rustlearn(&["borrow", "--example", "two_mutable_borrows"]));
+    assert!(text.contains(
+        "3 | let r2 = &mut s;
+  |          ^^^^^^ 第二次可变借用发生在这里
+4 | r1.push_str(\" world\");
+  | -- 第一次借用之后在这里被使用
+"
+    ));
+
+    // Vector 中保存的借用在调用 push 时被使用，标注在 Vector 上
+    let text = stdout(&simulate(
+        "let mut s = String::new();
+let mut a = Vec::new();
+a.push(&mut s);
+let r = &mut s;
+a.push(r);
+",
+    ));
+    assert!(text.contains(
+        "4 | let r = &mut s;
+  |         ^^^^^^ 第二次可变借用发生在这里
+5 | a.push(r);
+  | - 第一次借用之后在这里被使用
+"
+    ));
+}
+
+#[test]
+fn every_example_reports_its_codes() {
+    for (name, code) in [
+        ("two_mutable_borrows", "E0499"),
+        ("references_in_vec", "E0502"),
+        ("move_while_borrowed", "E0505"),
+        ("use_after_move", "E0382"),
+        ("dangling_reference", "E0597"),
+        ("assign_while_borrowed", "E0506"),
+        ("missing_mut", "E0596"),
+    ] {
+        let output = rustlearn(&["borrow", "--example", name]);
+        assert!(output.status.success(), "{}", name);
+        assert_eq!(codes(&stdout(&output)), [code], "{}", name);
+    }
+}
+
+#[test]
+fn references_stored_in_a_vector_are_held_by_the_vector() {
+    let text = stdout(&rustlearn(&["borrow", "--example", "references_in_vec"]));
+    assert!(text.contains("  v_refs  &s3（共享借用）：第 3 行到第 5 行\n"));
+}
+
+#[test]
+fn ownership_moves_are_listed() {
+    let text = stdout(&rustlearn(&["borrow", "--example", "use_after_move"]));
+    assert!(text.contains("所有权的转移：\n  第 2 行：`s1` 的所有权转移给了 `s2`\n  第 4 行：`s2` 的所有权转移给了 push()\n"));
+    assert!(text.contains("  = 注意：移动发生是因为 `s1` 的类型没有实现 Copy trait\n"));
+}
+
+#[test]
+fn reads_code_from_stdin() {
+    let output = simulate(
+        "fn main() {
+    let mut v = vec![String::from(\"a\")];
+    let last = v.last().unwrap();
+    v.clear();
+    let s = String::from(\"b\");
+    let t = s;
+    println!(\"{last} {s}\");
+}
+",
+    );
+    // 发现借用错误时退出码是 1
+    assert_eq!(output.status.code(), Some(1));
+    let text = stdout(&output);
+    assert_eq!(codes(&text), ["E0502", "E0382"]);
+    assert!(text.contains(" --> <stdin>:4:5\n"));
+    // 格式字符串中的变量标注在变量名上
+    assert!(text.contains("7 |     println!(\"{last} {s}\");\n  |                       ^ 值在移动之后在这里被借用\n"));
+}
+
+#[test]
+fn two_phase_borrows_and_reborrows_pass() {
+    let output = simulate(
+        "let mut v = Vec::new();
+v.push(v.len());
+let mut s = String::new();
+let a = &mut s;
+let b = &mut *a;
+b.push('1');
+a.push('2');
+",
+    );
+    let text = stdout(&output);
+    assert!(output.status.success());
+    assert!(text.ends_with("✓ 借用检查通过，没有发现错误\n"), "{}", text);
+}
+
+#[test]
+fn state_errors_are_reported() {
+    let text = stdout(&simulate(
+        "let x = 5;
+x = 6;
+let r = &x;
+*r = 7;
+println!(\"{}\", y);
+",
+    ));
+    assert_eq!(codes(&text), ["E0384", "E0594", "E0425"]);
+}
+
+#[test]
+fn parse_errors_are_usage_errors() {
+    for (code, message) in [
+        ("let x = 1\n", "第 1 行：应该是 \";\"，但是代码已经结束了"),
+        ("let y = §;\n", "第 1 行：无法识别的字符：§"),
+        ("{\nlet a = 1;\n", "第 2 行：缺少 }"),
+        ("let p = point.x;\n", "第 1 行：暂不支持字段访问，只能调用方法"),
+    ] {
+        let output = simulate(code);
+        assert_eq!(output.status.code(), Some(2), "{}", code);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(stderr.contains(&format!("无法解析代码：{}", message)), "{}: {}", code, stderr);
+    }
+
+    assert_eq!(rustlearn(&["borrow", "--example", "nope"]).status.code(), Some(2));
+    assert_eq!(rustlearn(&["borrow", "--bogus"]).status.code(), Some(2));
+}
+
+#[test]
+fn reports_in_english() {
+    let output = rustlearn(&["--lang", "en", "borrow", "--example", "move_while_borrowed"]);
+    let text = stdout(&output);
+
+    assert!(text.starts_with("=== A value is moved while it is still borrowed ===\n"));
+    assert!(text.contains("Timeline:\nLine  Code"));
+    assert!(text.contains("error[E0505]: cannot move out of `v` because it is borrowed\n"));
+    assert!(text.contains("borrow of `v` occurs here"));
+    assert!(text.contains("borrow later used here"));
+}
@@ -0,0 +1,60 @@
+// 非交互式命令行模式的测试
+// 通过真实运行 rustlearn 可执行文件，检查各个子命令的输出和退出码
+
+mod common;
+
+use common::{command, run_into_closed_pipe, rustlearn};
+
+#[test]
+fn list_shows_every_lesson() {
+    let output = rustlearn(&["list"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert_eq!(output.status.code(), Some(0));
+    assert!(stdout.contains(" 1. 变量和数据类型 (Variables and Data Types)"));
+    assert!(stdout.contains("10. 生命周期 (Lifetimes)"));
+}
+
+#[test]
+fn run_section_by_partial_id() {
+    let output = rustlearn(&["run", "6", "--section", "btreeset"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert_eq!(output.status.code(), Some(0));
+    assert!(stdout.starts_with("\n--- BTreeSet (有序集合) ---\n"));
+    assert!(!stdout.contains("--- BTreeMap (有序映射) ---"));
+}
+
+#[test]
+fn unknown_lesson_or_section_is_a_usage_error() {
+    assert_eq!(rustlearn(&["run", "11"]).status.code(), Some(2));
+    assert_eq!(rustlearn(&["run", "6", "--section", "nothing"]).status.code(), Some(2));
+    assert_eq!(rustlearn(&["run", "6", "--section", "collection"]).status.code(), Some(2));
+    assert_eq!(rustlearn(&["frobnicate"]).status.code(), Some(2));
+}
+
+#[test]
+fn error_libraries_examples_follow_the_cargo_feature() {
+    let output = rustlearn(&["run", "8", "--section", "error_libraries"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(0));
+
+    if cfg!(feature = "error-libraries") {
+        // 用 thiserror 改写的错误链和 anyhow 的上下文链
+        assert!(stdout.contains("  错误: 数据处理失败\n  原因: 无法解析数据\n  原因: invalid digit found in string\n"));
+        assert!(stdout.contains("  第 1 层: 无法用配置 'not a number' 启动服务\n"));
+        assert!(stdout.contains("用 downcast_ref 取回 ServiceError: Data(Parse(ParseIntError { kind: InvalidDigit }))\n"));
+        assert!(stdout.contains("anyhow::Error: "));
+    } else {
+        assert!(stdout.contains("cargo run --features error-libraries -- run 8 --section error_libraries\n"));
+    }
+}
+
+#[test]
+fn closed_pipe_is_not_an_error() {
+    for args in [&["help"][..], &["list"], &["run", "1"]] {
+        let output = run_into_closed_pipe(&mut command(args));
+        assert_eq!(output.status.code(), Some(0), "{:?}: {}", args, String::from_utf8_lossy(&output.stderr));
+        assert!(output.stderr.is_empty());
+    }
+}
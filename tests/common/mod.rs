@@ -0,0 +1,77 @@
+// 集成测试共用的辅助函数：启动 rustlearn、准备临时目录
+// 每个测试文件通过 `mod common;` 引入，不是每个文件都会用到所有函数
+#![allow(dead_code)]
+
+use std::env;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output, Stdio};
+
+// 以中文界面运行 rustlearn 的命令，调用者可以再设置环境变量
+pub fn command(args: &[&str]) -> Command {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_rustlearn"));
+    command.args(args).env("LANG", "zh_CN.UTF-8");
+    command
+}
+
+// 以指定的用户和数据目录运行 rustlearn 的命令，学习进度保存在 data_home 中
+pub fn learner(data_home: &Path, user: &str, args: &[&str]) -> Command {
+    let mut command = command(args);
+    command.env("XDG_DATA_HOME", data_home).env("USER", user);
+    command
+}
+
+// 运行 rustlearn 并等待它结束
+pub fn rustlearn(args: &[&str]) -> Output {
+    run(&mut command(args))
+}
+
+// 运行命令并等待它结束，标准输入是空的
+pub fn run(command: &mut Command) -> Output {
+    command.output().expect("无法运行 rustlearn")
+}
+
+// 运行命令，把 input 写入标准输入之后等待它结束
+pub fn run_with_input(command: &mut Command, input: &[u8]) -> Output {
+    let mut child = command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("无法运行 rustlearn");
+
+    child
+        .stdin
+        .take()
+        .expect("无法打开标准输入")
+        .write_all(input)
+        .expect("无法写入标准输入");
+
+    child.wait_with_output().expect("无法等待 rustlearn 结束")
+}
+
+// 运行命令，标准输出是一个读取端已经关闭的管道，就像 `rustlearn ... | head -1` 中 head 提前退出
+pub fn run_into_closed_pipe(command: &mut Command) -> Output {
+    let (reader, writer) = io::pipe().expect("无法创建管道");
+    drop(reader);
+    command.stdout(writer).output().expect("无法运行 rustlearn")
+}
+
+pub fn stdout(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+// 临时目录或文件的路径，先删除上一次运行留下的内容
+// 路径中包含测试文件名和进程编号，不同的测试文件、同时运行的测试进程互不影响
+pub fn temp_path(name: &str) -> PathBuf {
+    let path = env::temp_dir().join(format!(
+        "rustlearn-{}-test-{}-{}",
+        env!("CARGO_CRATE_NAME"),
+        std::process::id(),
+        name
+    ));
+    let _ = fs::remove_dir_all(&path);
+    let _ = fs::remove_file(&path);
+    path
+}
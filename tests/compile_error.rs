@@ -0,0 +1,92 @@
+// 编译错误示例的测试
+// 检查示例能否列出、编译器真实的错误信息是否和修改前后的代码一起显示，
+// 以及每段有错误的代码都产生预期的错误代码、修改之后都能通过编译
+
+mod common;
+
+use std::process::Output;
+
+use common::{command, run, run_into_closed_pipe};
+
+// 运行 rustlearn，extra_env 中的环境变量会覆盖默认值
+fn rustlearn(args: &[&str], extra_env: &[(&str, &str)]) -> Output {
+    run(command(args).envs(extra_env.iter().copied()))
+}
+
+#[test]
+fn lists_examples() {
+    let output = rustlearn(&["errors"], &[]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(output.status.success());
+    assert!(stdout.contains(" 10.1  E0106  函数返回的引用缺少生命周期"));
+    assert!(stdout.contains("  6.2  E0502  持有元素的引用时修改 Vector"));
+
+    let output = rustlearn(&["list", "6"], &[]);
+    assert!(String::from_utf8_lossy(&output.stdout).contains("编译错误示例：\n6.1 E0382 把 String push 进 Vec 之后继续使用"));
+}
+
+#[test]
+fn shows_real_diagnostics_next_to_the_fix() {
+    let output = rustlearn(&["errors", "10.1"], &[]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert!(stdout.contains("示例 10.1：函数返回的引用缺少生命周期"));
+    assert!(stdout.contains("rustlearn run 10 --section lifetime_elision"));
+    assert!(stdout.contains("fn longest(x: &str, y: &str) -> &str {    │ fn longest<'a>(x: &'a str, y: &'a str) -> &'a str {"));
+    assert!(stdout.contains("}                                         │ }"));
+    // 每一列的分隔线和这一列最长的一行一样长，右边的代码比左边的长
+    assert!(stdout.contains(&format!("\n{}─┼─{}\n", "─".repeat(41), "─".repeat(51))));
+    assert!(stdout.contains("编译器的输出（rustc "));
+    assert!(stdout.contains("error[E0106]: missing lifetime specifier"));
+    assert!(stdout.contains("--> longest.rs:1:33"));
+    assert!(!stdout.contains("aborting due to"));
+    assert!(stdout.contains("说明："));
+}
+
+#[test]
+fn finds_examples_by_error_code() {
+    let output = rustlearn(&["errors", "e0277"], &[]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(output.status.success());
+    assert!(stdout.contains("示例 8.1："));
+    assert!(stdout.contains("示例 9.1："));
+    assert_eq!(stdout.matches("error[E0277]").count(), 2);
+}
+
+#[test]
+fn every_example_fails_as_expected_and_the_fix_compiles() {
+    let output = rustlearn(&["errors", "--check"], &[]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(output.status.success(), "{}", stdout);
+    assert!(stdout.contains("✓ 6.3 E0499"));
+    assert!(!stdout.contains("✗"));
+}
+
+#[test]
+fn missing_rustc_is_reported() {
+    let output = rustlearn(&["errors", "1.1"], &[("RUSTC", "rustlearn-no-such-rustc")]);
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("找不到 rustc"));
+}
+
+#[test]
+fn unknown_example_is_a_usage_error() {
+    assert_eq!(rustlearn(&["errors", "3.1"], &[]).status.code(), Some(2));
+    assert_eq!(rustlearn(&["errors", "E9999"], &[]).status.code(), Some(2));
+    assert_eq!(rustlearn(&["errors", "--bogus"], &[]).status.code(), Some(2));
+    assert_eq!(rustlearn(&["errors", "1.1", "6.1"], &[]).status.code(), Some(2));
+}
+
+#[test]
+fn closed_pipe_is_not_an_error() {
+    for args in [&["errors"][..], &["errors", "10.1"]] {
+        let output = run_into_closed_pipe(&mut command(args));
+        assert_eq!(output.status.code(), Some(0), "{:?}: {}", args, String::from_utf8_lossy(&output.stderr));
+        assert!(output.stderr.is_empty());
+    }
+}
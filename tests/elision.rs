@@ -0,0 +1,201 @@
+// 生命周期省略规则演示的测试
+// 用各种签名检查三条规则的推断过程、补全之后的签名、无法省略时的说明，以及签名的解析和交互模式
+
+mod common;
+
+use common::{command, run_with_input, rustlearn};
+
+// 推断一个签名，返回输出的文字
+fn elide(signature: &str) -> String {
+    let output = rustlearn(&["elision", signature]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+// 补全之后的签名
+fn annotated(text: &str) -> &str {
+    text.split("完整的签名：\n  ").nth(1).expect("没有完整的签名").trim_end()
+}
+
+#[test]
+fn rule_one_gives_every_elided_input_its_own_lifetime() {
+    let text = elide("fn f(x: &str, y: &mut Vec<&str>, z: Cow<'_, str>, n: usize)");
+
+    assert!(text.contains("  x: &str → x: &'a str\n"));
+    assert!(text.contains("  y: &mut Vec<&str> → y: &'b mut Vec<&'c str>\n"));
+    assert!(text.contains("  z: Cow<'_, str> → z: Cow<'d, str>\n"));
+    assert!(!text.contains("n: usize →"));
+    assert!(text.contains("输入生命周期：'a、'b、'c、'd\n"));
+    assert!(text.contains("返回值中没有省略的生命周期，不需要使用规则二和规则三"));
+    assert_eq!(annotated(&text), "fn f<'a, 'b, 'c, 'd>(x: &'a str, y: &'b mut Vec<&'c str>, z: Cow<'d, str>, n: usize)");
+}
+
+#[test]
+fn rule_one_skips_lifetime_names_already_in_use() {
+    let text = elide("fn f<'a, T>(x: &str, y: &'a T) -> &'a T");
+
+    assert!(text.contains("  x: &str → x: &'b str\n"));
+    assert_eq!(annotated(&text), "fn f<'a, 'b, T>(x: &'b str, y: &'a T) -> &'a T");
+}
+
+#[test]
+fn rule_two_uses_the_only_input_lifetime() {
+    let text = elide("fn first_word(s: &str) -> &str");
+
+    assert!(text.contains("规则二：如果参数中只有一个生命周期，它被赋给返回值中所有省略的生命周期\n  适用：唯一的输入生命周期是 'a\n  返回值：&str → &'a str\n"));
+    assert!(text.contains("规则三：如果有 &self 或 &mut self 参数，self 的生命周期被赋给返回值中所有省略的生命周期\n  不适用：没有 &self 或 &mut self 参数\n"));
+    assert_eq!(annotated(&text), "fn first_word<'a>(s: &'a str) -> &'a str");
+
+    // 写出来的生命周期也算输入生命周期，包括 'static
+    let text = elide("fn f(x: &'static str, n: u32) -> Option<&str>");
+    assert!(text.contains("适用：唯一的输入生命周期是 'static"));
+    assert_eq!(annotated(&text), "fn f(x: &'static str, n: u32) -> Option<&'static str>");
+
+    // 同一个生命周期用了两次仍然只算一个
+    let text = elide("fn f<'x>(a: &'x str, b: &'x str) -> (&str, &'_ str)");
+    assert_eq!(annotated(&text), "fn f<'x>(a: &'x str, b: &'x str) -> (&'x str, &'x str)");
+}
+
+#[test]
+fn rule_three_uses_the_lifetime_of_self() {
+    let text = elide("fn get_name(&self, other: &str) -> &str");
+
+    assert!(text.contains("  &self → &'a self\n  other: &str → other: &'b str\n"));
+    assert!(text.contains("  不适用：参数中有 2 个生命周期（'a、'b）\n"));
+    assert!(text.contains("  适用：self 的生命周期是 'a\n  返回值：&str → &'a str\n"));
+    assert_eq!(annotated(&text), "fn get_name<'a, 'b>(&'a self, other: &'b str) -> &'a str");
+
+    let text = elide("pub fn iter_mut(&mut self, skip: &[usize]) -> impl Iterator<Item = &mut u8> + '_");
+    assert_eq!(
+        annotated(&text),
+        "pub fn iter_mut<'a, 'b>(&'a mut self, skip: &'b [usize]) -> impl Iterator<Item = &'a mut u8> + 'a"
+    );
+
+    // 写成 self: &Self 也一样
+    let text = elide("fn f(self: &Self, x: &str) -> &str");
+    assert_eq!(annotated(&text), "fn f<'a, 'b>(self: &'a Self, x: &'b str) -> &'a str");
+}
+
+#[test]
+fn rule_three_is_not_needed_when_self_is_the_only_lifetime() {
+    let text = elide("fn name(&self) -> &str");
+
+    assert!(text.contains("适用：唯一的输入生命周期是 'a"));
+    assert!(text.contains("不需要：self 的生命周期 'a 就是唯一的输入生命周期"));
+    assert_eq!(annotated(&text), "fn name<'a>(&'a self) -> &'a str");
+}
+
+#[test]
+fn self_by_value_is_not_a_borrow() {
+    let text = elide("fn into_parts(self, sep: &str) -> &str");
+    assert!(text.contains("适用：唯一的输入生命周期是 'a"));
+    assert_eq!(annotated(&text), "fn into_parts<'a>(self, sep: &'a str) -> &'a str");
+
+    let text = elide("fn f(self: Box<Self>, a: &str, b: &str) -> &str");
+    assert!(text.contains("不适用：没有 &self 或 &mut self 参数"));
+    assert!(text.contains("✗ 无法省略返回值的生命周期"));
+}
+
+#[test]
+fn longest_cannot_be_elided() {
+    let text = elide("fn longest(x: &str, y: &str) -> &str");
+
+    assert!(text.contains("✗ 无法省略返回值的生命周期，编译器会报告 E0106 错误\n"));
+    assert!(text.contains("参数中有 2 个生命周期，也没有 &self 或 &mut self，编译器不知道返回值借用自哪一个参数"));
+    assert!(text.contains("\n    fn longest<'a>(x: &'a str, y: &'a str) -> &'a str\n"));
+    assert!(text.contains("rustlearn explain E0106"));
+    assert!(!text.contains("完整的签名："));
+}
+
+#[test]
+fn suggestion_only_shares_the_outer_lifetime_of_each_parameter() {
+    let text = elide("fn pick(items: &mut Vec<&str>, fallback: &str) -> &str");
+    assert!(text.contains("\n    fn pick<'a, 'b>(items: &'a mut Vec<&'b str>, fallback: &'a str) -> &'a str\n"));
+
+    // 生命周期都写出来了，就让返回值使用第一个
+    let text = elide("fn f<'a, 'b>(x: &'a str, y: &'b str) -> &str");
+    assert!(text.contains("\n    fn f<'a, 'b>(x: &'a str, y: &'b str) -> &'a str\n"));
+}
+
+#[test]
+fn output_without_any_input_lifetime_suggests_static() {
+    let text = elide("fn make(n: usize) -> &str");
+
+    assert!(text.contains("参数中没有任何生命周期"));
+    assert!(text.contains("不适用：参数中没有生命周期"));
+    assert!(text.contains("例如用 String 代替 &str"));
+    assert!(text.contains("\n    fn make(n: usize) -> &'static str\n"));
+}
+
+#[test]
+fn fn_pointers_and_closures_have_their_own_lifetimes() {
+    let text = elide("fn apply(f: fn(&str) -> &str, g: &dyn Fn(&str) -> &str, s: String) -> &str");
+
+    assert!(text.contains("  g: &dyn Fn(&str) -> &str → g: &'a dyn Fn(&str) -> &str\n"));
+    assert_eq!(annotated(&text), "fn apply<'a>(f: fn(&str) -> &str, g: &'a dyn Fn(&str) -> &str, s: String) -> &'a str");
+}
+
+#[test]
+fn parses_common_signature_syntax() {
+    let text = elide("pub(crate) async fn f<T: Into<Vec<u8>>, const N: usize>(mut buf: [&u8; N], (a, b): (&T, i32)) -> Result<&[u8], Box<dyn Error + '_>> where T: Clone {");
+    assert!(text.contains("  (a, b): (&T, i32) → (a, b): (&'b T, i32)\n"));
+    assert!(text.contains(
+        "\n    pub(crate) async fn f<'a, T: Into<Vec<u8>>, const N: usize>(mut buf: [&'a u8; N], (a, b): (&'a T, i32)) -> Result<&'a [u8], Box<dyn Error + 'a>> where T: Clone\n"
+    ));
+    assert!(text.contains("参数中有 2 个生命周期"));
+
+    let text = elide("fn raw(p: *const u8, q: &&mut std::string::String) -> &str;");
+    assert!(text.contains("  q: &&mut std::string::String → q: &'a &'b mut std::string::String\n"));
+}
+
+#[test]
+fn warns_about_lifetimes_from_the_impl_block() {
+    let text = elide("fn get(&'a self, key: &str) -> Option<&str>");
+
+    assert!(text.contains("注意：'a 没有在函数的泛型参数中声明，需要由外层的 impl 块声明"));
+    assert!(text.contains("  key: &str → key: &'b str\n"));
+    assert_eq!(annotated(&text), "fn get<'b>(&'a self, key: &'b str) -> Option<&'a str>");
+}
+
+#[test]
+fn invalid_signatures_are_usage_errors() {
+    for (signature, message) in [
+        ("fn f(x: &str", "应该是 \")\"，但是签名已经结束了"),
+        ("let x = 5;", "签名中缺少 fn 关键字"),
+        ("fn f(x: &str) -> §", "无法识别的字符：§"),
+        ("fn f(x: &str) -> &str garbage", "签名之后还有多余的内容：\"garbage\""),
+        ("fn f(x: for<'a> fn(&'a str))", "暂不支持 for<'a> 这样的写法"),
+        ("fn f(x: ) -> &str", "应该是 类型，但是遇到了 \")\""),
+    ] {
+        let output = rustlearn(&["elision", signature]);
+        assert_eq!(output.status.code(), Some(2), "{}", signature);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(stderr.contains(&format!("无法解析函数签名：{}", message)), "{}: {}", signature, stderr);
+    }
+
+    assert_eq!(rustlearn(&["elision", "--bogus"]).status.code(), Some(2));
+}
+
+#[test]
+fn interactive_mode_reads_signatures_until_an_empty_line() {
+    let output = run_with_input(
+        &mut command(&["elision"]),
+        b"fn first(v: &[i32]) -> &i32\nfn broken(\n\nfn never(x: &str) -> &str\n",
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(output.status.success());
+    assert!(stdout.contains("fn first<'a>(v: &'a [i32]) -> &'a i32"));
+    assert!(stdout.contains("无法解析函数签名："));
+    assert!(!stdout.contains("fn never"));
+}
+
+#[test]
+fn explains_in_english() {
+    let output = rustlearn(&["--lang", "en", "elision", "fn longest(x: &str, y: &str) -> &str"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(stdout.starts_with("Signature: fn longest(x: &str, y: &str) -> &str\n\nRule 1: "));
+    assert!(stdout.contains("Input lifetimes: 'a, 'b"));
+    assert!(stdout.contains("the compiler reports error E0106"));
+}
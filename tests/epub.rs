@@ -0,0 +1,131 @@
+// EPUB 电子书导出的测试
+// 读取导出的 ZIP 文件，检查 EPUB 要求的文件顺序和结构、文件清单和导航文档，
+// 以及每一章包含源代码和真实的运行结果
+
+mod common;
+
+use std::collections::HashMap;
+use std::fs;
+
+use common::{rustlearn, temp_path};
+
+fn u16_at(data: &[u8], offset: usize) -> usize {
+    u16::from_le_bytes([data[offset], data[offset + 1]]) as usize
+}
+
+fn u32_at(data: &[u8], offset: usize) -> usize {
+    u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap()) as usize
+}
+
+fn crc32(data: &[u8]) -> usize {
+    let mut crc = !0u32;
+    for byte in data {
+        crc ^= u32::from(*byte);
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 { (crc >> 1) ^ 0xEDB8_8320 } else { crc >> 1 };
+        }
+    }
+    !crc as usize
+}
+
+// 按顺序读出 ZIP 中的所有文件，同时检查压缩方法、校验码和中央目录
+fn read_zip(data: &[u8]) -> Vec<(String, String)> {
+    let mut files = Vec::new();
+    let mut offset = 0;
+    while u32_at(data, offset) == 0x0403_4b50 {
+        assert_eq!(u16_at(data, offset + 8), 0, "文件没有以不压缩的方式存储");
+        let size = u32_at(data, offset + 18);
+        let name_length = u16_at(data, offset + 26);
+        let extra_length = u16_at(data, offset + 28);
+        let name = String::from_utf8(data[offset + 30..offset + 30 + name_length].to_vec()).unwrap();
+        let start = offset + 30 + name_length + extra_length;
+        let content = &data[start..start + size];
+        assert_eq!(u32_at(data, offset + 14), crc32(content), "{} 的校验码不正确", name);
+
+        files.push((name, String::from_utf8(content.to_vec()).unwrap()));
+        offset = start + size;
+    }
+
+    // 目录结束记录中的文件数和中央目录的位置
+    let end = data.len() - 22;
+    assert_eq!(u32_at(data, end), 0x0605_4b50);
+    assert_eq!(u16_at(data, end + 10), files.len());
+    assert_eq!(u32_at(data, end + 16), offset);
+    files
+}
+
+fn export(name: &str, args: &[&str]) -> (HashMap<String, String>, Vec<String>) {
+    let file = temp_path(&format!("{}.epub", name));
+    let mut command = vec!["export", "--epub", "--out", file.to_str().unwrap()];
+    command.splice(0..0, args.iter().copied());
+    let output = rustlearn(&command);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+
+    let files = read_zip(&fs::read(&file).unwrap());
+    let _ = fs::remove_file(&file);
+    let names = files.iter().map(|(name, _)| name.clone()).collect();
+    (files.into_iter().collect(), names)
+}
+
+#[test]
+fn epub_package_is_well_formed() {
+    let (files, names) = export("package", &[]);
+
+    // mimetype 必须是第一个文件
+    assert_eq!(names[0], "mimetype");
+    assert_eq!(files["mimetype"], "application/epub+zip");
+    assert!(files["META-INF/container.xml"].contains("full-path=\"OEBPS/content.opf\""));
+
+    let package = &files["OEBPS/content.opf"];
+    assert!(package.contains("version=\"3.0\""));
+    assert!(package.contains("<dc:language>zh</dc:language>"));
+    assert!(package.contains("<meta property=\"dcterms:modified\">"));
+    assert!(package.contains("properties=\"nav\""));
+    assert_eq!(package.matches("<itemref ").count(), 10, "每一课应该是一章");
+
+    // 文件清单中的每个文件都在 ZIP 中
+    for item in package.split("href=\"").skip(1) {
+        let href = item.split('"').next().unwrap();
+        assert!(files.contains_key(&format!("OEBPS/{}", href)), "清单中的 {} 不存在", href);
+    }
+}
+
+#[test]
+fn navigation_links_every_section() {
+    let (files, _) = export("nav", &[]);
+    let navigation = &files["OEBPS/nav.xhtml"];
+    assert!(navigation.contains("<nav epub:type=\"toc\""));
+    assert!(navigation.contains("<a href=\"lesson-08.xhtml#error_propagation\">错误传播</a>"));
+
+    // 导航中的每个锚点都存在于对应的章节中
+    for link in navigation.split("href=\"").skip(1) {
+        let target = link.split('"').next().unwrap();
+        if let Some((file, anchor)) = target.split_once('#') {
+            assert!(files[&format!("OEBPS/{}", file)].contains(&format!("id=\"{}\"", anchor)));
+        }
+    }
+}
+
+#[test]
+fn chapters_have_code_and_real_output() {
+    let (files, _) = export("chapter", &[]);
+    let chapter = &files["OEBPS/lesson-08.xhtml"];
+    assert!(chapter.contains("xml:lang=\"zh\""));
+    assert!(chapter.contains("<h1>第8课：错误处理 (Error Handling)</h1>"));
+    assert!(chapter.contains("<pre class=\"code\"><code>// 演示自定义错误类型\nfn custom_error_types(ctx: &amp;mut LessonContext) {"));
+    assert!(chapter.contains("处理失败: ParseError(ParseIntError { kind: InvalidDigit })"));
+    assert!(files["OEBPS/style.css"].contains("monospace"));
+}
+
+#[test]
+fn epub_follows_interface_language() {
+    let (files, _) = export("en", &["--lang", "en"]);
+    assert!(files["OEBPS/content.opf"].contains("<dc:language>en</dc:language>"));
+    assert!(files["OEBPS/lesson-08.xhtml"].contains("<h1>Lesson 8: Error Handling</h1>"));
+}
+
+#[test]
+fn export_requires_format() {
+    assert_eq!(rustlearn(&["export"]).status.code(), Some(2));
+    assert_eq!(rustlearn(&["export", "--pdf"]).status.code(), Some(2));
+}
@@ -0,0 +1,150 @@
+// 动手练习的测试
+// 检查练习能否准备到练习目录中、未完成的代码是否被判定为没有通过，
+// 以及每个练习的参考答案都能通过隐藏的测试
+
+mod common;
+
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::process::Output;
+
+use common::{learner, run, run_into_closed_pipe, temp_path};
+
+// 运行 rustlearn
+fn rustlearn(data_home: &Path, args: &[&str]) -> Output {
+    run(&mut learner(data_home, "alice", args))
+}
+
+// 找到与初始代码对应的参考答案：exercises/ 中与初始代码内容相同的文件旁边的 <名称>_solution.rs
+fn solution_for(starter: &str) -> String {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("exercises");
+
+    for lesson_dir in fs::read_dir(&root).expect("无法读取 exercises 目录") {
+        for entry in fs::read_dir(lesson_dir.unwrap().path()).unwrap() {
+            let path = entry.unwrap().path();
+            if fs::read_to_string(&path).unwrap() == starter {
+                let stem = path.file_stem().unwrap().to_string_lossy().into_owned();
+                return fs::read_to_string(path.with_file_name(format!("{}_solution.rs", stem))).unwrap();
+            }
+        }
+    }
+
+    panic!("找不到初始代码对应的参考答案");
+}
+
+#[test]
+fn lists_exercises() {
+    let data_home = temp_path("list");
+    let output = rustlearn(&data_home, &["exercise"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(output.status.success());
+    assert!(stdout.contains("3.2  判断矩形能否容纳另一个矩形"));
+    assert!(stdout.contains("9.1  泛型函数 find_max"));
+
+    let output = rustlearn(&data_home, &["list", "3"]);
+    assert!(String::from_utf8_lossy(&output.stdout).contains("练习：\n3.1 矩形的面积\n3.2 判断矩形能否容纳另一个矩形"));
+}
+
+#[test]
+fn starter_code_does_not_pass() {
+    let data_home = temp_path("starter");
+    let output = rustlearn(&data_home, &["exercise", "3.2"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stdout.contains("通过了 0/4 个测试"));
+    assert!(stdout.contains("代码中还有没有完成的 todo!()"));
+    assert!(stdout.contains("提示："));
+
+    let source = data_home.join("rustlearn/exercises/alice/3.2/src/lib.rs");
+    assert!(fs::read_to_string(&source).unwrap().contains("fn can_hold(&self, other: &Rectangle) -> bool"));
+    assert!(data_home.join("rustlearn/exercises/alice/3.2/Cargo.toml").exists());
+    let _ = fs::remove_dir_all(&data_home);
+}
+
+#[test]
+fn compile_errors_are_reported() {
+    let data_home = temp_path("compile");
+    let output = rustlearn(&data_home, &["exercise", "10.1"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stdout.contains("✗ 代码无法通过编译："));
+    assert!(stdout.contains("E0106"));
+    let _ = fs::remove_dir_all(&data_home);
+}
+
+#[test]
+fn learner_changes_are_kept_until_reset() {
+    let dir = temp_path("reset");
+    let dir_arg = dir.to_str().unwrap();
+    let data_home = temp_path("reset-data");
+
+    rustlearn(&data_home, &["exercise", "1.1", "--dir", dir_arg]);
+    let source = dir.join("src/lib.rs");
+    fs::write(&source, "fn average(values: &[i32]) -> f64 { 0.0 }\n").unwrap();
+
+    let output = rustlearn(&data_home, &["exercise", "1.1", "--dir", dir_arg]);
+    assert!(String::from_utf8_lossy(&output.stdout).contains("✗ average_of_integers"));
+    assert!(fs::read_to_string(&source).unwrap().contains("{ 0.0 }"));
+
+    rustlearn(&data_home, &["exercise", "1.1", "--dir", dir_arg, "--reset"]);
+    assert!(fs::read_to_string(&source).unwrap().contains("todo!()"));
+    let _ = fs::remove_dir_all(&dir);
+    let _ = fs::remove_dir_all(&data_home);
+}
+
+#[test]
+fn reference_solutions_pass() {
+    let data_home = temp_path("solutions");
+    let output = rustlearn(&data_home, &["exercise"]);
+    let list = String::from_utf8_lossy(&output.stdout);
+
+    for id in list.lines().filter_map(|line| line.split_whitespace().next()) {
+        let dir = data_home.join(id);
+        let dir_arg = dir.to_str().unwrap();
+
+        // 先准备练习目录，再把参考答案写进去
+        rustlearn(&data_home, &["exercise", id, "--dir", dir_arg]);
+        let source = dir.join("src/lib.rs");
+        let solution = solution_for(&fs::read_to_string(&source).unwrap());
+        fs::write(&source, solution).unwrap();
+
+        let output = rustlearn(&data_home, &["exercise", id, "--dir", dir_arg]);
+        assert!(
+            output.status.success(),
+            "练习 {} 的参考答案没有通过：\n{}",
+            id,
+            String::from_utf8_lossy(&output.stdout)
+        );
+    }
+    let _ = fs::remove_dir_all(&data_home);
+}
+
+#[test]
+fn unknown_exercise_is_a_usage_error() {
+    let data_home = temp_path("unknown");
+
+    assert_eq!(rustlearn(&data_home, &["exercise", "3.9"]).status.code(), Some(2));
+    assert_eq!(rustlearn(&data_home, &["exercise", "3"]).status.code(), Some(2));
+    assert_eq!(rustlearn(&data_home, &["exercise", "3.1", "--bogus"]).status.code(), Some(2));
+}
+
+#[test]
+fn closed_pipe_is_not_an_error() {
+    let data_home = temp_path("pipe");
+
+    for args in [&["exercise"][..], &["exercise", "3.2", "--solution"]] {
+        let output = run_into_closed_pipe(&mut learner(&data_home, "alice", args));
+        assert_eq!(output.status.code(), Some(0), "{:?}: {}", args, String::from_utf8_lossy(&output.stderr));
+        assert!(output.stderr.is_empty());
+    }
+
+    // 检查初始代码时测试没有通过，退出码仍然是 1
+    let output = run_into_closed_pipe(&mut learner(&data_home, "alice", &["exercise", "3.2"]));
+    assert_eq!(output.status.code(), Some(1), "{}", String::from_utf8_lossy(&output.stderr));
+    assert!(output.stderr.is_empty());
+    let _ = fs::remove_dir_all(&data_home);
+}
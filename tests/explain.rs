@@ -0,0 +1,66 @@
+// 编译错误代码说明的测试
+// 检查错误代码的查找、说明中的复现代码和相关小节，以及所有说明都能通过检查
+
+mod common;
+
+use common::{rustlearn, stdout};
+
+#[test]
+fn explain_check_passes() {
+    let output = rustlearn(&["explain", "--check"]);
+    assert!(output.status.success(), "{}", stdout(&output));
+    assert!(stdout(&output).contains("✓ E0597"));
+    assert!(!stdout(&output).contains("✗"));
+}
+
+#[test]
+fn lists_codes_by_category() {
+    let output = rustlearn(&["explain"]);
+    assert!(output.status.success());
+    let text = stdout(&output);
+    assert!(text.starts_with("所有权\n  E0382  使用了已经被移动的值\n"));
+    assert!(text.contains("生命周期\n  E0106  缺少生命周期标注\n"));
+    assert!(text.contains("模式的穷尽性\n  E0004"));
+}
+
+#[test]
+fn explanation_shows_reproduction_fix_and_sections() {
+    let output = rustlearn(&["explain", "E0597"]);
+    assert!(output.status.success());
+    let text = stdout(&output);
+    assert!(text.starts_with("=== E0597: 被借用的值活得不够久 ===\n分类：生命周期\n"));
+    assert!(text.contains("复现代码：\n    fn main() {\n        let r;\n"));
+    assert!(text.contains("修改方法：\n"));
+    assert!(text.contains(
+        "相关的小节：\n  第10课 第1节：生命周期的基本概念 (_10_lifetimes::lifetimes_basics)\n    rustlearn run 10 --section lifetimes_basics\n"
+    ));
+    assert!(text.contains("_10_lifetimes::lifetime_annotations"));
+    assert!(text.contains("`rustlearn errors E0597`"));
+    assert!(text.contains("rustc --explain E0597"));
+
+    let text = stdout(&rustlearn(&["explain", "E0499"]));
+    assert!(text.contains("(_06_collections::ownership_issues)"));
+}
+
+#[test]
+fn codes_are_found_in_any_form() {
+    for query in ["E0499", "e0499", "0499", "499"] {
+        let output = rustlearn(&["explain", query]);
+        assert!(output.status.success(), "{}", query);
+        assert!(stdout(&output).starts_with("=== E0499: "));
+    }
+
+    let output = rustlearn(&["--lang", "en", "explain", "e0382"]);
+    assert!(stdout(&output).starts_with("=== E0382: Use of a value that has been moved ===\nCategory: Ownership\n"));
+}
+
+#[test]
+fn unknown_code_is_a_usage_error() {
+    let output = rustlearn(&["explain", "E0001"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("rustc --explain E0001"));
+
+    assert_eq!(rustlearn(&["explain", "borrow"]).status.code(), Some(2));
+    assert_eq!(rustlearn(&["explain", "--bogus"]).status.code(), Some(2));
+    assert_eq!(rustlearn(&["explain", "E0499", "E0502"]).status.code(), Some(2));
+}
@@ -0,0 +1,15 @@
+// --bless 用的第 1 课 variable_mutability 注释块，标题行写成 "--- 标题 ---"；blessed.rs 是重新生成之后应有的内容
+fn variable_mutability(ctx: &mut LessonContext) {
+    writeln!(ctx, "不可变变量 x = {}", x);
+
+    // 运行结果：
+    // --- 变量的可变性与不可变性 ---
+    // 不可变变量 x = 5
+    // 可变变量 y = 5
+    // 修改后，可变变量 y = 10
+    // 变量遮蔽后，y = 15
+}
+
+fn basic_data_types(ctx: &mut LessonContext) {
+    // 这个函数没有注释块，--bless 不会改动它
+}
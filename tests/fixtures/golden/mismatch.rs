@@ -0,0 +1,8 @@
+// 第 1 课 variable_mutability 的注释块，第二行的值写错了，少了最后一行，又多了一行
+fn variable_mutability(ctx: &mut LessonContext) {
+    // 运行结果：
+    // 不可变变量 x = 5
+    // 可变变量 y = 6
+    // 修改后，可变变量 y = 10
+    // 多余的一行
+}
@@ -0,0 +1,13 @@
+// 第 9 课 performance_considerations 的注释块，计算耗时每次运行都不同，用占位符代替
+fn performance_considerations(ctx: &mut LessonContext) {
+    // 运行结果：
+    // 泛型的性能考量
+    // Rust 中的泛型在性能方面有几个重要特点：
+    // 1. 单态化（monomorphization）：编译器为每种使用的具体类型生成专用的代码
+    // 2. 零运行时开销：泛型不会引入额外的运行时开销
+    // 3. 静态分发：使用泛型的函数调用在编译时确定，与具体类型直接调用一样高效
+    // 4. 类型擦除 vs 单态化：与某些语言的类型擦除不同，Rust 的单态化确保了最佳性能
+    // 整数乘法结果: 200
+    // 浮点数乘法结果: 215.25
+    // 计算耗时: [具体时间，根据运行环境而定]
+}
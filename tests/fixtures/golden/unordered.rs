@@ -0,0 +1,16 @@
+// 第 6 课 hashmap_collection 的注释块，行的顺序和 {...} 中元素的顺序都与真实输出不同
+fn hashmap_collection(ctx: &mut LessonContext) {
+    // 运行结果（顺序不固定）：
+    // Alice 的分数: 100
+    // HashMap: {"Charlie": 90, "Alice": 100, "Bob": 85}
+    // 遍历 HashMap:
+    // Charlie: 90
+    // Bob: 85
+    // Alice: 100
+    // HashMap 中是否包含 David: false
+    // Alice 的旧分数: Some(100)
+    // 更新后的 HashMap: {"Alice": 105, "Charlie": 90, "Bob": 85}
+    // 使用 entry 方法后的 HashMap: {"David": 75, "Charlie": 90, "Bob": 85, "Alice": 105}
+    // HashMap 的长度: 4
+    // 删除 Alice 后的 HashMap: {"Charlie": 90, "David": 75, "Bob": 85}
+}
@@ -0,0 +1,56 @@
+// 术语表的测试
+// 检查术语的查找、定义和相关小节的显示，以及仓库中的术语表能够通过检查
+
+mod common;
+
+use common::{rustlearn, stdout};
+
+#[test]
+fn glossary_check_passes() {
+    let output = rustlearn(&["glossary", "--check"]);
+    assert!(output.status.success(), "{}", stdout(&output));
+    assert!(stdout(&output).starts_with("术语表检查通过"));
+}
+
+#[test]
+fn term_shows_definition_example_and_sections() {
+    let output = rustlearn(&["glossary", "关联类型"]);
+    assert!(output.status.success());
+    let text = stdout(&output);
+    assert!(text.starts_with("=== 关联类型 (associated type) ===\n"));
+    assert!(text.contains("Iterator 的 Item"));
+    assert!(text.contains("示例：\n    trait Container {\n"));
+    assert!(text.contains("介绍这个术语的小节：\n  第9课 第10节：泛型的高级用法\n    rustlearn run 9 --section advanced_generics\n"));
+
+    // 其他用到这个术语的小节是从课程内容中找出来的
+    let text = stdout(&rustlearn(&["glossary", "trait bound"]));
+    assert!(text.contains("用到这个术语的其他小节："));
+    assert!(text.contains("rustlearn run 10 --section lifetime_bounds"));
+}
+
+#[test]
+fn terms_are_found_by_any_spelling() {
+    // 英文名称不区分大小写，英文界面中先显示英文名称
+    let output = rustlearn(&["--lang", "en", "glossary", "Lifetime", "Elision"]);
+    assert!(output.status.success());
+    let text = stdout(&output);
+    assert!(text.starts_with("=== lifetime elision (生命周期省略) ===\n"));
+    assert!(text.contains("three fixed rules"));
+    assert!(text.contains("Introduced in:\n  Lesson 10, section 6: "));
+
+    // 名称的一部分只匹配一个术语时直接显示
+    assert!(stdout(&rustlearn(&["glossary", "单态"])).starts_with("=== 单态化 (monomorphization) ==="));
+
+    let output = rustlearn(&["glossary", "关联"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("匹配到多个术语"));
+    assert_eq!(rustlearn(&["glossary", "不存在的术语"]).status.code(), Some(2));
+}
+
+#[test]
+fn list_shows_every_term_with_its_section() {
+    let text = stdout(&rustlearn(&["glossary"]));
+    assert!(text.contains("  所有权 (ownership) — 第6课 第10节：集合的所有权问题\n"));
+    assert!(text.contains("  借用 (borrowing) — "));
+    assert!(text.contains("  生命周期省略 (lifetime elision) — 第10课 第6节：生命周期省略规则\n"));
+}
@@ -0,0 +1,122 @@
+// 运行结果回归测试
+// 每个小节的真实输出都必须与课程文件中 "// 运行结果：" 注释记录的内容一致
+// 如果修改了示例代码，可以运行 `cargo run -- verify --bless` 重新生成注释
+//
+// 其余的测试用 --source 指向 tests/fixtures/golden 中准备好的注释块，检查差异的输出、
+// 无序比较、占位符以及 --bless 重新生成注释的结果
+
+mod common;
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use common::{rustlearn, temp_path};
+
+fn fixture(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/golden").join(name)
+}
+
+// 把 fixture 复制到临时文件中，内容先经过 edit 修改
+fn temp_copy(name: &str, edit: impl Fn(&str) -> String) -> PathBuf {
+    let file = temp_path(name);
+    fs::write(&file, edit(&fs::read_to_string(fixture(name)).unwrap())).unwrap();
+    file
+}
+
+// 用 source 文件中的注释块校验第 lesson 课，返回退出码和标准输出
+fn verify(lesson: &str, source: &Path, bless: bool) -> (Option<i32>, String) {
+    let source = source.to_str().unwrap();
+    let mut args = vec!["verify", "--lesson", lesson, "--source", source];
+    if bless {
+        args.push("--bless");
+    }
+
+    let output = rustlearn(&args);
+    (output.status.code(), String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[test]
+fn lesson_outputs_match_documented_results() {
+    let output = rustlearn(&["verify"]);
+
+    assert!(
+        output.status.success(),
+        "运行结果与注释不一致：\n{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+#[test]
+fn mismatches_are_reported_as_a_line_diff() {
+    let (code, stdout) = verify("1", &fixture("mismatch.rs"), false);
+
+    assert_eq!(code, Some(1));
+    // 相同的行原样列出，"+" 是真实输出中的行，"-" 是注释中的行
+    let expected = [
+        "✗ 第1课 variable_mutability（变量的可变性与不可变性）",
+        "      不可变变量 x = 5",
+        "    + 可变变量 y = 5",
+        "    - 可变变量 y = 6",
+        "      修改后，可变变量 y = 10",
+        "    + 变量遮蔽后，y = 15",
+        "    - 多余的一行",
+    ];
+    assert!(stdout.starts_with(&format!("{}\n", expected.join("\n"))), "{}", stdout);
+    // fixture 中只有一个注释块，其他小节算作没有记录运行结果
+    assert!(stdout.contains("运行结果校验：0 个小节一致，1 个小节不一致，4 个小节没有记录运行结果"));
+}
+
+#[test]
+fn unordered_blocks_ignore_line_and_item_order() {
+    let (code, stdout) = verify("6", &fixture("unordered.rs"), false);
+    assert_eq!(code, Some(0), "{}", stdout);
+    assert!(stdout.contains("运行结果校验：1 个小节一致，0 个小节不一致"));
+
+    // 去掉 "（顺序不固定）" 之后按顺序比较，前两行的顺序和真实输出不同，一定不一致
+    let ordered = temp_copy("unordered.rs", |source| source.replace("（顺序不固定）", ""));
+    let (code, stdout) = verify("6", &ordered, false);
+    let _ = fs::remove_file(&ordered);
+
+    assert_eq!(code, Some(1));
+    assert!(stdout.contains("运行结果校验：0 个小节一致，1 个小节不一致"), "{}", stdout);
+}
+
+#[test]
+fn placeholders_match_any_text() {
+    let (code, stdout) = verify("9", &fixture("placeholder.rs"), false);
+    assert_eq!(code, Some(0), "{}", stdout);
+
+    // 占位符只代替方括号中的部分，前面的文字仍然要一致
+    let renamed = temp_copy("placeholder.rs", |source| source.replace("计算耗时: [", "计算用时: ["));
+    let (code, stdout) = verify("9", &renamed, false);
+    let _ = fs::remove_file(&renamed);
+
+    assert_eq!(code, Some(1));
+    assert!(stdout.contains("    - 计算用时: [具体时间，根据运行环境而定]"), "{}", stdout);
+}
+
+#[test]
+fn bless_rewrites_the_block_and_keeps_its_title_line() {
+    let source = temp_copy("bless.rs", str::to_string);
+
+    let (code, stdout) = verify("1", &source, true);
+    assert_eq!(code, Some(0), "{}", stdout);
+    assert!(stdout.contains("已根据真实输出重新生成 1 个注释块"));
+    // 标题行仍然是 "// --- 标题 ---"，没有注释块的函数保持不变
+    assert_eq!(fs::read_to_string(&source).unwrap(), fs::read_to_string(fixture("blessed.rs")).unwrap());
+
+    // 重新生成之后再校验就一致了
+    let (code, stdout) = verify("1", &source, false);
+    let _ = fs::remove_file(&source);
+
+    assert_eq!(code, Some(0));
+    assert!(stdout.contains("运行结果校验：1 个小节一致，0 个小节不一致"));
+}
+
+#[test]
+fn source_requires_a_lesson() {
+    assert_eq!(rustlearn(&["verify", "--source", "lesson.rs"]).status.code(), Some(2));
+    assert_eq!(rustlearn(&["verify", "--lesson", "1", "--source"]).status.code(), Some(2));
+}
@@ -0,0 +1,83 @@
+// 课程依赖图和学习路线的测试
+// 检查文字和 DOT 两种格式的依赖图、依赖图的自检，以及学习路线的列表和详情
+
+mod common;
+
+use std::fs;
+use std::process::Output;
+
+use common::{command, run, temp_path};
+
+fn rustlearn(args: &[&str]) -> Output {
+    // 使用空的数据目录，学习路线按照没有任何学习记录显示
+    let data_home = temp_path(&args.join("-"));
+    let output = run(command(args).env("XDG_DATA_HOME", &data_home));
+    let _ = fs::remove_dir_all(&data_home);
+    output
+}
+
+fn stdout(args: &[&str]) -> String {
+    let output = rustlearn(args);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn text_graph_lists_lesson_and_section_prerequisites() {
+    let text = stdout(&["graph"]);
+    assert!(text.contains("第1课：变量和数据类型 (Variables and Data Types)\n"));
+    assert!(text.contains("第10课：生命周期 (Lifetimes) ← 第3课、第9课\n"));
+    assert!(text.contains("    生命周期约束 ← 第9课 泛型约束\n"));
+    assert!(text.contains("    match 表达式基础 ← 第4课 枚举的模式匹配\n"));
+}
+
+#[test]
+fn dot_graph_is_well_formed() {
+    let dot = stdout(&["graph", "--dot"]);
+    assert!(dot.starts_with("digraph rustlearn {\n"));
+    assert!(dot.ends_with("}\n"));
+    assert_eq!(dot.matches('{').count(), dot.matches('}').count());
+    assert!(dot.contains("    \"9\" -> \"10\";\n"));
+    assert!(dot.contains("\"9/generic_constraints\" -> \"10/lifetime_bounds\" [style=dashed"));
+
+    // 每条边的两端都是声明过的节点
+    let nodes: Vec<&str> = dot
+        .lines()
+        .filter(|line| line.contains(" [label="))
+        .map(|line| line.trim().split(' ').next().unwrap())
+        .collect();
+    for line in dot.lines().filter(|line| line.contains(" -> ")) {
+        let (from, rest) = line.trim().split_once(" -> ").unwrap();
+        let to = rest.split([' ', ';']).next().unwrap();
+        assert!(nodes.contains(&from), "{} 没有声明", from);
+        assert!(nodes.contains(&to), "{} 没有声明", to);
+    }
+}
+
+#[test]
+fn graph_and_paths_pass_the_check() {
+    let text = stdout(&["graph", "--check"]);
+    assert!(text.starts_with("课程依赖图检查通过"), "{}", text);
+}
+
+#[test]
+fn paths_are_listed_and_shown() {
+    let list = stdout(&["path"]);
+    assert!(list.contains("  cpp     C++ 开发者快速通道\n"));
+    assert!(list.contains("  errors  错误处理专题\n"));
+
+    let path = stdout(&["path", "errors"]);
+    assert!(path.contains("假定已经掌握：第1课、第2课、第3课\n"));
+    assert!(path.contains(" 3. 第8课：错误处理 (Error Handling)\n"));
+    assert!(path.contains("下一步: 第4课 第1节：枚举定义与实例化\n    rustlearn run 4 --section enum_definition\n"));
+
+    let english = stdout(&["--lang", "en", "path", "cpp"]);
+    assert!(english.starts_with("Fast track for C++ developers\n"));
+}
+
+#[test]
+fn invalid_arguments_are_usage_errors() {
+    assert_eq!(rustlearn(&["path", "rust-for-go"]).status.code(), Some(2));
+    assert_eq!(rustlearn(&["path", "cpp", "errors"]).status.code(), Some(2));
+    assert_eq!(rustlearn(&["graph", "--svg"]).status.code(), Some(2));
+}
@@ -0,0 +1,97 @@
+// 多语言支持的测试
+// 检查消息目录是否完整，以及 --lang 参数和 LANG 环境变量能否正确选择界面语言
+
+mod common;
+
+use std::process::Output;
+
+use common::{command, run};
+
+// 使用给定的 LANG 环境变量运行 rustlearn
+fn rustlearn(lang: &str, args: &[&str]) -> Output {
+    run(command(args).env("LANG", lang))
+}
+
+#[test]
+fn english_catalog_has_no_missing_translations() {
+    let output = rustlearn("C.UTF-8", &["check-translations", "en"]);
+
+    assert!(
+        output.status.success(),
+        "消息目录中缺少译文：\n{}",
+        String::from_utf8_lossy(&output.stdout)
+    );
+}
+
+#[test]
+fn lang_option_selects_english() {
+    let output = rustlearn("zh_CN.UTF-8", &["--lang", "en", "run", "1", "--section", "1"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(output.status.success());
+    assert!(stdout.contains("--- Mutability and immutability of variables ---"));
+    assert!(stdout.contains("Immutable variable x = 5"));
+}
+
+#[test]
+fn lang_environment_variable_selects_english() {
+    let output = rustlearn("en_US.UTF-8", &["list"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(output.status.success());
+    assert!(stdout.contains(" 1. Variables and Data Types"));
+    assert!(stdout.contains("10. Lifetimes"));
+}
+
+#[test]
+fn unsupported_lang_environment_falls_back_to_chinese() {
+    let output = rustlearn("fr_FR.UTF-8", &["list"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(stdout.contains(" 1. 变量和数据类型 (Variables and Data Types)"));
+}
+
+#[test]
+fn unsupported_lang_option_is_a_usage_error() {
+    let output = rustlearn("zh_CN.UTF-8", &["--lang", "fr", "list"]);
+    assert_eq!(output.status.code(), Some(2));
+
+    let output = rustlearn("zh_CN.UTF-8", &["list", "--lang"]);
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn unsupported_lang_option_is_reported_in_the_lang_environment_language() {
+    let output = rustlearn("en_US.UTF-8", &["--lang", "fr", "list"]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Unsupported language: fr, use zh or en"), "{}", stderr);
+
+    let output = rustlearn("zh_CN.UTF-8", &["--lang", "fr", "list"]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("不支持的语言: fr，可以使用 zh 或 en"), "{}", stderr);
+}
+
+#[test]
+fn verify_reports_in_english() {
+    let output = rustlearn("zh_CN.UTF-8", &["--lang", "en", "verify", "--lesson", "6"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(output.status.success(), "{}", stdout);
+    assert!(stdout.contains("Output check: 10 sections match, 0 sections differ, 0 sections have no recorded output"));
+
+    let output = rustlearn("zh_CN.UTF-8", &["--lang", "en", "verify", "--lesson", "42"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("There is no lesson numbered 42"));
+}
+
+#[test]
+fn check_translations_reports_in_english() {
+    let output = rustlearn("zh_CN.UTF-8", &["--lang", "en", "check-translations", "xx"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Unsupported language: xx"));
+
+    let output = rustlearn("zh_CN.UTF-8", &["--lang", "en", "check-translations", "en"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "{}", stdout);
+    assert!(stdout.contains("The en catalog is complete; every message has a translation"));
+}
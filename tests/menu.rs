@@ -0,0 +1,94 @@
+// 交互式菜单的测试
+// 通过管道向 rustlearn 写入预先准备好的输入，模拟用户在菜单中的操作
+
+mod common;
+
+use std::fs;
+use std::process::Output;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use common::{command, run_with_input, temp_path};
+
+// 用给定的标准输入运行交互式菜单，界面语言固定为中文
+// 每次运行都使用一个新的空数据目录，学习进度不会影响其他测试，也不会写入真实的数据目录
+fn run_menu(input: &[u8]) -> Output {
+    static RUNS: AtomicUsize = AtomicUsize::new(0);
+    let data_home = temp_path(&RUNS.fetch_add(1, Ordering::Relaxed).to_string());
+    let output = run_with_input(command(&[]).env("XDG_DATA_HOME", &data_home), input);
+    let _ = fs::remove_dir_all(&data_home);
+    output
+}
+
+#[test]
+fn empty_input_exits_cleanly() {
+    let output = run_menu(b"");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(stdout.matches("=== Rust 学习示例程序 ===").count(), 1);
+    assert!(stdout.ends_with("感谢使用 Rust 学习示例程序！再见！\n"));
+}
+
+#[test]
+fn eof_inside_submenus_exits_cleanly() {
+    // 在小节菜单中输入结束
+    let output = run_menu(b"6\n");
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(String::from_utf8_lossy(&output.stdout).matches("请选择要运行的小节:").count(), 1);
+
+    // 在逐节浏览时输入结束，最后一行没有换行符
+    let output = run_menu(b"6\n3");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(stdout.matches("--- HashMap (哈希映射) ---").count(), 1);
+}
+
+#[test]
+fn scripted_navigation_between_sections() {
+    let output = run_menu(b"6\n3\nn\np\nl\nb\nq\n");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(stdout.matches("--- HashMap (哈希映射) ---").count(), 2);
+    assert_eq!(stdout.matches("--- HashSet (哈希集合) ---").count(), 1);
+    assert_eq!(stdout.matches("=== Rust 学习示例程序 ===").count(), 2);
+}
+
+#[test]
+fn invalid_utf8_is_treated_as_an_invalid_choice() {
+    let output = run_menu(b"\xff\xfe\nq\n");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert_eq!(output.status.code(), Some(0));
+    assert!(stdout.contains("无效的选择，请重新输入。"));
+    assert!(stdout.ends_with("感谢使用 Rust 学习示例程序！再见！\n"));
+}
+
+#[test]
+fn search_opens_selected_section() {
+    let output = run_menu("s\n生命周期 省略\n1\nb\nq\n".as_bytes());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert_eq!(output.status.code(), Some(0));
+    assert!(stdout.contains("1. 第10课 第6节：生命周期省略规则\n"));
+    assert!(stdout.contains("--- 生命周期省略规则 ---"));
+    assert!(stdout.contains("[第10课 第6/10节：生命周期省略规则]"));
+
+    // 没有结果或者直接回车时回到课程列表
+    let output = run_menu("s\nzzqqxx\ns\n\nq\n".as_bytes());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("没有找到与 \"zzqqxx\" 相关的小节"));
+    assert_eq!(stdout.matches("=== Rust 学习示例程序 ===").count(), 3);
+}
+
+#[test]
+fn menu_recommends_next_lesson_from_progress() {
+    // 学完第1课之后推荐第2课，选择 n 直接打开推荐的课程
+    let output = run_menu(b"1\na\n\nb\nn\nb\nq\n");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert_eq!(output.status.code(), Some(0));
+    assert!(stdout.contains("n. 推荐下一课：第1课：变量和数据类型 (Variables and Data Types)\n"));
+    assert!(stdout.contains("n. 推荐下一课：第2课：函数和流程控制 (Functions and Control Flow)（已学完它依赖的第1课）\n"));
+    assert!(stdout.contains("\n=== 第2课：函数和流程控制 (Functions and Control Flow) ===\n"));
+}
@@ -0,0 +1,82 @@
+// 学习进度的测试
+// 每个测试使用自己的临时数据目录，通过交互式菜单学习之后检查进度文件和进度报告
+
+mod common;
+
+use std::fs;
+use std::path::Path;
+use std::process::Output;
+
+use common::{learner, run_into_closed_pipe, run_with_input, temp_path};
+
+// 以指定的用户和数据目录运行 rustlearn，可以通过标准输入操作菜单
+fn rustlearn(data_home: &Path, user: &str, args: &[&str], input: &[u8]) -> Output {
+    run_with_input(&mut learner(data_home, user, args), input)
+}
+
+#[test]
+fn completed_sections_are_saved_per_user() {
+    let data_home = temp_path("saved");
+
+    // 学习第 3 课的前两节
+    let output = rustlearn(&data_home, "alice", &[], b"3\n1\nn\nb\nq\n");
+    assert!(output.status.success());
+
+    let file = data_home.join("rustlearn/progress/alice.txt");
+    let content = fs::read_to_string(&file).expect("没有生成进度文件");
+    assert!(content.contains("section\t3\tstruct_definition"));
+    assert!(content.contains("last\t3\t"));
+    assert_eq!(content.lines().filter(|line| line.starts_with("section\t")).count(), 2);
+
+    // 其他用户的进度互不影响
+    assert!(!data_home.join("rustlearn/progress/bob.txt").exists());
+    let _ = fs::remove_dir_all(&data_home);
+}
+
+#[test]
+fn menu_marks_progress_and_offers_to_continue() {
+    let data_home = temp_path("menu");
+
+    // 运行第 2 课的全部小节，再运行第 3 课的第一节
+    rustlearn(&data_home, "alice", &[], b"2\na\n\nb\n3\n1\nb\nq\n");
+
+    let output = rustlearn(&data_home, "alice", &[], b"c\nb\nq\n");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(stdout.contains("2. 函数和流程控制 (Functions and Control Flow) ✓"));
+    assert!(stdout.contains("3. 结构体 (Structs) (1/"));
+    assert!(stdout.contains("c. 从上次离开的地方继续（第3课 第2节：元组结构体）"));
+    assert!(stdout.contains("[第3课 第2/"));
+    let _ = fs::remove_dir_all(&data_home);
+}
+
+#[test]
+fn progress_report_lists_every_learner() {
+    let data_home = temp_path("report");
+
+    rustlearn(&data_home, "alice", &[], b"1\na\n\nq\n");
+    rustlearn(&data_home, "bob", &[], b"4\n1\nb\nq\n");
+
+    let output = rustlearn(&data_home, "mentor", &["progress", "--all"], b"");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(output.status.success());
+    assert!(stdout.contains("学习者: alice"));
+    assert!(stdout.contains("已完成 1/10 课"));
+    assert!(stdout.contains("学习者: bob"));
+    assert!(stdout.contains(" 4. 枚举 (Enums) - 已完成 1/"));
+    assert!(!stdout.contains("学习者: mentor"));
+    let _ = fs::remove_dir_all(&data_home);
+}
+
+#[test]
+fn closed_pipe_is_not_an_error() {
+    let data_home = temp_path("pipe");
+
+    rustlearn(&data_home, "alice", &[], b"1\na\n\nq\n");
+    let output = run_into_closed_pipe(&mut learner(&data_home, "mentor", &["progress", "--all"]));
+
+    assert_eq!(output.status.code(), Some(0), "{}", String::from_utf8_lossy(&output.stderr));
+    assert!(output.stderr.is_empty());
+    let _ = fs::remove_dir_all(&data_home);
+}
@@ -0,0 +1,71 @@
+// 课程测验的测试
+// 通过标准输入逐题作答，检查评分、解析以及成绩是否记录到学习进度中
+
+mod common;
+
+use std::fs;
+use std::path::Path;
+use std::process::Output;
+
+use common::{learner, run_with_input, temp_path};
+
+// 以指定的数据目录运行 rustlearn，通过标准输入作答
+fn rustlearn(data_home: &Path, args: &[&str], input: &[u8]) -> Output {
+    run_with_input(&mut learner(data_home, "alice", args), input)
+}
+
+#[test]
+fn quiz_is_scored_and_recorded() {
+    let data_home = temp_path("scored");
+
+    // 第 1 课：第一题答对，第二题答错，第三题先输入无效的编号再答对
+    let output = rustlearn(&data_home, &["quiz", "1"], b"3\n1\n5\n1\n");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(output.status.success());
+    assert!(stdout.contains("第 1/3 题（预测输出）"));
+    assert!(stdout.contains("let y = y + 5;"));
+    assert!(stdout.contains("✗ 回答错误，正确答案是 2. let mut x = 5;"));
+    assert!(stdout.contains("解析：Rust 中的变量默认是不可变的"));
+    assert!(stdout.contains("请输入 1 到 2 之间的编号"));
+    assert!(stdout.contains("测验结束：得分 2/3"));
+
+    let content = fs::read_to_string(data_home.join("rustlearn/progress/alice.txt")).expect("没有生成进度文件");
+    assert!(content.contains("quiz\t1\t2\t3\t"));
+
+    // 进度报告中显示测验成绩
+    let output = rustlearn(&data_home, &["progress"], b"");
+    assert!(String::from_utf8_lossy(&output.stdout).contains("测验成绩: 2/3"));
+    let _ = fs::remove_dir_all(&data_home);
+}
+
+#[test]
+fn unfinished_quiz_is_not_recorded() {
+    let data_home = temp_path("unfinished");
+
+    let output = rustlearn(&data_home, &["quiz", "2"], b"2\n");
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains("测验没有完成，成绩不会被记录"));
+    assert!(!data_home.join("rustlearn/progress/alice.txt").exists());
+    let _ = fs::remove_dir_all(&data_home);
+}
+
+#[test]
+fn quiz_can_be_taken_from_the_lesson_menu() {
+    let data_home = temp_path("menu");
+
+    rustlearn(&data_home, &[], b"10\nt\n2\n1\n2\nb\nq\n");
+
+    // 再次进入这一课时显示上次的得分
+    let output = rustlearn(&data_home, &[], b"10\nb\nq\n");
+    assert!(String::from_utf8_lossy(&output.stdout).contains("t. 参加本课测验（上次得分 3/3）"));
+    let _ = fs::remove_dir_all(&data_home);
+}
+
+#[test]
+fn quiz_requires_an_existing_lesson() {
+    let data_home = temp_path("missing");
+
+    assert_eq!(rustlearn(&data_home, &["quiz", "99"], b"").status.code(), Some(2));
+    assert_eq!(rustlearn(&data_home, &["quiz"], b"").status.code(), Some(2));
+}
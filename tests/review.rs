@@ -0,0 +1,192 @@
+// 间隔复习的测试
+// 直接写入带有复习计划的进度文件，让题目立即到期，再通过标准输入作答，
+// 检查 SM-2 算法计算出的难度系数、间隔和连续答对次数
+// 进度文件中的题目用题干的 FNV-1a 哈希标识
+
+mod common;
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Output;
+
+use common::{learner, run_into_closed_pipe, run_with_input, temp_path};
+
+// 第 1 课的三道测验题（第 10 课的第一题和第 1 课的第一题题干相同）和第 10 课的第二题
+const PREDICT: &str = "下面的代码会输出什么？";
+const MUTABLE: &str = "声明一个之后还可以修改的变量，应该使用哪种写法？";
+const CONSTANT: &str = "常量的值必须在编译时就能确定。";
+const LIFETIME: &str = "fn longest<'a>(x: &'a str, y: &'a str) -> &'a str 中的 'a 表示什么？";
+
+// 题目标识：题干的 FNV-1a 哈希，写成 8 位十六进制数
+fn question_id(prompt: &str) -> String {
+    let hash = prompt
+        .bytes()
+        .fold(0x811c_9dc5_u32, |hash, byte| (hash ^ u32::from(byte)).wrapping_mul(0x0100_0193));
+    format!("{:08x}", hash)
+}
+
+// 一条 review 记录，fields 是难度系数之后的各列
+fn review(lesson: &str, prompt: &str, fields: &str) -> String {
+    format!("review\t{}\t{}\t{}", lesson, question_id(prompt), fields)
+}
+
+fn progress_file(data_home: &Path) -> PathBuf {
+    data_home.join("rustlearn/progress/alice.txt")
+}
+
+// 写入进度文件，内容中的每一行是一条记录
+fn write_progress(data_home: &Path, records: &[String]) {
+    let file = progress_file(data_home);
+    fs::create_dir_all(file.parent().unwrap()).unwrap();
+    fs::write(file, records.join("\n") + "\n").unwrap();
+}
+
+fn rustlearn(data_home: &Path, args: &[&str], input: &[u8]) -> Output {
+    run_with_input(&mut learner(data_home, "alice", args), input)
+}
+
+#[test]
+fn quiz_adds_questions_to_review_schedule() {
+    let data_home = temp_path("quiz");
+
+    // 第 1 课：第一题和第三题答对，第二题答错
+    let output = rustlearn(&data_home, &["quiz", "1"], b"3\n1\n1\n");
+    assert!(output.status.success());
+
+    let content = fs::read_to_string(progress_file(&data_home)).unwrap();
+    assert!(content.contains(&review("1", PREDICT, "2.50\t1\t1\t")), "{}", content);
+    assert!(content.contains(&review("1", MUTABLE, "1.96\t1\t0\t")), "{}", content);
+    assert!(content.contains(&review("1", CONSTANT, "2.50\t1\t1\t")), "{}", content);
+
+    // 刚做完测验，第二天才到复习时间
+    let output = rustlearn(&data_home, &["review"], b"");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success());
+    assert!(stdout.contains("现在没有需要复习的测验题"));
+    assert!(stdout.contains("下一次复习时间: "));
+
+    let _ = fs::remove_dir_all(&data_home);
+}
+
+#[test]
+fn due_questions_are_scheduled_with_sm2() {
+    let data_home = temp_path("sm2");
+    write_progress(
+        &data_home,
+        &[
+            review("1", PREDICT, "2.50\t6\t2\t100"),
+            review("1", MUTABLE, "2.50\t1\t1\t200"),
+            review("1", CONSTANT, "2.50\t1\t1\t99999999999"),
+        ],
+    );
+
+    // 第一题答对并评价为 "很轻松"，第二题答错，第三题还没有到期
+    let output = rustlearn(&data_home, &["review"], b"3\n3\n1\n");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success());
+    assert!(stdout.contains("有 2 道题到了复习时间，本次复习 2 道"));
+    assert!(stdout.contains("第 1/2 题（第1课 · 预测输出）"));
+    assert!(stdout.contains("下次复习：15 天后"));
+    assert!(stdout.contains("解析：Rust 中的变量默认是不可变的"));
+    assert!(stdout.contains("复习结束：答对 1/2"));
+
+    // 答对：间隔 6 × 2.5 = 15 天，难度系数加 0.1；答错：间隔 1 天，连续答对次数清零
+    let content = fs::read_to_string(progress_file(&data_home)).unwrap();
+    assert!(content.contains(&review("1", PREDICT, "2.60\t15\t3\t")), "{}", content);
+    assert!(content.contains(&review("1", MUTABLE, "1.96\t1\t0\t")), "{}", content);
+    assert!(content.contains(&review("1", CONSTANT, "2.50\t1\t1\t99999999999")), "{}", content);
+
+    let _ = fs::remove_dir_all(&data_home);
+}
+
+#[test]
+fn limit_and_status() {
+    let data_home = temp_path("limit");
+    write_progress(
+        &data_home,
+        &[
+            review("10", PREDICT, "2.50\t1\t1\t100"),
+            review("1", MUTABLE, "2.50\t1\t1\t200"),
+            // 不存在的课程和题目会被跳过
+            review("99", PREDICT, "2.50\t1\t1\t100"),
+            review("1", LIFETIME, "2.50\t1\t1\t100"),
+        ],
+    );
+
+    let output = rustlearn(&data_home, &["review", "--status"], b"");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains(" 1. 变量和数据类型 (Variables and Data Types) - 1 道题，1 道到期"));
+    assert!(stdout.contains("10. 生命周期 (Lifetimes) - 1 道题，1 道到期"));
+    assert!(stdout.contains("共 2 道题，现在需要复习 2 道"));
+
+    // 只复习最早到期的一道题，之后提示还有题目到期
+    let output = rustlearn(&data_home, &["review", "--limit", "1"], b"1\n");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("第 1/1 题（第10课 · "));
+    assert!(stdout.contains("还有 1 道题到了复习时间"));
+
+    let _ = fs::remove_dir_all(&data_home);
+}
+
+#[test]
+fn unfinished_review_keeps_answered_questions() {
+    let data_home = temp_path("eof");
+    write_progress(&data_home, &[review("1", PREDICT, "2.50\t1\t1\t100"), review("1", MUTABLE, "2.50\t1\t1\t200")]);
+
+    let output = rustlearn(&data_home, &["review"], b"1\n");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success());
+    assert!(stdout.contains("复习没有完成，已经作答的题目都已经记录"));
+
+    let content = fs::read_to_string(progress_file(&data_home)).unwrap();
+    assert!(content.contains(&review("1", PREDICT, "1.96\t1\t0\t")), "{}", content);
+    assert!(content.contains(&review("1", MUTABLE, "2.50\t1\t1\t200")), "{}", content);
+
+    let _ = fs::remove_dir_all(&data_home);
+}
+
+#[test]
+fn numbered_records_are_converted_to_question_ids() {
+    let data_home = temp_path("numbered");
+    // 旧版本的进度文件按从 1 开始的题目编号记录，读取时换算成题目标识，不存在的题目被丢弃
+    write_progress(
+        &data_home,
+        &[
+            "review\t1\t2\t2.50\t1\t1\t200".to_string(),
+            "review\t1\t3\t2.50\t1\t1\t99999999999".to_string(),
+            "review\t1\t99\t2.50\t1\t1\t100".to_string(),
+        ],
+    );
+
+    let output = rustlearn(&data_home, &["review"], b"1\n");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success());
+    assert!(stdout.contains("有 1 道题到了复习时间，本次复习 1 道"), "{}", stdout);
+
+    let content = fs::read_to_string(progress_file(&data_home)).unwrap();
+    assert!(content.contains(&review("1", MUTABLE, "1.96\t1\t0\t")), "{}", content);
+    assert!(content.contains(&review("1", CONSTANT, "2.50\t1\t1\t99999999999")), "{}", content);
+    assert!(!content.contains("review\t1\t99\t"), "{}", content);
+
+    let _ = fs::remove_dir_all(&data_home);
+}
+
+#[test]
+fn status_into_closed_pipe_is_not_an_error() {
+    let data_home = temp_path("pipe");
+    write_progress(&data_home, &[review("1", MUTABLE, "2.50\t1\t1\t200")]);
+
+    // 相当于 `rustlearn review --status | head -1`
+    let output = run_into_closed_pipe(&mut learner(&data_home, "alice", &["review", "--status"]));
+    assert_eq!(output.status.code(), Some(0));
+    assert!(output.stderr.is_empty(), "{}", String::from_utf8_lossy(&output.stderr));
+
+    let _ = fs::remove_dir_all(&data_home);
+}
+
+#[test]
+fn invalid_arguments_are_usage_errors() {
+    let data_home = temp_path("usage");
+    assert_eq!(rustlearn(&data_home, &["review", "--limit", "0"], b"").status.code(), Some(2));
+    assert_eq!(rustlearn(&data_home, &["review", "1"], b"").status.code(), Some(2));
+}
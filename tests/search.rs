@@ -0,0 +1,69 @@
+// 课程全文搜索的测试
+// 检查中文和英文的查询都能找到对应的小节，并且最相关的小节排在第一位
+
+mod common;
+
+use common::{command, run_into_closed_pipe, rustlearn};
+
+// 第一条搜索结果的标题和运行命令
+fn best_match(args: &[&str]) -> (String, String) {
+    let output = rustlearn(args);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let stdout = String::from_utf8_lossy(&output.stdout).into_owned();
+    let mut lines = stdout.lines().skip(1);
+    (
+        lines.next().unwrap().to_string(),
+        lines.next().unwrap().trim().to_string(),
+    )
+}
+
+#[test]
+fn chinese_words_without_spaces_are_found() {
+    let (title, command) = best_match(&["search", "生命周期", "省略"]);
+    assert_eq!(title, "1. 第10课 第6节：生命周期省略规则");
+    assert_eq!(command, "rustlearn run 10 --section lifetime_elision");
+
+    // 查询中的中文不需要和标题完全一致
+    let (title, _) = best_match(&["search", "省略规则"]);
+    assert!(title.contains("生命周期省略规则"));
+}
+
+#[test]
+fn code_and_english_words_are_found() {
+    let (title, _) = best_match(&["search", "HashMap", "entry"]);
+    assert!(title.starts_with("1. 第6课 "), "{}", title);
+
+    let (_, command) = best_match(&["--lang", "en", "search", "lifetime", "elision"]);
+    assert_eq!(command, "rustlearn run 10 --section lifetime_elision");
+}
+
+#[test]
+fn limit_and_run() {
+    let output = rustlearn(&["search", "生命周期", "--limit", "2"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("\n2. "));
+    assert!(!stdout.contains("\n3. "));
+    assert!(stdout.contains("可以使用 --limit 显示更多"));
+
+    // --run 直接运行最相关的小节
+    let output = rustlearn(&["search", "生命周期", "省略", "--run"]);
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).starts_with("\n--- 生命周期省略规则 ---\n"));
+}
+
+#[test]
+fn no_results_and_usage_errors() {
+    let output = rustlearn(&["search", "zzqqxx"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("没有找到"));
+
+    assert_eq!(rustlearn(&["search"]).status.code(), Some(2));
+    assert_eq!(rustlearn(&["search", "x", "--limit", "0"]).status.code(), Some(2));
+}
+
+#[test]
+fn closed_pipe_is_not_an_error() {
+    let output = run_into_closed_pipe(&mut command(&["search", "生命周期"]));
+    assert_eq!(output.status.code(), Some(0), "{}", String::from_utf8_lossy(&output.stderr));
+    assert!(output.stderr.is_empty());
+}
@@ -0,0 +1,71 @@
+// 显示小节源代码的测试
+// 检查 --code 会在运行结果之前带行号显示小节的函数以及它用到的定义，并且每个小节都能找到源代码
+
+mod common;
+
+use common::rustlearn;
+
+// 运行 rustlearn 并返回标准输出
+fn stdout(args: &[&str]) -> String {
+    let output = rustlearn(args);
+    assert!(output.status.success(), "rustlearn {:?} 执行失败", args);
+    String::from_utf8(output.stdout).expect("输出不是合法的 UTF-8")
+}
+
+#[test]
+fn code_is_shown_before_the_output() {
+    let output = stdout(&["run", "8", "--section", "error_propagation", "--code"]);
+
+    let heading = output.find("--- 错误传播 ---").expect("没有小节标题");
+    let source = output.find("源代码（src/_08_error_handling.rs）：").expect("没有源代码");
+    let function = output.find(" | fn error_propagation(ctx: &mut LessonContext) {").expect("没有小节函数");
+    let label = output.find("运行结果：").expect("没有运行结果标签");
+    let result = output.find("错误: Os").expect("没有运行结果");
+
+    assert!(heading < source && source < function && function < label && label < result);
+    assert!(output.contains(" |     fn read_file_simple() -> Result<String, io::Error> {"));
+
+    // 行号与源文件中的行一致
+    let file = include_str!("../src/_08_error_handling.rs");
+    let number = file
+        .lines()
+        .position(|line| line == "fn error_propagation(ctx: &mut LessonContext) {")
+        .unwrap()
+        + 1;
+    assert!(output.contains(&format!("{} | fn error_propagation(", number)));
+}
+
+#[test]
+fn helper_definitions_are_included() {
+    let output = stdout(&["run", "3", "--section", "struct_methods", "--code"]);
+
+    assert!(output.contains(" | struct Rectangle {"));
+    assert!(output.contains(" | impl Rectangle {"));
+    assert!(output.contains(" |     fn can_hold(&self, other: &Rectangle) -> bool {"));
+    // 其他小节的函数和 Lesson 的实现不会被带上
+    assert!(!output.contains("fn struct_definition("));
+    assert!(!output.contains("impl Lesson for"));
+}
+
+#[test]
+fn every_section_has_code() {
+    for id in 1..=10 {
+        let id = id.to_string();
+        let code = stdout(&["run", &id, "--code"]);
+        let plain = stdout(&["run", &id]);
+
+        let headings = plain.lines().filter(|line| line.starts_with("--- ")).count();
+        assert!(headings > 0);
+        assert_eq!(
+            code.matches("源代码（").count(),
+            headings,
+            "第{}课有小节没有显示源代码",
+            id
+        );
+    }
+}
+
+#[test]
+fn code_is_not_shown_by_default() {
+    assert!(!stdout(&["run", "3"]).contains("源代码（"));
+}
@@ -0,0 +1,74 @@
+// 配色主题和语法高亮的测试
+// 输出不是终端时不应该有颜色；通过 CLICOLOR_FORCE 强制输出颜色，检查标题和代码的高亮，
+// 以及 NO_COLOR 总是优先
+
+mod common;
+
+use std::process::Output;
+
+use common::{command, run};
+
+// 运行 rustlearn，清除会影响颜色的环境变量后再设置 envs
+fn rustlearn(args: &[&str], envs: &[(&str, &str)]) -> Output {
+    run(command(args)
+        .env_remove("NO_COLOR")
+        .env_remove("CLICOLOR_FORCE")
+        .env_remove("RUSTLEARN_THEME")
+        .envs(envs.iter().copied()))
+}
+
+// 运行 rustlearn 并返回标准输出
+fn stdout(args: &[&str], envs: &[(&str, &str)]) -> String {
+    let output = rustlearn(args, envs);
+    assert!(output.status.success(), "rustlearn {:?} 执行失败", args);
+    String::from_utf8(output.stdout).expect("输出不是合法的 UTF-8")
+}
+
+const LIFETIMES: &[&str] = &["run", "10", "--section", "lifetime_annotations", "--code"];
+
+#[test]
+fn piped_output_is_plain() {
+    let output = stdout(&["run", "10", "--code", "--theme", "high-contrast"], &[]);
+    assert!(!output.contains('\x1b'));
+    assert!(output.contains("--- 生命周期注解语法 ---"));
+}
+
+#[test]
+fn forced_colors_highlight_headings_and_code() {
+    let output = stdout(LIFETIMES, &[("CLICOLOR_FORCE", "1")]);
+
+    // 默认的 dark 主题
+    assert!(output.contains("\x1b[1;93m--- 生命周期注解语法 ---\x1b[0m"));
+    assert!(output.contains("\x1b[1;95mfn\x1b[0m longest<\x1b[93m'a\x1b[0m>"));
+    assert!(output.contains("\x1b[1;94mwriteln!\x1b[0m(ctx, \x1b[92m\""));
+    assert!(output.contains("\x1b[3;90m// "));
+
+    // 课程的输出本身不着色
+    let (_, result) = output.split_once("\x1b[90m运行结果：\x1b[0m\n").expect("没有运行结果");
+    assert!(!result.is_empty() && !result.contains('\x1b'));
+}
+
+#[test]
+fn themes_use_different_colors() {
+    let dark = stdout(LIFETIMES, &[("CLICOLOR_FORCE", "1")]);
+    let light = stdout(LIFETIMES, &[("CLICOLOR_FORCE", "1"), ("RUSTLEARN_THEME", "light")]);
+    let contrast = stdout(&[LIFETIMES, &["--theme=high-contrast"]].concat(), &[("CLICOLOR_FORCE", "1")]);
+
+    assert!(light.contains("\x1b[1;34mfn\x1b[0m"));
+    assert!(contrast.contains("\x1b[1;93mfn\x1b[0m"));
+    // 高对比度主题不使用暗淡或斜体
+    assert!(!contrast.contains("\x1b[3;") && !contrast.contains(";90m"));
+    assert!(dark != light && dark != contrast);
+}
+
+#[test]
+fn no_color_wins() {
+    let output = stdout(LIFETIMES, &[("CLICOLOR_FORCE", "1"), ("NO_COLOR", "1")]);
+    assert!(!output.contains('\x1b'));
+}
+
+#[test]
+fn unknown_theme_is_a_usage_error() {
+    assert_eq!(rustlearn(&["list", "--theme", "solarized"], &[]).status.code(), Some(2));
+    assert_eq!(rustlearn(&["list", "--theme"], &[]).status.code(), Some(2));
+}
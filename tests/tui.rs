@@ -0,0 +1,220 @@
+// 全屏界面的测试
+// 全屏界面需要真正的终端，在管道中运行时只给出提示并以非零退出码结束，不会弄乱终端；
+// 界面的状态和绘制通过 tui --replay 检查：把按键写入标准输入，比较处理完所有按键之后的界面
+
+mod common;
+
+use std::process::Output;
+
+use common::{command, run_with_input};
+
+// 以管道作为标准输入和输出运行 rustlearn
+fn rustlearn(args: &[&str]) -> Output {
+    run_with_input(&mut command(args), b"")
+}
+
+// 在给定大小的窗口中依次按下 keys 中的按键，返回最后的界面，每一行去掉了行尾的空白
+fn replay(size: &str, keys: &[u8]) -> Vec<String> {
+    let output = run_with_input(&mut command(&["tui", "--replay", size]), keys);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    String::from_utf8_lossy(&output.stdout).lines().map(str::to_string).collect()
+}
+
+// 在默认的 24 行 80 列窗口中按下按键
+fn screen(keys: &[u8]) -> Vec<String> {
+    let lines = replay("24x80", keys);
+    assert_eq!(lines.len(), 24);
+    lines
+}
+
+// 标题栏
+fn title(lines: &[String]) -> &str {
+    &lines[0]
+}
+
+// 状态栏
+fn status(lines: &[String]) -> &str {
+    lines.last().unwrap()
+}
+
+// 目录中的第 row 行（从 0 开始）
+fn sidebar(lines: &[String], row: usize) -> &str {
+    lines[row + 1].split('│').next().unwrap()
+}
+
+// 右侧窗格中的第 row 行（从 0 开始），第 0 行是源代码窗格的标题
+fn pane(lines: &[String], row: usize) -> &str {
+    lines[row + 1].split_once('│').unwrap().1
+}
+
+// 24 行 80 列的窗口中，源代码窗格显示 8 行代码，运行结果窗格的标题在右侧第 9 行，下面显示 12 行运行结果
+const OUTPUT: usize = 9;
+
+#[test]
+fn requires_a_terminal() {
+    let output = rustlearn(&["tui"]);
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("全屏界面只能在终端中使用"));
+    // 没有切换到备用屏幕，也没有输出其他转义序列
+    assert!(output.stdout.is_empty());
+}
+
+#[test]
+fn rejects_arguments() {
+    assert_eq!(rustlearn(&["tui", "3"]).status.code(), Some(2));
+    assert_eq!(rustlearn(&["tui", "--replay", "80"]).status.code(), Some(2));
+    assert_eq!(rustlearn(&["tui", "--replay", "24x80", "1"]).status.code(), Some(2));
+}
+
+#[test]
+fn sidebar_expands_the_selected_lesson() {
+    let lines = screen(b"");
+    assert_eq!(title(&lines), " Rust 学习示例程序  |  第1课：变量和数据类型 (Variables and Data Types)");
+    assert_eq!(sidebar(&lines, 0), "▾ 1. 变量和数据类型 (Varia");
+    assert_eq!(sidebar(&lines, 1).trim_end(), "    1. 变量的可变性与不可");
+    assert_eq!(sidebar(&lines, 5).trim_end(), "    5. 常量和静态变量");
+    assert_eq!(sidebar(&lines, 6).trim_end(), "▸ 2. 函数和流程控制 (Funct");
+    assert_eq!(pane(&lines, 1), "源代码（src/_01_variables.rs）：");
+    assert_eq!(pane(&lines, OUTPUT + 1), "按回车运行选中的课程或小节");
+
+    // 移动到第 2 课时，第 1 课收起，第 2 课展开
+    let lines = screen(b"jjjjjj");
+    assert_eq!(title(&lines), " Rust 学习示例程序  |  第2课：函数和流程控制 (Functions and Control Flow)");
+    assert_eq!(sidebar(&lines, 0).trim_end(), "▸ 1. 变量和数据类型 (Varia");
+    assert_eq!(sidebar(&lines, 1).trim_end(), "▾ 2. 函数和流程控制 (Funct");
+    assert_eq!(sidebar(&lines, 2).trim_end(), "    1. 函数的定义与调用");
+    assert_eq!(pane(&lines, 1), "源代码（src/_02_functions_control_flow.rs）：");
+
+    // 在第一行向上移动不会越界
+    let lines = screen(b"kkj");
+    assert_eq!(title(&lines), " Rust 学习示例程序  |  第1课 第1/5节：变量的可变性与不可变性");
+}
+
+#[test]
+fn enter_runs_the_selection_and_marks_it_completed() {
+    let lines = screen(b"jj\r");
+    assert_eq!(title(&lines), " Rust 学习示例程序  |  第1课 第2/5节：基本数据类型");
+    assert_eq!(sidebar(&lines, 2).trim_end(), "    2. 基本数据类型 ✓");
+    assert_eq!(sidebar(&lines, 3).trim_end(), "    3. 类型标注");
+    assert!(pane(&lines, OUTPUT).starts_with("── 运行结果：第1课 第2/5节：基本数据类型 ───"));
+    assert_eq!(pane(&lines, OUTPUT + 1), "--- 基本数据类型 ---");
+    assert_eq!(pane(&lines, OUTPUT + 2), "整数类型：");
+    // 源代码窗格只显示选中的小节
+    assert_eq!(pane(&lines, 2), "195 | // 演示 Rust 的基本数据类型");
+}
+
+#[test]
+fn next_and_previous_cross_lessons() {
+    // 选中第 2 课时，上一节是第 1 课的最后一节
+    let lines = screen(b"jjjjjjp");
+    assert_eq!(title(&lines), " Rust 学习示例程序  |  第1课 第5/5节：常量和静态变量");
+    assert_eq!(pane(&lines, OUTPUT + 1), "--- 常量和静态变量 ---");
+
+    // 从第 1 课的最后一节往下是第 2 课的第一节
+    let lines = screen(b"jjjjjjpn");
+    assert_eq!(title(&lines), " Rust 学习示例程序  |  第2课 第1/9节：函数的定义与调用");
+    assert_eq!(pane(&lines, OUTPUT + 1), "--- 函数的定义与调用 ---");
+
+    // r 重新运行显示中的小节，而不是目录中选中的位置
+    let lines = screen(b"jjjjjjpkkr");
+    assert!(pane(&lines, OUTPUT).starts_with("── 运行结果：第1课 第5/5节：常量和静态变量 ───"));
+
+    assert_eq!(status(&screen(b"p")), "已经是第一节了。");
+    let lines = screen(&[b'n'; 200]);
+    assert_eq!(title(&lines), " Rust 学习示例程序  |  第10课 第10/10节：高级生命周期用法");
+    assert_eq!(status(&lines), "已经是最后一节了。");
+}
+
+#[test]
+fn paging_scrolls_the_focused_pane() {
+    // 运行结果窗格有 12 行，翻一页之后从第 13 行开始显示
+    let lines = screen(b"\r\x1b[6~");
+    assert_eq!(pane(&lines, OUTPUT + 1), "i32: -42");
+    let lines = screen(b"\r  b");
+    assert_eq!(pane(&lines, OUTPUT + 1), "i32: -42");
+    let lines = screen(b"\r\x1b[6~g");
+    assert_eq!(pane(&lines, OUTPUT + 1), "=== 第1课：变量和数据类型 ===");
+
+    // G 滚动到结尾，最后一行正好在窗格的底部
+    let lines = screen(b"\rG");
+    assert_eq!(pane(&lines, OUTPUT + 12), "更新后，静态变量 COUNTER = 2");
+
+    // Tab 切换到源代码窗格之后，翻页只滚动源代码，运行结果保持不动
+    let lines = screen(b"\r\t\x1b[6~");
+    assert_eq!(pane(&lines, 1), "  8 | // 4. 类型标注");
+    assert_eq!(pane(&lines, OUTPUT + 1), "=== 第1课：变量和数据类型 ===");
+    let lines = screen(b"\r\t\t\x1b[6~");
+    assert_eq!(pane(&lines, 1), "源代码（src/_01_variables.rs）：");
+}
+
+#[test]
+fn search_finds_each_match_in_turn() {
+    // 第一处匹配就在窗格顶部，所以从第二处开始
+    let lines = screen("\r/变量\r".as_bytes());
+    assert_eq!(status(&lines), "\"变量\"：第 2/10 处");
+    assert_eq!(pane(&lines, OUTPUT + 1), "本示例将介绍 Rust 中的变量声明、可变性、基本数据类型");
+
+    // 直接回车查找下一处
+    let lines = screen("\r/变量\r/\r".as_bytes());
+    assert_eq!(status(&lines), "\"变量\"：第 3/10 处");
+
+    assert_eq!(status(&screen("\r/不存在\r".as_bytes())), "没有找到 \"不存在\"");
+
+    // 输入时可以删除，Esc 取消搜索
+    assert_eq!(status(&screen(b"\r/ab\x7f")), "/a▏");
+    assert_eq!(
+        status(&screen(b"\r/ab\x1b")),
+        "↑↓ 选择  回车 运行  n/p 下一节/上一节  r 重新运行  Tab 切换窗格  / 搜索  q 退出"
+    );
+}
+
+#[test]
+fn decodes_escape_sequences_and_utf8_keys() {
+    // 方向键，包括应用模式下的 "\x1bOB"
+    let lines = screen(b"\x1b[B\x1b[B\x1b[A\x1bOB");
+    assert_eq!(title(&lines), " Rust 学习示例程序  |  第1课 第2/5节：基本数据类型");
+
+    // Home 和 End
+    let lines = screen(b"\r\x1b[F");
+    assert_eq!(pane(&lines, OUTPUT + 12), "更新后，静态变量 COUNTER = 2");
+    let lines = screen(b"\r\x1b[F\x1b[H");
+    assert_eq!(pane(&lines, OUTPUT + 1), "=== 第1课：变量和数据类型 ===");
+
+    // 不认识的转义序列被忽略，之后的按键照常处理
+    let lines = screen(b"\x1b[1;5Cj");
+    assert_eq!(title(&lines), " Rust 学习示例程序  |  第1课 第1/5节：变量的可变性与不可变性");
+
+    // 多字节的 UTF-8 字符
+    assert_eq!(status(&screen("/变量".as_bytes())), "/变量▏");
+
+    // Ctrl-C 和 q 退出，之后的按键不再处理
+    assert_eq!(title(&screen(b"\x03j")), " Rust 学习示例程序  |  第1课：变量和数据类型 (Variables and Data Types)");
+    assert_eq!(pane(&screen(b"q\r"), OUTPUT + 1), "按回车运行选中的课程或小节");
+}
+
+#[test]
+fn wide_characters_take_two_columns() {
+    // 44 列的窗口中目录宽 14 列：放不下一个中文字符时用空格补齐
+    let lines = replay("12x44", b"");
+    assert_eq!(sidebar(&lines, 0), "▾ 1. 变量和数 ");
+    assert_eq!(sidebar(&lines, 1), "    1. 变量的 ");
+    assert_eq!(sidebar(&lines, 7), "▸ 3. 结构体 (S");
+    assert_eq!(lines[0], " Rust 学习示例程序  |  第1课：变量和数据类型");
+    assert_eq!(status(&lines), "↑↓ 选择  回车 运行  n/p 下一节/上一节  r 重");
+
+    // 41 列的窗口中运行结果窗格宽 27 列，中文按两列计算自动换行
+    let lines = replay("12x41", b"\r");
+    assert_eq!(pane(&lines, 5), "=== 第1课：变量和数据类型 =");
+    assert_eq!(pane(&lines, 6), "==");
+    assert_eq!(pane(&lines, 7), "本示例将介绍 Rust 中的变量");
+    assert_eq!(pane(&lines, 8), "声明、可变性、基本数据类型");
+    assert_eq!(pane(&lines, 9), "和类型转换。");
+}
+
+#[test]
+fn small_windows_show_a_hint() {
+    for size in ["9x80", "24x39"] {
+        assert_eq!(replay(size, b""), ["终端窗口太小了，请调大窗口或者按 q 退出"]);
+    }
+}
@@ -0,0 +1,231 @@
+// 网页界面的测试
+// 以系统分配的端口启动服务器，通过 HTTP 请求检查各个页面的内容、只有 POST 才会记录学习进度，
+// 页面没有引用任何外部资源，以及其他网站的请求和过大的请求会被拒绝
+
+mod common;
+
+use std::fs;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpStream;
+use std::path::PathBuf;
+use std::process::{Child, Stdio};
+
+use common::{learner, rustlearn, temp_path};
+
+// 运行中的服务器，测试结束时停止
+struct Server {
+    child: Child,
+    address: String,
+    data_home: PathBuf,
+}
+
+impl Server {
+    fn start(name: &str) -> Server {
+        let data_home = temp_path(name);
+
+        let mut child = learner(&data_home, "alice", &["serve", "--port", "0"])
+            .stdout(Stdio::piped())
+            .spawn()
+            .expect("无法运行 rustlearn");
+
+        // 第一行输出中包含实际监听的地址
+        let mut line = String::new();
+        BufReader::new(child.stdout.take().unwrap()).read_line(&mut line).unwrap();
+        let address = line
+            .split("http://")
+            .nth(1)
+            .and_then(|rest| rest.split('/').next())
+            .expect("输出中没有服务器地址")
+            .to_string();
+
+        Server { child, address, data_home }
+    }
+
+    // 发送请求，返回状态行和正文
+    fn request(&self, method: &str, path: &str) -> (String, String) {
+        let mut stream = TcpStream::connect(&self.address).expect("无法连接服务器");
+        write!(stream, "{} {} HTTP/1.1\r\nHost: {}\r\n\r\n", method, path, self.address).unwrap();
+
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        let (head, body) = response.split_once("\r\n\r\n").expect("响应格式不正确");
+        (head.lines().next().unwrap().to_string(), body.to_string())
+    }
+
+    // 发送原样的请求，只返回状态行
+    // 服务器拒绝请求时可能没有读完请求就关闭连接，之后的读取会出错，所以只读第一行
+    fn status(&self, request: &str) -> String {
+        let mut stream = TcpStream::connect(&self.address).expect("无法连接服务器");
+        stream.write_all(request.as_bytes()).unwrap();
+
+        let mut line = String::new();
+        BufReader::new(stream).read_line(&mut line).unwrap();
+        line.trim_end().to_string()
+    }
+
+    fn get(&self, path: &str) -> String {
+        let (status, body) = self.request("GET", path);
+        assert_eq!(status, "HTTP/1.1 200 OK", "{} 返回了 {}", path, status);
+        body
+    }
+}
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+        let _ = fs::remove_dir_all(&self.data_home);
+    }
+}
+
+#[test]
+fn pages_show_lessons_sections_and_output() {
+    let server = Server::start("pages");
+
+    let index = server.get("/");
+    assert!(index.contains("<a href=\"/lessons/3\">第3课：结构体 (Structs)</a>"));
+    assert!(index.contains("<progress value=\"0\" max=\"8\"></progress>"));
+
+    let lesson = server.get("/lessons/8");
+    assert!(lesson.contains("<h2>知识点</h2>"));
+    assert!(lesson.contains("<a href=\"/lessons/8/error_propagation\">错误传播</a>"));
+    assert!(lesson.contains("<a href=\"/lessons/9\">第9课：泛型 (Generics) →</a>"));
+
+    let section = server.get("/lessons/8/error_propagation");
+    assert!(section.contains("<span class=\"keyword\">fn</span> read_file_simple()"));
+    assert!(section.contains("<pre class=\"output\">--- 错误传播 ---"));
+    assert!(section.contains("Result&lt;T, E&gt;"));
+    assert!(section.contains("<a href=\"/lessons/8/custom_error_types\">自定义错误类型 →</a>"));
+}
+
+#[test]
+fn reading_pages_does_not_record_progress() {
+    let server = Server::start("read-only");
+
+    server.get("/lessons/3/struct_methods");
+    assert_eq!(server.request("HEAD", "/lessons/3/struct_methods").0, "HTTP/1.1 200 OK");
+
+    let index = server.get("/");
+    assert!(index.contains("<progress value=\"0\" max=\"8\"></progress>"));
+    assert!(!index.contains("从上次离开的地方继续"));
+    assert!(!server.data_home.join("rustlearn/progress/alice.txt").exists());
+}
+
+#[test]
+fn completing_a_section_records_progress() {
+    let server = Server::start("progress");
+
+    let section = server.get("/lessons/3/struct_methods");
+    assert!(section.contains(
+        "<form class=\"complete\" method=\"post\" action=\"/lessons/3/struct_methods/complete\"><button type=\"submit\">标记为已学完</button></form>"
+    ));
+
+    // 记录之后重定向回小节页面
+    // 浏览器提交表单时带有同源的 Origin 和正文
+    let mut stream = TcpStream::connect(&server.address).unwrap();
+    write!(
+        stream,
+        "POST /lessons/3/struct_methods/complete HTTP/1.1\r\nHost: {}\r\nOrigin: http://{}\r\nContent-Length: 3\r\n\r\na=1",
+        server.address, server.address
+    )
+    .unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    assert!(response.starts_with("HTTP/1.1 303 See Other\r\nLocation: /lessons/3/struct_methods\r\n"), "{}", response);
+
+    let section = server.get("/lessons/3/struct_methods");
+    assert!(section.contains("<p class=\"done\">✓ 已经学完这一节</p>"));
+    assert!(!section.contains("<form"));
+
+    let index = server.get("/");
+    assert!(index.contains("<progress value=\"1\" max=\"8\"></progress>"));
+    assert!(index.contains("从上次离开的地方继续"));
+
+    let content = fs::read_to_string(server.data_home.join("rustlearn/progress/alice.txt")).unwrap();
+    assert!(content.contains("struct_methods"));
+}
+
+#[test]
+fn pages_are_self_contained() {
+    let server = Server::start("offline");
+
+    for path in ["/", "/lessons/6", "/lessons/6/hashmap_collection"] {
+        let page = server.get(path);
+        assert!(page.contains("<style>"));
+        assert!(!page.contains("src=\"http") && !page.contains("href=\"http") && !page.contains("@import"));
+    }
+}
+
+#[test]
+fn unknown_pages_and_methods_are_rejected() {
+    let server = Server::start("errors");
+
+    assert_eq!(server.request("GET", "/lessons/99").0, "HTTP/1.1 404 Not Found");
+    assert_eq!(server.request("GET", "/lessons/3/nothing").0, "HTTP/1.1 404 Not Found");
+    assert_eq!(server.request("POST", "/").0, "HTTP/1.1 405 Method Not Allowed");
+    assert_eq!(server.request("POST", "/lessons/3/struct_methods").0, "HTTP/1.1 405 Method Not Allowed");
+    assert_eq!(server.request("POST", "/lessons/3/nothing/complete").0, "HTTP/1.1 404 Not Found");
+    assert_eq!(server.request("DELETE", "/").0, "HTTP/1.1 405 Method Not Allowed");
+
+    let (status, body) = server.request("HEAD", "/");
+    assert_eq!(status, "HTTP/1.1 200 OK");
+    assert!(body.is_empty());
+}
+
+#[test]
+fn foreign_hosts_are_rejected() {
+    let server = Server::start("host");
+    let port = server.address.rsplit(':').next().unwrap();
+
+    // 通过 DNS 重绑定访问时，Host 是别的网站的域名
+    let request = |host: &str| format!("GET / HTTP/1.1\r\nHost: {}\r\n\r\n", host);
+    assert_eq!(server.status(&request(&format!("evil.example:{}", port))), "HTTP/1.1 403 Forbidden");
+    assert_eq!(server.status(&request("127.0.0.1:1")), "HTTP/1.1 403 Forbidden");
+    assert_eq!(server.status("GET / HTTP/1.1\r\n\r\n"), "HTTP/1.1 403 Forbidden");
+    assert_eq!(server.status(&request(&format!("localhost:{}", port))), "HTTP/1.1 200 OK");
+}
+
+#[test]
+fn foreign_origins_cannot_record_progress() {
+    let server = Server::start("origin");
+
+    let post = |origin: &str| {
+        format!(
+            "POST /lessons/3/struct_methods/complete HTTP/1.1\r\nHost: {}\r\nOrigin: {}\r\nContent-Length: 0\r\n\r\n",
+            server.address, origin
+        )
+    };
+    assert_eq!(server.status(&post("http://evil.example")), "HTTP/1.1 403 Forbidden");
+    assert_eq!(server.status(&post("null")), "HTTP/1.1 403 Forbidden");
+    assert!(!server.data_home.join("rustlearn/progress/alice.txt").exists());
+
+    // 没有 Origin 的请求（例如 curl）和同源的请求可以记录学习进度
+    assert_eq!(server.request("POST", "/lessons/3/struct_methods/complete").0, "HTTP/1.1 303 See Other");
+}
+
+#[test]
+fn oversized_requests_are_rejected() {
+    let server = Server::start("limits");
+
+    let long_target = format!("GET /{} HTTP/1.1\r\n\r\n", "a".repeat(10_000));
+    assert_eq!(server.status(&long_target), "HTTP/1.1 400 Bad Request");
+
+    let padding = "a".repeat(10_000);
+    let long_header = format!("GET / HTTP/1.1\r\nHost: {}\r\nX-Padding: {}\r\n\r\n", server.address, padding);
+    assert_eq!(server.status(&long_header), "HTTP/1.1 431 Request Header Fields Too Large");
+
+    let many_headers = format!("GET / HTTP/1.1\r\nHost: {}\r\n{}\r\n", server.address, "X-Padding: a\r\n".repeat(100));
+    assert_eq!(server.status(&many_headers), "HTTP/1.1 431 Request Header Fields Too Large");
+
+    let large_body = format!("POST / HTTP/1.1\r\nHost: {}\r\nContent-Length: 1000000\r\n\r\n", server.address);
+    assert_eq!(server.status(&large_body), "HTTP/1.1 413 Payload Too Large");
+
+    // 被拒绝的请求不影响之后的请求
+    server.get("/");
+}
+
+#[test]
+fn invalid_port_is_a_usage_error() {
+    let output = rustlearn(&["serve", "--port", "http"]);
+    assert_eq!(output.status.code(), Some(2));
+}